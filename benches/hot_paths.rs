@@ -0,0 +1,77 @@
+//! Micro-benchmarks for the solver's hot paths.
+//!
+//! Run with `cargo bench`; pass a substring to only run matching benchmarks.
+//! Criterion reports each with its spread and how it moved since the last
+//! run.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use wordle::{
+    dictionary::Dictionary,
+    guesser::{Correctness, Guess},
+    hint::Scoring,
+    knowledge::KnowledgeState,
+    model::AnswerModel,
+    packed::Packed,
+    strategy::{Entropy, Likeliest, Rollout, Strategy},
+};
+
+static DICTIONARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/word-counts.bin.z"));
+
+fn hot_paths(c: &mut Criterion) {
+    let corpus = Dictionary::inflate_compact(DICTIONARY);
+    let pairs = corpus.by_frequency();
+    let words: Vec<&str> = pairs.iter().map(|&(word, _)| word).collect();
+    let model = AnswerModel::new(pairs.iter().copied());
+
+    c.bench_function("compute", |b| {
+        b.iter(|| Correctness::compute(black_box("racer"), black_box("tares")))
+    });
+
+    c.bench_function("compute_batch/dictionary", |b| {
+        b.iter(|| Correctness::compute_batch(black_box("salet"), &words))
+    });
+
+    let packed = Packed::new(&words);
+    c.bench_function("compute_packed/dictionary", |b| {
+        b.iter(|| Correctness::compute_packed(black_box("salet"), &packed))
+    });
+
+    let guess = Guess::check("racer", "tares");
+    c.bench_function("matches", |b| b.iter(|| guess.matches(black_box("pacer"))));
+
+    let knowledge = KnowledgeState::from_history(&[Guess::check("racer", "salet")]);
+    c.bench_function("filter/dictionary", |b| {
+        b.iter(|| words.iter().filter(|word| knowledge.allows(word)).count())
+    });
+    c.bench_function("filter/packed", |b| {
+        b.iter(|| {
+            packed
+                .iter()
+                .filter(|word| knowledge.allows_bytes(&word[..]))
+                .count()
+        })
+    });
+
+    let candidates: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|word| knowledge.allows(word))
+        .collect();
+
+    let rollout = Rollout::default();
+    let remaining = Entropy {
+        scoring: Scoring::Remaining,
+        ..Entropy::default()
+    };
+    let strategies: [&dyn Strategy; 4] = [&Likeliest, &Entropy::default(), &remaining, &rollout];
+    for strategy in strategies {
+        c.bench_function(&format!("choose/{}", strategy.name()), |b| {
+            b.iter(|| strategy.choose(&candidates, &candidates, &model))
+        });
+    }
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
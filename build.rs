@@ -0,0 +1,67 @@
+//! Preprocess the embedded corpus into a compact, pre-sorted binary form so
+//! the binary doesn't have to parse and sort it on every run, and deflate it
+//! and the other embedded lists so they take less room in the binary.
+//!
+//! The compact corpus is a sequence of fixed-width records, most frequent
+//! first: five bytes of word followed by its count as a little-endian `u64`.
+
+#[cfg(feature = "embedded-wordlists")]
+use std::{env, fs, path::Path};
+
+const CORPUS: &str = "corpus/word-counts.txt";
+
+/// The lists embedded as text, besides the corpus, and their deflated names.
+const LISTS: [(&str, &str); 2] = [
+    ("answers.txt", "answers.txt.z"),
+    ("corpus/valid-words.txt", "valid-words.txt.z"),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed={CORPUS}");
+    for (list, _) in LISTS {
+        println!("cargo:rerun-if-changed={list}");
+    }
+    embed();
+}
+
+#[cfg(feature = "embedded-wordlists")]
+fn embed() {
+    let out = env::var("OUT_DIR").unwrap();
+    let out = Path::new(&out);
+    for (list, name) in LISTS {
+        let text = fs::read(list).unwrap_or_else(|e| panic!("couldn't read {list}: {e}"));
+        fs::write(out.join(name), deflate(&text)).expect("couldn't write a deflated list");
+    }
+
+    let text = fs::read_to_string(CORPUS).expect("couldn't read the corpus");
+    let mut pairs: Vec<(&str, u64)> = text
+        .lines()
+        .filter_map(|line| {
+            let (word, count) = line.split_once(' ')?;
+            (word.len() == 5 && word.is_ascii())
+                .then(|| count.trim().parse().ok().map(|count| (word, count)))
+                .flatten()
+        })
+        .collect();
+    pairs.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    let mut blob = Vec::with_capacity(pairs.len() * 13);
+    for (word, count) in pairs {
+        blob.extend_from_slice(word.as_bytes());
+        blob.extend_from_slice(&count.to_le_bytes());
+    }
+
+    fs::write(out.join("word-counts.bin.z"), deflate(&blob))
+        .expect("couldn't write the compact corpus");
+}
+
+/// Without the embedded lists, there's nothing to prepare.
+#[cfg(not(feature = "embedded-wordlists"))]
+fn embed() {}
+
+/// `data` deflated as tightly as it goes, for the binary to inflate with
+/// `Dictionary::inflate`.
+#[cfg(feature = "embedded-wordlists")]
+fn deflate(data: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec(data, 10)
+}
@@ -0,0 +1,167 @@
+use std::{error::Error, fmt};
+
+use crate::dictionary::Dictionary;
+
+/// The symbols that words may be spelled with, in order.
+///
+/// Nothing in the solver depends on words being made of letters, so variants
+/// spelled with digits or symbols, such as Primel, only need their alphabet
+/// given (or inferred from the word list). Symbols must be single ASCII
+/// characters, since words are handled as five bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: Vec<u8>,
+    known: [bool; 128],
+}
+
+/// Why an alphabet couldn't be built.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AlphabetError {
+    Empty,
+    NotAscii(char),
+    Duplicate(char),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::Empty => write!(f, "the alphabet is empty"),
+            AlphabetError::NotAscii(c) => write!(f, "{c:?} isn't a printable ASCII symbol"),
+            AlphabetError::Duplicate(c) => write!(f, "{c:?} appears in the alphabet twice"),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+impl Alphabet {
+    /// The symbols of the original game.
+    pub const LATIN: &'static str = "abcdefghijklmnopqrstuvwxyz";
+
+    /// An alphabet of `symbols`, in the order given. Whitespace is ignored.
+    pub fn new(symbols: &str) -> Result<Self, AlphabetError> {
+        let mut alphabet = Self {
+            symbols: Vec::new(),
+            known: [false; 128],
+        };
+
+        for c in symbols.chars().filter(|c| !c.is_whitespace()) {
+            if !c.is_ascii_graphic() {
+                return Err(AlphabetError::NotAscii(c));
+            }
+            if alphabet.contains(c as u8) {
+                return Err(AlphabetError::Duplicate(c));
+            }
+            alphabet.symbols.push(c as u8);
+            alphabet.known[c as usize] = true;
+        }
+
+        if alphabet.symbols.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+        Ok(alphabet)
+    }
+
+    /// The alphabet of every printable ASCII symbol used in `words`, in byte
+    /// order.
+    pub fn infer<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut known = [false; 128];
+        for word in words {
+            for b in word.bytes().filter(u8::is_ascii_graphic) {
+                known[b as usize] = true;
+            }
+        }
+
+        Self {
+            symbols: (0..128u8).filter(|&b| known[b as usize]).collect(),
+            known,
+        }
+    }
+
+    /// The alphabet of every word in `lists`. A run infers it from the
+    /// guesses and corpus as well as the answers, since a short answer list
+    /// may not use every letter the guesses need.
+    pub fn infer_lists<'a>(lists: impl IntoIterator<Item = &'a Dictionary>) -> Self {
+        Self::infer(lists.into_iter().flat_map(Dictionary::words))
+    }
+
+    #[inline]
+    pub fn contains(&self, symbol: u8) -> bool {
+        self.known.get(symbol as usize).copied().unwrap_or(false)
+    }
+
+    /// Whether `word` is five symbols from this alphabet.
+    pub fn spells(&self, word: &str) -> bool {
+        word.len() == 5 && word.bytes().all(|b| self.contains(b))
+    }
+
+    /// The position of `symbol` in the alphabet.
+    pub fn index(&self, symbol: u8) -> Option<usize> {
+        self.symbols.iter().position(|&s| s == symbol)
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::new(Self::LATIN).expect("the latin alphabet is valid")
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &symbol in &self.symbols {
+            write!(f, "{}", symbol as char)?;
+        }
+        Ok(())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn latin() {
+        let alphabet = Alphabet::default();
+        assert_eq!(alphabet.len(), 26);
+        assert!(alphabet.spells("cigar"));
+        assert!(!alphabet.spells("CIGAR"));
+        assert!(!alphabet.spells("cigars"));
+        assert_eq!(alphabet.index(b'c'), Some(2));
+    }
+
+    #[test]
+    fn digits() {
+        let alphabet = Alphabet::new("0123456789").unwrap();
+        assert!(alphabet.spells("10007"));
+        assert!(!alphabet.spells("cigar"));
+        assert_eq!(Alphabet::infer(["10007", "99991"]).to_string(), "0179");
+    }
+
+    #[test]
+    fn lists() {
+        // Two answers use few letters, but every guess, the opener among them,
+        // stays spelled with the alphabet of all the lists.
+        let answers = Dictionary::embedded("cigar\nrebut\n");
+        let guesses = Dictionary::embedded("salet\nfjord\nquack\nvowel\n");
+        let alphabet = Alphabet::infer_lists([&answers, &guesses]);
+        assert!(guesses.words().all(|word| alphabet.spells(word)));
+        assert!(alphabet.spells(crate::guesser::OPENER));
+        assert!(!Alphabet::infer(answers.words()).spells(crate::guesser::OPENER));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Alphabet::new(" "), Err(AlphabetError::Empty));
+        assert_eq!(Alphabet::new("ab×"), Err(AlphabetError::NotAscii('×')));
+        assert_eq!(Alphabet::new("aba"), Err(AlphabetError::Duplicate('a')));
+    }
+}
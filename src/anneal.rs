@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::random::Rng;
+
+/// A simulated-annealing search for the opening sequence a cost function
+/// scores lowest, such as a benchmark's average.
+///
+/// Each step swaps one word of the sequence for a random word from the
+/// pool, keeping the change if it's better, and otherwise with a chance that
+/// shrinks with how much worse it is and with the temperature. The
+/// temperature cools geometrically from `start` to `end` over the steps, so
+/// the search roams early and settles late. Every sequence is scored once.
+pub struct Annealer {
+    pub steps: usize,
+    /// The temperature at the first step, in units of cost.
+    pub start: f64,
+    /// The temperature at the last step.
+    pub end: f64,
+    rng: Rng,
+}
+
+impl Annealer {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            steps: 200,
+            start: 0.05,
+            end: 0.001,
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// The temperature at `step`.
+    fn temperature(&self, step: usize) -> f64 {
+        let progress = step as f64 / self.steps.saturating_sub(1).max(1) as f64;
+        self.start * (self.end / self.start).powf(progress)
+    }
+
+    /// Search from `initial` over sequences of words from `pool`, with no
+    /// word twice, for the one `cost` scores lowest. `progress` hears each
+    /// new best as it's found, with the step it was found at. Returns the
+    /// best sequence with its cost.
+    pub fn search<'a>(
+        &mut self,
+        pool: &[&'a str],
+        initial: Vec<&'a str>,
+        mut cost: impl FnMut(&[&'a str]) -> f64,
+        mut progress: impl FnMut(usize, &[&'a str], f64),
+    ) -> (Vec<&'a str>, f64) {
+        let mut costs: HashMap<Vec<&'a str>, f64> = HashMap::new();
+        let mut score = |sequence: &[&'a str]| {
+            *costs
+                .entry(sequence.to_vec())
+                .or_insert_with(|| cost(sequence))
+        };
+
+        let mut current = initial;
+        let mut current_cost = score(&current);
+        let (mut best, mut best_cost) = (current.clone(), current_cost);
+        progress(0, &best, best_cost);
+        if current.is_empty() || pool.len() <= current.len() {
+            return (best, best_cost);
+        }
+
+        for step in 1..self.steps {
+            let mut next = current.clone();
+            let word = loop {
+                let word = pool[self.rng.below(pool.len())];
+                if !next.contains(&word) {
+                    break word;
+                }
+            };
+            let position = self.rng.below(next.len());
+            next[position] = word;
+
+            let next_cost = score(&next);
+            let worse = next_cost - current_cost;
+            if worse <= 0.0 || self.rng.next_f64() < (-worse / self.temperature(step)).exp() {
+                (current, current_cost) = (next, next_cost);
+                if current_cost < best_cost {
+                    (best, best_cost) = (current.clone(), current_cost);
+                    progress(step, &best, best_cost);
+                }
+            }
+        }
+        (best, best_cost)
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn cools() {
+        let annealer = Annealer::new(1);
+        assert_eq!(annealer.temperature(0), annealer.start);
+        assert!((annealer.temperature(annealer.steps - 1) - annealer.end).abs() < 1e-12);
+    }
+
+    #[test]
+    fn finds_the_cheapest_pair() {
+        // Each word costs its position in the pool, so the best pair is the
+        // first two words, in either order.
+        let pool = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let cost = |sequence: &[&str]| -> f64 {
+            sequence
+                .iter()
+                .map(|w| pool.iter().position(|p| p == w).unwrap() as f64)
+                .sum()
+        };
+        let mut calls = 0;
+        let (best, score) = Annealer::new(3).with_steps(300).search(
+            &pool,
+            vec!["g", "h"],
+            |sequence| {
+                calls += 1;
+                cost(sequence)
+            },
+            |_, _, _| {},
+        );
+
+        assert_eq!(score, 1.0, "{best:?}");
+        assert!(best.contains(&"a") && best.contains(&"b"));
+        // There are only 56 sequences, and none is scored twice.
+        assert!(calls <= 56);
+    }
+}
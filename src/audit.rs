@@ -0,0 +1,315 @@
+use std::fmt;
+
+use crate::{
+    guesser::Correctness,
+    model::AnswerModel,
+    parallel,
+    pattern::{self, ParseError, Tiles},
+    stats::GUESSES,
+};
+
+/// A share grid as posted: the header's puzzle number and score, if it had
+/// them, and a pattern per row.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grid {
+    pub number: Option<usize>,
+    /// The score claimed, with `Some(None)` for an `X`.
+    pub score: Option<Option<usize>>,
+    pub rows: Vec<[Correctness; 5]>,
+}
+
+impl Grid {
+    /// Read a grid as the game shares it, such as `Wordle 1,234 4/6*`
+    /// followed by its rows. Lines that are neither the header nor a row,
+    /// like a link pasted after the grid, are skipped; a line of tiles that
+    /// isn't five long is an error.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut grid = Self::default();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            match pattern::parse(line) {
+                Ok(mask) => grid.rows.push(mask),
+                // Color letters and digits spell ordinary words too, so only
+                // a line with emoji tiles counts as a broken row.
+                Err(e @ ParseError::WrongLength(_)) if !line.is_ascii() => return Err(e),
+                Err(_) if grid.rows.is_empty() && grid.score.is_none() => grid.header(line),
+                Err(_) => {},
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Take the puzzle number and score from a header line.
+    fn header(&mut self, line: &str) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(at) = words.iter().position(|word| word.contains('/')) else {
+            return;
+        };
+        let score = words[at].split('/').next().unwrap_or("");
+        self.score = match score {
+            "X" | "x" => Some(None),
+            _ => score.parse().ok().map(Some),
+        };
+        let digits = at.checked_sub(1).map(|before| {
+            words[before]
+                .trim_start_matches('#')
+                .replace([',', '.'], "")
+        });
+        self.number = digits.and_then(|digits| digits.parse().ok());
+    }
+}
+
+/// Something about a grid that no honest game could have produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// No word gives row `index` (from zero) against the answer.
+    Impossible(usize),
+    /// Row `index` comes after the answer was already found.
+    AfterSolved(usize),
+    /// The header claims a score the rows don't add up to.
+    WrongScore,
+    /// More rows than the game allows.
+    TooManyRows,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Impossible(index) => {
+                write!(
+                    f,
+                    "row {}: no word gives this against the answer",
+                    index + 1
+                )
+            },
+            Problem::AfterSolved(index) => {
+                write!(f, "row {}: comes after the answer was found", index + 1)
+            },
+            Problem::WrongScore => f.write_str("the score doesn't match the rows"),
+            Problem::TooManyRows => write!(f, "more than {GUESSES} rows"),
+        }
+    }
+}
+
+/// What a grid says about a game for one answer: the words that could have
+/// been played for each row, and anything that couldn't have happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Audit<'a> {
+    pub rows: Vec<([Correctness; 5], Vec<&'a str>)>,
+    pub problems: Vec<Problem>,
+}
+
+impl<'a> Audit<'a> {
+    /// Check `grid` against `answer`, with `words` the legal guesses in the
+    /// order to list them. Any word may be played on any turn, so each row is
+    /// possible exactly when some word gives it on its own.
+    pub fn new(grid: &Grid, answer: &str, words: &[&'a str]) -> Self {
+        let rows: Vec<_> = grid
+            .rows
+            .iter()
+            .map(|&mask| {
+                let fitting: Vec<&str> = words
+                    .iter()
+                    .copied()
+                    .filter(|word| Correctness::compute(answer, word) == mask)
+                    .collect();
+                (mask, fitting)
+            })
+            .collect();
+
+        let mut problems = Vec::new();
+        for (index, (_, fitting)) in rows.iter().enumerate() {
+            if fitting.is_empty() {
+                problems.push(Problem::Impossible(index));
+            }
+        }
+        let solved = grid
+            .rows
+            .iter()
+            .position(|&mask| mask == [Correctness::Correct; 5]);
+        if let Some(solved) = solved {
+            problems.extend((solved + 1..grid.rows.len()).map(Problem::AfterSolved));
+        }
+        if grid.rows.len() > GUESSES {
+            problems.push(Problem::TooManyRows);
+        }
+        let score = solved.map(|index| index + 1).filter(|&n| n <= GUESSES);
+        if grid.score.is_some_and(|claimed| claimed != score) {
+            problems.push(Problem::WrongScore);
+        }
+        Self { rows, problems }
+    }
+
+    /// Whether some sequence of legal guesses gives the grid.
+    pub fn is_possible(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Print each row with how many words give it and the first of them,
+    /// shown by `decode`, then every problem found.
+    pub fn print(&self, decode: impl Fn(&str) -> String) {
+        for (index, (mask, fitting)) in self.rows.iter().enumerate() {
+            let words = match fitting[..] {
+                [] => "no words".to_string(),
+                [only] => format!("only {}", decode(only)),
+                [first, ..] => format!("{} words, such as {}", fitting.len(), decode(first)),
+            };
+            println!("row {} {} {words}", index + 1, Tiles(*mask));
+        }
+        println!();
+        if self.is_possible() {
+            println!("possible: legal guesses give every row");
+        }
+        for problem in &self.problems {
+            println!("impossible: {problem}");
+        }
+    }
+}
+
+/// An answer a grid could have been played for, and how likely it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reading<'a> {
+    pub answer: &'a str,
+    /// The chance this was the answer, given the grid.
+    pub probability: f64,
+    /// How many words give each row against the answer.
+    pub counts: Vec<usize>,
+    /// A guess for each row: the first word that gives it.
+    pub example: Vec<&'a str>,
+}
+
+/// Every one of `answers` that `grid` could have been played for, likeliest
+/// first, working back from the rows alone. Each guess is taken to be any of
+/// `words` with equal chance, so an answer that many words give every row for
+/// is likelier than one that only a few do, and the model weighs answers
+/// before any row is seen. `words` should hold the answers, so a winning row
+/// can be explained.
+pub fn readings<'a>(
+    grid: &Grid,
+    answers: &[&'a str],
+    words: &[&'a str],
+    model: &AnswerModel,
+) -> Vec<Reading<'a>> {
+    let work = answers.len() * words.len();
+    let fits = parallel::map(answers, work, |&answer| {
+        let mut counts = vec![0; grid.rows.len()];
+        let mut example = vec![None; grid.rows.len()];
+        for &word in words {
+            let mask = Correctness::compute(answer, word);
+            for (row, _) in grid.rows.iter().enumerate().filter(|&(_, &r)| r == mask) {
+                counts[row] += 1;
+                example[row].get_or_insert(word);
+            }
+        }
+        (counts, example)
+    });
+
+    let prior = model.distribution(answers);
+    let guesses = words.len().max(1) as f64;
+    let mut readings: Vec<Reading> = answers
+        .iter()
+        .zip(fits)
+        .zip(prior)
+        .filter_map(|((&answer, (counts, example)), prior)| {
+            let example = example.into_iter().collect::<Option<Vec<_>>>()?;
+            let likelihood: f64 = counts.iter().map(|&n| n as f64 / guesses).product();
+            Some(Reading {
+                answer,
+                probability: prior * likelihood,
+                counts,
+                example,
+            })
+        })
+        .collect();
+
+    let total: f64 = readings.iter().map(|reading| reading.probability).sum();
+    if total > 0.0 {
+        for reading in &mut readings {
+            reading.probability /= total;
+        }
+    }
+    readings.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+    readings
+}
+
+/// Print the first `n` readings: each answer, its chance, and a sequence of
+/// guesses that gives the grid, shown by `decode`.
+pub fn print_readings(readings: &[Reading], n: usize, decode: impl Fn(&str) -> String) {
+    println!("{} answers fit the grid", readings.len());
+    if readings.is_empty() {
+        return;
+    }
+    println!();
+    println!("{:<8} {:>8}  for example", "answer", "chance");
+    for reading in readings.iter().take(n) {
+        let example: Vec<String> = reading.example.iter().map(|word| decode(word)).collect();
+        println!(
+            "{:<8} {:>7.2}%  {}",
+            decode(reading.answer),
+            reading.probability * 100.0,
+            example.join(" ")
+        );
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let grid =
+            Grid::parse("Wordle 1,234 3/6*\n\n⬛🟨🟨⬛⬛\n🟩⬛⬛🟩🟨\n🟩🟩🟩🟩🟩\n").unwrap();
+        assert_eq!(grid.number, Some(1234));
+        assert_eq!(grid.score, Some(Some(3)));
+        assert_eq!(grid.rows.len(), 3);
+
+        let grid = Grid::parse("Wordle 987 X/6\n⬛⬛⬛⬛⬛\nhttps://example.com").unwrap();
+        assert_eq!((grid.number, grid.score), (Some(987), Some(None)));
+        assert_eq!(grid.rows.len(), 1);
+
+        assert!(Grid::parse("Wordle 1 2/6\n⬛🟨🟨⬛\n🟩🟩🟩🟩🟩").is_err());
+        assert_eq!(Grid::parse("⬛⬛⬛⬛⬛").unwrap().number, None);
+    }
+
+    #[test]
+    fn audit() {
+        let words = ["tares", "hatch", "cigar", "crony"];
+        let grid = Grid::parse("Wordle 0 2/6\n⬛🟨🟨⬛⬛\n🟩🟩🟩🟩🟩").unwrap();
+        let audit = Audit::new(&grid, "cigar", &words);
+        assert!(audit.is_possible());
+        assert_eq!(audit.rows[0].1, ["tares"]);
+
+        // Four greens and a yellow can't happen: the yellow letter would have
+        // to be where a green one is.
+        let grid = Grid::parse("Wordle 0 3/6\n🟩🟩🟩🟩🟨\n🟩🟩🟩🟩🟩\n⬛⬛⬛⬛⬛").unwrap();
+        let audit = Audit::new(&grid, "cigar", &words);
+        assert_eq!(
+            audit.problems,
+            [
+                Problem::Impossible(0),
+                Problem::Impossible(2),
+                Problem::AfterSolved(2),
+                Problem::WrongScore,
+            ]
+        );
+    }
+
+    #[test]
+    fn work_back() {
+        let answers = ["hatch", "catch", "cigar"];
+        let words = ["hatch", "catch", "cigar", "latch", "match"];
+        let model = AnswerModel::new([("hatch", 1), ("catch", 1), ("cigar", 1)]);
+        let grid = Grid::parse("⬛🟩🟩🟩🟩\n🟩🟩🟩🟩🟩").unwrap();
+        let readings = readings(&grid, &answers, &words, &model);
+
+        // Only cigar has no row of four greens to give.
+        let found: Vec<&str> = readings.iter().map(|r| r.answer).collect();
+        assert_eq!(found.len(), 2);
+        assert!(!found.contains(&"cigar"));
+        // The first row could be any of the three other "-atch" words for
+        // either of the two left, so they tie.
+        assert_eq!(readings[0].counts, [3, 1]);
+        assert!((readings[0].probability - 0.5).abs() < 1e-9);
+        assert_eq!(readings[0].example.len(), 2);
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::Instant;
+
+use crate::{
+    game::KnownAnswer,
+    guesser::{Guesser, SolveResult},
+    report::{Outcome, Report},
+};
+
+/// Solve each of `answers` in turn with `guesser`, and collect how every one
+/// went, with the run's wall-clock time.
+///
+/// This is the whole benchmark, for anything that wants its results rather
+/// than its printout. Each answer solved is ruled out for the rest of the
+/// run, as the daily game never repeats one.
+pub fn run<'a>(guesser: &mut Guesser<'a, '_>, answers: &[&'a str]) -> Report<'a> {
+    run_with(guesser, answers, |guesser, answer| {
+        let duplicates = guesser.duplicates();
+        guesser.solve(&mut KnownAnswer { answer, duplicates })
+    })
+}
+
+/// [`run`], playing each answer with `play`, which is handed the guesser
+/// reset for it, as when every guess is to be traced.
+pub fn run_with<'a, 'b>(
+    guesser: &mut Guesser<'a, 'b>,
+    answers: &[&'a str],
+    play: impl FnMut(&mut Guesser<'a, 'b>, &'a str) -> SolveResult<'a>,
+) -> Report<'a> {
+    run_following(guesser, answers, play, |_| {})
+}
+
+/// [`run_with`], handing each answer's outcome to `follow` as soon as it's
+/// done, for a long run to show as it goes.
+pub fn run_following<'a, 'b>(
+    guesser: &mut Guesser<'a, 'b>,
+    answers: &[&'a str],
+    mut play: impl FnMut(&mut Guesser<'a, 'b>, &'a str) -> SolveResult<'a>,
+    mut follow: impl FnMut(&Outcome<'a>),
+) -> Report<'a> {
+    let mut report = Report::default();
+    let start = Instant::now();
+    for &answer in answers {
+        guesser.reset();
+        let result = play(guesser, answer);
+        let outcome = Outcome {
+            answer,
+            result,
+            timings: guesser.timings(),
+        };
+        if outcome.solved() {
+            guesser.exclude(answer);
+        }
+        follow(&outcome);
+        report.push(outcome);
+    }
+
+    report.set_elapsed(start.elapsed());
+    report
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::{model::AnswerModel, strategy::Likeliest};
+
+    #[test]
+    fn runs() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        let report = run(&mut guesser, &["catch", "hatch", "match"]);
+        let answers: Vec<&str> = report.outcomes().iter().map(|o| o.answer).collect();
+        assert_eq!(answers, ["catch", "hatch", "match"]);
+        // With catch and then hatch ruled out, the salet opener leaves only
+        // match.
+        assert_eq!(report.distribution(), [0, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(report.failures().count(), 0);
+
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+        let mut played = Vec::new();
+        let report = run_with(&mut guesser, &["match"], |guesser, answer| {
+            played.push(answer);
+            guesser.solve(&mut KnownAnswer::new(answer))
+        });
+        assert_eq!(played, ["match"]);
+        assert_eq!(report.outcomes()[0].guesses(), Some(4));
+
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+        let mut followed = Vec::new();
+        let report = run_following(
+            &mut guesser,
+            &["hatch", "match"],
+            |guesser, answer| guesser.solve(&mut KnownAnswer::new(answer)),
+            |outcome| followed.push(outcome.line()),
+        );
+        let lines: Vec<String> = report.outcomes().iter().map(Outcome::line).collect();
+        assert_eq!(followed, lines);
+    }
+}
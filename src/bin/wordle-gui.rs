@@ -0,0 +1,398 @@
+//! A desktop front end: the solver's suggestions on a board whose tiles are
+//! clicked to the colors the game showed, for solving without a terminal.
+//!
+//! Build with `cargo run --features gui --bin wordle-gui`. Pass
+//! `--high-contrast` for orange and blue tiles, and `--symbols` to mark each
+//! tile with a shape too, as the terminal does.
+
+use std::collections::HashSet;
+
+use eframe::egui::{self, Color32, Event, Key as KeyCode, RichText, Vec2};
+use wordle::{
+    alphabet::Alphabet,
+    dictionary::Dictionary,
+    guesser::{Correctness, Guesser},
+    hint::{self, Scoring},
+    keyboard::{Key, Keyboard, ROWS},
+    model::AnswerModel,
+    pattern::{self, Palette},
+    strategy::{Likeliest, Strategy},
+};
+
+static ANSWERS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/answers.txt.z"));
+static DICTIONARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/word-counts.bin.z"));
+static GUESSES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/valid-words.txt.z"));
+
+/// How many suggestions the side panel shows.
+const SUGGESTED: usize = 8;
+
+/// Above this many candidates, only the solver's own choice is suggested;
+/// scoring every candidate against every other would stall the window.
+const SUGGESTIBLE: usize = 1_500;
+
+/// How many candidates the list shows before summarizing the rest.
+const LISTED: usize = 500;
+
+const TILE: f32 = 56.0;
+
+const GREEN: Color32 = Color32::from_rgb(106, 170, 100);
+const YELLOW: Color32 = Color32::from_rgb(201, 180, 88);
+// The game's high-contrast colors, for `--high-contrast`.
+const ORANGE: Color32 = Color32::from_rgb(245, 121, 58);
+const BLUE: Color32 = Color32::from_rgb(133, 192, 249);
+const GRAY: Color32 = Color32::from_rgb(120, 124, 126);
+const BLANK: Color32 = Color32::from_rgb(211, 214, 218);
+
+fn tile_color(c: Correctness) -> Color32 {
+    match (c, pattern::palette().high_contrast) {
+        (Correctness::Correct, false) => GREEN,
+        (Correctness::Misplaced, false) => YELLOW,
+        (Correctness::Correct, true) => ORANGE,
+        (Correctness::Misplaced, true) => BLUE,
+        (Correctness::Wrong, _) => GRAY,
+    }
+}
+
+fn key_color(key: Key) -> Color32 {
+    key.correctness().map_or(BLANK, tile_color)
+}
+
+/// The tile's next color when clicked: gray, then yellow, then green.
+fn cycle(c: Correctness) -> Correctness {
+    match c {
+        Correctness::Wrong => Correctness::Misplaced,
+        Correctness::Misplaced => Correctness::Correct,
+        Correctness::Correct => Correctness::Wrong,
+    }
+}
+
+struct App {
+    guesser: Guesser<'static, 'static>,
+    /// Every word that may be played.
+    words: HashSet<&'static str>,
+    model: &'static AnswerModel<'static>,
+    /// The word on the current row, the solver's choice unless one was
+    /// picked or typed.
+    pending: Option<&'static str>,
+    /// The colors clicked onto the current row so far.
+    mask: [Correctness; 5],
+    /// Letters typed toward a word of the player's own.
+    typed: String,
+    /// The best guesses for the current candidates, with their information
+    /// and the guesses each is expected to take.
+    suggestions: Vec<(&'static str, f64, f64)>,
+    message: Option<String>,
+}
+
+impl App {
+    fn new(
+        guesser: Guesser<'static, 'static>,
+        words: HashSet<&'static str>,
+        model: &'static AnswerModel<'static>,
+    ) -> Self {
+        let mut app = Self {
+            guesser,
+            words,
+            model,
+            pending: None,
+            mask: [Correctness::Wrong; 5],
+            typed: String::new(),
+            suggestions: Vec::new(),
+            message: None,
+        };
+        app.next_turn();
+        app
+    }
+
+    /// Set up the current row for the next guess.
+    fn next_turn(&mut self) {
+        self.pending = self.guesser.next_guess();
+        self.mask = [Correctness::Wrong; 5];
+        self.typed.clear();
+
+        let candidates = self.guesser.candidates();
+        self.suggestions = if self.guesser.history().is_empty() {
+            Vec::new()
+        } else if candidates.len() <= SUGGESTIBLE {
+            hint::suggest(
+                candidates,
+                candidates,
+                self.model,
+                Scoring::Information,
+                SUGGESTED,
+            )
+            .into_iter()
+            .map(|s| (s.word, s.information, s.expected_guesses))
+            .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn solved(&self) -> bool {
+        self.guesser
+            .history()
+            .last()
+            .is_some_and(|g| g.is_correct())
+    }
+
+    /// Play `word` on the current row instead of the solver's choice.
+    fn pick(&mut self, word: &'static str) {
+        self.pending = Some(word);
+        self.mask = [Correctness::Wrong; 5];
+        self.typed.clear();
+        self.message = None;
+    }
+
+    fn submit(&mut self) {
+        let Some(word) = self.pending else {
+            return;
+        };
+        let remaining = self.guesser.apply_feedback(word, self.mask);
+        self.message = if self.solved() {
+            Some(format!("solved in {}", self.guesser.history().len()))
+        } else if remaining == 0 {
+            Some("no words fit that feedback; undo to correct it".to_string())
+        } else {
+            None
+        };
+        self.next_turn();
+    }
+
+    fn undo(&mut self) {
+        self.message = self
+            .guesser
+            .undo()
+            .is_none()
+            .then(|| "nothing to undo".to_string());
+        self.next_turn();
+    }
+
+    fn reset(&mut self) {
+        self.guesser.reset();
+        self.message = None;
+        self.next_turn();
+    }
+
+    fn type_letter(&mut self, letter: char) {
+        if self.typed.len() < 5 && letter.is_ascii_alphabetic() {
+            self.typed.push(letter.to_ascii_lowercase());
+        }
+    }
+
+    /// Play the typed word, if it's one that may be played.
+    fn enter(&mut self) {
+        if self.typed.is_empty() {
+            return self.submit();
+        }
+        match self.words.get(self.typed.as_str()) {
+            Some(&word) => self.pick(word),
+            None => self.message = Some(format!("{}: not in word list", self.typed)),
+        }
+    }
+
+    fn handle_keys(&mut self, ui: &egui::Ui) {
+        let events = ui.input(|i| i.events.clone());
+        for event in events {
+            match event {
+                Event::Text(text) => text.chars().for_each(|c| self.type_letter(c)),
+                Event::Key {
+                    key: KeyCode::Backspace,
+                    pressed: true,
+                    ..
+                } => {
+                    self.typed.pop();
+                },
+                Event::Key {
+                    key: KeyCode::Enter,
+                    pressed: true,
+                    ..
+                } => self.enter(),
+                _ => {},
+            }
+        }
+    }
+
+    fn board(&mut self, ui: &mut egui::Ui) {
+        let tile = |ui: &mut egui::Ui, letter: char, c: Option<Correctness>| {
+            let mut label = letter.to_ascii_uppercase().to_string();
+            if let Some(c) = c.filter(|_| pattern::palette().symbols) {
+                label.push(c.symbol());
+            }
+            let fill = c.map_or(BLANK, tile_color);
+            let text = RichText::new(label)
+                .size(28.0)
+                .strong()
+                .color(Color32::WHITE);
+            ui.add(
+                egui::Button::new(text)
+                    .fill(fill)
+                    .min_size(Vec2::splat(TILE)),
+            )
+        };
+
+        for guess in self.guesser.history() {
+            ui.horizontal(|ui| {
+                for (letter, c) in guess.word().chars().zip(guess.mask()) {
+                    tile(ui, letter, Some(c));
+                }
+            });
+        }
+        if self.solved() {
+            return;
+        }
+
+        // The current row: typed letters uncolored, or the pending word with
+        // tiles that cycle through the colors when clicked.
+        ui.horizontal(|ui| {
+            if !self.typed.is_empty() {
+                let mut letters = self.typed.chars();
+                for _ in 0..5 {
+                    tile(ui, letters.next().unwrap_or(' '), None);
+                }
+            } else if let Some(word) = self.pending {
+                for (i, letter) in word.chars().enumerate() {
+                    if tile(ui, letter, Some(self.mask[i])).clicked() {
+                        self.mask[i] = cycle(self.mask[i]);
+                    }
+                }
+            }
+        });
+    }
+
+    fn keyboard(&mut self, ui: &mut egui::Ui) {
+        let keyboard = Keyboard::from_history(self.guesser.history());
+        for (i, row) in ROWS.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if i == ROWS.len() - 1 && ui.button("enter").clicked() {
+                    self.enter();
+                }
+                for letter in row.bytes() {
+                    let text =
+                        RichText::new((letter as char).to_ascii_uppercase().to_string()).size(18.0);
+                    let key = egui::Button::new(text)
+                        .fill(key_color(keyboard.key(letter)))
+                        .min_size(Vec2::new(32.0, 40.0));
+                    if ui.add(key).clicked() {
+                        self.type_letter(letter as char);
+                    }
+                }
+                if i == ROWS.len() - 1 && ui.button("⌫").clicked() {
+                    self.typed.pop();
+                }
+            });
+        }
+    }
+
+    fn suggestions(&mut self, ui: &mut egui::Ui) {
+        ui.heading("suggestions");
+        let mut picked = None;
+        match (self.pending, self.suggestions.is_empty()) {
+            (Some(word), true) => {
+                if ui.link(format!("{word} (solver)")).clicked() {
+                    picked = Some(word);
+                }
+            },
+            (None, true) => {
+                ui.label("none");
+            },
+            (_, false) => {
+                for &(word, information, guesses) in &self.suggestions {
+                    let label = format!("{word}  {information:.2} bits, {guesses:.2} guesses");
+                    if ui.link(label).clicked() {
+                        picked = Some(word);
+                    }
+                }
+            },
+        }
+
+        ui.separator();
+        let candidates = self.guesser.candidates();
+        ui.heading(format!("{} candidates", candidates.len()));
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for &word in candidates.iter().take(LISTED) {
+                if ui.link(word).clicked() {
+                    picked = Some(word);
+                }
+            }
+            if candidates.len() > LISTED {
+                ui.label(format!("and {} more", candidates.len() - LISTED));
+            }
+        });
+
+        if let Some(word) = picked {
+            self.pick(word);
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+        self.handle_keys(ui);
+
+        egui::Panel::right("suggestions")
+            .default_size(220.0)
+            .show(ui, |ui| self.suggestions(ui));
+
+        egui::CentralPanel::default_margins().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("enter feedback").clicked() {
+                    self.submit();
+                }
+                if ui.button("undo").clicked() {
+                    self.undo();
+                }
+                if ui.button("new game").clicked() {
+                    self.reset();
+                }
+            });
+            ui.label("click a tile to cycle its color, or type a word of your own");
+            if let Some(message) = &self.message {
+                ui.label(RichText::new(message).strong());
+            }
+            ui.separator();
+
+            self.board(ui);
+            ui.add_space(16.0);
+            self.keyboard(ui);
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    pattern::set_palette(Palette {
+        high_contrast: args.iter().any(|arg| arg == "--high-contrast"),
+        symbols: args.iter().any(|arg| arg == "--symbols"),
+    });
+
+    // The window lives as long as the process, so the word lists and model
+    // are leaked to let the guesser borrow them for good.
+    let answers = Dictionary::inflate(ANSWERS);
+    let corpus: &'static Dictionary = Box::leak(Box::new(Dictionary::inflate_compact(DICTIONARY)));
+    // Guesses missing from the corpus are still fine to play.
+    let guesses: &'static Dictionary = Box::leak(Box::new(Dictionary::inflate(GUESSES)));
+    let alphabet = Alphabet::infer_lists([&answers, corpus, guesses]);
+    let mut pairs = corpus.by_frequency();
+    pairs.retain(|(word, _)| alphabet.spells(word));
+
+    let model: &'static AnswerModel = Box::leak(Box::new(AnswerModel::new(pairs.iter().copied())));
+    let dictionary: &'static [&'static str] =
+        Box::leak(pairs.iter().map(|&(word, _)| word).collect());
+    let mut words: HashSet<&'static str> = dictionary.iter().copied().collect();
+    words.extend(guesses.words().filter(|word| alphabet.spells(word)));
+
+    let exclusions: &'static HashSet<&'static str> = Box::leak(Box::default());
+    let strategy: &'static dyn Strategy = &Likeliest;
+    let guesser = Guesser::new(dictionary, exclusions, model, strategy);
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([720.0, 640.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "wordle",
+        options,
+        Box::new(|_| Ok(Box::new(App::new(guesser, words, model)))),
+    )
+}
@@ -0,0 +1,441 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    guesser::Guesser,
+    json::{self, Value},
+    league::League,
+    pattern,
+    report::MAX_GUESSES,
+    server::{Api, Request, Response},
+};
+
+/// What a message must start with to be taken as a command.
+pub const PREFIX: &str = "!wordle";
+
+/// How many suggestions a reply lists beside the solver's guess.
+const SUGGESTED: usize = 3;
+
+/// How many candidates a reply lists, when that few are left.
+const LISTED: usize = 5;
+
+const USAGE: &str = "\
+`!wordle suggest [word=feedback]...`: the next guess for a game played elsewhere, \
+with feedback as in `crane=BYBBG`
+`!wordle guess <word>`: play today's shared puzzle
+`!wordle board`: today's leaderboard";
+
+/// How long the bot waits between checks for new messages, which keeps it
+/// well inside Discord's rate limits.
+pub const INTERVAL: Duration = Duration::from_secs(3);
+
+const DISCORD: &str = "https://discord.com/api/v10";
+
+/// A message posted to a channel the bot watches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub id: u64,
+    pub channel: String,
+    pub author: String,
+    /// Whether another bot posted it, so it's never answered.
+    pub bot: bool,
+    pub content: String,
+}
+
+impl Message {
+    /// A message from the JSON Discord describes it with.
+    fn parse(value: &Value, channel: &str) -> Option<Self> {
+        let author = value.get("author")?;
+        Some(Self {
+            id: value.get("id")?.as_str()?.parse().ok()?,
+            channel: channel.to_string(),
+            author: author.get("username")?.as_str()?.to_string(),
+            bot: author.get("bot").and_then(Value::as_bool).unwrap_or(false),
+            content: value.get("content")?.as_str()?.to_string(),
+        })
+    }
+}
+
+/// Answer a chat command from `author`, sent on `day`: suggestions come from
+/// `api` and the shared daily game from `league`. `None` if `content` isn't
+/// a command.
+///
+/// Replies show a guess's feedback only as tiles, so players sharing a
+/// channel don't give the answer away to each other.
+pub fn reply<'a: 'b, 'b, G, F>(
+    api: &Api<'a, '_, G>,
+    league: &mut League<'a, F>,
+    author: &str,
+    content: &str,
+    day: i64,
+) -> Option<String>
+where
+    G: Fn() -> Guesser<'a, 'b>,
+    F: Fn(i64) -> Option<(usize, &'a str)>,
+{
+    let command = content.trim().strip_prefix(PREFIX)?;
+    if !command.is_empty() && !command.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let reply = match words[..] {
+        ["suggest", ref given @ ..] => suggest(api, given),
+        ["guess", word] => {
+            let body = format!(
+                "{{\"player\":{},\"word\":{}}}",
+                json::quote(&player(author)),
+                json::quote(word)
+            );
+            call(
+                |request| league.handle(request, day),
+                "POST",
+                "/daily/guess",
+                body,
+            )
+            .map(|game| played(author, &game))
+        },
+        ["board"] => call(
+            |request| league.handle(request, day),
+            "GET",
+            "/daily/leaderboard",
+            String::new(),
+        )
+        .map(|board| leaderboard(&board)),
+        _ => Ok(USAGE.to_string()),
+    };
+    Some(reply.unwrap_or_else(|message| format!("{author}: {message}")))
+}
+
+/// The reply to `suggest`, given guesses written as `word=feedback`.
+fn suggest<'a: 'b, 'b, G: Fn() -> Guesser<'a, 'b>>(
+    api: &Api<'a, '_, G>,
+    given: &[&str],
+) -> Result<String, String> {
+    let history: Vec<String> = given
+        .iter()
+        .map(|guess| {
+            let (word, feedback) = guess
+                .split_once('=')
+                .ok_or_else(|| format!("{guess}: give each guess as word=feedback"))?;
+            Ok(format!(
+                "{{\"word\":{},\"feedback\":{}}}",
+                json::quote(word),
+                json::quote(feedback)
+            ))
+        })
+        .collect::<Result<_, String>>()?;
+    let body = format!(
+        "{{\"top\":{SUGGESTED},\"history\":[{}]}}",
+        history.join(",")
+    );
+    let result = call(|request| api.handle(request), "POST", "/suggest", body)?;
+
+    let count = result
+        .get("candidates")
+        .and_then(Value::as_usize)
+        .unwrap_or(0);
+    let Some(guess) = result.get("guess").and_then(Value::as_str) else {
+        return Err("no word fits that feedback".to_string());
+    };
+    let words = |key: &str, field: Option<&str>| -> Vec<String> {
+        let values = result
+            .get(key)
+            .and_then(Value::as_array)
+            .unwrap_or_default();
+        values
+            .iter()
+            .filter_map(|value| field.map_or(Some(value), |field| value.get(field)))
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let mut reply = format!("{count} left; try **{guess}**");
+    let others: Vec<String> = words("suggestions", Some("word"))
+        .into_iter()
+        .filter(|word| word != guess)
+        .collect();
+    if !others.is_empty() {
+        reply.push_str(&format!(" (or {})", others.join(", ")));
+    }
+    if (2..=LISTED).contains(&count) {
+        reply.push_str(&format!("\ncould be: {}", words("sample", None).join(", ")));
+    }
+    Ok(reply)
+}
+
+/// A player's game so far, as tiles.
+fn played(author: &str, game: &Value) -> String {
+    let rows = game
+        .get("rows")
+        .and_then(Value::as_array)
+        .unwrap_or_default();
+    let number = game.get("number").and_then(Value::as_usize).unwrap_or(0);
+    let mut reply = format!("{author}: Wordle {number}");
+    for row in rows {
+        let feedback = row.get("feedback").and_then(Value::as_str).unwrap_or("");
+        if let Ok(mask) = pattern::parse(feedback) {
+            reply.push('\n');
+            let palette = pattern::palette();
+            reply.extend(mask.iter().map(|c| c.emoji(palette)));
+        }
+    }
+    if game.get("over").and_then(Value::as_bool) == Some(true) {
+        let last = rows.last().and_then(|row| row.get("feedback")?.as_str());
+        if last == Some("GGGGG") {
+            reply.push_str(&format!("\nsolved in {}", rows.len()));
+        } else {
+            reply.push_str("\nout of guesses");
+        }
+    }
+    reply
+}
+
+/// The leaderboard, a line per player.
+fn leaderboard(board: &Value) -> String {
+    let players = board
+        .get("players")
+        .and_then(Value::as_array)
+        .unwrap_or_default();
+    if players.is_empty() {
+        return "no one has played yet".to_string();
+    }
+    let lines: Vec<String> = players
+        .iter()
+        .map(|p| {
+            let number = |key: &str| p.get(key).and_then(Value::as_usize);
+            let today = number("today").map_or("-".to_string(), |n| format!("{n}/{MAX_GUESSES}"));
+            format!(
+                "{}: today {today}, streak {}, won {}/{}",
+                p.get("player").and_then(Value::as_str).unwrap_or("?"),
+                number("streak").unwrap_or(0),
+                number("wins").unwrap_or(0),
+                number("played").unwrap_or(0)
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Make a request of a handler, as though over HTTP, and read its JSON
+/// result or error.
+fn call(
+    handle: impl FnOnce(&Request) -> Response,
+    method: &str,
+    path: &str,
+    body: String,
+) -> Result<Value, String> {
+    let response = handle(&Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    });
+    let value = json::parse(&response.body).map_err(|e| e.to_string())?;
+    match response.status {
+        200 => Ok(value),
+        _ => Err(value
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("something went wrong")
+            .to_string()),
+    }
+}
+
+/// A chat name as the league accepts it: letters, digits, dashes, and
+/// underscores, with anything else made a dash.
+fn player(author: &str) -> String {
+    author
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(20)
+        .collect()
+}
+
+/// A bot account on Discord, reached over its HTTP API with `curl`.
+pub struct Discord {
+    token: String,
+}
+
+impl Discord {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+
+    /// The messages in `channel` after the one with id `after`, oldest
+    /// first, or only the latest if `after` is `None`.
+    pub fn messages(&self, channel: &str, after: Option<u64>) -> io::Result<Vec<Message>> {
+        let query = match after {
+            Some(after) => format!("after={after}&limit=50"),
+            None => "limit=1".to_string(),
+        };
+        let path = format!("/channels/{channel}/messages?{query}");
+        let body = self.call("GET", &path, None)?;
+        let value = json::parse(&body).map_err(io::Error::other)?;
+        let mut messages: Vec<Message> = value
+            .as_array()
+            .ok_or_else(|| io::Error::other("expected a list of messages"))?
+            .iter()
+            .filter_map(|message| Message::parse(message, channel))
+            .collect();
+        messages.sort_by_key(|message| message.id);
+        Ok(messages)
+    }
+
+    /// Post `text` to `channel`.
+    pub fn send(&self, channel: &str, text: &str) -> io::Result<()> {
+        let body = format!("{{\"content\":{}}}", json::quote(text));
+        self.call(
+            "POST",
+            &format!("/channels/{channel}/messages"),
+            Some(&body),
+        )?;
+        Ok(())
+    }
+
+    /// Call the API, with the token and body handed to `curl` on its stdin
+    /// so they never show in a process list.
+    fn call(&self, method: &str, path: &str, body: Option<&str>) -> io::Result<String> {
+        let mut config = format!(
+            "url = \"{}\"\nrequest = \"{method}\"\nheader = \"Authorization: Bot {}\"\n",
+            escape(&format!("{DISCORD}{path}")),
+            escape(&self.token)
+        );
+        if let Some(body) = body {
+            config.push_str("header = \"Content-Type: application/json\"\n");
+            config.push_str(&format!("data-binary = \"{}\"\n", escape(body)));
+        }
+        let output = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--max-time", "30"])
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(config.as_bytes())?;
+                }
+                child.wait_with_output()
+            })?;
+
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(message.trim().to_string()));
+        }
+        String::from_utf8(output.stdout).map_err(io::Error::other)
+    }
+}
+
+/// `text` as a quoted string in a `curl` config file.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Watch `channels` on `discord`, checking every `interval` for messages
+/// posted since the bot started, and post whatever `reply` makes of each one
+/// from a person. Fails only if a channel can't be read at the start; later
+/// failures, as when rate limited, are reported and tried again.
+pub fn run(
+    discord: &Discord,
+    channels: &[String],
+    interval: Duration,
+    mut reply: impl FnMut(&Message) -> Option<String>,
+) -> io::Result<()> {
+    let mut last: Vec<u64> = channels
+        .iter()
+        .map(|channel| {
+            let latest = discord.messages(channel, None)?;
+            Ok(latest.last().map_or(0, |message| message.id))
+        })
+        .collect::<io::Result<_>>()?;
+
+    loop {
+        thread::sleep(interval);
+        for (channel, last) in channels.iter().zip(&mut last) {
+            let messages = match discord.messages(channel, Some(*last)) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    eprintln!("warning: couldn't read channel {channel}: {e}");
+                    continue;
+                },
+            };
+            for message in messages {
+                *last = message.id.max(*last);
+                if message.bot {
+                    continue;
+                }
+                let Some(text) = reply(&message) else {
+                    continue;
+                };
+                if let Err(e) = discord.send(channel, &text) {
+                    eprintln!("warning: couldn't reply in channel {channel}: {e}");
+                }
+            }
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn commands() {
+        let words = ["hatch", "catch", "match", "patch"];
+        let model = crate::model::AnswerModel::new([]);
+        let exclusions = std::collections::HashSet::new();
+        let strategy = crate::strategy::Likeliest;
+        let api = Api::new(
+            || Guesser::new(&words, &exclusions, &model, &strategy).with_opener(None),
+            &words,
+            &model,
+        );
+        let mut league = League::new(|day: i64| Some((day as usize, "match")), &words);
+        let mut say = |author: &str, content: &str| reply(&api, &mut league, author, content, 3);
+
+        assert_eq!(say("ann", "good morning"), None);
+        assert_eq!(say("ann", "!wordlebot"), None);
+        assert_eq!(say("ann", "!wordle").as_deref(), Some(USAGE));
+
+        let suggestion = say("ann", "!wordle suggest hatch=BGGGG").unwrap();
+        assert!(suggestion.starts_with("3 left; try **"), "{suggestion}");
+        assert!(suggestion.contains("could be: catch, match, patch"));
+        let error = say("ann", "!wordle suggest hatch").unwrap();
+        assert!(error.contains("word=feedback"));
+
+        assert_eq!(
+            say("ann.b", "!wordle guess hatch").unwrap(),
+            "ann.b: Wordle 3\n⬛🟩🟩🟩🟩"
+        );
+        let won = say("ann.b", "!wordle guess match").unwrap();
+        assert!(won.ends_with("\nsolved in 2"));
+        // The answer never shows in the channel.
+        assert!(!won.contains("match"));
+        assert!(say("bob", "!wordle guess zzzzz")
+            .unwrap()
+            .contains("not in word list"));
+        assert_eq!(
+            say("bob", "!wordle board").unwrap(),
+            "ann-b: today 2/6, streak 1, won 1/1"
+        );
+    }
+
+    #[test]
+    fn config() {
+        assert_eq!(
+            escape(r#"{"content":"a \"b\""}"#),
+            r#"{\"content\":\"a \\\"b\\\"\"}"#
+        );
+    }
+}
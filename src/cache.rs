@@ -0,0 +1,274 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    engine::Duplicates,
+    guesser::{Correctness, Guess},
+    knowledge::KnowledgeState,
+    packed::Packed,
+};
+
+const MAGIC: &[u8; 4] = b"WRC2";
+
+/// Identifies a candidate pool within a [`ReductionCache`].
+pub type PoolId = usize;
+
+/// Memoizes the candidate sets that result from applying a guess and its
+/// feedback to a candidate pool.
+///
+/// Pools are stored as indices into the word list the cache was built with,
+/// and every distinct reduction gets its own id, so the result of playing the
+/// same guess against the same pool is only ever computed once, no matter how
+/// many answers lead there. Pools with the same words share an id however
+/// they were reached, so an id stands for a set of candidates.
+pub struct ReductionCache<'a> {
+    words: &'a [&'a str],
+    /// The same words packed together, which is what reductions filter.
+    packed: Packed,
+    pools: Vec<Vec<u32>>,
+    /// The id of each distinct pool.
+    ids: HashMap<Vec<u32>, PoolId>,
+    /// The pool each pool reduces to under a guess, keyed by the guess's
+    /// letters and its feedback's code.
+    reductions: HashMap<(PoolId, [u8; 5], u8), PoolId>,
+    /// How the feedback reduced by colors repeated letters.
+    duplicates: Duplicates,
+    hits: usize,
+    misses: usize,
+}
+
+impl<'a> ReductionCache<'a> {
+    /// The pool containing every word.
+    pub const ROOT: PoolId = 0;
+
+    pub fn new(words: &'a [&'a str]) -> Self {
+        let root: Vec<u32> = (0..words.len() as u32).collect();
+        Self {
+            words,
+            packed: Packed::new(words),
+            ids: [(root.clone(), Self::ROOT)].into_iter().collect(),
+            pools: vec![root],
+            reductions: HashMap::new(),
+            duplicates: Duplicates::Official,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Reduce by feedback colored by `rule`, rather than as the official game
+    /// colors it. Reductions don't hold from one rule to another, so a saved
+    /// cache should only be loaded for the rule it was built with.
+    pub fn with_duplicates(mut self, rule: Duplicates) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    /// The pool of words in `pool` that are consistent with `guess`.
+    pub fn reduce(&mut self, pool: PoolId, guess: &Guess<'a>) -> PoolId {
+        let key = (
+            pool,
+            word_key(guess.word()),
+            Correctness::encode(guess.mask()),
+        );
+        if let Some(&reduced) = self.reductions.get(&key) {
+            self.hits += 1;
+            return reduced;
+        }
+
+        self.misses += 1;
+        let knowledge = KnowledgeState::from_history_with(&[*guess], self.duplicates);
+        let reduced: Vec<u32> = self.pools[pool]
+            .iter()
+            .copied()
+            .filter(|&i| knowledge.allows_bytes(self.packed.get(i as usize)))
+            .collect();
+
+        let id = match self.ids.get(&reduced) {
+            Some(&id) => id,
+            None => {
+                let id = self.pools.len();
+                self.ids.insert(reduced.clone(), id);
+                self.pools.push(reduced);
+                id
+            },
+        };
+        self.reductions.insert(key, id);
+        id
+    }
+
+    /// The words in `pool`, in the order of the original word list.
+    pub fn words(&self, pool: PoolId) -> impl Iterator<Item = &'a str> + '_ {
+        self.pools[pool].iter().map(|&i| self.words[i as usize])
+    }
+
+    /// A stable hash of a word list, used to key saved caches.
+    pub fn fingerprint(words: &[&str]) -> u64 {
+        // FNV-1a, which unlike `DefaultHasher` is stable across releases.
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for word in words {
+            for byte in word.bytes().chain([b'\n']) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    }
+
+    /// Write the cache in a compact binary format.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&Self::fingerprint(self.words).to_le_bytes())?;
+
+        w.write_all(&(self.pools.len() as u32).to_le_bytes())?;
+        for pool in &self.pools {
+            w.write_all(&(pool.len() as u32).to_le_bytes())?;
+            for index in pool {
+                w.write_all(&index.to_le_bytes())?;
+            }
+        }
+
+        w.write_all(&(self.reductions.len() as u32).to_le_bytes())?;
+        for ((pool, word, code), reduced) in &self.reductions {
+            w.write_all(&(*pool as u32).to_le_bytes())?;
+            w.write_all(word)?;
+            w.write_all(&[*code])?;
+            w.write_all(&(*reduced as u32).to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a cache written by [`save`](Self::save), which must have been
+    /// built over the same word list.
+    pub fn load(words: &'a [&'a str], mut r: impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a reduction cache"));
+        }
+        if read_u64(&mut r)? != Self::fingerprint(words) {
+            return Err(invalid("cache was built for a different word list"));
+        }
+
+        let mut pools = Vec::new();
+        for _ in 0..read_u32(&mut r)? {
+            let pool = (0..read_u32(&mut r)?)
+                .map(|_| read_u32(&mut r))
+                .collect::<io::Result<Vec<u32>>>()?;
+            if pool.iter().any(|&i| i as usize >= words.len()) {
+                return Err(invalid("word index out of range"));
+            }
+            pools.push(pool);
+        }
+
+        let mut reductions = HashMap::new();
+        for _ in 0..read_u32(&mut r)? {
+            let pool = read_u32(&mut r)? as PoolId;
+            let mut word = [0; 5];
+            r.read_exact(&mut word)?;
+            let mut code = [0; 1];
+            r.read_exact(&mut code)?;
+            let [code] = code;
+            if Correctness::decode(code).is_none() {
+                return Err(invalid("bad pattern"));
+            }
+            let reduced = read_u32(&mut r)? as PoolId;
+
+            if pool >= pools.len() || reduced >= pools.len() {
+                return Err(invalid("pool id out of range"));
+            }
+            reductions.insert((pool, word, code), reduced);
+        }
+
+        let mut ids = HashMap::new();
+        for (id, pool) in pools.iter().enumerate() {
+            ids.entry(pool.clone()).or_insert(id);
+        }
+        Ok(Self {
+            words,
+            packed: Packed::new(words),
+            ids,
+            pools,
+            reductions,
+            duplicates: Duplicates::Official,
+            hits: 0,
+            misses: 0,
+        })
+    }
+
+    /// The number of cache hits and misses so far.
+    pub fn stats(&self) -> (usize, usize) {
+        (self.hits, self.misses)
+    }
+}
+
+#[inline]
+fn word_key(word: &str) -> [u8; 5] {
+    let mut key = [0; 5];
+    key.copy_from_slice(&word.as_bytes()[..5]);
+    key
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    r.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn reduces_once() {
+        let words = ["hatch", "catch", "match", "cigar"];
+        let mut cache = ReductionCache::new(&words);
+
+        let guess = Guess::check("catch", "hatch");
+        let pool = cache.reduce(ReductionCache::ROOT, &guess);
+        assert_eq!(
+            cache.words(pool).collect::<Vec<_>>(),
+            vec!["catch", "match"]
+        );
+
+        // Another answer with the same feedback lands in the same pool.
+        let guess = Guess::check("match", "hatch");
+        assert_eq!(cache.reduce(ReductionCache::ROOT, &guess), pool);
+        assert_eq!(cache.stats(), (1, 1));
+
+        // So does a different guess that leaves the same words.
+        let guess = Guess::check("catch", "hitch");
+        assert_eq!(cache.reduce(ReductionCache::ROOT, &guess), pool);
+        assert_eq!(cache.stats(), (1, 2));
+    }
+
+    #[test]
+    fn round_trip() {
+        let words = ["hatch", "catch", "match", "cigar"];
+        let mut cache = ReductionCache::new(&words);
+        let pool = cache.reduce(ReductionCache::ROOT, &Guess::check("catch", "hatch"));
+
+        let mut bytes = Vec::new();
+        cache.save(&mut bytes).unwrap();
+
+        let mut loaded = ReductionCache::load(&words, bytes.as_slice()).unwrap();
+        let reduced = loaded.reduce(ReductionCache::ROOT, &Guess::check("match", "hatch"));
+        assert_eq!(reduced, pool);
+        assert_eq!(loaded.stats(), (1, 0));
+
+        let other = ["hatch", "catch"];
+        assert!(ReductionCache::load(&other, bytes.as_slice()).is_err());
+    }
+}
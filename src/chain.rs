@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use crate::report::{Outcome, Verbosity, MAX_GUESSES};
+
+/// A run of puzzles in which each answer is the forced first guess of the
+/// next, as in Hurdle.
+#[derive(Default)]
+pub struct Chain<'a> {
+    puzzles: Vec<Outcome<'a>>,
+}
+
+impl<'a> Chain<'a> {
+    pub fn push(&mut self, outcome: Outcome<'a>) {
+        self.puzzles.push(outcome);
+    }
+
+    /// The word the next puzzle must open with, if any puzzle has been played.
+    pub fn opener(&self) -> Option<&'a str> {
+        self.puzzles.last().map(|o| o.answer)
+    }
+
+    pub fn puzzles(&self) -> &[Outcome<'a>] {
+        &self.puzzles
+    }
+
+    /// Guesses across every puzzle, with each failure scored as one past the
+    /// limit.
+    pub fn total(&self) -> usize {
+        self.puzzles.iter().map(Outcome::score).sum()
+    }
+
+    pub fn solved(&self) -> bool {
+        self.puzzles.iter().all(Outcome::solved)
+    }
+}
+
+/// Results collected over a chained benchmark run.
+#[derive(Default)]
+pub struct ChainReport<'a> {
+    chains: Vec<Chain<'a>>,
+    elapsed: Duration,
+}
+
+impl<'a> ChainReport<'a> {
+    pub fn push(&mut self, chain: Chain<'a>) {
+        self.chains.push(chain);
+    }
+
+    /// Record the wall-clock time of the whole run.
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    pub fn chains(&self) -> &[Chain<'a>] {
+        &self.chains
+    }
+
+    /// The mean total guesses per chain.
+    pub fn average(&self) -> f32 {
+        let total: usize = self.chains.iter().map(Chain::total).sum();
+        total as f32 / self.chains.len() as f32
+    }
+
+    /// The chain that took the most guesses in total.
+    pub fn worst(&self) -> Option<&Chain<'a>> {
+        self.chains.iter().max_by_key(|c| c.total())
+    }
+
+    pub fn print(&self, strategy: &str, verbosity: Verbosity) {
+        let broken = self.chains.iter().filter(|c| !c.solved()).count();
+        let length = self.chains.first().map_or(0, |c| c.puzzles.len());
+        if verbosity == Verbosity::Quiet {
+            println!(
+                "{strategy}: average total {:.4}, broken {broken} of {}",
+                self.average(),
+                self.chains.len()
+            );
+            return;
+        }
+        if verbosity == Verbosity::Verbose {
+            for (i, chain) in self.chains.iter().enumerate() {
+                println!("chain {} ({} guesses):", i + 1, chain.total());
+                for outcome in chain.puzzles() {
+                    println!("  {}: {}", outcome.answer, outcome.words());
+                }
+            }
+            println!();
+        }
+
+        println!("strategy: {strategy}");
+        println!("chains: {} of {length} puzzles", self.chains.len());
+        println!("average total guesses: {}", self.average());
+        println!("broken chains: {broken}");
+
+        if let Some(worst) = self.worst() {
+            println!();
+            println!("worst chain: {} guesses", worst.total());
+            for outcome in worst.puzzles() {
+                let guesses = match outcome.guesses() {
+                    Some(n) if n <= MAX_GUESSES => n.to_string(),
+                    _ => "-".to_string(),
+                };
+                println!("  {} ({guesses}): {}", outcome.answer, outcome.words());
+            }
+        }
+
+        println!();
+        println!("wall clock: {:?}", self.elapsed);
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::outcome;
+
+    #[test]
+    fn totals() {
+        let mut chain = Chain::default();
+        assert_eq!(chain.opener(), None);
+        chain.push(outcome("cigar", Some(4)));
+        assert_eq!(chain.opener(), Some("cigar"));
+        chain.push(outcome("rebut", Some(8)));
+        assert_eq!(chain.total(), 4 + MAX_GUESSES + 1);
+        assert!(!chain.solved());
+
+        let mut other = Chain::default();
+        other.push(outcome("sissy", Some(3)));
+        other.push(outcome("humph", Some(2)));
+
+        let mut report = ChainReport::default();
+        report.push(chain);
+        report.push(other);
+        assert_eq!(report.average(), 8.0);
+        assert_eq!(report.worst().map(Chain::total), Some(11));
+    }
+}
@@ -0,0 +1,314 @@
+use std::{io, path::Path};
+
+use plotters::{coord::Shift, prelude::*};
+
+use crate::{
+    matrix::Matrix,
+    pareto::{self, Point},
+    report::{Report, MAX_GUESSES},
+};
+
+/// The size of a chart, in pixels for a PNG and user units for an SVG.
+const SIZE: (u32, u32) = (960, 540);
+
+/// A chart that can be drawn on any backend.
+trait Draw {
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root: DrawingArea<DB, Shift>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>;
+}
+
+/// Draw `chart` to `path`, as an SVG if it ends in `.svg` and a PNG if it
+/// ends in `.png`.
+fn save(chart: &impl Draw, path: &Path) -> io::Result<()> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension.to_ascii_lowercase().as_str() {
+        "svg" => chart
+            .draw(SVGBackend::new(path, SIZE).into_drawing_area())
+            .map_err(io::Error::other),
+        "png" => chart
+            .draw(BitMapBackend::new(path, SIZE).into_drawing_area())
+            .map_err(io::Error::other),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "charts are drawn to .png or .svg files",
+        )),
+    }
+}
+
+/// A bar chart: a group of bars at each label, one bar per series, ready to
+/// draw to a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bars {
+    pub title: String,
+    /// What the bars measure, down the side.
+    pub measure: String,
+    pub labels: Vec<String>,
+    /// Each series' name, for the legend, and its value at every label.
+    pub series: Vec<(String, Vec<f64>)>,
+    /// Whether bars start from zero. Averages of whole benchmarks sit close
+    /// together, so without it the axis starts just below the lowest bar.
+    pub from_zero: bool,
+}
+
+impl Bars {
+    /// How many answers each run solved in each number of guesses, with
+    /// failures last.
+    pub fn distribution(runs: &[(&str, &Report)]) -> Self {
+        let labels = (1..=MAX_GUESSES)
+            .map(|guesses| guesses.to_string())
+            .chain(["failed".to_string()])
+            .collect();
+        let series = runs
+            .iter()
+            .map(|(name, report)| {
+                let counts = report.distribution().map(|count| count as f64);
+                (name.to_string(), counts.to_vec())
+            })
+            .collect();
+        Self {
+            title: "guess distribution".to_string(),
+            measure: "answers".to_string(),
+            labels,
+            series,
+            from_zero: true,
+        }
+    }
+
+    /// The average score of every opener, one series per strategy.
+    pub fn matrix(matrix: &Matrix) -> Self {
+        let labels = matrix
+            .rows()
+            .iter()
+            .map(|(opener, _)| opener.clone())
+            .collect();
+        let series = matrix
+            .strategies()
+            .iter()
+            .enumerate()
+            .map(|(column, strategy)| {
+                let averages = matrix
+                    .rows()
+                    .iter()
+                    .map(|(_, cells)| cells[column].average as f64)
+                    .collect();
+                (strategy.clone(), averages)
+            })
+            .collect();
+        Self {
+            title: "openers compared".to_string(),
+            measure: "average guesses".to_string(),
+            labels,
+            series,
+            from_zero: false,
+        }
+    }
+
+    /// Draw the chart to `path`, as an SVG if it ends in `.svg` and a PNG if
+    /// it ends in `.png`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        save(self, path)
+    }
+
+    /// The range up the side: from zero or just below the lowest bar, to a
+    /// little above the highest.
+    fn range(&self) -> (f64, f64) {
+        let values = || {
+            self.series
+                .iter()
+                .flat_map(|(_, values)| values.iter().copied())
+        };
+        let high = values().fold(0.0, f64::max);
+        if self.from_zero {
+            return (0.0, (high * 1.1).max(1.0));
+        }
+        let low = values().fold(f64::INFINITY, f64::min);
+        let pad = ((high - low) * 0.2).max(0.05);
+        ((low - pad).max(0.0), high + pad)
+    }
+}
+
+impl Draw for Bars {
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root: DrawingArea<DB, Shift>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        root.fill(&WHITE)?;
+        let (low, high) = self.range();
+        // Each label sits on a whole number, with its group of bars centered
+        // on it.
+        let groups = self.labels.len().max(1);
+        let mut chart = ChartBuilder::on(&root)
+            .caption(&self.title, ("sans-serif", 28))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(64)
+            .build_cartesian_2d(-0.5..groups as f64 - 0.5, low..high)?;
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(groups)
+            .x_label_formatter(&|x| {
+                let index = x.round();
+                if (x - index).abs() > 1e-6 || index < 0.0 {
+                    return String::new();
+                }
+                self.labels.get(index as usize).cloned().unwrap_or_default()
+            })
+            .y_label_formatter(&|&y| {
+                let places = if self.from_zero { 0 } else { 2 };
+                format!("{y:.places$}")
+            })
+            .y_desc(&self.measure)
+            .draw()?;
+
+        let width = 0.8 / self.series.len().max(1) as f64;
+        for (index, (name, values)) in self.series.iter().enumerate() {
+            let color = Palette99::pick(index).to_rgba();
+            let left = |group: usize| group as f64 - 0.4 + index as f64 * width;
+            chart
+                .draw_series(values.iter().enumerate().map(|(group, &value)| {
+                    Rectangle::new(
+                        [(left(group), low), (left(group) + width, value)],
+                        color.filled(),
+                    )
+                }))?
+                .label(name)
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 6), (x + 12, y + 6)], color.filled())
+                });
+        }
+        if self.series.len() > 1 {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+        root.present()
+    }
+}
+
+/// Strategies placed by average score across and misses up, each labeled,
+/// with those on the Pareto frontier filled in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frontier {
+    pub points: Vec<Point>,
+}
+
+impl Frontier {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+
+    /// Draw the chart to `path`, as [`Bars::save`] does.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        save(self, path)
+    }
+}
+
+impl Draw for Frontier {
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root: DrawingArea<DB, Shift>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        root.fill(&WHITE)?;
+        let averages = || self.points.iter().map(|point| point.average as f64);
+        let low = averages().fold(f64::INFINITY, f64::min);
+        let high = averages().fold(0.0, f64::max);
+        let pad = ((high - low) * 0.2).max(0.05);
+        let most = self.points.iter().map(|point| point.failures).max();
+        let top = most.unwrap_or(0) as f64 * 1.2 + 1.0;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("strategies compared", ("sans-serif", 28))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(64)
+            .build_cartesian_2d(low - pad..high + pad, 0.0..top)?;
+        chart
+            .configure_mesh()
+            .x_desc("average guesses")
+            .y_label_formatter(&|&y| format!("{y:.0}"))
+            .y_desc("missed")
+            .draw()?;
+
+        let dominated = pareto::dominated_by(&self.points);
+        chart.draw_series(self.points.iter().zip(&dominated).map(|(point, by)| {
+            let at = (point.average as f64, point.failures as f64);
+            let style = match by {
+                None => BLUE.filled(),
+                Some(_) => BLUE.stroke_width(2),
+            };
+            let label = format!("{} ({:.1?})", point.name, point.per_solve);
+            EmptyElement::at(at)
+                + Circle::new((0, 0), 6, style)
+                + Text::new(label, (10, -8), ("sans-serif", 16))
+        }))?;
+        root.present()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::matrix::Cell;
+
+    #[test]
+    fn distribution() {
+        let report = Report::default();
+        let bars = Bars::distribution(&[("likeliest", &report), ("entropy", &report)]);
+        assert_eq!(bars.labels.len(), MAX_GUESSES + 1);
+        assert_eq!(bars.labels.last().map(String::as_str), Some("failed"));
+        assert_eq!(
+            bars.series[1],
+            ("entropy".to_string(), vec![0.0; MAX_GUESSES + 1])
+        );
+        // An empty run still gets an axis to draw against.
+        assert_eq!(bars.range(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn drawn_as_svg() {
+        let mut matrix = Matrix::new(["likeliest", "entropy"]);
+        let cell = |average| Cell {
+            average,
+            failures: 0,
+        };
+        matrix.push("salet", vec![cell(3.5), cell(3.4)]);
+        matrix.push("crane", vec![cell(3.6), cell(3.5)]);
+        let bars = Bars::matrix(&matrix);
+        assert_eq!(bars.labels, ["salet", "crane"]);
+        assert_eq!(
+            bars.series[1],
+            ("entropy".to_string(), vec![3.4f32 as f64, 3.5f32 as f64])
+        );
+
+        let path = std::env::temp_dir().join(format!("wordle-chart-{}.svg", std::process::id()));
+        bars.save(&path).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("openers compared"));
+        assert!(svg.contains("crane"));
+
+        assert!(bars.save(Path::new("chart.gif")).is_err());
+
+        let point = |name: &str, average, failures| Point {
+            name: name.to_string(),
+            average,
+            failures,
+            per_solve: std::time::Duration::from_millis(2),
+        };
+        let frontier = Frontier::new(&[point("entropy", 3.5, 5), point("likeliest", 3.6, 16)]);
+        frontier.save(&path).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(svg.contains("strategies compared"));
+        assert!(svg.contains("likeliest (2.0ms)"));
+    }
+}
@@ -0,0 +1,1454 @@
+use std::{fmt, num::NonZeroUsize, time::Duration};
+
+use wordle::{
+    guesser::Correctness, normalize, parallel::Order, pattern, report::Verbosity, schedule,
+};
+
+/// The subcommand to run.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    /// Solve every answer with one strategy and summarize the results.
+    Benchmark,
+    /// Solve the answers as chains, each answer opening the next puzzle.
+    Chain,
+    /// Solve every answer in Wordle Peaks, where tiles point up or down the
+    /// alphabet.
+    Peaks,
+    /// Solve every answer in Jotto, where each guess is told only how many
+    /// of its letters are in place and how many elsewhere, or host a game of
+    /// it if this is set.
+    Jotto(bool),
+    /// Solve a Waffle grid read from stdin, finding its six words and the
+    /// fewest swaps that put them in place.
+    Waffle,
+    /// Solve each word read from stdin, one result line per word.
+    Solve,
+    /// Suggest guesses for a game played elsewhere, reading the feedback.
+    Assist,
+    /// Step through a game by commands read from stdin, mixing the solver's
+    /// guesses with your own.
+    Repl,
+    /// Play as a bot for an external referee, over a line protocol on stdin
+    /// and stdout.
+    Compete,
+    /// Play against a host that dodges the solver's guesses.
+    Adversary,
+    /// Solve today's puzzle, optionally posting the result to a webhook.
+    Daily,
+    /// Solve today's puzzle unattended, as from cron: record it in the
+    /// history store and post it to the webhook, if any, once per day. Exits
+    /// 0 when solved or already recorded, 1 when not solved, and 2 on errors.
+    Auto,
+    /// Solve an earlier daily puzzle by its number, or assist with it.
+    Archive(usize),
+    /// Host a game for a human to play.
+    Play,
+    /// Host a game of Anti-Wordle, where the goal is to avoid the answer.
+    Anti,
+    /// Check whether legal guesses could have given a share grid read from
+    /// stdin, for the answer or puzzle number given, or else the puzzle its
+    /// header names.
+    Audit(Option<String>),
+    /// Work back from a share grid read from stdin to the answers it could
+    /// have been played for, likeliest first.
+    Infer,
+    /// Host a game the solver plays too, showing only the colors of its
+    /// guesses until the end.
+    Race,
+    /// Host a game against the clock, timing each guess and keeping the
+    /// fastest win in the stats.
+    Speedrun,
+    /// Print the best next guesses with their scores.
+    Hint,
+    /// Answer suggestion requests over HTTP on localhost, with a web page
+    /// to play through them if `--ui` is given, or as JSON-RPC over stdin
+    /// and stdout with `--stdio`.
+    Serve,
+    /// List the words that fit the clues given with `--green`, `--yellow`,
+    /// and `--gray`, most frequent first.
+    Query,
+    /// List the words that fit a crossword-style pattern such as `s_a_e`,
+    /// where `_`, `?`, or `.` stands for any letter.
+    Match([Option<u8>; 5]),
+    /// Run two strategies over the same answers and compare them.
+    Duel(String, String),
+    /// Compare two runs saved with `--save-report`, answer by answer.
+    Compare(String, String),
+    /// Run the benchmark and save it as a baseline for `check`.
+    Snapshot(String),
+    /// Run the benchmark and exit 1 if it does worse than a baseline saved
+    /// with `snapshot`, beyond the tolerances.
+    Check(String),
+    /// Run every opener with every strategy and compare them in a grid.
+    Matrix(Vec<String>, Vec<String>),
+    /// Run every strategy and find those no other beats on average score,
+    /// misses, and time per solve at once.
+    Pareto(Vec<String>),
+    /// Search for the opening sequence of this many words with the best
+    /// benchmark average, by simulated annealing.
+    Openings(usize),
+    /// Derive letter weights from the corpus and write them to a file.
+    Train(String),
+    /// Evolve the strategy's knobs against the benchmark and write the best
+    /// to a profile for `--profile`.
+    Tune(String),
+    /// Count the words in raw text, or merge frequency lists, into a corpus
+    /// written to stdout. Reads stdin if no files are given.
+    Corpus(Vec<String>),
+    /// Drop corpus entries that aren't legal guesses, writing what's left to
+    /// stdout and reporting what was dropped.
+    Prune,
+    /// Solve every answer with the strategy and write the paths it takes as
+    /// a decision tree, to replay without searching.
+    BuildTree(String),
+    /// Search every guess at every turn for the policy with the fewest
+    /// guesses on average, and report its average and worst case.
+    Optimal,
+    /// Print how many words have each letter at each position, over the
+    /// answers, or over the dictionary if this is set.
+    LetterStats(bool),
+    /// Group the answers by the state the strategy leaves them in after a
+    /// few guesses, listing the families it couldn't tell apart.
+    Clusters,
+    /// Compare which answers the strategy finds hard with which people do,
+    /// from a CSV of each answer's average human guesses.
+    Humans(String),
+    /// Draw a daily answer for each day from the first date to the last,
+    /// without repeats, and write them to a file for `--schedule`.
+    Schedule(i64, i64, String),
+    /// Rate each guess of a finished game for skill, against the solver's
+    /// best choice, and for luck, by how much its feedback narrowed things
+    /// down. A guess without its feedback is checked against the last one,
+    /// taken as the answer.
+    Rate(Vec<(String, Option<[Correctness; 5]>)>),
+    /// Rate every game in chat logs of share grids with the words played
+    /// beside them, and total each player's skill and luck by month. Reads
+    /// stdin if no files are given.
+    Import(Vec<String>),
+    /// Answer commands posted in these Discord channels, by their ids, as
+    /// the bot whose token is in `DISCORD_TOKEN`.
+    Bot(Vec<String>),
+}
+
+/// A bundle of the tuning options for `--preset`, from quickest to fewest
+/// guesses, so a casual player needn't learn each flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Preset {
+    /// The likeliest candidate over the 10,000 most frequent guesses: about
+    /// 3.6 guesses on average over the answers, instantly.
+    Fast,
+    /// Entropy with an exact search over the last 8 candidates, and a
+    /// reduction cache kept in the home directory: about 3.5, in seconds.
+    Balanced,
+    /// Entropy probing with any word and searching exactly over the last 20
+    /// candidates, with the cache: under 3.5, in minutes.
+    Optimal,
+}
+
+impl Preset {
+    pub(crate) fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "fast" => Some(Preset::Fast),
+            "balanced" => Some(Preset::Balanced),
+            "optimal" => Some(Preset::Optimal),
+            _ => None,
+        }
+    }
+
+    /// Whether reductions are cached on disk between runs, under the home
+    /// directory unless `--cache-dir` says where.
+    pub(crate) fn caches(self) -> bool {
+        self != Preset::Fast
+    }
+
+    /// Set the options the preset bundles, leaving any given on their own.
+    fn apply(self, options: &mut Options) {
+        let (strategy, endgame, top_n, probe) = match self {
+            Preset::Fast => ("likeliest", None, Some(10_000), false),
+            Preset::Balanced => ("entropy", Some(8), None, false),
+            Preset::Optimal => ("entropy", Some(20), None, true),
+        };
+        if options.strategy == Options::default().strategy {
+            options.strategy = strategy.to_string();
+        }
+        options.endgame = options.endgame.or(endgame);
+        options.top_n = options.top_n.or(top_n);
+        options.probe |= probe;
+    }
+}
+
+/// Options accepted on the command line.
+pub(crate) struct Options {
+    pub(crate) command: Command,
+    /// A built-in strategy by name, or one loaded from a shared library as
+    /// `plugin:<path>`.
+    pub(crate) strategy: String,
+    pub(crate) smoothing: f64,
+    /// Weigh answers by a sigmoid over frequency rank, centered on this rank,
+    /// rather than by corpus count.
+    pub(crate) sigmoid: Option<usize>,
+    pub(crate) sigmoid_width: f64,
+    /// Strategy knobs given as `--param key=value`, in order.
+    pub(crate) params: Vec<(String, f64)>,
+    /// A file of strategy knobs written by `tune`, applied before `--param`.
+    pub(crate) profile: Option<String>,
+    /// The keyboard layout `typing-penalty` measures typing on.
+    pub(crate) layout: Option<String>,
+    /// How many settings `tune` tries in each generation.
+    pub(crate) population: usize,
+    /// How many generations `tune` breeds.
+    pub(crate) generations: usize,
+    /// How many sequences `openings` tries.
+    pub(crate) steps: usize,
+    /// How much to weigh plurals and past tenses as answers; zero drops them.
+    pub(crate) inflection_weight: f64,
+    pub(crate) scoring: String,
+    /// Letter weights written by `train`, for the letters strategy.
+    pub(crate) weights: Option<String>,
+    /// What the letters strategy counts letter frequencies over: the
+    /// answers, the guess dictionary, or the corpus, rather than the
+    /// candidates left each turn.
+    pub(crate) freq_source: Option<String>,
+    /// A decision tree written by `build-tree`, to measure a benchmark's
+    /// regret against answer by answer.
+    pub(crate) reference: Option<String>,
+    /// Search exactly once this few candidates remain.
+    pub(crate) endgame: Option<usize>,
+    /// What the strategies aim for: `avg`, `worst`, or `fail-rate`.
+    pub(crate) objective: Option<String>,
+    /// How long strategies may spend choosing each guess.
+    pub(crate) budget: Option<Duration>,
+    /// The most guesses the optimal search may plan for, or the guesses
+    /// `clusters` groups answers after.
+    pub(crate) depth: Option<usize>,
+    /// Train only on corpus words that are also answers.
+    pub(crate) answers_only: bool,
+    /// Read `corpus` input as `word count` lines rather than running text.
+    pub(crate) frequency_list: bool,
+    pub(crate) top_n: Option<usize>,
+    pub(crate) top_k: usize,
+    pub(crate) chain_length: usize,
+    pub(crate) dictionary: Option<String>,
+    /// The format word lists read from files are in, if not detected:
+    /// `native`, `plain`, `csv`, or `json`.
+    pub(crate) list_format: Option<String>,
+    pub(crate) answers: Option<String>,
+    pub(crate) guesses: Option<String>,
+    pub(crate) exclude: Option<String>,
+    /// The only words that may be guessed, such as the words a player knows.
+    pub(crate) allow_guesses: Option<String>,
+    /// Words that may never be guessed, though they may be the answer.
+    pub(crate) deny_guesses: Option<String>,
+    pub(crate) alphabet: Option<String>,
+    /// Treat accented vowels as the plain ones, in word lists and guesses.
+    pub(crate) fold_accents: bool,
+    /// How repeated letters are colored: `official`, or `naive` as in clones
+    /// that mark every copy yellow.
+    pub(crate) duplicates: Option<String>,
+    /// Multi-letter tiles, besides any the answer list declares.
+    pub(crate) tiles: Vec<String>,
+    /// The language of the word lists, for `play` and `assist` to speak.
+    pub(crate) language: Option<String>,
+    pub(crate) cache_dir: Option<String>,
+    /// The bundle of tuning options asked for, already applied to them.
+    pub(crate) preset: Option<Preset>,
+    pub(crate) seed: Option<u64>,
+    /// Benchmark only one side of a seeded split of the answers.
+    pub(crate) split: Option<String>,
+    /// Benchmark only this many answers, picked by `--seed`.
+    pub(crate) sample: Option<usize>,
+    /// The fewest corpus appearances a hosted game's answer may have.
+    pub(crate) min_count: usize,
+    pub(crate) blocklist: Option<String>,
+    pub(crate) proper_nouns: bool,
+    pub(crate) difficulty: Option<String>,
+    /// Which guesses a hosted game accepts: `strict`, `dictionary`, or
+    /// `open`.
+    pub(crate) accept: Option<String>,
+    /// Where to keep player statistics, if not the default.
+    pub(crate) stats: Option<String>,
+    pub(crate) keep_stats: bool,
+    /// Put the share grid on the clipboard when a game or solve is over.
+    pub(crate) copy: bool,
+    /// A Slack or Discord webhook to post the daily result to.
+    pub(crate) webhook: Option<String>,
+    /// Where `auto` records its results, if not the default.
+    pub(crate) history: Option<String>,
+    /// Answers by date written by `schedule`, for the daily puzzles and
+    /// hosted games to draw on rather than the published ones.
+    pub(crate) schedule: Option<String>,
+    /// The time zone that decides the day's puzzle, if not local time.
+    pub(crate) timezone: Option<String>,
+    /// Assist with a daily or archived puzzle rather than solving it.
+    pub(crate) assist: bool,
+    /// Known letters, by position counting from zero.
+    pub(crate) green: Vec<(usize, u8)>,
+    /// Letters in the answer, with where they aren't, if known.
+    pub(crate) yellow: Vec<(Option<usize>, u8)>,
+    pub(crate) gray: Vec<u8>,
+    /// Guesses already played elsewhere, with their feedback, to start
+    /// `solve` and `assist` from.
+    pub(crate) given: Vec<(String, [Correctness; 5])>,
+    pub(crate) answer_pool: bool,
+    pub(crate) probe: bool,
+    pub(crate) unlimited: bool,
+    /// Check every candidate the solver keeps or drops by brute force.
+    pub(crate) verify: bool,
+    /// Where a benchmark saves its run as JSON, for `compare`.
+    pub(crate) save_report: Option<String>,
+    /// Where a benchmark, duel, matrix, or pareto run draws its results, as a
+    /// PNG or SVG.
+    pub(crate) chart: Option<String>,
+    /// Where a benchmark writes its results as a single HTML page.
+    pub(crate) html_report: Option<String>,
+    /// Where the words a benchmark or a played game struggled with are kept
+    /// as a study deck, CSV or, for a `.tsv` file, TSV.
+    pub(crate) deck: Option<String>,
+    /// How far `check` lets the average score rise over the baseline.
+    pub(crate) average_tolerance: f64,
+    /// How many threads parallel work may use, rather than one per core.
+    pub(crate) jobs: Option<NonZeroUsize>,
+    /// How many more misses than the baseline `check` allows.
+    pub(crate) failure_tolerance: usize,
+    /// The port `serve` listens on.
+    pub(crate) port: u16,
+    /// The address `serve` listens on, such as `0.0.0.0` for the whole
+    /// network.
+    pub(crate) bind: String,
+    /// Serve a daily game everyone shares instead of suggestions, keeping
+    /// the players' records in this file. A bot keeps its shared game's
+    /// records here too.
+    pub(crate) league: Option<String>,
+    /// Serve the bundled web page as well as the API.
+    pub(crate) ui: bool,
+    /// Serve JSON-RPC over stdin and stdout rather than HTTP.
+    pub(crate) stdio: bool,
+    /// Print a transcript of every solve: each guess, its feedback, the
+    /// candidates it eliminated, and the best alternatives.
+    pub(crate) trace: bool,
+    /// Shade tables by their values, as with `stats letters`.
+    pub(crate) heatmap: bool,
+    /// Orange and blue tiles instead of green and yellow.
+    pub(crate) high_contrast: bool,
+    /// Mark tiles with shapes as well as colors.
+    pub(crate) symbols: bool,
+    /// Explain each guess `assist` suggests, beside the best alternatives.
+    pub(crate) explain: bool,
+    /// Offer to fix the likeliest mistyped tile when `assist`'s entries rule
+    /// out every word.
+    pub(crate) fix_typos: bool,
+    /// Keep suggesting once `assist`'s entries rule out every word, from the
+    /// corpus and then from letter sequences that fit.
+    pub(crate) open_vocabulary: bool,
+    /// Print transcripts only for the answers that were missed.
+    pub(crate) failures_only: bool,
+    /// Print each answer's result as soon as it's solved, in this order when
+    /// answers are solved in parallel.
+    pub(crate) follow: Option<Order>,
+    pub(crate) verbosity: Verbosity,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            command: Command::Benchmark,
+            strategy: "likeliest".to_string(),
+            smoothing: 0.0,
+            sigmoid: None,
+            sigmoid_width: 250.0,
+            params: Vec::new(),
+            profile: None,
+            layout: None,
+            population: 12,
+            generations: 10,
+            steps: 200,
+            inflection_weight: 1.0,
+            scoring: "information".to_string(),
+            weights: None,
+            freq_source: None,
+            reference: None,
+            endgame: None,
+            objective: None,
+            budget: None,
+            depth: None,
+            answers_only: false,
+            frequency_list: false,
+            top_n: None,
+            top_k: 10,
+            chain_length: 5,
+            dictionary: None,
+            list_format: None,
+            answers: None,
+            guesses: None,
+            exclude: None,
+            allow_guesses: None,
+            deny_guesses: None,
+            alphabet: None,
+            fold_accents: false,
+            duplicates: None,
+            tiles: Vec::new(),
+            language: None,
+            cache_dir: None,
+            preset: None,
+            seed: None,
+            split: None,
+            sample: None,
+            min_count: 0,
+            blocklist: None,
+            proper_nouns: true,
+            difficulty: None,
+            accept: None,
+            stats: None,
+            keep_stats: true,
+            copy: false,
+            webhook: None,
+            history: None,
+            schedule: None,
+            timezone: None,
+            assist: false,
+            green: Vec::new(),
+            given: Vec::new(),
+            yellow: Vec::new(),
+            gray: Vec::new(),
+            answer_pool: false,
+            probe: false,
+            unlimited: false,
+            verify: false,
+            save_report: None,
+            chart: None,
+            html_report: None,
+            deck: None,
+            average_tolerance: 0.0,
+            jobs: None,
+            failure_tolerance: 0,
+            port: 8000,
+            bind: "127.0.0.1".to_string(),
+            league: None,
+            ui: false,
+            stdio: false,
+            trace: false,
+            heatmap: false,
+            high_contrast: false,
+            explain: false,
+            fix_typos: false,
+            open_vocabulary: false,
+            symbols: false,
+            failures_only: false,
+            follow: None,
+            verbosity: Verbosity::Normal,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    MissingValue(String),
+    InvalidValue(String, String),
+    UnknownArgument(String),
+    UnknownCommand(String),
+    WrongArguments(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingValue(flag) => write!(f, "{flag} requires a value"),
+            Error::InvalidValue(flag, value) => write!(f, "invalid value for {flag}: {value}"),
+            Error::UnknownArgument(arg) => write!(f, "unknown argument: {arg}"),
+            Error::UnknownCommand(command) => write!(f, "unknown command: {command}"),
+            Error::WrongArguments(usage) => write!(f, "usage: {usage}"),
+        }
+    }
+}
+
+impl Options {
+    pub(crate) fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Error> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        let mut positional = Vec::new();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| Error::MissingValue(arg.clone()));
+
+            match arg.as_str() {
+                "--strategy" => options.strategy = value()?,
+                "--smoothing" => options.smoothing = parse_value(&arg, value()?)?,
+                "--sigmoid" => options.sigmoid = Some(parse_value(&arg, value()?)?),
+                "--sigmoid-width" => {
+                    options.sigmoid_width = parse_value(&arg, value()?)?;
+                    if options.sigmoid_width <= 0.0 {
+                        return Err(Error::InvalidValue(arg, options.sigmoid_width.to_string()));
+                    }
+                },
+                "--inflection-weight" => {
+                    options.inflection_weight = parse_value(&arg, value()?)?;
+                    if !(0.0..=1.0).contains(&options.inflection_weight) {
+                        return Err(Error::InvalidValue(
+                            arg,
+                            options.inflection_weight.to_string(),
+                        ));
+                    }
+                },
+                "--param" => {
+                    let value = value()?;
+                    let invalid = || Error::InvalidValue(arg.clone(), value.clone());
+                    let (key, number) = value.split_once('=').ok_or_else(invalid)?;
+                    let number = number.trim().parse().map_err(|_| invalid())?;
+                    options.params.push((key.trim().to_string(), number));
+                },
+                "--profile" => options.profile = Some(value()?),
+                "--layout" => options.layout = Some(value()?),
+                "--language" => options.language = Some(value()?),
+                "--population" => options.population = parse_value(&arg, value()?)?,
+                "--generations" => options.generations = parse_value(&arg, value()?)?,
+                "--steps" => options.steps = parse_value(&arg, value()?)?,
+                "--scoring" => options.scoring = value()?,
+                "--weights" => options.weights = Some(value()?),
+                "--freq-source" => options.freq_source = Some(value()?),
+                "--reference" => options.reference = Some(value()?),
+                "--endgame" => options.endgame = Some(parse_value(&arg, value()?)?),
+                "--objective" => options.objective = Some(value()?),
+                "--budget" => options.budget = Some(parse_duration(&arg, value()?)?),
+                "--depth" => options.depth = Some(parse_value(&arg, value()?)?),
+                "--answers-only" => options.answers_only = true,
+                "--frequency-list" => options.frequency_list = true,
+                "--top-n" => options.top_n = Some(parse_value(&arg, value()?)?),
+                "--dictionary" => options.dictionary = Some(value()?),
+                "--list-format" => options.list_format = Some(value()?),
+                "--top-k" => options.top_k = parse_value(&arg, value()?)?,
+                "--chain-length" => options.chain_length = parse_value(&arg, value()?)?,
+                "--answers" => options.answers = Some(value()?),
+                "--guesses" => options.guesses = Some(value()?),
+                "--exclude" => options.exclude = Some(value()?),
+                "--allow-guesses" => options.allow_guesses = Some(value()?),
+                "--deny-guesses" => options.deny_guesses = Some(value()?),
+                "--alphabet" => options.alphabet = Some(value()?),
+                "--cache-dir" => options.cache_dir = Some(value()?),
+                "--preset" => {
+                    let name = value()?;
+                    let preset = Preset::by_name(&name).ok_or(Error::InvalidValue(arg, name))?;
+                    options.preset = Some(preset);
+                },
+                "--given" => {
+                    let value = value()?;
+                    let invalid = || Error::InvalidValue(arg.clone(), value.clone());
+                    let (word, feedback) = value.split_once('=').ok_or_else(invalid)?;
+                    let mask =
+                        pattern::parse(&normalize::input(feedback)).map_err(|_| invalid())?;
+                    options.given.push((word.trim().to_string(), mask));
+                },
+                "--green" => {
+                    for (position, letter) in parse_clues(&arg, value()?)? {
+                        let position = position.ok_or_else(|| {
+                            Error::InvalidValue(arg.clone(), format!("{}", letter as char))
+                        })?;
+                        options.green.push((position, letter));
+                    }
+                },
+                "--yellow" => options.yellow.extend(parse_clues(&arg, value()?)?),
+                "--gray" | "--grey" => {
+                    for (position, letter) in parse_clues(&arg, value()?)? {
+                        if position.is_some() {
+                            return Err(Error::InvalidValue(arg, format!("{}", letter as char)));
+                        }
+                        options.gray.push(letter);
+                    }
+                },
+                "--seed" => options.seed = Some(parse_value(&arg, value()?)?),
+                "--split" => options.split = Some(value()?),
+                "--sample" => options.sample = Some(parse_value(&arg, value()?)?),
+                "--min-count" => options.min_count = parse_value(&arg, value()?)?,
+                "--blocklist" => options.blocklist = Some(value()?),
+                "--no-proper-nouns" => options.proper_nouns = false,
+                "--difficulty" => options.difficulty = Some(value()?),
+                "--accept" => options.accept = Some(value()?),
+                "--stats" => options.stats = Some(value()?),
+                "--no-stats" => options.keep_stats = false,
+                "--copy" => options.copy = true,
+                "--fold-accents" => options.fold_accents = true,
+                "--duplicates" => options.duplicates = Some(value()?),
+                "--tiles" => {
+                    let value = value()?;
+                    let tiles = value.split(',').map(str::trim).filter(|t| !t.is_empty());
+                    options.tiles.extend(tiles.map(str::to_string));
+                },
+                "--webhook" => options.webhook = Some(value()?),
+                "--history" => options.history = Some(value()?),
+                "--schedule" => options.schedule = Some(value()?),
+                "--timezone" => options.timezone = Some(value()?),
+                "--assist" => options.assist = true,
+                "--answer-pool" => options.answer_pool = true,
+                "--probe" => options.probe = true,
+                "--unlimited" => options.unlimited = true,
+                "--verify" => options.verify = true,
+                "-j" | "--jobs" => options.jobs = Some(parse_value(&arg, value()?)?),
+                "--save-report" => options.save_report = Some(value()?),
+                "--chart" => options.chart = Some(value()?),
+                "--report" => options.html_report = Some(value()?),
+                "--deck" => options.deck = Some(value()?),
+                "--average-tolerance" => options.average_tolerance = parse_value(&arg, value()?)?,
+                "--failure-tolerance" => options.failure_tolerance = parse_value(&arg, value()?)?,
+                "--port" => options.port = parse_value(&arg, value()?)?,
+                "--bind" => options.bind = value()?,
+                "--league" => options.league = Some(value()?),
+                "--ui" => options.ui = true,
+                "--heatmap" => options.heatmap = true,
+                "--high-contrast" => options.high_contrast = true,
+                "--symbols" => options.symbols = true,
+                "--stdio" => options.stdio = true,
+                "--trace" => options.trace = true,
+                "--explain" => options.explain = true,
+                "--fix-typos" => options.fix_typos = true,
+                "--open-vocabulary" => options.open_vocabulary = true,
+                "--failures-only" => options.failures_only = true,
+                "--follow" => options.follow = Some(options.follow.unwrap_or_default()),
+                "--follow-ordered" => options.follow = Some(Order::Input),
+                "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+                _ if arg.starts_with('-') => return Err(Error::UnknownArgument(arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        options.command = Command::parse(positional)?;
+        if let Some(preset) = options.preset {
+            preset.apply(&mut options);
+        }
+
+        // A word list of exclusions makes no sense when matching a pattern,
+        // so there `--exclude` takes letters, like `--gray`.
+        if let Command::Match(_) = options.command {
+            if let Some(letters) = options.exclude.take() {
+                let clues = parse_clues("--exclude", letters)?;
+                options
+                    .gray
+                    .extend(clues.into_iter().map(|(_, letter)| letter));
+            }
+        }
+        Ok(options)
+    }
+
+    /// Whether the default strategy plays over the embedded lists, as it was
+    /// when the embedded decision tree was built, so replaying the tree plays
+    /// as well as searching would. Any option that changes which words are
+    /// candidates, guesses, or excluded rules the tree out.
+    pub(crate) fn plays_by_default(&self) -> bool {
+        let default = Self::default();
+        self.strategy == default.strategy
+            && self.scoring == default.scoring
+            && self.params.is_empty()
+            && self.profile.is_none()
+            && self.weights.is_none()
+            && self.endgame.is_none()
+            && self.objective.is_none()
+            && self.budget.is_none()
+            && self.answers.is_none()
+            && self.dictionary.is_none()
+            && self.guesses.is_none()
+            && self.exclude.is_none()
+            && self.allow_guesses.is_none()
+            && self.deny_guesses.is_none()
+            && self.top_n.is_none()
+            && self.answer_pool == default.answer_pool
+            && self.probe == default.probe
+            && self.alphabet.is_none()
+            && self.tiles.is_empty()
+            && !self.fold_accents
+            && self.duplicates.is_none()
+    }
+}
+
+impl Command {
+    fn parse(positional: Vec<String>) -> Result<Self, Error> {
+        let mut positional = positional.into_iter();
+        let Some(command) = positional.next() else {
+            return Ok(Command::Benchmark);
+        };
+        let rest: Vec<String> = positional.collect();
+
+        match command.as_str() {
+            "benchmark" if rest.is_empty() => Ok(Command::Benchmark),
+            "benchmark" => Err(Error::WrongArguments("wordle benchmark")),
+            "chain" if rest.is_empty() => Ok(Command::Chain),
+            "chain" => Err(Error::WrongArguments("wordle chain [--chain-length <n>]")),
+            "peaks" if rest.is_empty() => Ok(Command::Peaks),
+            "peaks" => Err(Error::WrongArguments("wordle peaks")),
+            "jotto" => match &rest[..] {
+                [] => Ok(Command::Jotto(false)),
+                [play] if play == "play" => Ok(Command::Jotto(true)),
+                _ => Err(Error::WrongArguments("wordle jotto [play]")),
+            },
+            "waffle" if rest.is_empty() => Ok(Command::Waffle),
+            "waffle" => Err(Error::WrongArguments("wordle waffle < grid.txt")),
+            "solve" if rest.is_empty() => Ok(Command::Solve),
+            "solve" => Err(Error::WrongArguments("wordle solve < words.txt")),
+            "assist" if rest.is_empty() => Ok(Command::Assist),
+            "assist" => Err(Error::WrongArguments("wordle assist")),
+            "repl" if rest.is_empty() => Ok(Command::Repl),
+            "repl" => Err(Error::WrongArguments("wordle repl")),
+            "compete" if rest.is_empty() => Ok(Command::Compete),
+            "compete" => Err(Error::WrongArguments("wordle compete")),
+            "adversary" if rest.is_empty() => Ok(Command::Adversary),
+            "adversary" => Err(Error::WrongArguments("wordle adversary")),
+            "daily" if rest.is_empty() => Ok(Command::Daily),
+            "daily" => Err(Error::WrongArguments("wordle daily [--webhook <url>]")),
+            "auto" if rest.is_empty() => Ok(Command::Auto),
+            "auto" => Err(Error::WrongArguments(
+                "wordle auto [--history <file>] [--webhook <url>]",
+            )),
+            "archive" => {
+                let usage = "wordle archive <number> [--assist]";
+                let [number] =
+                    <[String; 1]>::try_from(rest).map_err(|_| Error::WrongArguments(usage))?;
+                // Numbers are accepted as the game shares them, as in #1,234.
+                let digits = number.trim_start_matches('#').replace(',', "");
+                digits
+                    .parse()
+                    .map(Command::Archive)
+                    .map_err(|_| Error::WrongArguments(usage))
+            },
+            "play" if rest.is_empty() => Ok(Command::Play),
+            "play" => Err(Error::WrongArguments("wordle play [--seed <n>]")),
+            "anti" if rest.is_empty() => Ok(Command::Anti),
+            "anti" => Err(Error::WrongArguments("wordle anti [--seed <n>]")),
+            "audit" if rest.len() <= 1 => Ok(Command::Audit(rest.into_iter().next())),
+            "audit" => Err(Error::WrongArguments(
+                "wordle audit [<answer> | <number>] < grid.txt",
+            )),
+            "infer" if rest.is_empty() => Ok(Command::Infer),
+            "infer" => Err(Error::WrongArguments(
+                "wordle infer [--top-k <n>] < grid.txt",
+            )),
+            "race" if rest.is_empty() => Ok(Command::Race),
+            "race" => Err(Error::WrongArguments("wordle race [--seed <n>]")),
+            "speedrun" if rest.is_empty() => Ok(Command::Speedrun),
+            "speedrun" => Err(Error::WrongArguments("wordle speedrun [--seed <n>]")),
+            "query" if rest.is_empty() => Ok(Command::Query),
+            "query" => Err(Error::WrongArguments(
+                "wordle query [--green 3=a] [--yellow r] [--gray s,t,e]",
+            )),
+            "match" => {
+                let usage = "wordle match <pattern> [--exclude r,t]";
+                let [pattern] =
+                    <[String; 1]>::try_from(rest).map_err(|_| Error::WrongArguments(usage))?;
+                parse_pattern(&pattern)
+                    .ok_or(Error::WrongArguments(usage))
+                    .map(Command::Match)
+            },
+            "hint" if rest.is_empty() => Ok(Command::Hint),
+            "hint" => Err(Error::WrongArguments("wordle hint [--top-k <k>]")),
+            "serve" if rest.is_empty() => Ok(Command::Serve),
+            "serve" => Err(Error::WrongArguments(
+                "wordle serve [--port <n>] [--bind <address>] [--ui | --stdio] [--league <file>]",
+            )),
+            "duel" => match <[String; 2]>::try_from(rest) {
+                Ok([a, b]) => Ok(Command::Duel(a, b)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle duel <strategy-a> <strategy-b>",
+                )),
+            },
+            "compare" => match <[String; 2]>::try_from(rest) {
+                Ok([a, b]) => Ok(Command::Compare(a, b)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle compare <run-a.json> <run-b.json>",
+                )),
+            },
+            "snapshot" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::Snapshot(path)),
+                Err(_) => Err(Error::WrongArguments("wordle snapshot <baseline.json>")),
+            },
+            "check" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::Check(path)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle check <baseline.json> [--average-tolerance <x>] \
+                     [--failure-tolerance <n>]",
+                )),
+            },
+            "matrix" => {
+                let usage = "wordle matrix <opener,...> <strategy,...>";
+                let list = |value: &str| -> Vec<String> {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                };
+                match <[String; 2]>::try_from(rest) {
+                    Ok([openers, strategies]) => {
+                        let (openers, strategies) = (list(&openers), list(&strategies));
+                        if openers.is_empty() || strategies.is_empty() {
+                            return Err(Error::WrongArguments(usage));
+                        }
+                        Ok(Command::Matrix(openers, strategies))
+                    },
+                    Err(_) => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "pareto" => {
+                let usage = "wordle pareto <strategy,...>";
+                match <[String; 1]>::try_from(rest) {
+                    Ok([strategies]) => {
+                        let strategies: Vec<String> = strategies
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|item| !item.is_empty())
+                            .map(str::to_string)
+                            .collect();
+                        if strategies.is_empty() {
+                            return Err(Error::WrongArguments(usage));
+                        }
+                        Ok(Command::Pareto(strategies))
+                    },
+                    Err(_) => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "train" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::Train(path)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle train <weights-file> [--answers-only]",
+                )),
+            },
+            "openings" => {
+                let usage = "wordle openings <2|3> [--steps <n>] [--seed <n>]";
+                match <[String; 1]>::try_from(rest).as_ref().map(|[n]| n.parse()) {
+                    Ok(Ok(length @ 2..=3)) => Ok(Command::Openings(length)),
+                    _ => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "tune" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::Tune(path)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle tune <profile> [--population <n>] [--generations <n>]",
+                )),
+            },
+            "build-tree" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::BuildTree(path)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle build-tree <tree-file> [--strategy <name>]",
+                )),
+            },
+            "optimal" if rest.is_empty() => Ok(Command::Optimal),
+            "optimal" => Err(Error::WrongArguments(
+                "wordle optimal [--depth <guesses>] [--probe]",
+            )),
+            "stats" => {
+                let usage = "wordle stats letters [answers | dictionary] [--heatmap]";
+                match rest.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+                    ["letters"] | ["letters", "answers"] => Ok(Command::LetterStats(false)),
+                    ["letters", "dictionary"] => Ok(Command::LetterStats(true)),
+                    _ => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "clusters" if rest.is_empty() => Ok(Command::Clusters),
+            "clusters" => Err(Error::WrongArguments(
+                "wordle clusters [--depth <guesses>] [--top-k <n>]",
+            )),
+            "humans" => match <[String; 1]>::try_from(rest) {
+                Ok([path]) => Ok(Command::Humans(path)),
+                Err(_) => Err(Error::WrongArguments(
+                    "wordle humans <scores.csv> [--top-k <n>]",
+                )),
+            },
+            "schedule" => {
+                let usage = "wordle schedule <first date> <last date> <file> [--seed <n>]";
+                let [first, last, path] =
+                    <[String; 3]>::try_from(rest).map_err(|_| Error::WrongArguments(usage))?;
+                match (schedule::parse_date(&first), schedule::parse_date(&last)) {
+                    (Some(first), Some(last)) if first <= last => {
+                        Ok(Command::Schedule(first, last, path))
+                    },
+                    _ => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "rate" => {
+                let usage = "wordle rate <guess>[=<feedback>]...";
+                let game = rest
+                    .iter()
+                    .map(|guess| match guess.split_once('=') {
+                        Some((word, feedback)) => pattern::parse(&normalize::input(feedback))
+                            .map(|mask| (word.trim().to_string(), Some(mask)))
+                            .map_err(|_| Error::WrongArguments(usage)),
+                        None => Ok((guess.trim().to_string(), None)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                // Without feedback on every guess, the last must be the answer.
+                match game.last() {
+                    Some((_, None)) => Ok(Command::Rate(game)),
+                    Some(_) if game.iter().all(|(_, mask)| mask.is_some()) => {
+                        Ok(Command::Rate(game))
+                    },
+                    _ => Err(Error::WrongArguments(usage)),
+                }
+            },
+            "import" => Ok(Command::Import(rest)),
+            "bot"
+                if !rest.is_empty()
+                    && rest.iter().all(|id| id.bytes().all(|b| b.is_ascii_digit())) =>
+            {
+                Ok(Command::Bot(rest))
+            },
+            "bot" => Err(Error::WrongArguments(
+                "wordle bot <channel id>... [--league <file>]",
+            )),
+            "corpus" => Ok(Command::Corpus(rest)),
+            "prune" if rest.is_empty() => Ok(Command::Prune),
+            "prune" => Err(Error::WrongArguments(
+                "wordle prune [--dictionary <corpus>] [--guesses <file>]",
+            )),
+            _ => Err(Error::UnknownCommand(command)),
+        }
+    }
+}
+
+/// Parse a five-letter pattern in which `_`, `?`, or `.` stands for any
+/// letter.
+fn parse_pattern(pattern: &str) -> Option<[Option<u8>; 5]> {
+    let slots: Vec<Option<u8>> = normalize::input(pattern)
+        .bytes()
+        .map(|b| match b {
+            b'_' | b'?' | b'.' => Some(None),
+            _ if b.is_ascii_graphic() => Some(Some(b.to_ascii_lowercase())),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    slots.try_into().ok()
+}
+
+/// Parse a comma-separated list of clues, each a letter optionally preceded
+/// by its position counting from one, as in `3=a,r`.
+fn parse_clues(flag: &str, value: String) -> Result<Vec<(Option<usize>, u8)>, Error> {
+    let invalid = || Error::InvalidValue(flag.to_string(), value.clone());
+
+    let mut clues = Vec::new();
+    for clue in normalize::input(&value)
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+    {
+        let (position, letter) = match clue.split_once('=') {
+            Some((position, letter)) => match position.trim().parse::<usize>() {
+                Ok(n @ 1..=5) => (Some(n - 1), letter.trim()),
+                _ => return Err(invalid()),
+            },
+            None => (None, clue),
+        };
+
+        match letter.as_bytes() {
+            &[letter] if letter.is_ascii_graphic() => {
+                clues.push((position, letter.to_ascii_lowercase()))
+            },
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(clues)
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: String) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidValue(flag.to_string(), value))
+}
+
+/// A duration such as `200ms` or `1.5s`.
+fn parse_duration(flag: &str, value: String) -> Result<Duration, Error> {
+    let invalid = || Error::InvalidValue(flag.to_string(), value.clone());
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 1e-3)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        return Err(invalid());
+    };
+    let seconds: f64 = number.parse().map_err(|_| invalid())?;
+    Duration::try_from_secs_f64(seconds * scale).map_err(|_| invalid())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn parse(args: &[&str]) -> Result<Options, Error> {
+        Options::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options.command, Command::Benchmark);
+        assert_eq!(options.strategy, "likeliest");
+        assert_eq!(options.smoothing, 0.0);
+        assert_eq!(options.sigmoid, None);
+        assert_eq!(options.inflection_weight, 1.0);
+        assert!(options.plays_by_default());
+        assert!(parse(&["solve", "--copy", "--unlimited"])
+            .unwrap()
+            .plays_by_default());
+        assert!(!parse(&["solve", "--strategy", "entropy"])
+            .unwrap()
+            .plays_by_default());
+        assert!(!parse(&["daily", "--endgame", "20"])
+            .unwrap()
+            .plays_by_default());
+        assert!(!parse(&["solve", "--duplicates", "naive"])
+            .unwrap()
+            .plays_by_default());
+    }
+
+    #[test]
+    fn lists_rule_out_the_tree() {
+        for args in [
+            &["solve", "--dictionary", "corpus.txt"][..],
+            &["solve", "--guesses", "guesses.txt"],
+            &["solve", "--exclude", "past.txt"],
+            &["solve", "--allow-guesses", "known.txt"],
+            &["solve", "--deny-guesses", "rude.txt"],
+            &["solve", "--top-n", "5000"],
+            &["solve", "--answer-pool"],
+            &["solve", "--probe"],
+        ] {
+            assert!(!parse(args).unwrap().plays_by_default(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn flags() {
+        let options = parse(&[
+            "--strategy",
+            "entropy",
+            "--smoothing",
+            "0.5",
+            "--top-n",
+            "3000",
+        ])
+        .unwrap();
+        assert_eq!(options.strategy, "entropy");
+        assert_eq!(options.smoothing, 0.5);
+        assert_eq!(options.top_n, Some(3000));
+        let options = parse(&["--sigmoid", "3000", "--sigmoid-width", "400"]).unwrap();
+        assert_eq!(options.sigmoid, Some(3000));
+        assert_eq!(options.sigmoid_width, 400.0);
+        assert_eq!(options.endgame, None);
+        let options = parse(&["--param", "answer-weight=3", "--param", "smoothing=2"]).unwrap();
+        assert_eq!(
+            options.params,
+            [
+                ("answer-weight".to_string(), 3.0),
+                ("smoothing".to_string(), 2.0)
+            ]
+        );
+        assert!(parse(&["--param", "answer-weight"]).is_err());
+        assert!(parse(&["--param", "answer-weight=x"]).is_err());
+        assert_eq!(parse(&["--endgame", "20"]).unwrap().endgame, Some(20));
+        let options = parse(&["--objective", "worst"]).unwrap();
+        assert_eq!(options.objective.as_deref(), Some("worst"));
+        let budget = |value| parse(&["assist", "--budget", value]).map(|o| o.budget);
+        assert_eq!(budget("200ms").unwrap(), Some(Duration::from_millis(200)));
+        assert_eq!(budget("1.5s").unwrap(), Some(Duration::from_millis(1500)));
+        assert!(budget("200").is_err());
+        assert!(budget("-1s").is_err());
+
+        let options = parse(&["--preset", "balanced"]).unwrap();
+        assert_eq!(options.strategy, "entropy");
+        assert_eq!((options.endgame, options.top_n), (Some(8), None));
+        assert!(options.preset.is_some_and(Preset::caches));
+        // Options given on their own win, wherever they come.
+        let options = parse(&[
+            "--endgame",
+            "4",
+            "--preset",
+            "optimal",
+            "--strategy",
+            "rollout",
+        ]);
+        let options = options.unwrap();
+        assert_eq!(options.strategy, "rollout");
+        assert_eq!((options.endgame, options.probe), (Some(4), true));
+        assert_eq!(parse(&["--preset", "fast"]).unwrap().top_n, Some(10_000));
+        assert!(parse(&["--preset", "slow"]).is_err());
+
+        let options = parse(&["--answers", "a.txt", "--answer-pool", "--probe"]).unwrap();
+        assert_eq!(options.answers.as_deref(), Some("a.txt"));
+        let listed = parse(&["--answers", "a.json", "--list-format", "json"]).unwrap();
+        assert_eq!(listed.list_format.as_deref(), Some("json"));
+        assert_eq!(options.guesses, None);
+        assert_eq!(options.exclude, None);
+        assert_eq!(
+            (options.allow_guesses.clone(), options.deny_guesses.clone()),
+            (None, None)
+        );
+        let restricted =
+            parse(&["--allow-guesses", "known.txt", "--deny-guesses", "rude.txt"]).unwrap();
+        assert_eq!(restricted.allow_guesses.as_deref(), Some("known.txt"));
+        assert_eq!(restricted.deny_guesses.as_deref(), Some("rude.txt"));
+        assert_eq!(options.alphabet, None);
+        assert_eq!(options.cache_dir, None);
+        assert!(options.answer_pool);
+        assert!(options.probe);
+        assert!(!options.fold_accents);
+        assert_eq!(options.duplicates, None);
+        assert!(options.tiles.is_empty());
+        assert_eq!(options.language, None);
+
+        let options = parse(&["--tiles", "ch, ll", "--tiles", "rr"]).unwrap();
+        assert_eq!(options.tiles, ["ch", "ll", "rr"]);
+        assert!(!options.unlimited);
+        assert!(!options.verify);
+        assert!(parse(&["solve", "--verify"]).unwrap().verify);
+        assert!(!options.trace);
+        assert!(parse(&["--trace"]).unwrap().trace);
+        assert!(parse(&["--failures-only"]).unwrap().failures_only);
+        assert_eq!(
+            parse(&["--follow"]).unwrap().follow,
+            Some(Order::Completion)
+        );
+        let options = parse(&["build-tree", "t.txt", "--follow", "--follow-ordered"]).unwrap();
+        assert_eq!(options.follow, Some(Order::Input));
+        assert_eq!(options.verbosity, Verbosity::Normal);
+        assert_eq!(parse(&["-q"]).unwrap().verbosity, Verbosity::Quiet);
+        assert_eq!(
+            parse(&["chain", "--verbose"]).unwrap().verbosity,
+            Verbosity::Verbose
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let options = parse(&["duel", "likeliest", "--probe", "entropy"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Duel("likeliest".to_string(), "entropy".to_string())
+        );
+        assert!(options.probe);
+
+        assert!(matches!(
+            parse(&["duel", "entropy"]),
+            Err(Error::WrongArguments(_))
+        ));
+        assert_eq!(
+            parse(&["matrix", "salet,crane", "entropy"])
+                .unwrap()
+                .command,
+            Command::Matrix(
+                vec!["salet".to_string(), "crane".to_string()],
+                vec!["entropy".to_string()]
+            )
+        );
+        assert!(matches!(
+            parse(&["matrix", "salet", ","]),
+            Err(Error::WrongArguments(_))
+        ));
+        assert_eq!(
+            parse(&["pareto", "likeliest, entropy"]).unwrap().command,
+            Command::Pareto(vec!["likeliest".to_string(), "entropy".to_string()])
+        );
+        assert!(parse(&["pareto"]).is_err());
+        let options = parse(&["compare", "a.json", "b.json"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Compare("a.json".to_string(), "b.json".to_string())
+        );
+        assert!(parse(&["compare", "a.json"]).is_err());
+        let options = parse(&["--save-report", "a.json"]).unwrap();
+        assert_eq!(options.save_report.as_deref(), Some("a.json"));
+        let options = parse(&["duel", "likeliest", "entropy", "--chart", "d.svg"]).unwrap();
+        assert_eq!(options.chart.as_deref(), Some("d.svg"));
+        let options = parse(&["--report", "run.html"]).unwrap();
+        assert_eq!(options.html_report.as_deref(), Some("run.html"));
+        let options = parse(&["play", "--deck", "hard.tsv"]).unwrap();
+        assert_eq!(options.deck.as_deref(), Some("hard.tsv"));
+
+        assert_eq!(parse(&["--jobs", "1"]).unwrap().jobs, NonZeroUsize::new(1));
+        assert!(parse(&["-j", "0"]).is_err());
+
+        let options = parse(&["snapshot", "base.json"]).unwrap();
+        assert_eq!(options.command, Command::Snapshot("base.json".to_string()));
+        let options = parse(&[
+            "check",
+            "base.json",
+            "--average-tolerance",
+            "0.01",
+            "--failure-tolerance",
+            "2",
+        ])
+        .unwrap();
+        assert_eq!(options.command, Command::Check("base.json".to_string()));
+        assert_eq!(options.average_tolerance, 0.01);
+        assert_eq!(options.failure_tolerance, 2);
+
+        let options = parse(&["serve", "--port", "8080", "--ui"]).unwrap();
+        assert_eq!(options.command, Command::Serve);
+        assert_eq!((options.port, options.ui), (8080, true));
+        assert!(parse(&["serve", "--port", "70000"]).is_err());
+        let options = parse(&["serve", "--league", "family.txt", "--bind", "0.0.0.0"]).unwrap();
+        assert_eq!(options.league.as_deref(), Some("family.txt"));
+        assert_eq!(options.bind, "0.0.0.0");
+        assert!(parse(&["serve", "--stdio"]).unwrap().stdio);
+
+        let options = parse(&["train", "weights.txt", "--answers-only"]).unwrap();
+        assert_eq!(options.command, Command::Train("weights.txt".to_string()));
+        assert!(options.answers_only);
+        let options = parse(&["tune", "entropy.profile", "--generations", "3"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Tune("entropy.profile".to_string())
+        );
+        assert_eq!((options.population, options.generations), (12, 3));
+        let options = parse(&["openings", "3", "--steps", "50"]).unwrap();
+        assert_eq!((options.command, options.steps), (Command::Openings(3), 50));
+        assert!(parse(&["openings", "4"]).is_err());
+        assert!(parse(&["openings"]).is_err());
+        let options = parse(&["--layout", "dvorak"]).unwrap();
+        assert_eq!(options.layout.as_deref(), Some("dvorak"));
+        let options = parse(&["--profile", "entropy.profile"]).unwrap();
+        assert_eq!(options.profile.as_deref(), Some("entropy.profile"));
+        let options = parse(&["--reference", "tree.txt"]).unwrap();
+        assert_eq!(options.reference.as_deref(), Some("tree.txt"));
+        let options = parse(&["--strategy", "letters", "--weights", "weights.txt"]).unwrap();
+        assert_eq!(options.weights.as_deref(), Some("weights.txt"));
+        let options = parse(&["--strategy", "letters", "--freq-source", "answers"]).unwrap();
+        assert_eq!(options.freq_source.as_deref(), Some("answers"));
+        assert!(!options.answers_only);
+        let options = parse(&["build-tree", "tree.txt", "--strategy", "entropy"]).unwrap();
+        assert_eq!(options.command, Command::BuildTree("tree.txt".to_string()));
+        assert!(parse(&["build-tree"]).is_err());
+        let options = parse(&["optimal", "--depth", "5"]).unwrap();
+        assert_eq!(options.command, Command::Optimal);
+        assert_eq!(options.depth, Some(5));
+        assert!(parse(&["optimal", "salet"]).is_err());
+        let options = parse(&["clusters", "--depth", "2"]).unwrap();
+        assert_eq!(options.command, Command::Clusters);
+        assert_eq!(options.depth, Some(2));
+        assert!(parse(&["clusters", "salet"]).is_err());
+        let options = parse(&["humans", "scores.csv"]).unwrap();
+        assert_eq!(options.command, Command::Humans("scores.csv".to_string()));
+        assert!(parse(&["humans"]).is_err());
+        let options = parse(&["schedule", "2026-01-01", "2026-12-31", "family.txt"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Schedule(20454, 20818, "family.txt".to_string())
+        );
+        assert!(parse(&["schedule", "2026-12-31", "2026-01-01", "family.txt"]).is_err());
+        assert!(parse(&["schedule", "2026-02-30", "2026-03-01", "family.txt"]).is_err());
+        let options = parse(&["rate", "crane", "moist=YBBBG", "joist"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Rate(vec![
+                ("crane".to_string(), None),
+                ("moist".to_string(), Some(pattern::parse("YBBBG").unwrap())),
+                ("joist".to_string(), None),
+            ])
+        );
+        assert!(parse(&["rate"]).is_err());
+        let options = parse(&["bot", "1234", "5678", "--league", "l.txt"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Bot(vec!["1234".to_string(), "5678".to_string()])
+        );
+        assert!(parse(&["bot"]).is_err());
+        assert!(parse(&["bot", "general"]).is_err());
+        let options = parse(&["import", "chat.txt"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Import(vec!["chat.txt".to_string()])
+        );
+        assert!(parse(&["rate", "crane", "moist=YBBBG"]).is_err());
+        assert!(parse(&["rate", "crane=GYB"]).is_err());
+        let options = parse(&["daily", "--schedule", "family.txt"]).unwrap();
+        assert_eq!(options.schedule.as_deref(), Some("family.txt"));
+        let options = parse(&["stats", "letters", "--heatmap"]).unwrap();
+        assert_eq!(options.command, Command::LetterStats(false));
+        assert!(options.heatmap);
+        let options = parse(&[
+            "assist",
+            "--given",
+            "crane=GYBBY",
+            "--given",
+            "moist=🟨⬛⬛⬛🟩",
+        ]);
+        let given = options.unwrap().given;
+        assert_eq!(given[0].0, "crane");
+        assert_eq!(given[1].1, pattern::parse("YBBBG").unwrap());
+        assert!(parse(&["solve", "--given", "crane"]).is_err());
+        assert!(parse(&["solve", "--given", "crane=GYB"]).is_err());
+        let options = parse(&["assist", "--fix-typos", "--open-vocabulary"]).unwrap();
+        assert!(options.fix_typos && options.open_vocabulary);
+        let options = parse(&["play", "--high-contrast", "--symbols"]).unwrap();
+        assert!(options.high_contrast && options.symbols);
+        let options = parse(&["play", "--language", "es"]).unwrap();
+        assert_eq!(options.language.as_deref(), Some("es"));
+        let options = parse(&["stats", "letters", "dictionary"]).unwrap();
+        assert_eq!(options.command, Command::LetterStats(true));
+        assert!(parse(&["stats"]).is_err());
+        assert!(parse(&["stats", "letters", "guesses"]).is_err());
+        let options = parse(&["corpus", "a.txt", "b.txt", "--frequency-list"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Corpus(vec!["a.txt".to_string(), "b.txt".to_string()])
+        );
+        assert!(options.frequency_list);
+        assert_eq!(parse(&["corpus"]).unwrap().command, Command::Corpus(vec![]));
+        assert_eq!(parse(&["prune"]).unwrap().command, Command::Prune);
+        assert!(matches!(
+            parse(&["prune", "words.txt"]),
+            Err(Error::WrongArguments(_))
+        ));
+        assert_eq!(parse(&["solve"]).unwrap().command, Command::Solve);
+        assert_eq!(parse(&["repl"]).unwrap().command, Command::Repl);
+        assert_eq!(parse(&["compete"]).unwrap().command, Command::Compete);
+        assert_eq!(parse(&["peaks"]).unwrap().command, Command::Peaks);
+        assert_eq!(
+            parse(&["jotto", "play"]).unwrap().command,
+            Command::Jotto(true)
+        );
+        assert_eq!(parse(&["waffle"]).unwrap().command, Command::Waffle);
+        let options = parse(&["chain", "--chain-length", "4"]).unwrap();
+        assert_eq!(options.command, Command::Chain);
+        assert_eq!(options.chain_length, 4);
+        let options = parse(&["play", "--min-count", "100", "--no-proper-nouns"]).unwrap();
+        assert_eq!(options.min_count, 100);
+        assert_eq!(options.blocklist, None);
+        assert!(!options.proper_nouns);
+        assert_eq!(options.difficulty, None);
+        assert!(options.keep_stats);
+        assert!(!options.copy);
+        let options = parse(&["play", "--difficulty", "easy", "--no-stats", "--copy"]).unwrap();
+        assert_eq!(options.difficulty.as_deref(), Some("easy"));
+        assert_eq!(options.accept, None);
+        assert!(!options.keep_stats);
+        assert!(options.copy);
+        let options = parse(&["play", "--accept", "open"]).unwrap();
+        assert_eq!(options.accept.as_deref(), Some("open"));
+
+        let options = parse(&["daily", "--webhook", "https://example.com/hook"]).unwrap();
+        assert_eq!(options.command, Command::Daily);
+        assert_eq!(options.webhook.as_deref(), Some("https://example.com/hook"));
+
+        let options = parse(&["auto", "--history", "h.txt", "--timezone", "+09:00"]).unwrap();
+        assert_eq!(options.command, Command::Auto);
+        assert_eq!(options.history.as_deref(), Some("h.txt"));
+        assert_eq!(options.timezone.as_deref(), Some("+09:00"));
+        assert!(matches!(
+            parse(&["auto", "now"]),
+            Err(Error::WrongArguments(_))
+        ));
+
+        let options = parse(&["archive", "#1,234", "--assist"]).unwrap();
+        assert_eq!(options.command, Command::Archive(1234));
+        assert!(options.assist);
+        assert_eq!(
+            parse(&["archive", "7"]).unwrap().command,
+            Command::Archive(7)
+        );
+        assert!(matches!(
+            parse(&["archive", "seven"]),
+            Err(Error::WrongArguments(_))
+        ));
+        assert!(matches!(parse(&["archive"]), Err(Error::WrongArguments(_))));
+
+        let options = parse(&["anti", "--seed", "7"]).unwrap();
+        assert_eq!(options.command, Command::Anti);
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.split, None);
+        let options = parse(&["audit", "#1,234"]).unwrap();
+        assert_eq!(options.command, Command::Audit(Some("#1,234".to_string())));
+        assert_eq!(parse(&["audit"]).unwrap().command, Command::Audit(None));
+        assert!(parse(&["audit", "cigar", "1"]).is_err());
+        assert_eq!(parse(&["infer"]).unwrap().command, Command::Infer);
+        assert!(parse(&["infer", "cigar"]).is_err());
+        let options = parse(&["race", "--strategy", "entropy"]).unwrap();
+        assert_eq!(options.command, Command::Race);
+        assert!(parse(&["race", "cigar"]).is_err());
+        assert_eq!(parse(&["speedrun"]).unwrap().command, Command::Speedrun);
+        assert!(parse(&["speedrun", "now"]).is_err());
+        let options = parse(&["duel", "a", "b", "--split", "eval", "--seed", "3"]).unwrap();
+        assert_eq!(options.split.as_deref(), Some("eval"));
+        let options = parse(&["--sample", "200", "--seed", "3"]).unwrap();
+        assert_eq!((options.sample, options.seed), (Some(200), Some(3)));
+        assert!(parse(&["--sample", "some"]).is_err());
+        assert_eq!(parse(&["play"]).unwrap().command, Command::Play);
+        let options = parse(&["hint", "--top-k", "3"]).unwrap();
+        assert_eq!(options.command, Command::Hint);
+        assert_eq!(options.top_k, 3);
+        assert!(matches!(parse(&["bogus"]), Err(Error::UnknownCommand(_))));
+    }
+
+    #[test]
+    fn clues() {
+        let options = parse(&[
+            "query", "--green", "3=a", "--yellow", "r,1=E", "--gray", "s, t", "--grey", "e",
+        ])
+        .unwrap();
+        assert_eq!(options.command, Command::Query);
+        assert_eq!(options.green, vec![(2, b'a')]);
+        assert_eq!(options.yellow, vec![(None, b'r'), (Some(0), b'e')]);
+        assert_eq!(options.gray, vec![b's', b't', b'e']);
+
+        // Fullwidth letters, as some input methods type them, read as ASCII.
+        let options = parse(&["--green", "２＝Ｃ", "--gray", " ｘ\u{200b}"]).unwrap();
+        assert_eq!(options.green, vec![(1, b'c')]);
+        assert_eq!(options.gray, vec![b'x']);
+
+        for (flag, value) in [
+            ("--green", "a"),
+            ("--green", "6=a"),
+            ("--yellow", "ab"),
+            ("--gray", "2=s"),
+        ] {
+            assert!(matches!(
+                parse(&[flag, value]),
+                Err(Error::InvalidValue(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn pattern() {
+        let options = parse(&["match", "S_a?e", "--exclude", "r,t"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Match([Some(b's'), None, Some(b'a'), None, Some(b'e')])
+        );
+        assert_eq!(options.exclude, None);
+        assert_eq!(options.gray, vec![b'r', b't']);
+        assert_eq!(
+            parse(&["match", " ｓ＿ａ？ｅ "]).unwrap().command,
+            Command::Match([Some(b's'), None, Some(b'a'), None, Some(b'e')])
+        );
+
+        // Elsewhere, --exclude is still a word list.
+        let options = parse(&["--exclude", "r,t"]).unwrap();
+        assert_eq!(options.exclude.as_deref(), Some("r,t"));
+
+        assert!(matches!(
+            parse(&["match", "s_a_"]),
+            Err(Error::WrongArguments(_))
+        ));
+        assert!(matches!(parse(&["match"]), Err(Error::WrongArguments(_))));
+    }
+
+    #[test]
+    fn bad_flags() {
+        assert!(matches!(
+            parse(&["--strategy"]),
+            Err(Error::MissingValue(_))
+        ));
+        assert!(matches!(
+            parse(&["--smoothing", "x"]),
+            Err(Error::InvalidValue(..))
+        ));
+        assert!(matches!(
+            parse(&["--sigmoid-width", "0"]),
+            Err(Error::InvalidValue(..))
+        ));
+        assert!(matches!(
+            parse(&["--inflection-weight", "2"]),
+            Err(Error::InvalidValue(..))
+        ));
+        assert!(matches!(
+            parse(&["--bogus"]),
+            Err(Error::UnknownArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::{
+    guesser::{Correctness, Guess},
+    pattern::Tiles,
+    report::{Report, MAX_GUESSES},
+};
+
+/// The turn whose state families are found at unless another is given: the
+/// answers still together when the fourth guess is chosen.
+pub const TURNS: usize = 3;
+
+/// Answers a strategy couldn't tell apart within some number of guesses:
+/// each got the same feedback to the same guesses, so all reached the same
+/// state, and whatever went wrong for one went wrong for the family.
+#[derive(Clone, Debug)]
+pub struct Family<'a> {
+    /// The guesses every answer in the family was given, with their feedback.
+    pub path: Vec<Guess<'a>>,
+    /// Each answer in the family with the guesses it took in the end, scored
+    /// as [`Outcome::score`](crate::report::Outcome::score) does.
+    pub answers: Vec<(&'a str, usize)>,
+}
+
+impl Family<'_> {
+    /// The guesses the family took on average.
+    pub fn average(&self) -> f64 {
+        let total: usize = self.answers.iter().map(|&(_, score)| score).sum();
+        total as f64 / self.answers.len().max(1) as f64
+    }
+
+    /// How many of the family weren't found within the limit.
+    pub fn missed(&self) -> usize {
+        self.answers
+            .iter()
+            .filter(|&&(_, score)| score > MAX_GUESSES)
+            .count()
+    }
+}
+
+/// The answers in `report` grouped by their path, as the states they share
+/// after `turns` guesses. Answers found by then, and states only one answer
+/// reaches, are left out, since nothing was trapped there. The most missed
+/// families come first, then the largest, then the slowest.
+pub fn families<'a>(report: &Report<'a>, turns: usize) -> Vec<Family<'a>> {
+    let mut states: HashMap<Vec<(&'a str, u8)>, Family<'a>> = HashMap::new();
+    for outcome in report.outcomes() {
+        let path = &outcome.result.path;
+        if path.len() <= turns {
+            continue;
+        }
+        let key = path[..turns]
+            .iter()
+            .map(|guess| (guess.word(), Correctness::encode(guess.mask())))
+            .collect();
+        states
+            .entry(key)
+            .or_insert_with(|| Family {
+                path: path[..turns].to_vec(),
+                answers: Vec::new(),
+            })
+            .answers
+            .push((outcome.answer, outcome.score()));
+    }
+
+    let mut families: Vec<Family> = states
+        .into_values()
+        .filter(|family| family.answers.len() > 1)
+        .collect();
+    for family in &mut families {
+        family.answers.sort_unstable();
+    }
+    families.sort_by(|a, b| {
+        b.missed()
+            .cmp(&a.missed())
+            .then(b.answers.len().cmp(&a.answers.len()))
+            .then(b.average().total_cmp(&a.average()))
+            .then_with(|| a.answers.cmp(&b.answers))
+    });
+    families
+}
+
+/// Print the first `top` of `families`, found after `turns` guesses over
+/// `count` answers, with words shown through `decode`.
+pub fn print(
+    families: &[Family],
+    turns: usize,
+    count: usize,
+    top: usize,
+    decode: impl Fn(&str) -> String,
+) {
+    let trapped: usize = families.iter().map(|family| family.answers.len()).sum();
+    println!(
+        "{} families after {turns} guesses, holding {trapped} of {count} answers",
+        families.len()
+    );
+    for family in families.iter().take(top) {
+        println!();
+        let path: Vec<String> = family
+            .path
+            .iter()
+            .map(|guess| format!("{} {}", decode(guess.word()), Tiles(guess.mask())))
+            .collect();
+        println!("  {}", path.join("  "));
+        println!(
+            "  {} answers, {:.2} guesses on average, {} missed",
+            family.answers.len(),
+            family.average(),
+            family.missed()
+        );
+        let answers: Vec<String> = family
+            .answers
+            .iter()
+            .map(|&(answer, score)| match score {
+                score if score > MAX_GUESSES => format!("{}(x)", decode(answer)),
+                score => format!("{}({score})", decode(answer)),
+            })
+            .collect();
+        println!("    {}", answers.join(" "));
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::{outcome, Outcome};
+
+    /// An outcome for `answer` that guessed `words`, the last being it.
+    #[cfg(test)]
+    fn played<'a>(answer: &'a str, words: &[&'a str]) -> Outcome<'a> {
+        let mut outcome = outcome(answer, Some(words.len()));
+        for (guess, word) in outcome.result.path.iter_mut().zip(words) {
+            *guess = Guess::check(answer, word);
+        }
+        outcome
+    }
+
+    #[test]
+    fn traps() {
+        let mut report = Report::default();
+        // The "atch" words all look alike after two guesses.
+        report.push(played("hatch", &["salet", "latch", "hatch"]));
+        report.push(played("catch", &["salet", "latch", "hatch", "catch"]));
+        report.push(played(
+            "match",
+            &["salet", "latch", "hatch", "catch", "match"],
+        ));
+        // Found on the second guess, so never trapped.
+        report.push(played("latch", &["salet", "latch"]));
+        // Alone in its state.
+        report.push(played("chomp", &["salet", "crony", "chomp"]));
+
+        let families = families(&report, 2);
+        assert_eq!(families.len(), 1);
+        let family = &families[0];
+        assert_eq!(family.answers, [("catch", 4), ("hatch", 3), ("match", 5)]);
+        assert_eq!(family.path[1].word(), "latch");
+        assert_eq!(family.average(), 4.0);
+        assert_eq!(family.missed(), 0);
+
+        // By the next turn "hatch" has been found, but the others are still
+        // together, and they split after that.
+        let families = super::families(&report, 3);
+        assert_eq!(families[0].answers, [("catch", 4), ("match", 5)]);
+        assert!(super::families(&report, 4).is_empty());
+    }
+}
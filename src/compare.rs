@@ -0,0 +1,389 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    json::{self, Value},
+    report::{Report, MAX_GUESSES},
+    significance,
+};
+
+/// How many of the answers whose guesses changed are listed.
+const CHANGED: usize = 20;
+
+/// How one answer went in a saved run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solve {
+    pub answer: String,
+    /// Every word guessed, ending with the answer if it was found.
+    pub path: Vec<String>,
+    /// Whether the answer was found at all, however late.
+    pub found: bool,
+}
+
+impl Solve {
+    /// The guesses taken, with every failure scored as one past the limit,
+    /// as in [`Outcome::score`](crate::report::Outcome::score).
+    pub fn score(&self) -> usize {
+        match self.path.len() {
+            n if self.found && n <= MAX_GUESSES => n,
+            _ => MAX_GUESSES + 1,
+        }
+    }
+
+    pub fn solved(&self) -> bool {
+        self.score() <= MAX_GUESSES
+    }
+}
+
+/// A benchmark run saved to a file, to compare with later runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Run {
+    pub strategy: String,
+    pub solves: Vec<Solve>,
+}
+
+impl Run {
+    /// The run in `report`, with every word spelled by `decode` as it's
+    /// typed rather than as it's stored.
+    pub fn new(strategy: &str, report: &Report, decode: impl Fn(&str) -> String) -> Self {
+        let solves = report
+            .outcomes()
+            .iter()
+            .map(|outcome| Solve {
+                answer: decode(outcome.answer),
+                path: outcome
+                    .result
+                    .path
+                    .iter()
+                    .map(|g| decode(g.word()))
+                    .collect(),
+                found: outcome.result.solved,
+            })
+            .collect();
+        Self {
+            strategy: strategy.to_string(),
+            solves,
+        }
+    }
+
+    /// The average guesses over the answers solved within the limit.
+    pub fn average(&self) -> f64 {
+        let solved: Vec<usize> = self
+            .solves
+            .iter()
+            .filter(|s| s.solved())
+            .map(Solve::score)
+            .collect();
+        solved.iter().sum::<usize>() as f64 / solved.len() as f64
+    }
+
+    pub fn missed(&self) -> usize {
+        self.solves.iter().filter(|s| !s.solved()).count()
+    }
+
+    /// Write the run as JSON, one answer per line so runs diff well as text.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "{{")?;
+        writeln!(w, "  \"strategy\": {},", json::quote(&self.strategy))?;
+        writeln!(w, "  \"answers\": [")?;
+        for (i, solve) in self.solves.iter().enumerate() {
+            let path = Value::Array(solve.path.iter().map(|w| w.as_str().into()).collect());
+            let solve = Value::Object(vec![
+                ("answer".to_string(), solve.answer.as_str().into()),
+                ("path".to_string(), path),
+                ("found".to_string(), Value::Bool(solve.found)),
+            ]);
+            let comma = if i + 1 < self.solves.len() { "," } else { "" };
+            writeln!(w, "    {solve}{comma}")?;
+        }
+        writeln!(w, "  ]")?;
+        writeln!(w, "}}")
+    }
+
+    /// Read a run written by [`save`](Self::save).
+    pub fn load(mut r: impl Read) -> io::Result<Self> {
+        let mut text = String::new();
+        r.read_to_string(&mut text)?;
+        let value =
+            json::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let strategy = value.get("strategy").and_then(Value::as_str);
+        let answers = value.get("answers").and_then(Value::as_array);
+        let (Some(strategy), Some(answers)) = (strategy, answers) else {
+            return Err(invalid("expected a strategy and answers"));
+        };
+        let solves = answers
+            .iter()
+            .map(|solve| {
+                let answer = solve.get("answer").and_then(Value::as_str);
+                let path: Option<Vec<String>> = solve
+                    .get("path")
+                    .and_then(Value::as_array)
+                    .and_then(|path| {
+                        path.iter()
+                            .map(|word| word.as_str().map(str::to_string))
+                            .collect()
+                    });
+                let found = solve.get("found").and_then(Value::as_bool);
+                match (answer, path, found) {
+                    (Some(answer), Some(path), Some(found)) => Ok(Solve {
+                        answer: answer.to_string(),
+                        path,
+                        found,
+                    }),
+                    _ => Err(invalid(&format!("bad answer: {solve}"))),
+                }
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            strategy: strategy.to_string(),
+            solves,
+        })
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// The differences between two runs over the same answers.
+#[derive(Debug, Default, PartialEq)]
+pub struct Comparison<'r> {
+    /// Answers solved in a different number of guesses, as `(answer, a's
+    /// score, b's score)`, the largest change first.
+    pub changed: Vec<(&'r str, usize, usize)>,
+    /// Answers the first run solved and the second missed.
+    pub new_failures: Vec<&'r str>,
+    /// Answers the first run missed and the second solved.
+    pub fixed: Vec<&'r str>,
+    /// How many answers only one of the runs tried.
+    pub unmatched: usize,
+    /// The second run's guesses less the first's, over the answers both
+    /// tried, with failures scored as one past the limit.
+    pub delta: isize,
+    /// The scores of each answer both runs tried, in the first run's order.
+    pub pairs: Vec<(usize, usize)>,
+}
+
+/// Compare two runs answer by answer, matching answers by name.
+pub fn compare<'r>(a: &'r Run, b: &'r Run) -> Comparison<'r> {
+    let b_solves: HashMap<&str, &Solve> = b.solves.iter().map(|s| (s.answer.as_str(), s)).collect();
+
+    let mut comparison = Comparison::default();
+    let mut matched = 0;
+    for a in &a.solves {
+        let Some(b) = b_solves.get(a.answer.as_str()) else {
+            continue;
+        };
+        matched += 1;
+        comparison.delta += b.score() as isize - a.score() as isize;
+        comparison.pairs.push((a.score(), b.score()));
+        match (a.solved(), b.solved()) {
+            (true, false) => comparison.new_failures.push(&a.answer),
+            (false, true) => comparison.fixed.push(&a.answer),
+            _ => {},
+        }
+        if a.score() != b.score() {
+            comparison.changed.push((&a.answer, a.score(), b.score()));
+        }
+    }
+    comparison.unmatched = a.solves.len() + b.solves.len() - 2 * matched;
+    comparison
+        .changed
+        .sort_by_key(|&(_, a, b)| std::cmp::Reverse(a.abs_diff(b)));
+    comparison
+}
+
+/// How much worse than a baseline a run may do and still pass a check.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tolerance {
+    /// How far the average score may rise.
+    pub average: f64,
+    /// How many more answers may be missed.
+    pub failures: usize,
+}
+
+/// The ways `run` is worse than `baseline` by more than `tolerance`, each
+/// described on a line. Empty if the run passes.
+pub fn regressions(baseline: &Run, run: &Run, tolerance: Tolerance) -> Vec<String> {
+    let mut regressions = Vec::new();
+    let (before, after) = (baseline.average(), run.average());
+    // A run that solves nothing has no average, which is as bad as it gets.
+    if after.is_nan() && !before.is_nan() || after - before > tolerance.average {
+        regressions.push(format!(
+            "average score rose from {before:.4} to {after:.4}, past a tolerance of {}",
+            tolerance.average
+        ));
+    }
+    if run.missed() > baseline.missed() + tolerance.failures {
+        regressions.push(format!(
+            "missed words rose from {} to {}, past a tolerance of {}",
+            baseline.missed(),
+            run.missed(),
+            tolerance.failures
+        ));
+    }
+    regressions
+}
+
+/// Print how the run in `b_name` differs from the one in `a_name`.
+pub fn print(a_name: &str, a: &Run, b_name: &str, b: &Run) {
+    let score = |n: usize| {
+        if n > MAX_GUESSES {
+            "-".to_string()
+        } else {
+            n.to_string()
+        }
+    };
+    let comparison = compare(a, b);
+
+    println!("{:<16} {a_name:>12} {b_name:>12}", "");
+    println!("{:<16} {:>12} {:>12}", "strategy", a.strategy, b.strategy);
+    println!(
+        "{:<16} {:>12.4} {:>12.4} {:>+10.4}",
+        "average score",
+        a.average(),
+        b.average(),
+        b.average() - a.average()
+    );
+    println!(
+        "{:<16} {:>12} {:>12} {:>+10}",
+        "missed words",
+        a.missed(),
+        b.missed(),
+        b.missed() as isize - a.missed() as isize
+    );
+    println!();
+    println!("total guesses: {:+}", comparison.delta);
+    print!("{}", significance::paired(&comparison.pairs));
+    if comparison.unmatched > 0 {
+        println!(
+            "answers in only one run: {} (not compared)",
+            comparison.unmatched
+        );
+    }
+
+    let better = comparison.changed.iter().filter(|(_, a, b)| b < a).count();
+    println!(
+        "changed answers: {} ({better} better, {} worse)",
+        comparison.changed.len(),
+        comparison.changed.len() - better
+    );
+    for (answer, a, b) in comparison.changed.iter().take(CHANGED) {
+        println!("  {answer}: {} -> {}", score(*a), score(*b));
+    }
+
+    println!("new failures:");
+    for answer in &comparison.new_failures {
+        println!("  {answer}");
+    }
+    println!("fixed failures:");
+    for answer in &comparison.fixed {
+        println!("  {answer}");
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn run(strategy: &str, results: &[(&str, Option<usize>)]) -> Run {
+        let solves = results
+            .iter()
+            .map(|&(answer, guesses)| {
+                let mut path = vec!["salet".to_string(); guesses.unwrap_or(MAX_GUESSES)];
+                if guesses.is_some() {
+                    *path.last_mut().unwrap() = answer.to_string();
+                }
+                Solve {
+                    answer: answer.to_string(),
+                    path,
+                    found: guesses.is_some(),
+                }
+            })
+            .collect();
+        Run {
+            strategy: strategy.to_string(),
+            solves,
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let run = run("likeliest", &[("cigar", Some(3)), ("rebut", None)]);
+        let mut saved = Vec::new();
+        run.save(&mut saved).unwrap();
+        assert!(String::from_utf8_lossy(&saved).contains(
+            "    {\"answer\":\"cigar\",\"path\":[\"salet\",\"salet\",\"cigar\"],\"found\":true},\n"
+        ));
+        assert_eq!(Run::load(&saved[..]).unwrap(), run);
+
+        assert!(Run::load(&b"{\"answers\": []}"[..]).is_err());
+        assert!(Run::load(&b"{\"strategy\": \"x\", \"answers\": [{}]}"[..]).is_err());
+    }
+
+    #[test]
+    fn regressed() {
+        let baseline = run("likeliest", &[("cigar", Some(3)), ("rebut", Some(4))]);
+        let better = run("likeliest", &[("cigar", Some(3)), ("rebut", Some(3))]);
+        let worse = run("likeliest", &[("cigar", Some(4)), ("rebut", None)]);
+
+        assert!(regressions(&baseline, &better, Tolerance::default()).is_empty());
+        assert!(regressions(&baseline, &baseline, Tolerance::default()).is_empty());
+        assert_eq!(
+            regressions(&baseline, &worse, Tolerance::default()).len(),
+            2
+        );
+
+        let tolerance = Tolerance {
+            average: 0.5,
+            failures: 1,
+        };
+        assert!(regressions(&baseline, &worse, tolerance).is_empty());
+        let none = run("likeliest", &[("cigar", None), ("rebut", None)]);
+        let tolerance = Tolerance {
+            average: 0.5,
+            failures: 2,
+        };
+        assert_eq!(regressions(&baseline, &none, tolerance).len(), 1);
+    }
+
+    #[test]
+    fn differences() {
+        let a = run(
+            "likeliest",
+            &[
+                ("cigar", Some(3)),
+                ("rebut", None),
+                ("sissy", Some(4)),
+                ("humph", Some(5)),
+            ],
+        );
+        let b = run(
+            "entropy",
+            &[
+                ("cigar", Some(3)),
+                ("rebut", Some(2)),
+                ("sissy", Some(7)),
+                ("awake", Some(4)),
+            ],
+        );
+        assert_eq!((a.average(), a.missed()), (4.0, 1));
+
+        assert_eq!(
+            compare(&a, &b),
+            Comparison {
+                changed: vec![("rebut", 7, 2), ("sissy", 4, 7)],
+                new_failures: vec!["sissy"],
+                fixed: vec!["rebut"],
+                unmatched: 2,
+                delta: -2,
+                pairs: vec![(3, 3), (7, 2), (4, 7)],
+            }
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    guesser::{Correctness, Guesser},
+    normalize::{self, Normalizer},
+    pattern,
+};
+
+/// A message from the referee.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// Start a new game, abandoning any in progress.
+    New,
+    /// The feedback for the last guess.
+    Feedback([Correctness; 5]),
+    Quit,
+}
+
+impl Message {
+    /// Read a message from a line. Returns `Ok(None)` for a blank line.
+    pub fn parse(line: &str) -> Result<Option<Self>, String> {
+        let line = normalize::input(line);
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            return Ok(None);
+        };
+        let argument = words.next();
+        if words.next().is_some() {
+            return Err(format!("too many arguments to {keyword}"));
+        }
+
+        let message = match (keyword.to_ascii_uppercase().as_str(), argument) {
+            ("NEW", None) => Message::New,
+            ("FEEDBACK", Some(mask)) => {
+                Message::Feedback(pattern::parse(mask).map_err(|e| e.to_string())?)
+            },
+            ("FEEDBACK", None) => return Err("FEEDBACK takes a pattern".to_string()),
+            ("QUIT", None) => Message::Quit,
+            ("NEW" | "QUIT", Some(_)) => return Err(format!("{keyword} takes no arguments")),
+            _ => return Err(format!("unknown message {keyword}")),
+        };
+        Ok(Some(message))
+    }
+}
+
+/// The solver as a bot for a referee that speaks over pipes, as Wordle-bot
+/// tournaments do, a message per line:
+///
+/// - the bot sends `READY` when it starts and whenever a game ends;
+/// - the referee sends `NEW`, and the bot answers `GUESS <word>`;
+/// - the referee sends `FEEDBACK <pattern>` for that guess, and the bot
+///   answers with its next `GUESS`, or `READY` once the feedback is all
+///   green;
+/// - the referee sends `QUIT` to stop the bot.
+///
+/// Anything the bot can't act on gets `ERROR <reason>`. If no word fits the
+/// feedback, or the bot runs out of guesses, the game ends with an `ERROR`
+/// and then `READY`.
+pub struct Competitor<'a, 'b> {
+    guesser: Guesser<'a, 'b>,
+    /// The word the referee's feedback will be for, while a game is on.
+    guess: Option<&'a str>,
+    normalizer: Normalizer,
+}
+
+impl<'a, 'b> Competitor<'a, 'b> {
+    pub fn new(guesser: Guesser<'a, 'b>) -> Self {
+        Self {
+            guesser,
+            guess: None,
+            normalizer: Normalizer::default(),
+        }
+    }
+
+    /// Send guesses in the form `normalizer` reads them, as the word lists
+    /// were loaded.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Answer the referee's messages from `input` until `QUIT` or the end
+    /// of the input, flushing each reply so the referee never waits on it.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "READY")?;
+        output.flush()?;
+        for line in input.lines() {
+            let replies = match Message::parse(&line?) {
+                Ok(Some(Message::Quit)) => break,
+                Ok(Some(message)) => self.handle(message),
+                Ok(None) => continue,
+                Err(e) => vec![format!("ERROR {e}")],
+            };
+            for reply in replies {
+                writeln!(output, "{reply}")?;
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// The replies to `message`, in order.
+    pub fn handle(&mut self, message: Message) -> Vec<String> {
+        match message {
+            Message::New => {
+                self.guesser.reset();
+                self.next()
+            },
+            Message::Feedback(mask) => {
+                let Some(word) = self.guess.take() else {
+                    return vec!["ERROR no game in progress; send NEW".to_string()];
+                };
+                let remaining = self.guesser.apply_feedback(word, mask);
+                if mask.iter().all(|&c| c == Correctness::Correct) {
+                    vec!["READY".to_string()]
+                } else if remaining == 0 {
+                    game_over("no word fits the feedback")
+                } else {
+                    self.next()
+                }
+            },
+            Message::Quit => Vec::new(),
+        }
+    }
+
+    /// The bot's next guess, or the end of the game if it has none.
+    fn next(&mut self) -> Vec<String> {
+        self.guess = self.guesser.next_guess();
+        match self.guess {
+            Some(word) => vec![format!("GUESS {}", self.normalizer.tiles().decode(word))],
+            None => game_over("no guesses left"),
+        }
+    }
+}
+
+/// The replies ending a game the bot couldn't finish.
+fn game_over(reason: &str) -> Vec<String> {
+    vec![format!("ERROR {reason}"), "READY".to_string()]
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+    #[test]
+    fn parse() {
+        assert_eq!(Message::parse("NEW\n"), Ok(Some(Message::New)));
+        assert_eq!(
+            Message::parse("feedback GYBBY"),
+            Ok(Some(Message::Feedback([C, M, W, W, M])))
+        );
+        assert_eq!(Message::parse(" "), Ok(None));
+        assert!(Message::parse("FEEDBACK").is_err());
+        assert!(Message::parse("NEW game").is_err());
+        assert!(Message::parse("GUESS crane").is_err());
+    }
+
+    #[test]
+    fn plays() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        let script = "FEEDBACK GGGGG\nNEW\nFEEDBACK BGBBY\nFEEDBACK BGGGG\nbogus\n\
+                      FEEDBACK GGGGG\nNEW\nQUIT\nNEW\n";
+        let mut output = Vec::new();
+        Competitor::new(guesser)
+            .run(script.as_bytes(), &mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "READY",
+                "ERROR no game in progress; send NEW",
+                "GUESS salet",
+                "GUESS hatch",
+                "GUESS catch",
+                "ERROR unknown message bogus",
+                "READY",
+                "GUESS salet",
+            ]
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crossterm::style::{Color, Stylize};
+
+static COLORS: AtomicBool = AtomicBool::new(true);
+
+/// Get the terminal ready for colored output. Windows consoles are switched
+/// to reading escape codes; older ones that can't be are left without
+/// colors, and tiles are drawn with their shapes instead. Call it once,
+/// before anything is drawn.
+pub fn init() {
+    #[cfg(windows)]
+    if !crossterm::ansi_support::supports_ansi() {
+        COLORS.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Whether output may be colored.
+pub fn colors() -> bool {
+    COLORS.load(Ordering::Relaxed)
+}
+
+/// `text` in `foreground` on `background`, or plain if the terminal can't
+/// show colors.
+pub fn paint(text: impl fmt::Display, foreground: Color, background: Color) -> String {
+    if !colors() {
+        return text.to_string();
+    }
+    text.to_string().with(foreground).on(background).to_string()
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn painted() {
+        assert_eq!(
+            paint(" A ", Color::Black, Color::DarkGreen),
+            "\x1b[48;5;2m\x1b[38;5;0m A \x1b[49m\x1b[39m"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// Word counts gathered from raw text or other frequency lists, to write out
+/// in the `word count` format the solver reads.
+#[derive(Clone, Debug, Default)]
+pub struct CorpusBuilder {
+    counts: HashMap<String, usize>,
+}
+
+impl CorpusBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count `word` `count` more times.
+    pub fn add(&mut self, word: &str, count: usize) {
+        match self.counts.get_mut(word) {
+            Some(total) => *total += count,
+            None => {
+                self.counts.insert(word.to_string(), count);
+            },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Keep only the words `keep` accepts, returning the rest with their
+    /// counts, from most to least frequent.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) -> Vec<(String, usize)> {
+        let mut removed = Vec::new();
+        self.counts.retain(|word, &mut count| {
+            let kept = keep(word);
+            if !kept {
+                removed.push((word.clone(), count));
+            }
+            kept
+        });
+        removed.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        removed
+    }
+
+    /// Every word and its total count, from most to least frequent, then
+    /// alphabetically.
+    pub fn entries(&self) -> Vec<(&str, usize)> {
+        let mut entries: Vec<(&str, usize)> = self
+            .counts
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries
+    }
+
+    /// Write the corpus as `word count` lines, most frequent first.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        for (word, count) in self.entries() {
+            writeln!(w, "{word} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The words in running text: runs of letters, split at anything else.
+pub fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|token| !token.is_empty())
+}
+
+/// A line of a frequency list, as `word count` or `count word`, separated by
+/// whitespace, a tab, or a comma. Header lines and comments don't parse.
+pub fn parse_count(line: &str) -> Option<(&str, usize)> {
+    let fields: Vec<&str> = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|field| !field.is_empty())
+        .collect();
+    let [a, b] = fields[..] else {
+        return None;
+    };
+
+    match (a.parse(), b.parse()) {
+        (Err(_), Ok(count)) => Some((a, count)),
+        (Ok(count), Err(_)) => Some((b, count)),
+        _ => None,
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn merges() {
+        let mut corpus = CorpusBuilder::new();
+        for word in tokens("Cigar, cigar! The rebut—rebut's 12 reasons.") {
+            corpus.add(&word.to_lowercase(), 1);
+        }
+        corpus.add("rebut", 3);
+        assert_eq!(
+            corpus.entries(),
+            [
+                ("rebut", 5),
+                ("cigar", 2),
+                ("reasons", 1),
+                ("s", 1),
+                ("the", 1)
+            ]
+        );
+
+        let mut saved = Vec::new();
+        corpus.save(&mut saved).unwrap();
+        assert!(saved.starts_with(b"rebut 5\ncigar 2\n"));
+    }
+
+    #[test]
+    fn prune() {
+        let mut corpus = CorpusBuilder::new();
+        for (word, count) in [("cigar", 9), ("xyzzy", 2), ("rebut", 4), ("qqqqq", 7)] {
+            corpus.add(word, count);
+        }
+        let legal = ["cigar", "rebut", "sissy"];
+        let removed = corpus.retain(|word| legal.contains(&word));
+        assert_eq!(
+            removed,
+            [("qqqqq".to_string(), 7), ("xyzzy".to_string(), 2)]
+        );
+        assert_eq!(corpus.entries(), [("cigar", 9), ("rebut", 4)]);
+    }
+
+    #[test]
+    fn frequency_lines() {
+        assert_eq!(parse_count("cigar 12"), Some(("cigar", 12)));
+        assert_eq!(parse_count("cigar\t12\r"), Some(("cigar", 12)));
+        assert_eq!(parse_count("12,cigar"), Some(("cigar", 12)));
+        assert_eq!(parse_count("word,count"), None);
+        assert_eq!(parse_count("# 12 words"), None);
+        assert_eq!(parse_count("cigar"), None);
+    }
+}
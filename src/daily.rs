@@ -0,0 +1,70 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::timezone::TimeZone;
+
+/// The day of puzzle 0, 2021-06-19, counted in days since 1970-01-01.
+const FIRST_DAY: i64 = 18797;
+
+/// Today's date in `zone`, in days since 1970-01-01. Puzzles roll over at
+/// local midnight, so this decides which one is today's.
+pub fn today(zone: &TimeZone) -> i64 {
+    let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    zone.day(seconds)
+}
+
+/// The number of the puzzle published on `day`, or `None` before the first.
+pub fn number(day: i64) -> Option<usize> {
+    usize::try_from(day - FIRST_DAY).ok()
+}
+
+/// The day puzzle `number` was published, in days since 1970-01-01.
+pub fn day(number: usize) -> i64 {
+    FIRST_DAY + number as i64
+}
+
+/// The answer to puzzle `number`, from an answer list in publication order.
+pub fn answer<'a>(number: usize, answers: &[&'a str]) -> Option<&'a str> {
+    answers.get(number).copied()
+}
+
+/// How the game titles puzzle `number` when sharing it, as in `Wordle 1,234`.
+pub fn title(number: usize) -> String {
+    let digits = number.to_string();
+    let mut title = "Wordle ".to_string();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            title.push(',');
+        }
+        title.push(digit);
+    }
+    title
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn numbering() {
+        assert_eq!(number(FIRST_DAY - 1), None);
+        assert_eq!(number(FIRST_DAY), Some(0));
+        // 2022-01-01, the day of puzzle 196.
+        assert_eq!(number(18993), Some(196));
+        assert_eq!(day(196), 18993);
+
+        let answers = ["cigar", "rebut", "sissy"];
+        assert_eq!(answer(1, &answers), Some("rebut"));
+        assert_eq!(answer(3, &answers), None);
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(title(7), "Wordle 7");
+        assert_eq!(title(196), "Wordle 196");
+        assert_eq!(title(1234), "Wordle 1,234");
+        assert_eq!(title(1234567), "Wordle 1,234,567");
+    }
+}
@@ -0,0 +1,481 @@
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::{
+    json::{self, Value},
+    normalize,
+    tiles::Tileset,
+};
+
+/// The width of a record in the compact corpus format: a five-letter word
+/// followed by a little-endian `u64` count.
+const RECORD: usize = 5 + 8;
+
+/// A word list, either embedded in the binary or read at runtime.
+///
+/// Nothing is parsed when a dictionary is constructed: [`words`](Self::words)
+/// and [`counts`](Self::counts) parse the text as they're iterated, so callers
+/// only pay for what they use.
+pub struct Dictionary {
+    source: Source,
+}
+
+enum Source {
+    Text(Cow<'static, str>),
+    /// A corpus preprocessed by the build script, already sorted from most to
+    /// least frequent.
+    Compact(Cow<'static, [u8]>),
+    /// Words with their counts, parsed up front from a list in another
+    /// format, in the list's order.
+    Entries(Vec<(String, usize)>),
+}
+
+/// The layout of a word list read at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Words one per line or whitespace-separated, or a `word count` corpus,
+    /// as the lists embedded in the binary are written.
+    Native,
+    /// Words one per line, each counted once.
+    Plain,
+    /// `word,weight` lines, where a missing weight counts once and a header
+    /// line is skipped.
+    Csv,
+    /// An array of words, of `[word, weight]` pairs, or of objects with a
+    /// `word` and a `count` or `weight`; or an object from words to weights.
+    Json,
+}
+
+impl Format {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "native" => Some(Self::Native),
+            "plain" | "txt" => Some(Self::Plain),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// The format of `text`, read from `path` if it was: by the path's
+    /// extension, or else by the text itself. Native lists never hold a
+    /// comma or open with a bracket, so anything else is taken as native.
+    pub fn detect(path: Option<&Path>, text: &str) -> Self {
+        let extension = path
+            .and_then(Path::extension)
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => return Self::Csv,
+            Some("json") => return Self::Json,
+            _ => {},
+        }
+
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        match lines.next() {
+            Some(line) if line.starts_with(['[', '{']) => Self::Json,
+            Some(line) if line.contains(',') => Self::Csv,
+            _ => Self::Native,
+        }
+    }
+}
+
+impl Dictionary {
+    pub fn embedded(text: &'static str) -> Self {
+        Self {
+            source: Source::Text(Cow::Borrowed(text)),
+        }
+    }
+
+    /// A corpus in the compact format written by the build script.
+    pub fn compact(blob: &'static [u8]) -> Self {
+        Self {
+            source: Source::Compact(Cow::Borrowed(blob)),
+        }
+    }
+
+    /// A list embedded deflated by the build script.
+    ///
+    /// # Panics
+    ///
+    /// If `blob` isn't deflated UTF-8 text, which the build script always
+    /// writes.
+    #[cfg(feature = "embedded-wordlists")]
+    pub fn inflate(blob: &[u8]) -> Self {
+        let text = String::from_utf8(inflated(blob)).expect("embedded list isn't UTF-8");
+        Self {
+            source: Source::Text(Cow::Owned(text)),
+        }
+    }
+
+    /// A corpus in the compact format, embedded deflated by the build
+    /// script.
+    ///
+    /// # Panics
+    ///
+    /// If `blob` isn't deflated, which the build script always does.
+    #[cfg(feature = "embedded-wordlists")]
+    pub fn inflate_compact(blob: &[u8]) -> Self {
+        Self {
+            source: Source::Compact(Cow::Owned(inflated(blob))),
+        }
+    }
+
+    /// A list read from `r`, in whatever format it appears to be in.
+    pub fn from_reader(r: impl Read) -> io::Result<Self> {
+        Self::from_reader_as(r, None)
+    }
+
+    /// A list read from `r` in `format`, or as [`Format::detect`] finds it.
+    pub fn from_reader_as(mut r: impl Read, format: Option<Format>) -> io::Result<Self> {
+        let mut text = String::new();
+        r.read_to_string(&mut text)?;
+        let format = format.unwrap_or_else(|| Format::detect(None, &text));
+        Self::parse(text, format)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_file_as(path, None)
+    }
+
+    /// A list read from the file at `path` in `format`, or as
+    /// [`Format::detect`] finds it from the path and the text.
+    pub fn from_file_as(path: impl AsRef<Path>, format: Option<Format>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        let format = format.unwrap_or_else(|| Format::detect(Some(path), &text));
+        Self::parse(text, format)
+    }
+
+    fn parse(text: String, format: Format) -> io::Result<Self> {
+        let source = match format {
+            Format::Native => Source::Text(Cow::Owned(text)),
+            Format::Plain => Source::Entries(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(|word| (word.to_string(), 1))
+                    .collect(),
+            ),
+            Format::Csv => Source::Entries(scaled(parse_csv(&text)?)),
+            Format::Json => Source::Entries(scaled(parse_json(&text)?)),
+        };
+        Ok(Self { source })
+    }
+
+    /// The same list with accented vowels folded to plain ones, so words
+    /// with and without them match.
+    pub fn fold_accents(self) -> Self {
+        match &self.source {
+            Source::Text(text) if !text.is_ascii() => Self {
+                source: Source::Text(Cow::Owned(normalize::fold_accents(text).into_owned())),
+            },
+            Source::Entries(_) => self.map_words(|word| normalize::fold_accents(word).into_owned()),
+            _ => self,
+        }
+    }
+
+    /// The same list with each of `tiles` encoded as its symbol.
+    pub fn encode_tiles(self, tiles: &Tileset) -> Self {
+        match &self.source {
+            Source::Text(text) if !tiles.is_empty() => Self {
+                source: Source::Text(Cow::Owned(tiles.encode(text).into_owned())),
+            },
+            Source::Entries(_) if !tiles.is_empty() => {
+                self.map_words(|word| tiles.encode(word).into_owned())
+            },
+            _ => self,
+        }
+    }
+
+    /// The same entries with each word replaced by `f`'s version of it.
+    fn map_words(self, f: impl Fn(&str) -> String) -> Self {
+        match self.source {
+            Source::Entries(entries) => Self {
+                source: Source::Entries(
+                    entries
+                        .into_iter()
+                        .map(|(word, count)| (f(&word), count))
+                        .collect(),
+                ),
+            },
+            source => Self { source },
+        }
+    }
+
+    /// Multi-letter tiles the list declares on a `# tiles:` line, as in
+    /// `# tiles: ch ll rr`.
+    pub fn declared_tiles(&self) -> Vec<&str> {
+        match &self.source {
+            Source::Text(text) => text
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("# tiles:"))
+                .flat_map(str::split_whitespace)
+                .collect(),
+            Source::Compact(_) | Source::Entries(_) => Vec::new(),
+        }
+    }
+
+    /// Words in a plain list, one per line (or whitespace-separated). Lines
+    /// starting with `#` are comments.
+    pub fn words(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        match &self.source {
+            Source::Text(text) => Box::new(
+                text.lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .flat_map(str::split_ascii_whitespace),
+            ),
+            Source::Compact(_) => Box::new(self.counts().map(|(word, _)| word)),
+            Source::Entries(entries) => Box::new(entries.iter().map(|(word, _)| word.as_str())),
+        }
+    }
+
+    /// Entries of a `word count` corpus, in file order. Lines that don't
+    /// parse are skipped.
+    pub fn counts(&self) -> Box<dyn Iterator<Item = (&str, usize)> + '_> {
+        match &self.source {
+            Source::Text(text) => {
+                Box::new(
+                    text.split('\n')
+                        .filter_map(|pair| match pair.split_once(' ') {
+                            Some((word, count_str)) => {
+                                count_str.trim().parse().map(|c| (word, c)).ok()
+                            },
+                            _ => None,
+                        }),
+                )
+            },
+            Source::Compact(blob) => Box::new(blob.chunks_exact(RECORD).filter_map(|record| {
+                let (word, count) = record.split_at(5);
+                let count = u64::from_le_bytes(count.try_into().ok()?);
+                Some((std::str::from_utf8(word).ok()?, count as usize))
+            })),
+            Source::Entries(entries) => {
+                Box::new(entries.iter().map(|(word, count)| (word.as_str(), *count)))
+            },
+        }
+    }
+
+    /// Entries of a `word count` corpus, from most to least frequent.
+    pub fn by_frequency(&self) -> Vec<(&str, usize)> {
+        let mut pairs: Vec<(&str, usize)> = self.counts().collect();
+        if !matches!(self.source, Source::Compact(_)) {
+            pairs.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        }
+        pairs
+    }
+}
+
+#[cfg(feature = "embedded-wordlists")]
+fn inflated(blob: &[u8]) -> Vec<u8> {
+    miniz_oxide::inflate::decompress_to_vec(blob).expect("embedded list is corrupt")
+}
+
+/// The entries of a `word,weight` CSV, with a header line, if any, skipped.
+fn parse_csv(text: &str) -> io::Result<Vec<(String, f64)>> {
+    let mut entries = Vec::new();
+    let lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    for (index, line) in lines.enumerate() {
+        let mut fields = line.split(',').map(|field| field.trim().trim_matches('"'));
+        let word = fields.next().unwrap_or_default();
+        let weight = match fields.next().filter(|field| !field.is_empty()) {
+            Some(field) => match field.parse::<f64>() {
+                Ok(weight) if weight.is_finite() && weight >= 0.0 => weight,
+                Err(_) if index == 0 => continue,
+                _ => return Err(invalid(format!("bad weight in line: {line}"))),
+            },
+            None => 1.0,
+        };
+        entries.push((word.to_string(), weight));
+    }
+    Ok(entries)
+}
+
+/// The entries of a JSON list, in any of the shapes [`Format::Json`] takes.
+fn parse_json(text: &str) -> io::Result<Vec<(String, f64)>> {
+    let value = json::parse(text).map_err(|e| invalid(e.to_string()))?;
+    let weight = |value: &Value| value.as_f64().filter(|w| w.is_finite() && *w >= 0.0);
+    let entry = |item: &Value| -> Option<(String, f64)> {
+        match item {
+            Value::String(word) => Some((word.clone(), 1.0)),
+            Value::Array(pair) => match &pair[..] {
+                [Value::String(word), count] => Some((word.clone(), weight(count)?)),
+                _ => None,
+            },
+            Value::Object(_) => {
+                let word = item.get("word")?.as_str()?.to_string();
+                match item.get("count").or_else(|| item.get("weight")) {
+                    Some(count) => Some((word, weight(count)?)),
+                    None => Some((word, 1.0)),
+                }
+            },
+            _ => None,
+        }
+    };
+
+    match &value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| entry(item).ok_or_else(|| invalid(format!("bad list entry: {item}"))))
+            .collect(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(word, count)| match weight(count) {
+                Some(count) => Ok((word.clone(), count)),
+                None => Err(invalid(format!("bad weight for {word}: {count}"))),
+            })
+            .collect(),
+        _ => Err(invalid(
+            "expected an array or an object of words".to_string(),
+        )),
+    }
+}
+
+/// The most that scaled weights add up to: where `f64` stops counting
+/// every whole number, and well within `usize` on 64-bit targets, so sums of
+/// the counts can't overflow.
+const SCALED_TOTAL: f64 = (1u64 << 53) as f64;
+
+/// `entries` with whole counts. Fractional weights, such as frequencies,
+/// are scaled up so their proportions survive rounding, though never so far
+/// that the counts together pass [`SCALED_TOTAL`].
+fn scaled(entries: Vec<(String, f64)>) -> Vec<(String, usize)> {
+    let scale = if entries.iter().all(|(_, weight)| weight.fract() == 0.0) {
+        1.0
+    } else {
+        let total: f64 = entries.iter().map(|(_, weight)| weight).sum();
+        (SCALED_TOTAL / total).min(1e9)
+    };
+    entries
+        .into_iter()
+        .map(|(word, weight)| (word, (weight * scale).round() as usize))
+        .collect()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn parse_words() {
+        let dictionary = Dictionary::embedded("cigar\nrebut\r\n sissy\n");
+        assert_eq!(
+            dictionary.words().collect::<Vec<_>>(),
+            vec!["cigar", "rebut", "sissy"]
+        );
+    }
+
+    #[test]
+    fn parse_counts() {
+        let dictionary = Dictionary::embedded("cigar 3\nrebut 10\nbogus\nsissy x\n");
+        assert_eq!(dictionary.by_frequency(), vec![("rebut", 10), ("cigar", 3)]);
+    }
+
+    #[test]
+    fn compact() {
+        static BLOB: &[u8] = b"rebut\x0a\0\0\0\0\0\0\0cigar\x03\0\0\0\0\0\0\0";
+        let dictionary = Dictionary::compact(BLOB);
+        assert_eq!(dictionary.by_frequency(), vec![("rebut", 10), ("cigar", 3)]);
+        assert_eq!(
+            dictionary.words().collect::<Vec<_>>(),
+            vec!["rebut", "cigar"]
+        );
+    }
+
+    #[test]
+    fn folded() {
+        let dictionary = Dictionary::embedded("árbol 3\ncesta 2\n").fold_accents();
+        assert_eq!(dictionary.by_frequency(), vec![("arbol", 3), ("cesta", 2)]);
+    }
+
+    #[test]
+    fn tiles() {
+        let dictionary = Dictionary::embedded("# tiles: ll ch\ncalle\nchico\n");
+        assert_eq!(dictionary.declared_tiles(), vec!["ll", "ch"]);
+        assert_eq!(
+            dictionary.words().collect::<Vec<_>>(),
+            vec!["calle", "chico"]
+        );
+
+        let tiles = Tileset::new(dictionary.declared_tiles(), &Default::default()).unwrap();
+        let encoded = dictionary.encode_tiles(&tiles);
+        assert_eq!(encoded.words().collect::<Vec<_>>(), vec!["caAe", "Bico"]);
+    }
+
+    #[test]
+    fn from_reader() {
+        let dictionary = Dictionary::from_reader("cigar 3\n".as_bytes()).unwrap();
+        assert_eq!(dictionary.counts().collect::<Vec<_>>(), vec![("cigar", 3)]);
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-wordlists")]
+    fn inflate() {
+        let text = miniz_oxide::deflate::compress_to_vec(b"cigar\nrebut\n", 10);
+        let dictionary = Dictionary::inflate(&text);
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), ["cigar", "rebut"]);
+
+        let mut record = b"cigar".to_vec();
+        record.extend_from_slice(&7u64.to_le_bytes());
+        let compact =
+            Dictionary::inflate_compact(&miniz_oxide::deflate::compress_to_vec(&record, 10));
+        assert_eq!(compact.by_frequency(), [("cigar", 7)]);
+    }
+
+    #[test]
+    fn formats() {
+        let read = |text: &str, format| {
+            let dictionary = Dictionary::from_reader_as(text.as_bytes(), format).unwrap();
+            dictionary
+                .counts()
+                .map(|(word, count)| (word.to_string(), count))
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![("cigar".to_string(), 3), ("rebut".to_string(), 1)];
+
+        assert_eq!(read("word,count\ncigar,3\nrebut\n", None), expected);
+        assert_eq!(read("[[\"cigar\", 3], \"rebut\"]", None), expected);
+        assert_eq!(read("{\"cigar\": 3, \"rebut\": 1}", None), expected);
+        assert_eq!(
+            read(
+                "[{\"word\": \"cigar\", \"count\": 3}, {\"word\": \"rebut\"}]",
+                None
+            ),
+            expected
+        );
+        assert_eq!(read("cigar\nrebut\n", Some(Format::Plain))[1], expected[1]);
+        // Frequencies keep their proportions.
+        assert_eq!(
+            read("cigar,0.75\nrebut,0.25\n", Some(Format::Csv))[0].1,
+            750_000_000
+        );
+        // Mixed with huge counts, they're scaled only as far as fits.
+        let mixed = read("cigar,3000000000000\nrebut,0.5\n", Some(Format::Csv));
+        let (cigar, rebut) = (mixed[0].1 as f64, mixed[1].1 as f64);
+        assert!(cigar + rebut <= SCALED_TOTAL);
+        assert!((cigar / rebut / 6e12 - 1.0).abs() < 1e-3);
+
+        assert!(Dictionary::from_reader_as("cigar,3\nrebut,lots\n".as_bytes(), None).is_err());
+        assert!(Dictionary::from_reader_as("[3]".as_bytes(), None).is_err());
+        assert_eq!(
+            Format::detect(Some(Path::new("words.CSV")), "cigar"),
+            Format::Csv
+        );
+        assert_eq!(Format::detect(None, "# words\ncigar 3\n"), Format::Native);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    report::{Report, MAX_GUESSES},
+    significance,
+};
+
+/// How many of the most divergent answers a duel lists.
+const DIVERGENT: usize = 20;
+
+/// Per-answer comparison of two runs over the same answers.
+#[derive(Debug, Default, PartialEq)]
+pub struct HeadToHead {
+    pub a_wins: usize,
+    pub b_wins: usize,
+    pub ties: usize,
+}
+
+/// Compare two reports answer by answer; a win is solving in fewer guesses.
+pub fn head_to_head(a: &Report, b: &Report) -> HeadToHead {
+    let mut result = HeadToHead::default();
+    for (a, b) in a.outcomes().iter().zip(b.outcomes()) {
+        match a.score().cmp(&b.score()) {
+            std::cmp::Ordering::Less => result.a_wins += 1,
+            std::cmp::Ordering::Greater => result.b_wins += 1,
+            std::cmp::Ordering::Equal => result.ties += 1,
+        }
+    }
+    result
+}
+
+/// The `n` answers with the largest difference in score between the runs,
+/// as `(answer, a's score, b's score)`.
+pub fn divergences<'a>(a: &Report<'a>, b: &Report<'a>, n: usize) -> Vec<(&'a str, usize, usize)> {
+    let mut divergent: Vec<(&str, usize, usize)> = a
+        .outcomes()
+        .iter()
+        .zip(b.outcomes())
+        .filter(|(a, b)| a.score() != b.score())
+        .map(|(a, b)| (a.answer, a.score(), b.score()))
+        .collect();
+    divergent.sort_by_key(|&(_, a, b)| std::cmp::Reverse(a.abs_diff(b)));
+    divergent.truncate(n);
+    divergent
+}
+
+/// Print a side-by-side comparison of two runs over the same answers.
+pub fn print(a_name: &str, a: &Report, b_name: &str, b: &Report) {
+    let score = |n: usize| {
+        if n > MAX_GUESSES {
+            "-".to_string()
+        } else {
+            n.to_string()
+        }
+    };
+
+    println!("{:<16} {a_name:>12} {b_name:>12}", "");
+    println!(
+        "{:<16} {:>12.4} {:>12.4}",
+        "average score",
+        a.average(),
+        b.average()
+    );
+    println!(
+        "{:<16} {:>12} {:>12}",
+        "missed words",
+        a.failures().count(),
+        b.failures().count()
+    );
+
+    println!();
+    for (i, (a, b)) in a.distribution().iter().zip(b.distribution()).enumerate() {
+        let label = if i < MAX_GUESSES {
+            format!("solved in {}", i + 1)
+        } else {
+            "missed".to_string()
+        };
+        println!("{label:<16} {a:>12} {b:>12}");
+    }
+
+    let result = head_to_head(a, b);
+    println!();
+    println!("head to head:");
+    println!("  {a_name} better: {}", result.a_wins);
+    println!("  {b_name} better: {}", result.b_wins);
+    println!("  tied: {}", result.ties);
+    println!();
+    let scores: Vec<(usize, usize)> = a
+        .outcomes()
+        .iter()
+        .zip(b.outcomes())
+        .map(|(a, b)| (a.score(), b.score()))
+        .collect();
+    print!("{}", significance::paired(&scores));
+    println!();
+
+    println!("largest divergences:");
+    for (answer, a, b) in divergences(a, b, DIVERGENT) {
+        println!("  {answer}: {} vs {}", score(a), score(b));
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::outcome;
+
+    #[cfg(test)]
+    fn report(results: &[(&'static str, Option<usize>)]) -> Report<'static> {
+        let mut report = Report::default();
+        for &(answer, guesses) in results {
+            report.push(outcome(answer, guesses));
+        }
+        report
+    }
+
+    #[test]
+    fn compare() {
+        let a = report(&[("cigar", Some(3)), ("rebut", None), ("sissy", Some(4))]);
+        let b = report(&[("cigar", Some(3)), ("rebut", Some(2)), ("sissy", Some(5))]);
+
+        assert_eq!(
+            head_to_head(&a, &b),
+            HeadToHead {
+                a_wins: 1,
+                b_wins: 1,
+                ties: 1
+            }
+        );
+        assert_eq!(
+            divergences(&a, &b, 10),
+            vec![("rebut", 7, 2), ("sissy", 4, 5)]
+        );
+    }
+}
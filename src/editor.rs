@@ -0,0 +1,170 @@
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, Read, Write},
+    rc::Rc,
+};
+
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+
+/// The most completions offered for one prefix.
+const COMPLETIONS: usize = 50;
+
+/// Completes the word under the cursor from a word list.
+struct Words(Vec<String>);
+
+impl Completer for Words {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let prefix = line[start..pos].to_lowercase();
+        let matches = self
+            .0
+            .iter()
+            .filter(|word| word.starts_with(&prefix))
+            .take(COMPLETIONS)
+            .cloned()
+            .collect();
+        Ok((start, matches))
+    }
+}
+
+impl Hinter for Words {
+    type Hint = String;
+}
+
+impl Highlighter for Words {}
+
+impl Validator for Words {}
+
+impl Helper for Words {}
+
+/// Line editing, history, and word completion for the interactive modes.
+///
+/// The modes write a prompt and then read a line, so the editor comes in two
+/// halves: a [`Writer`] that holds back any unfinished line it's given, and a
+/// [`Reader`] that shows that line as the prompt while editing.
+pub struct LineEditor {
+    editor: Editor<Words, DefaultHistory>,
+}
+
+impl LineEditor {
+    /// An editor that completes words from `words`.
+    pub fn new(words: impl IntoIterator<Item = String>) -> io::Result<Self> {
+        let mut editor = Editor::new().map_err(io::Error::other)?;
+        editor.set_helper(Some(Words(words.into_iter().collect())));
+        Ok(Self { editor })
+    }
+
+    /// Split into an input and an output for an interactive mode.
+    pub fn split(self) -> (Reader, Writer) {
+        let prompt = Rc::new(RefCell::new(Vec::new()));
+        let reader = Reader {
+            editor: self.editor,
+            prompt: Rc::clone(&prompt),
+            line: Vec::new(),
+            pos: 0,
+        };
+        let writer = Writer {
+            prompt,
+            output: io::stdout(),
+        };
+        (reader, writer)
+    }
+}
+
+/// Reads edited lines, as a [`BufRead`].
+pub struct Reader {
+    editor: Editor<Words, DefaultHistory>,
+    /// Whatever the writer was given after its last newline.
+    prompt: Rc<RefCell<Vec<u8>>>,
+    line: Vec<u8>,
+    pos: usize,
+}
+
+impl Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.fill_buf()?.read(buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Reader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.line.len() {
+            let prompt = std::mem::take(&mut *self.prompt.borrow_mut());
+            self.line.clear();
+            self.pos = 0;
+            match self.editor.readline(&String::from_utf8_lossy(&prompt)) {
+                Ok(line) => {
+                    if !line.trim().is_empty() {
+                        self.editor
+                            .add_history_entry(line.as_str())
+                            .map_err(io::Error::other)?;
+                    }
+                    self.line.extend_from_slice(line.as_bytes());
+                    self.line.push(b'\n');
+                },
+                // Ctrl-C and Ctrl-D both end the session, as end of input.
+                Err(ReadlineError::Eof | ReadlineError::Interrupted) => {},
+                Err(e) => return Err(io::Error::other(e)),
+            }
+        }
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.line.len());
+    }
+}
+
+/// Writes to stdout, holding back an unfinished line to prompt with.
+pub struct Writer {
+    prompt: Rc<RefCell<Vec<u8>>>,
+    output: io::Stdout,
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut prompt = self.prompt.borrow_mut();
+        prompt.extend_from_slice(buf);
+        if let Some(end) = prompt.iter().rposition(|&b| b == b'\n') {
+            self.output.write_all(&prompt[..=end])?;
+            prompt.drain(..=end);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn completes_the_last_word() {
+        let words = Words(vec!["crane".into(), "crate".into(), "slate".into()]);
+        let history = DefaultHistory::new();
+        let context = Context::new(&history);
+        assert_eq!(
+            words.complete("guess CR", 8, &context).unwrap(),
+            (6, vec!["crane".to_string(), "crate".to_string()])
+        );
+        assert_eq!(
+            words.complete("sl", 2, &context).unwrap(),
+            (0, vec!["slate".to_string()])
+        );
+    }
+}
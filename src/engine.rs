@@ -0,0 +1,378 @@
+//! The matching and scoring core of the solver, with no I/O and nothing from
+//! `std` beyond `alloc`, so it builds with `--no-default-features` for
+//! embedded and WASM targets.
+//!
+//! Everything else in the crate builds on this, and needs the `std` feature:
+//! the word lists, the answer model, the strategies, and the command line.
+
+use alloc::vec::Vec;
+
+/// How many feedback patterns there are: three colors at five positions.
+pub const PATTERNS: usize = 243;
+
+/// How a guess's repeated letters are colored.
+///
+/// Patterns are computed by the official rule unless a rule is given, as to
+/// [`Correctness::compute_with`]; a guesser or host playing a clone is given
+/// its rule. Strategies score guesses by the official rule either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Duplicates {
+    /// As the official game does: each copy of a letter in the answer lights
+    /// up one copy in the guess, greens first, and further copies are gray.
+    #[default]
+    Official,
+    /// As some clones do: every copy that isn't green is yellow as long as
+    /// the answer has the letter at all, however many copies are accounted
+    /// for.
+    Naive,
+}
+
+impl Duplicates {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "official" => Some(Duplicates::Official),
+            "naive" => Some(Duplicates::Naive),
+            _ => None,
+        }
+    }
+}
+
+/// The feedback for one letter of a guess.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Correctness {
+    Correct,
+    Misplaced,
+    Wrong,
+}
+
+impl Correctness {
+    #[inline]
+    pub fn compute(answer: &str, word: &str) -> [Self; 5] {
+        Self::compute_bytes(answer.as_bytes(), word.as_bytes())
+    }
+
+    /// [`compute`](Self::compute) for words as bytes, as packed word lists
+    /// store them.
+    #[inline]
+    pub fn compute_bytes(answer: &[u8], word: &[u8]) -> [Self; 5] {
+        Self::compute_with(answer, word, Duplicates::Official)
+    }
+
+    /// [`compute_bytes`](Self::compute_bytes) with repeated letters colored
+    /// by `rule`.
+    pub fn compute_with(answer: &[u8], word: &[u8], rule: Duplicates) -> [Self; 5] {
+        let mut c = [Correctness::Wrong; 5];
+        let mut used = [false; 5];
+
+        for (i, (&a, &g)) in answer.iter().zip(word).enumerate() {
+            if a == g {
+                c[i] = Correctness::Correct;
+                used[i] = true;
+            }
+        }
+
+        let naive = rule == Duplicates::Naive;
+        for (i, &g) in word.iter().enumerate() {
+            if c[i] == Correctness::Correct {
+                continue;
+            }
+
+            if naive {
+                if answer.contains(&g) {
+                    c[i] = Correctness::Misplaced;
+                }
+            } else if answer.iter().enumerate().any(|(i, &a)| {
+                if a == g && !used[i] {
+                    used[i] = true;
+                    return true;
+                }
+
+                false
+            }) {
+                c[i] = Correctness::Misplaced;
+            }
+        }
+
+        c
+    }
+
+    /// The code of each pattern `guess` gets against `answers`, in order.
+    ///
+    /// This is [`compute`](Self::compute) and [`encode`](Self::encode) for a
+    /// whole list at once, as scoring a guess needs: what only depends on the
+    /// guess is worked out once, and each answer then takes a pass for the
+    /// greens and one for the rest, without tracking which letters are used.
+    pub fn compute_many(guess: &str, answers: &[&str]) -> Vec<u8> {
+        Self::compute_many_bytes(guess.as_bytes(), answers.iter().map(|a| a.as_bytes()))
+    }
+
+    /// [`compute_many`](Self::compute_many) for words as bytes, as packed
+    /// word lists store them.
+    pub fn compute_many_bytes<'w>(
+        guess: &[u8],
+        answers: impl Iterator<Item = &'w [u8]>,
+    ) -> Vec<u8> {
+        let guess: [u8; 5] = core::array::from_fn(|i| guess[i]);
+        // The earlier positions in the guess with the same letter as each.
+        let earlier: [u8; 5] = core::array::from_fn(|i| {
+            (0..i)
+                .filter(|&j| guess[j] == guess[i])
+                .fold(0, |bits, j| bits | 1 << j)
+        });
+
+        answers
+            .map(|answer| {
+                let mut green = 0u8;
+                for i in 0..5 {
+                    if answer[i] == guess[i] {
+                        green |= 1 << i;
+                    }
+                }
+
+                // A letter that isn't green is misplaced if the answer has
+                // more copies of it outside the greens than the guess has
+                // already claimed before it.
+                let mut code = 0;
+                for i in 0..5 {
+                    let digit = if green & (1 << i) != 0 {
+                        2
+                    } else {
+                        let available = (0..5)
+                            .filter(|&j| green & (1 << j) == 0 && answer[j] == guess[i])
+                            .count();
+                        let claimed = (earlier[i] & !green).count_ones() as usize;
+                        u8::from(claimed < available)
+                    };
+                    code = code * 3 + digit;
+                }
+                code
+            })
+            .collect()
+    }
+
+    /// `mask` as a code below [`PATTERNS`], for storing patterns compactly
+    /// or counting them in an array. The code's base-3 digits, first tile
+    /// first, are 2 for correct, 1 for misplaced, and 0 for wrong, as in the
+    /// digit form of a pattern, so all wrong is 0 and all correct is 242.
+    pub fn encode(mask: [Self; 5]) -> u8 {
+        mask.iter().fold(0, |code, c| {
+            code * 3
+                + match c {
+                    Correctness::Wrong => 0,
+                    Correctness::Misplaced => 1,
+                    Correctness::Correct => 2,
+                }
+        })
+    }
+
+    /// The pattern [`encode`](Self::encode) gives `code`, or `None` if no
+    /// pattern does.
+    pub fn decode(code: u8) -> Option<[Self; 5]> {
+        if code as usize >= PATTERNS {
+            return None;
+        }
+        let mut mask = [Correctness::Wrong; 5];
+        let mut code = code;
+        for c in mask.iter_mut().rev() {
+            *c = match code % 3 {
+                0 => Correctness::Wrong,
+                1 => Correctness::Misplaced,
+                _ => Correctness::Correct,
+            };
+            code /= 3;
+        }
+        Some(mask)
+    }
+}
+
+/// Whether `word` could be the answer, given that guessing `guess` got
+/// `mask`.
+#[inline]
+pub fn matches(guess: &str, mask: [Correctness; 5], word: &str) -> bool {
+    Correctness::compute(word, guess) == mask
+}
+
+/// Keep only the `candidates` that could be the answer after `guess` got
+/// `mask`, in order.
+pub fn filter(candidates: &mut Vec<&str>, guess: &str, mask: [Correctness; 5]) {
+    candidates.retain(|word| matches(guess, mask, word));
+}
+
+/// How many of `candidates` would remain after guessing `guess`, summed over
+/// every answer among them; the expected number remaining, times their
+/// count. Lower is better, and no floating point is needed to compare.
+pub fn remaining(guess: &str, candidates: &[&str]) -> usize {
+    let mut counts = [0usize; PATTERNS];
+    for code in Correctness::compute_many(guess, candidates) {
+        counts[code as usize] += 1;
+    }
+    counts.iter().map(|&n| n * n).sum()
+}
+
+/// The guess from `guesses` expected to leave the fewest of `candidates`.
+///
+/// A guess that could itself be the answer wins ties, since it might finish
+/// the game; after that, the earliest guess wins. Any candidate can be
+/// played when `guesses` is empty.
+pub fn best_guess<'a>(guesses: &[&'a str], candidates: &[&'a str]) -> Option<&'a str> {
+    if candidates.len() <= 2 {
+        return candidates.first().copied();
+    }
+    let guesses = if guesses.is_empty() {
+        candidates
+    } else {
+        guesses
+    };
+
+    let mut best: Option<((usize, bool), &str)> = None;
+    for &guess in guesses {
+        let key = (remaining(guess, candidates), !candidates.contains(&guess));
+        if best.is_none_or(|(best, _)| key < best) {
+            best = Some((key, guess));
+        }
+    }
+    best.map(|(_, guess)| guess)
+}
+
+/// A game in progress, narrowed by the feedback to each guess.
+///
+/// This is the solver with no answer model: every candidate is taken to be
+/// equally likely, and guesses are chosen by [`best_guess`].
+#[derive(Clone, Debug)]
+pub struct Solver<'a> {
+    guesses: &'a [&'a str],
+    candidates: Vec<&'a str>,
+    turns: usize,
+}
+
+impl<'a> Solver<'a> {
+    /// A game whose answer is one of `answers`, guessing from `guesses`, or
+    /// only the answers if that's empty.
+    pub fn new(guesses: &'a [&'a str], answers: &[&'a str]) -> Self {
+        Self {
+            guesses,
+            candidates: answers.to_vec(),
+            turns: 0,
+        }
+    }
+
+    /// The words that could still be the answer.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    /// How many guesses have been given feedback.
+    pub fn turns(&self) -> usize {
+        self.turns
+    }
+
+    /// The word to play next, or `None` if no candidates are left.
+    pub fn next_guess(&self) -> Option<&'a str> {
+        best_guess(self.guesses, &self.candidates)
+    }
+
+    /// Narrow the candidates by the feedback `mask` to `guess`, returning how
+    /// many remain.
+    pub fn feedback(&mut self, guess: &str, mask: [Correctness; 5]) -> usize {
+        self.turns += 1;
+        filter(&mut self.candidates, guess, mask);
+        self.candidates.len()
+    }
+
+    /// Play against a known `answer` until it's found or `limit` guesses
+    /// have been made, returning the guesses taken if it was found.
+    pub fn solve(&mut self, answer: &str, limit: usize) -> Option<usize> {
+        while self.turns < limit {
+            let guess = self.next_guess()?;
+            let mask = Correctness::compute(answer, guess);
+            self.feedback(guess, mask);
+            if mask == [Correctness::Correct; 5] {
+                return Some(self.turns);
+            }
+        }
+        None
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+    #[test]
+    fn patterns() {
+        assert_eq!(Correctness::compute("abcde", "aadcx"), [C, W, M, M, W]);
+        assert_eq!(Correctness::encode([W; 5]), 0);
+        assert_eq!(Correctness::encode([C; 5]) as usize, PATTERNS - 1);
+        assert_eq!(Correctness::encode([C, M, W, W, C]), 2 * 81 + 27 + 2);
+        assert!(matches("crane", [W, W, C, W, C], "shade"));
+        assert!(!matches("crane", [W, W, C, W, C], "plane"));
+    }
+
+    #[test]
+    fn naive_duplicates() {
+        let naive = |answer: &str, word: &str| {
+            Correctness::compute_with(answer.as_bytes(), word.as_bytes(), Duplicates::Naive)
+        };
+        // The answer's one a is green, yet the guess's other a is still
+        // yellow, where the official game has it gray.
+        assert_eq!(naive("abcde", "aadcx"), [C, M, M, M, W]);
+        assert_eq!(
+            naive("cigar", "crane"),
+            Correctness::compute("cigar", "crane")
+        );
+        assert_eq!(Duplicates::by_name("naive"), Some(Duplicates::Naive));
+        assert_eq!(Duplicates::by_name("lenient"), None);
+    }
+
+    #[test]
+    fn codes() {
+        for code in 0..PATTERNS as u8 {
+            let mask = Correctness::decode(code).unwrap();
+            assert_eq!(Correctness::encode(mask), code);
+        }
+        assert_eq!(Correctness::decode(PATTERNS as u8), None);
+        assert_eq!(Correctness::decode(u8::MAX), None);
+    }
+
+    #[test]
+    fn many() {
+        let answers = [
+            "stare", "tares", "chomp", "party", "ccccc", "eerie", "abbey",
+        ];
+        for guess in ["tardy", "ccccg", "geese", "babes", "stare"] {
+            let expected: Vec<u8> = answers
+                .iter()
+                .map(|answer| Correctness::encode(Correctness::compute(answer, guess)))
+                .collect();
+            assert_eq!(Correctness::compute_many(guess, &answers), expected);
+        }
+    }
+
+    #[test]
+    fn scoring() {
+        let candidates = ["hatch", "catch", "match", "patch", "latch"];
+        // Each candidate rules out only itself.
+        assert_eq!(remaining("hatch", &candidates), 1 + 4 * 4);
+        // A probe with c, m, and p splits them apart.
+        assert_eq!(remaining("chomp", &candidates), 1 + 1 + 1 + 4);
+        assert_eq!(best_guess(&["chomp", "clamp"], &candidates), Some("clamp"));
+        assert_eq!(best_guess(&[], &candidates), Some("hatch"));
+        assert_eq!(best_guess(&[], &[]), None);
+    }
+
+    #[test]
+    fn solving() {
+        let answers = ["hatch", "catch", "match", "patch", "latch"];
+        let guesses = ["hatch", "catch", "match", "patch", "latch", "clamp"];
+        let mut solver = Solver::new(&guesses, &answers);
+        assert_eq!(solver.solve("patch", 6), Some(2));
+        assert_eq!(solver.candidates(), ["patch"]);
+
+        let mut solver = Solver::new(&[], &answers);
+        assert_eq!(solver.solve("latch", 6), Some(5));
+        let mut solver = Solver::new(&[], &answers);
+        assert_eq!(solver.solve("latch", 3), None);
+    }
+}
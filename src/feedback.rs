@@ -0,0 +1,143 @@
+use std::hash::Hash;
+
+use crate::{guesser::Correctness, hint::Suggestion, model::AnswerModel, parallel};
+
+/// What a host tells the player about a guess, for any variant of the game.
+///
+/// Nothing else needs to be known about a kind of feedback to solve with it: a
+/// word is still a candidate exactly when it would have produced the same
+/// feedback as the answer did.
+pub trait Feedback: Copy + Eq + Hash + Send + Sync {
+    /// The feedback for playing `word` when the answer is `answer`.
+    fn compute(answer: &str, word: &str) -> Self;
+
+    /// The feedback for playing `word` against each of `answers`, in order.
+    fn compute_batch(word: &str, answers: &[&str]) -> Vec<Self> {
+        answers
+            .iter()
+            .map(|answer| Self::compute(answer, word))
+            .collect()
+    }
+
+    /// Whether this feedback means the guess was the answer.
+    fn is_solved(&self) -> bool;
+}
+
+impl Feedback for [Correctness; 5] {
+    #[inline]
+    fn compute(answer: &str, word: &str) -> Self {
+        Correctness::compute(answer, word)
+    }
+
+    fn compute_batch(word: &str, answers: &[&str]) -> Vec<Self> {
+        Correctness::compute_batch(word, answers)
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.iter().all(|&c| c == Correctness::Correct)
+    }
+}
+
+/// A solver that works with any kind of [`Feedback`], for variants the
+/// [`Guesser`](crate::guesser::Guesser) doesn't understand.
+///
+/// It always plays the guess with the most expected information, with the
+/// chance of it being the answer breaking ties.
+pub struct Solver<'a, F> {
+    candidates: Vec<&'a str>,
+    history: Vec<(&'a str, F)>,
+}
+
+impl<'a, F: Feedback> Solver<'a, F> {
+    pub fn new(candidates: &[&'a str]) -> Self {
+        Self {
+            candidates: candidates.to_vec(),
+            history: Vec::new(),
+        }
+    }
+
+    /// The best of `guesses` to play next, or `None` if no candidates remain.
+    pub fn best_guess(&self, guesses: &[&'a str], model: &AnswerModel) -> Option<&'a str> {
+        match self.candidates[..] {
+            [] => return None,
+            [only] => return Some(only),
+            _ => {},
+        }
+
+        let distribution = model.distribution(&self.candidates);
+        let work = guesses.len() * self.candidates.len();
+        let best = parallel::best_by(guesses, work, |&word| {
+            let s = Suggestion::evaluate_with::<F>(word, &self.candidates, &distribution);
+            s.information + s.probability
+        });
+
+        best.copied()
+    }
+
+    /// Record the feedback for playing `word`, keeping only the candidates
+    /// that would have given the same. Returns how many candidates remain.
+    pub fn apply(&mut self, word: &'a str, feedback: F) -> usize {
+        self.candidates
+            .retain(|candidate| F::compute(candidate, word) == feedback);
+        self.history.push((word, feedback));
+        self.candidates.len()
+    }
+
+    /// Play against `answer`, opening with `opener` and then choosing from
+    /// `guesses`, until it's found or `limit` guesses have been made. Returns
+    /// the number of guesses taken if the answer was found.
+    pub fn solve(
+        &mut self,
+        answer: &str,
+        opener: &'a str,
+        guesses: &[&'a str],
+        model: &AnswerModel,
+        limit: Option<usize>,
+    ) -> Option<usize> {
+        let mut word = opener;
+        loop {
+            let feedback = F::compute(answer, word);
+            self.apply(word, feedback);
+            if feedback.is_solved() {
+                return Some(self.history.len());
+            }
+            if limit.is_some_and(|limit| self.history.len() >= limit) {
+                return None;
+            }
+
+            word = self.best_guess(guesses, model)?;
+        }
+    }
+
+    /// The answers still consistent with every guess so far.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    /// Every guess made so far, with its feedback, in order.
+    pub fn history(&self) -> &[(&'a str, F)] {
+        &self.history
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn wordle_feedback() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+
+        let mut solver = Solver::<[Correctness; 5]>::new(&candidates);
+        assert_eq!(solver.best_guess(&guesses, &model), Some("chomp"));
+        assert_eq!(
+            solver.solve("match", "chomp", &candidates, &model, None),
+            Some(2)
+        );
+        assert_eq!(solver.candidates(), ["match"]);
+    }
+}
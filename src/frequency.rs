@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crossterm::style::Color;
+
+use crate::console;
+
+/// Which words letter frequencies are counted over, for the heuristics that
+/// weigh letters by how common they are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The answer list, each word once.
+    Answers,
+    /// Every word that may be guessed, each once.
+    Dictionary,
+    /// The corpus, each word as often as it was counted.
+    Corpus,
+}
+
+impl Source {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "answers" => Some(Self::Answers),
+            "dictionary" | "guesses" => Some(Self::Dictionary),
+            "corpus" => Some(Self::Corpus),
+            _ => None,
+        }
+    }
+}
+
+/// How many words have each letter at each position, as the heuristic
+/// strategies weigh them and as people like to study them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PositionFrequency {
+    /// The words with each letter at each position, for every letter seen.
+    counts: BTreeMap<u8, [usize; 5]>,
+    words: usize,
+}
+
+impl PositionFrequency {
+    pub fn count<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut frequency = Self::default();
+        for word in words {
+            frequency.words += 1;
+            for (i, letter) in word.bytes().enumerate().take(5) {
+                frequency.counts.entry(letter).or_default()[i] += 1;
+            }
+        }
+        frequency
+    }
+
+    /// How many words were counted.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// How many words have `letter` at position `i`, counting from zero.
+    pub fn get(&self, letter: u8, i: usize) -> usize {
+        self.counts.get(&letter).map_or(0, |counts| counts[i])
+    }
+
+    /// The share of words with `letter` at position `i`.
+    pub fn share(&self, letter: u8, i: usize) -> f64 {
+        self.get(letter, i) as f64 / self.words.max(1) as f64
+    }
+
+    /// A table with a row per letter seen, in byte order, and a column per
+    /// position, giving each cell's count. With `heatmap`, each cell's
+    /// background shades from dark to light as its count nears the largest.
+    /// Letters are labeled by `label`, as tiles spell them.
+    pub fn table(&self, heatmap: bool, label: impl Fn(u8) -> String) -> String {
+        let most = self
+            .counts
+            .values()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let labels: Vec<(u8, String)> = self.counts.keys().map(|&l| (l, label(l))).collect();
+        let width = labels
+            .iter()
+            .map(|(_, label)| label.chars().count())
+            .max()
+            .unwrap_or(1);
+        let cell = self.words.to_string().len().max(2) + 1;
+
+        let mut table = format!("{:width$}", "");
+        for i in 1..=5 {
+            write!(table, " {i:>cell$}").unwrap();
+        }
+        table.push('\n');
+        for (letter, label) in labels {
+            write!(table, "{label:width$}").unwrap();
+            for i in 0..5 {
+                let count = self.get(letter, i);
+                if heatmap {
+                    // The 24 grays of the 256-color palette, darkest first.
+                    let shade = 232 + count * 23 / most;
+                    let text = if shade < 244 {
+                        Color::White
+                    } else {
+                        Color::Black
+                    };
+                    let padded = format!("{count:>cell$}");
+                    let shade = Color::AnsiValue(shade as u8);
+                    write!(table, " {}", console::paint(padded, text, shade)).unwrap();
+                } else {
+                    write!(table, " {count:>cell$}").unwrap();
+                }
+            }
+            table.push('\n');
+        }
+        table
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn sources() {
+        assert_eq!(Source::by_name("answers"), Some(Source::Answers));
+        assert_eq!(Source::by_name("guesses"), Some(Source::Dictionary));
+        assert_eq!(Source::by_name("candidates"), None);
+    }
+
+    #[test]
+    fn counts() {
+        let frequency = PositionFrequency::count(["hatch", "catch", "chomp"]);
+        assert_eq!(frequency.words(), 3);
+        assert_eq!(frequency.get(b'c', 0), 2);
+        assert_eq!(frequency.get(b'c', 3), 2);
+        assert_eq!(frequency.get(b'z', 0), 0);
+        assert_eq!(frequency.share(b'h', 4), 2.0 / 3.0);
+
+        let table = frequency.table(false, |l| (l as char).to_string());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "    1   2   3   4   5");
+        assert_eq!(lines[2], "c   2   0   0   2   0");
+        assert_eq!(lines.len(), 1 + 7);
+        assert!(frequency
+            .table(true, |l| (l as char).to_string())
+            .contains("\x1b[48;5;255m\x1b[38;5;0m"));
+    }
+}
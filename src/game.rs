@@ -0,0 +1,1090 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    engine::Duplicates,
+    guesser::{Correctness, Guess, Guesser},
+    hint::Scoring,
+    keyboard::Keyboard,
+    knowledge::KnowledgeState,
+    locale::{Language, Message},
+    normalize::{self, Normalizer},
+    odds,
+    open::OpenVocabulary,
+    pattern::{self, Tiles},
+    race, share, spelling,
+    tiles::Tileset,
+};
+
+/// The host of a game: something that answers guesses with feedback.
+///
+/// Separating the host from the solver means the solver never needs to know
+/// the answer, so the same solver can play against a known word, a human
+/// reading feedback off another screen, or an adversary.
+pub trait Oracle {
+    /// The feedback for `guess`, or `None` if the host can't (or won't) give
+    /// any, which ends the game.
+    fn feedback(&mut self, guess: &str) -> Option<[Correctness; 5]>;
+}
+
+/// A host that knows the answer.
+pub struct KnownAnswer<'a> {
+    pub answer: &'a str,
+    /// How the feedback colors repeated letters.
+    pub duplicates: Duplicates,
+}
+
+impl<'a> KnownAnswer<'a> {
+    /// A host for `answer` that gives feedback as the official game does.
+    pub fn new(answer: &'a str) -> Self {
+        Self {
+            answer,
+            duplicates: Duplicates::Official,
+        }
+    }
+}
+
+impl Oracle for KnownAnswer<'_> {
+    fn feedback(&mut self, guess: &str) -> Option<[Correctness; 5]> {
+        Some(Correctness::compute_with(
+            self.answer.as_bytes(),
+            guess.as_bytes(),
+            self.duplicates,
+        ))
+    }
+}
+
+/// A host that asks a human for the feedback, for assisting with a game being
+/// played elsewhere.
+pub struct Interactive<R, W> {
+    input: R,
+    output: W,
+    keyboard: bool,
+    language: Language,
+    /// The scoring to explain each suggestion by, if explaining.
+    explain: Option<Scoring>,
+    odds: bool,
+    corrections: bool,
+    /// Where to look for the answer once no listed word fits.
+    open: Option<OpenVocabulary>,
+    /// How many candidates have been listed since the last entry.
+    listed: usize,
+}
+
+impl<R: BufRead, W: Write> Interactive<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            keyboard: false,
+            language: Language::default(),
+            explain: None,
+            odds: false,
+            corrections: false,
+            open: None,
+            listed: 0,
+        }
+    }
+
+    /// Explain each suggestion before asking for its feedback, comparing it
+    /// with the best alternatives by `scoring`.
+    pub fn with_explanations(mut self, scoring: Option<Scoring>) -> Self {
+        self.explain = scoring;
+        self
+    }
+
+    /// Show what's known about each letter on a keyboard after every entry.
+    pub fn with_keyboard(mut self, show: bool) -> Self {
+        self.keyboard = show;
+        self
+    }
+
+    /// Prompt and explain in `language`.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Estimate the chance of solving in the guesses left after every entry,
+    /// to warn when the game is slipping away.
+    pub fn with_odds(mut self, show: bool) -> Self {
+        self.odds = show;
+        self
+    }
+
+    /// When an entry rules out every word, look for the one tile that was
+    /// likeliest mistyped and offer to fix it, rather than only to undo.
+    pub fn with_corrections(mut self, offer: bool) -> Self {
+        self.corrections = offer;
+        self
+    }
+
+    /// When an entry rules out every word and there's no correction to
+    /// offer, go on suggesting from `open` rather than giving up, as for a
+    /// clone whose answers aren't in the word lists.
+    pub fn with_open_vocabulary(mut self, open: Option<OpenVocabulary>) -> Self {
+        self.open = open;
+        self
+    }
+
+    fn show_odds(&mut self, guesser: &Guesser) -> Option<()> {
+        let solved = guesser.history().last().is_some_and(Guess::is_correct);
+        if !self.odds || solved || guesser.candidates().is_empty() {
+            return Some(());
+        }
+        let (Some(odds), Some(turns)) =
+            (guesser.win_probability(odds::SAMPLES), guesser.turns_left())
+        else {
+            return Some(());
+        };
+        let percent = format!("{:.0}", odds * 100.0);
+        self.say(Message::Odds, &[("percent", &percent), ("turns", &turns)])
+    }
+
+    /// The next page of candidates, likeliest first, starting over once
+    /// every one has been listed.
+    fn list(&mut self, guesser: &Guesser) -> Option<()> {
+        let ranked = guesser.ranked();
+        if ranked.is_empty() {
+            return self.say(Message::Remaining, &[("count", &0)]);
+        }
+        if self.listed >= ranked.len() {
+            self.listed = 0;
+        }
+        let page = &ranked[self.listed..(self.listed + PAGE).min(ranked.len())];
+        for (i, (word, p)) in page.iter().enumerate() {
+            let percent = format!("{:.1}", p * 100.0);
+            self.say(
+                Message::Ranked,
+                &[
+                    ("rank", &(self.listed + i + 1)),
+                    ("word", word),
+                    ("percent", &percent),
+                ],
+            )?;
+        }
+        self.listed += page.len();
+        match ranked.len() - self.listed {
+            0 => Some(()),
+            more => self.say(Message::MoreCandidates, &[("count", &more)]),
+        }
+    }
+
+    /// `message` in the session's language, with `args` filled in.
+    fn say(&mut self, message: Message, args: &[(&str, &dyn fmt::Display)]) -> Option<()> {
+        writeln!(self.output, "{}", self.language.text(message, args)).ok()
+    }
+
+    fn show_keyboard(&mut self, history: &[Guess]) -> Option<()> {
+        if self.keyboard {
+            write!(self.output, "{}", Keyboard::from_history(history)).ok()?;
+        }
+        Some(())
+    }
+}
+
+/// A line of input in an interactive session.
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    Feedback([Correctness; 5]),
+    /// Take back the last feedback entered.
+    Undo,
+    /// Accept the correction offered for a contradiction.
+    Fix,
+    /// Show the next page of candidates.
+    List,
+}
+
+/// How many candidates `list` shows at a time.
+const PAGE: usize = 10;
+
+impl<R: BufRead, W: Write> Interactive<R, W> {
+    /// Show `guess` and read a reply, asking again until it parses. Returns
+    /// `None` at the end of the input.
+    pub fn prompt(&mut self, guess: &str) -> Option<Input> {
+        let word = guess.to_ascii_uppercase();
+        self.say(Message::Suggestion, &[("word", &word)])?;
+        self.read()
+    }
+
+    fn read(&mut self) -> Option<Input> {
+        loop {
+            let prompt = self.language.template(Message::FeedbackPrompt);
+            write!(self.output, "{prompt}").ok()?;
+            self.output.flush().ok()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+
+            if line.trim().eq_ignore_ascii_case("undo") {
+                return Some(Input::Undo);
+            }
+            if line.trim().eq_ignore_ascii_case("fix") {
+                return Some(Input::Fix);
+            }
+            if line.trim().eq_ignore_ascii_case("list") {
+                return Some(Input::List);
+            }
+
+            match pattern::parse(&normalize::input(&line)) {
+                Ok(mask) => return Some(Input::Feedback(mask)),
+                Err(e) => self.say(Message::FeedbackHelp, &[("error", &e)])?,
+            }
+        }
+    }
+
+    /// Drive `guesser` with feedback read from the input, allowing earlier
+    /// entries to be undone and the candidates to be listed. Returns the
+    /// number of guesses taken if the answer was found.
+    pub fn assist(&mut self, guesser: &mut Guesser) -> Option<usize> {
+        loop {
+            let input = match guesser.next_guess() {
+                Some(word) => {
+                    if let Some(scoring) = self.explain {
+                        write!(self.output, "{}", guesser.explain(word, scoring)).ok()?;
+                    }
+                    match self.prompt(word)? {
+                        Input::Feedback(mask) => {
+                            guesser.apply_feedback(word, mask);
+                            self.listed = 0;
+                            self.show_keyboard(guesser.history())?;
+                            self.show_odds(guesser)?;
+                            continue;
+                        },
+                        Input::Undo => Input::Undo,
+                        Input::List => {
+                            self.list(guesser)?;
+                            continue;
+                        },
+                        Input::Fix => {
+                            self.say(Message::NothingToFix, &[])?;
+                            continue;
+                        },
+                    }
+                },
+                // A mistyped entry can rule out every word; offer to take it
+                // back rather than giving up.
+                None if guesser.candidates().is_empty() => {
+                    let history = guesser.history();
+                    match (guesser.conflict(), history.last()) {
+                        (Some(i), Some(latest)) => self.say(
+                            Message::Contradiction,
+                            &[
+                                ("latest", &format!("{latest:#}")),
+                                ("number", &(i + 1)),
+                                ("earlier", &format!("{:#}", history[i])),
+                            ],
+                        )?,
+                        _ => self.say(Message::NoWordsFit, &[])?,
+                    }
+                    let correction = self.corrections.then(|| guesser.correction()).flatten();
+                    if let (None, Some(open)) = (correction, self.open.take()) {
+                        let outcome = self.assist_open(guesser, &open);
+                        self.open = Some(open);
+                        match outcome? {
+                            Some(n) => return Some(n),
+                            None => continue,
+                        }
+                    }
+                    match correction {
+                        Some((index, fixed)) => self.say(
+                            Message::Correction,
+                            &[("number", &(index + 1)), ("fixed", &format!("{fixed:#}"))],
+                        )?,
+                        None => self.say(Message::UndoHint, &[])?,
+                    }
+                    match (self.read()?, correction) {
+                        (Input::Fix, Some((index, fixed))) => {
+                            let count = guesser.amend(index, fixed.mask())?;
+                            self.listed = 0;
+                            self.say(
+                                Message::Fixed,
+                                &[
+                                    ("number", &(index + 1)),
+                                    ("fixed", &format!("{fixed:#}")),
+                                    ("count", &count),
+                                ],
+                            )?;
+                            self.show_keyboard(guesser.history())?;
+                            continue;
+                        },
+                        (Input::Fix, None) => {
+                            self.say(Message::NothingToFix, &[])?;
+                            continue;
+                        },
+                        (input, _) => input,
+                    }
+                },
+                None => break,
+            };
+
+            match input {
+                Input::Undo => {
+                    match guesser.undo() {
+                        Some(guess) => {
+                            self.say(Message::Undid, &[("guess", &format!("{guess:#}"))])?
+                        },
+                        None => self.say(Message::NothingToUndo, &[])?,
+                    }
+                    self.listed = 0;
+                    self.show_keyboard(guesser.history())?;
+                },
+                Input::List => self.list(guesser)?,
+                _ => {},
+            }
+        }
+
+        let history = guesser.history();
+        history
+            .last()
+            .is_some_and(Guess::is_correct)
+            .then_some(history.len())
+    }
+}
+
+impl<R: BufRead, W: Write> Interactive<R, W> {
+    /// Suggest sequences from `open` that fit every entry, until the answer
+    /// is found or every entry since the word lists ran out is undone, which
+    /// undoes the entry that emptied them as well. Returns the number of
+    /// guesses taken if the answer was found, or `Some(None)` to go back to
+    /// the word lists.
+    fn assist_open(
+        &mut self,
+        guesser: &mut Guesser,
+        open: &OpenVocabulary,
+    ) -> Option<Option<usize>> {
+        self.say(Message::OpenVocabulary, &[])?;
+        let mut entries: Vec<(String, [Correctness; 5])> = Vec::new();
+        loop {
+            let candidates = {
+                let history = open_history(guesser, &entries);
+                open.candidates(&KnowledgeState::from_history(&history), PAGE)
+            };
+            let input = match candidates.first() {
+                Some(word) => self.prompt(word)?,
+                None => {
+                    self.say(Message::NoWordsFit, &[])?;
+                    self.say(Message::UndoHint, &[])?;
+                    self.read()?
+                },
+            };
+
+            match input {
+                Input::Feedback(mask) => {
+                    let Some(word) = candidates.first() else {
+                        continue;
+                    };
+                    entries.push((word.clone(), mask));
+                    if mask == [Correctness::Correct; 5] {
+                        return Some(Some(guesser.history().len() + entries.len()));
+                    }
+                    self.show_keyboard(&open_history(guesser, &entries))?;
+                },
+                Input::Undo => {
+                    let undone = match entries.pop() {
+                        Some((word, mask)) => Some(format!("{:#}", Guess::new(&word, mask))),
+                        None => guesser.undo().map(|guess| format!("{guess:#}")),
+                    };
+                    match undone {
+                        Some(guess) => self.say(Message::Undid, &[("guess", &guess)])?,
+                        None => self.say(Message::NothingToUndo, &[])?,
+                    }
+                    self.show_keyboard(&open_history(guesser, &entries))?;
+                    if entries.is_empty() && !guesser.candidates().is_empty() {
+                        return Some(None);
+                    }
+                },
+                Input::List => {
+                    for (i, word) in candidates.iter().enumerate() {
+                        self.say(Message::Sequence, &[("rank", &(i + 1)), ("word", word)])?;
+                    }
+                },
+                Input::Fix => self.say(Message::NothingToFix, &[])?,
+            }
+        }
+    }
+}
+
+/// The guesser's history followed by the entries made since the word lists
+/// ran out.
+fn open_history<'e>(
+    guesser: &'e Guesser,
+    entries: &'e [(String, [Correctness; 5])],
+) -> Vec<Guess<'e>> {
+    let extra = entries.iter().map(|(word, mask)| Guess::new(word, *mask));
+    guesser.history().iter().copied().chain(extra).collect()
+}
+
+impl<R: BufRead, W: Write> Oracle for Interactive<R, W> {
+    fn feedback(&mut self, guess: &str) -> Option<[Correctness; 5]> {
+        loop {
+            match self.prompt(guess)? {
+                Input::Feedback(mask) => return Some(mask),
+                Input::Undo | Input::Fix => self.say(Message::CantUndo, &[])?,
+                Input::List => self.say(Message::CantList, &[])?,
+            }
+        }
+    }
+}
+
+/// A host that never commits to an answer, instead giving whichever feedback
+/// keeps the most words in play, as in Absurdle.
+pub struct Adversarial<'a> {
+    candidates: Vec<&'a str>,
+    duplicates: Duplicates,
+}
+
+impl<'a> Adversarial<'a> {
+    pub fn new(candidates: &[&'a str]) -> Self {
+        Self {
+            candidates: candidates.to_vec(),
+            duplicates: Duplicates::Official,
+        }
+    }
+
+    /// Color repeated letters by `rule`, rather than as the official game
+    /// does.
+    pub fn with_duplicates(mut self, rule: Duplicates) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    /// The words still consistent with every answer given so far.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+}
+
+impl Oracle for Adversarial<'_> {
+    fn feedback(&mut self, guess: &str) -> Option<[Correctness; 5]> {
+        let mut buckets: HashMap<[Correctness; 5], Vec<&str>> = HashMap::new();
+        for &word in &self.candidates {
+            buckets
+                .entry(Correctness::compute_with(
+                    word.as_bytes(),
+                    guess.as_bytes(),
+                    self.duplicates,
+                ))
+                .or_default()
+                .push(word);
+        }
+
+        // The biggest bucket wins; a win for the guesser is the last resort,
+        // and remaining ties are broken by the pattern's rendering so the
+        // adversary is deterministic.
+        let (mask, candidates) = buckets.into_iter().max_by(|(a_mask, a), (b_mask, b)| {
+            let solved = |mask: &[Correctness; 5]| mask.iter().all(|&c| c == Correctness::Correct);
+            (!solved(a_mask), a.len(), Tiles(*b_mask).to_string()).cmp(&(
+                !solved(b_mask),
+                b.len(),
+                Tiles(*a_mask).to_string(),
+            ))
+        })?;
+
+        self.candidates = candidates;
+        Some(mask)
+    }
+}
+
+/// What a human playing against a [`Host`] is trying to do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Goal {
+    /// Find the answer in as few guesses as possible.
+    #[default]
+    Find,
+    /// Put off finding the answer for as long as possible, as in Anti-Wordle.
+    /// Every guess must be consistent with all the feedback so far.
+    Avoid,
+}
+
+/// Which guesses a hosted game accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Only words on the allowed-guess list, as the official game does.
+    #[default]
+    Strict,
+    /// Any word in the corpus.
+    Dictionary,
+    /// Any string of letters of the answer's length.
+    Open,
+}
+
+impl Policy {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "strict" => Some(Policy::Strict),
+            "dictionary" => Some(Policy::Dictionary),
+            "open" => Some(Policy::Open),
+            _ => None,
+        }
+    }
+}
+
+/// The most near-miss words to suggest for a guess that isn't accepted.
+const SUGGESTIONS: usize = 5;
+
+type Validator<'a> = Box<dyn Fn(&str) -> bool + 'a>;
+
+/// Hosts a game for a human: reads their guesses and answers with feedback.
+pub struct Host<'a, R, W> {
+    answer: &'a str,
+    /// The words that may be guessed, in the order to suggest them.
+    list: Vec<&'a str>,
+    words: HashSet<&'a str>,
+    /// Decides which guesses are accepted in place of `words`, if given.
+    validator: Option<Validator<'a>>,
+    goal: Goal,
+    history: Vec<Guess<'a>>,
+    /// A solver's game for the same answer, to race against.
+    rival: Vec<Guess<'a>>,
+    /// How the feedback colors repeated letters.
+    duplicates: Duplicates,
+    /// How long each guess took to play, from the end of the one before.
+    times: Vec<Duration>,
+    /// Show each guess's time beside it.
+    timer: bool,
+    keyboard: bool,
+    normalizer: Normalizer,
+    language: Language,
+    input: R,
+    output: W,
+}
+
+impl<'a, R: BufRead, W: Write> Host<'a, R, W> {
+    /// Host a game for `answer`, accepting it or any of `words` as a guess.
+    /// When a guess isn't accepted, the words it could be a typo of are
+    /// suggested in the order of `words`.
+    pub fn new(answer: &'a str, words: &[&'a str], input: R, output: W) -> Self {
+        Self {
+            answer,
+            list: words.to_vec(),
+            words: words.iter().copied().chain([answer]).collect(),
+            validator: None,
+            goal: Goal::default(),
+            history: Vec::new(),
+            rival: Vec::new(),
+            duplicates: Duplicates::Official,
+            times: Vec::new(),
+            timer: false,
+            keyboard: false,
+            normalizer: Normalizer::default(),
+            language: Language::default(),
+            input,
+            output,
+        }
+    }
+
+    pub fn with_goal(mut self, goal: Goal) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Show what's known about each letter on a keyboard after every guess.
+    pub fn with_keyboard(mut self, show: bool) -> Self {
+        self.keyboard = show;
+        self
+    }
+
+    /// Accept the guesses of the answer's length that `validator` approves,
+    /// rather than those on the word list. The answer itself is always
+    /// accepted, and the word list is still what typos are corrected to.
+    pub fn with_validator(mut self, validator: impl Fn(&str) -> bool + 'a) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Race against `guesses`, a solver's game for the same answer: beside
+    /// each guess, show the colors the solver got on the same turn, without
+    /// its letters.
+    pub fn with_rival(mut self, guesses: &[Guess<'a>]) -> Self {
+        self.rival = guesses.to_vec();
+        self
+    }
+
+    /// Color repeated letters by `rule`, rather than as the official game
+    /// does.
+    pub fn with_duplicates(mut self, rule: Duplicates) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    /// Show how long each guess took beside it, for playing against the
+    /// clock.
+    pub fn with_timer(mut self, show: bool) -> Self {
+        self.timer = show;
+        self
+    }
+
+    /// Fold accented vowels in guesses to plain ones, for word lists that
+    /// were folded when loaded.
+    pub fn with_folded_accents(mut self, fold: bool) -> Self {
+        self.normalizer = self.normalizer.with_folded_accents(fold);
+        self
+    }
+
+    /// Read and show guesses with `tiles`, for word lists encoded with them.
+    pub fn with_tiles(mut self, tiles: Tileset) -> Self {
+        self.normalizer = self.normalizer.with_tiles(tiles);
+        self
+    }
+
+    /// Prompt and explain in `language`.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Why `word` can't be played, if it can't.
+    fn reject(&self, word: &str) -> Option<String> {
+        let tiles = self.normalizer.tiles();
+        let (length, typed) = (self.answer.chars().count(), word.chars().count());
+        let accepted = match &self.validator {
+            Some(validator) => typed == length && (word == self.answer || validator(word)),
+            None => self.words.contains(word),
+        };
+        if !accepted {
+            let shown = tiles.decode(word);
+            let text =
+                |message, args: &[(&str, &dyn fmt::Display)]| self.language.text(message, args);
+            let reason = match typed {
+                0 => return Some(text(Message::EmptyGuess, &[])),
+                _ if typed != length => text(
+                    Message::WrongLength,
+                    &[("length", &length), ("typed", &typed)],
+                ),
+                _ => text(Message::NotInList, &[]),
+            };
+
+            let suggestions: Vec<_> = spelling::near_misses(word, &self.list, SUGGESTIONS)
+                .into_iter()
+                .map(|s| tiles.decode(s))
+                .collect();
+            if suggestions.is_empty() {
+                return Some(text(
+                    Message::Rejected,
+                    &[("word", &shown), ("reason", &reason)],
+                ));
+            }
+            return Some(text(
+                Message::DidYouMean,
+                &[
+                    ("word", &shown),
+                    ("reason", &reason),
+                    ("suggestions", &suggestions.join(", ")),
+                ],
+            ));
+        }
+
+        if self.goal == Goal::Avoid {
+            let broken = self.history.iter().position(|g| !g.matches(word))?;
+            return Some(self.language.text(
+                Message::DoesntFit,
+                &[
+                    ("word", &tiles.decode(word)),
+                    ("number", &(broken + 1)),
+                    ("guess", &tiles.render(&self.history[broken], true)),
+                ],
+            ));
+        }
+
+        None
+    }
+
+    /// Play until the answer is guessed, returning how many guesses that
+    /// took, or `None` if the input ends first.
+    pub fn play(&mut self) -> Option<usize> {
+        let mut since = Instant::now();
+        loop {
+            let prompt = self.language.template(Message::GuessPrompt);
+            write!(self.output, "{prompt}").ok()?;
+            self.output.flush().ok()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+
+            let [exact, lowered] = self.normalizer.word(&line);
+            let word = if self.words.contains(exact.as_str()) {
+                exact
+            } else {
+                lowered
+            };
+            if let Some(reason) = self.reject(&word) {
+                writeln!(self.output, "{reason}").ok()?;
+                continue;
+            }
+
+            let word = match self.words.get(word.as_str()) {
+                Some(&word) => word,
+                // A word only the validator knows must outlive the game's
+                // history; the few a game brings in are leaked.
+                None => {
+                    let word: &'a str = Box::leak(word.into_boxed_str());
+                    self.words.insert(word);
+                    word
+                },
+            };
+            let guess = Guess::check_with(self.answer, word, self.duplicates);
+            let time = since.elapsed();
+            since = Instant::now();
+            self.times.push(time);
+            let mut shown = self.normalizer.tiles().render(&guess, false);
+            if self.timer {
+                shown = format!("{shown}  {}", share::seconds(time));
+            }
+            let rival = self.rival.get(self.history.len()).map(race::masked);
+            writeln!(self.output, "{}", race::beside(&shown, rival.as_deref())).ok()?;
+            self.history.push(guess);
+            if self.keyboard && !guess.is_correct() {
+                write!(self.output, "{}", Keyboard::from_history(&self.history)).ok()?;
+            }
+
+            if guess.is_correct() {
+                return Some(self.history.len());
+            }
+        }
+    }
+
+    /// Every guess made so far, with its feedback, in order.
+    pub fn history(&self) -> &[Guess<'a>] {
+        &self.history
+    }
+
+    /// How long each guess so far took, in order.
+    pub fn times(&self) -> &[Duration] {
+        &self.times
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+    #[test]
+    fn known_answer() {
+        let mut oracle = KnownAnswer::new("racer");
+        assert_eq!(oracle.feedback("tares"), Some([W, C, M, C, W]));
+    }
+
+    #[test]
+    fn interactive() {
+        let input = "nope\nGYBBG\n".as_bytes();
+        let mut output = Vec::new();
+        let mut oracle = Interactive::new(input, &mut output);
+
+        assert_eq!(oracle.feedback("salet"), Some([C, M, W, W, C]));
+        assert_eq!(oracle.feedback("salet"), None);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("guess: SALET\n"));
+        assert!(output.contains("invalid feedback tile"));
+    }
+
+    #[test]
+    fn assist_with_undo() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        // The second entry is a typo that contradicts the first.
+        let input = "BGBBY\nbbbbb\nundo\nbgggg\nbgggg\nggggg\n".as_bytes();
+        let mut output = Vec::new();
+        let solved = Interactive::new(input, &mut output)
+            .with_keyboard(true)
+            .assist(&mut guesser);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("hatch ⬛⬛⬛⬛⬛ contradicts guess 1 (salet ⬛🟩⬛⬛🟨)"));
+        assert!(output.contains("undid hatch ⬛⬛⬛⬛⬛"));
+        // The keyboard forgets the undone entry.
+        let undone = output.split("undid").nth(1).unwrap();
+        assert!(
+            undone.contains(&Keyboard::from_history(&[Guess::check("catch", "salet")]).to_string())
+        );
+        assert_eq!(solved, Some(4));
+        let words: Vec<&str> = guesser.history().iter().map(|g| g.word()).collect();
+        assert_eq!(words, vec!["salet", "hatch", "catch", "match"]);
+    }
+
+    #[test]
+    fn assist_with_corrections() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        // The last tile of the second entry should have been green.
+        let input = "BGBBY\nbgggb\nfix\nggggg\n".as_bytes();
+        let mut output = Vec::new();
+        let solved = Interactive::new(input, &mut output)
+            .with_corrections(true)
+            .assist(&mut guesser);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("guess 2 was likely hatch ⬛🟩🟩🟩🟩; fix to correct it, or undo"));
+        assert!(output.contains("corrected guess 2 to hatch ⬛🟩🟩🟩🟩; 2 candidates remain"));
+        assert_eq!(solved, Some(3));
+        let words: Vec<&str> = guesser.history().iter().map(|g| g.word()).collect();
+        assert_eq!(words, vec!["salet", "hatch", "catch"]);
+    }
+
+    #[test]
+    fn assist_with_open_vocabulary() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let open = || Some(OpenVocabulary::new(dictionary).with_words(["latch"]));
+
+        // The answer is latch, which only the secondary list has.
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+        let input = "BGYBY\nlist\nggggg\n".as_bytes();
+        let mut output = Vec::new();
+        let solved = Interactive::new(input, &mut output)
+            .with_open_vocabulary(open())
+            .assist(&mut guesser);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("no listed word fits; guessing letter sequences"));
+        assert!(output.contains("1. latch\n"));
+        assert_eq!(solved, Some(2));
+
+        // Undoing every entry since goes back to the word lists.
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+        let input = "BGYBY\nundo\nBGBBY\n".as_bytes();
+        let mut output = Vec::new();
+        Interactive::new(input, &mut output)
+            .with_open_vocabulary(open())
+            .assist(&mut guesser);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("undid salet ⬛🟩🟨⬛🟨"));
+        assert_eq!(
+            guesser.history()[0].mask(),
+            pattern::parse("BGBBY").unwrap()
+        );
+    }
+
+    #[test]
+    fn assist_with_odds() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser =
+            Guesser::new(&dictionary, &exclusions, &model, &strategy).with_limit(Some(3));
+
+        let input = "BGBBY\nbgggg\n".as_bytes();
+        let mut output = Vec::new();
+        Interactive::new(input, &mut output)
+            .with_odds(true)
+            .assist(&mut guesser);
+
+        // "match" is the one answer that takes three more guesses.
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("83% chance to solve in time, with 2 to go\n"));
+        assert!(output.contains("67% chance to solve in time, with 1 to go\n"));
+    }
+
+    #[test]
+    fn assist_with_list() {
+        let dictionary = [
+            "hatch", "catch", "match", "batch", "latch", "patch", "watch", "fight", "light",
+            "might", "night", "right",
+        ];
+        let exclusions = std::collections::HashSet::new();
+        let model = crate::model::AnswerModel::new(
+            dictionary.iter().zip((1..=12).rev()).map(|(&w, n)| (w, n)),
+        );
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        let input = "list\nlist\nBGBBY\nlist\n".as_bytes();
+        let mut output = Vec::new();
+        Interactive::new(input, &mut output).assist(&mut guesser);
+
+        // The first page stops at ten, and the second picks up from there.
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("1. hatch 15.4%\n"));
+        assert!(output.contains("10. might 3.8%\n2 more; list to see them\n"));
+        assert!(output.contains("12. right 1.3%\n"));
+
+        // Feedback narrows the listing and starts it over.
+        let listing = output.split("guess: HATCH").nth(1).unwrap();
+        assert!(listing.contains("1. hatch 21.8%\n"));
+        assert!(!listing.contains("latch") && !listing.contains("more"));
+    }
+
+    #[test]
+    fn adversarial() {
+        let mut oracle = Adversarial::new(&["hatch", "catch", "match", "cigar"]);
+
+        // Three of the words look the same to "hatch", so it keeps those.
+        assert_eq!(oracle.feedback("hatch"), Some([W, C, C, C, C]));
+        assert_eq!(oracle.candidates(), &["catch", "match"]);
+
+        // Only a lone word is left to give away.
+        let mut oracle = Adversarial::new(&["hatch"]);
+        assert_eq!(oracle.feedback("hatch"), Some([C, C, C, C, C]));
+    }
+
+    #[test]
+    fn host() {
+        let words = ["hatch", "catch", "match", "cigar"];
+        let input = "bogus\nHATCH\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("catch", &words, input, &mut output);
+
+        assert_eq!(host.play(), Some(2));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("bogus: not in word list\n"));
+    }
+
+    #[test]
+    fn host_with_timer() {
+        let words = ["hatch", "catch", "match"];
+        let input = "hatch\nnope\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("catch", &words, input, &mut output).with_timer(true);
+
+        assert_eq!(host.play(), Some(2));
+        // A rejected word is part of the time for the guess after it.
+        let times = host.times().to_vec();
+        assert_eq!(times.len(), 2);
+        let output = String::from_utf8(output).unwrap();
+        let last = Tileset::default().render(&Guess::check("catch", "catch"), false);
+        assert!(output.contains(&format!("{last}  {}\n", share::seconds(times[1]))));
+    }
+
+    #[test]
+    fn host_with_rival() {
+        let words = ["hatch", "catch", "match"];
+        let rival = [
+            Guess::check("catch", "match"),
+            Guess::check("catch", "catch"),
+        ];
+        let input = "hatch\nmatch\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("catch", &words, input, &mut output).with_rival(&rival);
+
+        assert_eq!(host.play(), Some(3));
+        let output = String::from_utf8(output).unwrap();
+        let first = Guess::check("catch", "hatch");
+        let tiles = Tileset::default();
+        assert!(output.contains(&race::beside(
+            &tiles.render(&first, false),
+            Some(&race::masked(&rival[0]))
+        )));
+        // The solver was done by the third guess, so it's shown alone.
+        let last = tiles.render(&Guess::check("catch", "catch"), false);
+        assert!(output.ends_with(&format!("{last}\n")));
+    }
+
+    #[test]
+    fn host_in_spanish() {
+        let words = ["hatch", "catch"];
+        let input = "bogus\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host =
+            Host::new("catch", &words, input, &mut output).with_language(Language::Spanish);
+
+        assert_eq!(host.play(), Some(1));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("intento> "));
+        assert!(output.contains("bogus: no está en la lista de palabras\n"));
+    }
+
+    #[test]
+    fn host_suggests() {
+        let words = ["hatch", "catch", "match", "cigar"];
+        let input = "hacth\nmatch\n".as_bytes();
+        let mut output = Vec::new();
+        Host::new("match", &words, input, &mut output).play();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("hacth: not in word list; did you mean hatch?"));
+    }
+
+    #[test]
+    fn host_folds_accents() {
+        let words = ["arbol", "cesta"];
+        let input = "árbol\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("arbol", &words, input, &mut output).with_folded_accents(true);
+        assert_eq!(host.play(), Some(1));
+    }
+
+    #[test]
+    fn host_with_validator() {
+        let words = ["catch", "hatch"];
+        let input = "zzzzz\nabc\nhatch\nqatch\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("catch", &words, input, &mut output)
+            .with_validator(|word| !word.starts_with('h'));
+        assert_eq!(host.play(), Some(3));
+        drop(host);
+
+        // Words off the list are played if approved, and listed ones aren't.
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("abc: guesses are 5 letters, not 3"));
+        assert!(output.contains("hatch: not in word list"));
+        assert!(!output.contains("zzzzz:") && !output.contains("qatch:"));
+    }
+
+    #[test]
+    fn host_tiles() {
+        let tiles = Tileset::new(["ll"], &Default::default()).unwrap();
+        let words = ["Aaves", "lacio"];
+        let input = "llavez\nLLAVES\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("Aaves", &words, input, &mut output).with_tiles(tiles);
+        assert_eq!(host.play(), Some(1));
+        drop(host);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("llavez: not in word list; did you mean llaves?"));
+        assert!(output.contains(" LL "));
+    }
+
+    #[test]
+    fn host_normalizes() {
+        let words = ["crane", "cigar"];
+        let input = "\n  ＣＲＡＮＥＳ \n  ＣＩＧＡＲ\u{200b}\n".as_bytes();
+        let mut output = Vec::new();
+        assert_eq!(
+            Host::new("cigar", &words, input, &mut output).play(),
+            Some(1)
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("enter a word to guess"));
+        assert!(output.contains("cranes: guesses are 5 letters, not 6; did you mean crane?"));
+    }
+
+    #[test]
+    fn host_avoid() {
+        let words = ["hatch", "catch", "match", "cigar"];
+        let input = "hatch\ncigar\nmatch\ncatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("catch", &words, input, &mut output).with_goal(Goal::Avoid);
+
+        // "cigar" was fine as a first guess, but breaks hard mode after "hatch".
+        assert_eq!(host.play(), Some(3));
+        let words: Vec<&str> = host.history().iter().map(|g| g.word()).collect();
+        assert_eq!(words, vec!["hatch", "match", "catch"]);
+        drop(host);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("cigar doesn't fit guess 1 (hatch ⬛🟩🟩🟩🟩)"));
+    }
+}
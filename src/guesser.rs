@@ -1,4 +1,24 @@
-use std::{borrow::Cow, collections::HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::AddAssign,
+    time::{Duration, Instant},
+};
+
+pub use crate::engine::Correctness;
+use crate::{
+    cache::{PoolId, ReductionCache},
+    engine::Duplicates,
+    game::Oracle,
+    hint::{self, Explanation, Scoring, Simulation, Suggestion},
+    knowledge::KnowledgeState,
+    model::AnswerModel,
+    odds,
+    packed::Packed,
+    strategy::{Entropy, Strategy},
+    tree::DecisionTree,
+};
 
 macro_rules! mask {
     (C) => {Correctness::Correct};
@@ -9,177 +29,809 @@ macro_rules! mask {
     ]};
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Correctness {
-    Correct,
-    Misplaced,
-    Wrong,
-}
-
 impl Correctness {
-    fn compute(answer: &str, word: &str) -> [Self; 5] {
-        let mut c = [Correctness::Wrong; 5];
-        let mut used = [false; 5];
-
-        for (i, (a, g)) in answer.bytes().zip(word.bytes()).enumerate() {
-            if a == g {
-                c[i] = Correctness::Correct;
-                used[i] = true;
-            }
-        }
+    /// Compute the pattern of `word` against each of `answers`, in order.
+    pub fn compute_batch(word: &str, answers: &[&str]) -> Vec<[Self; 5]> {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return crate::simd::compute_batch(word.as_bytes(), answers.iter().map(|a| a.as_bytes()));
 
-        for (i, g) in word.bytes().enumerate() {
-            if c[i] == Correctness::Correct {
-                continue;
-            }
-
-            if answer.bytes().enumerate().any(|(i, a)| {
-                if a == g && !used[i] {
-                    used[i] = true;
-                    return true;
-                }
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        answers
+            .iter()
+            .map(|answer| Self::compute(answer, word))
+            .collect()
+    }
 
-                false
-            }) {
-                c[i] = Correctness::Misplaced;
-            }
-        }
+    /// Compute the pattern of `word` against each of `answers`, in order,
+    /// reading them from one contiguous buffer.
+    pub fn compute_packed(word: &str, answers: &Packed) -> Vec<[Self; 5]> {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return crate::simd::compute_batch(word.as_bytes(), answers.iter().map(|a| &a[..]));
 
-        c
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        answers
+            .iter()
+            .map(|answer| Self::compute_bytes(answer, word.as_bytes()))
+            .collect()
     }
 }
 
-#[derive(Clone, Copy)]
-struct Guess<'a> {
+#[derive(Clone, Copy, Debug)]
+pub struct Guess<'a> {
     word: &'a str,
     mask: [Correctness; 5],
 }
 
 impl<'a> Guess<'a> {
-    fn check(answer: &'a str, word: &'a str) -> Self {
+    pub fn check(answer: &'a str, word: &'a str) -> Self {
+        Self::check_with(answer, word, Duplicates::Official)
+    }
+
+    /// [`check`](Self::check) with repeated letters colored by `rule`.
+    pub fn check_with(answer: &'a str, word: &'a str, rule: Duplicates) -> Self {
         Self {
             word,
-            mask: Correctness::compute(answer, word),
+            mask: Correctness::compute_with(answer.as_bytes(), word.as_bytes(), rule),
         }
     }
 
-    fn matches(&self, word: &str) -> bool {
-        let mut used = [false; 5];
+    /// A guess of `word` that got `mask`, as entered from a game played
+    /// elsewhere.
+    pub fn new(word: &'a str, mask: [Correctness; 5]) -> Self {
+        Self { word, mask }
+    }
 
-        'outer: for (i, ((g, &m), w)) in self
-            .word
-            .bytes()
-            .zip(&self.mask)
-            .zip(word.bytes())
-            .enumerate()
-        {
-            if m == Correctness::Correct {
-                if w != g {
-                    return false;
-                } else {
-                    used[i] = true;
-                    continue;
-                }
-            }
+    #[inline]
+    pub fn word(&self) -> &'a str {
+        self.word
+    }
 
-            for (j, (g_i, &m_i)) in self.word.bytes().zip(&self.mask).enumerate() {
-                if g_i != w || used[j] {
-                    continue;
-                }
+    #[inline]
+    pub fn mask(&self) -> [Correctness; 5] {
+        self.mask
+    }
 
-                match m_i {
-                    Correctness::Correct => continue,
-                    Correctness::Misplaced if j != i => {
-                        used[j] = true;
-                        continue 'outer;
-                    },
-                    _ => {
-                        return false;
-                    },
-                }
-            }
-        }
+    /// Whether `word` could be the answer given this guess's feedback. To
+    /// check many words, or against several guesses, build a
+    /// [`KnowledgeState`] once instead.
+    pub fn matches(&self, word: &str) -> bool {
+        KnowledgeState::from_history(&[*self]).allows(word)
+    }
 
-        for (&m, u) in self.mask.iter().zip(&used) {
-            if m == Correctness::Misplaced && !u {
-                return false;
-            }
+    #[inline]
+    pub fn is_correct(&self) -> bool {
+        self.mask == mask![C C C C C]
+    }
+
+    /// Whether no word could produce both this feedback and `other`'s, because
+    /// they disagree about a position or about how often a letter appears.
+    pub fn contradicts(&self, other: &Guess) -> bool {
+        !KnowledgeState::from_history(&[*self, *other]).is_consistent()
+    }
+}
+
+/// Time spent in each phase of solving.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub filtering: Duration,
+    pub scoring: Duration,
+}
+
+impl AddAssign for Timings {
+    fn add_assign(&mut self, other: Self) {
+        self.filtering += other.filtering;
+        self.scoring += other.scoring;
+    }
+}
+
+impl fmt::Display for Guess<'_> {
+    /// Renders the guess as colored letters, or with `{:#}`, as the word
+    /// followed by its emoji tiles for terminals without color.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{} {}", self.word, crate::pattern::Tiles(self.mask));
         }
 
-        true
+        for (letter, c) in self.word.chars().zip(self.mask) {
+            f.write_str(&c.paint(letter.to_ascii_uppercase()))?;
+        }
+        Ok(())
     }
+}
+
+/// Everything that happened while solving one game.
+#[derive(Clone, Debug)]
+pub struct SolveResult<'a> {
+    /// Every guess played, with its feedback, in order.
+    pub path: Vec<Guess<'a>>,
+    /// How many candidates were left after each guess.
+    pub remaining: Vec<usize>,
+    /// Whether the answer was found, regardless of how many guesses it took.
+    pub solved: bool,
+    pub elapsed: Duration,
+}
 
+impl SolveResult<'_> {
+    /// The number of guesses taken, if the answer was found at all.
     #[inline]
-    fn is_correct(&self) -> bool {
-        self.mask == mask![C C C C C]
+    pub fn guesses(&self) -> Option<usize> {
+        self.solved.then_some(self.path.len())
     }
 }
 
-pub(crate) struct Guesser<'a> {
-    answer: &'a str,
-    dictionary: Cow<'a, Vec<&'a str>>,
-    exclusions: &'a HashSet<&'a str>,
-    history: [Option<Guess<'a>>; 6],
+/// What an observer is told after each guess.
+#[derive(Clone, Copy, Debug)]
+pub struct GuessEvent<'a> {
+    /// The turn the guess was played on, counting from one.
+    pub turn: usize,
+    pub guess: Guess<'a>,
+    /// How many candidates are left after applying the feedback.
+    pub remaining: usize,
+}
+
+/// A candidate the guesser kept or dropped against what the feedback says,
+/// found when checking its filtering by brute force.
+#[derive(Clone, Copy, Debug)]
+pub struct Divergence<'a> {
+    pub guess: Guess<'a>,
+    pub candidate: &'a str,
+    /// Whether the guesser kept the candidate, which it shouldn't have.
+    pub kept: bool,
+    /// The feedback the guess really gets against the candidate.
+    pub actual: [Correctness; 5],
 }
 
-impl<'a> Guesser<'a> {
-    pub(crate) fn new(
-        answer: &'a str,
-        dictionary: &'a Vec<&'a str>,
-        exclusions: &'a HashSet<&'a str>,
+impl fmt::Display for Divergence<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:#} against {}: {}, but it gives {}",
+            self.guess,
+            self.candidate,
+            if self.kept { "kept" } else { "dropped" },
+            crate::pattern::Tiles(self.actual)
+        )
+    }
+}
+
+/// The candidates in `before` whose fate in `after` disagrees with the
+/// definition of a match: that playing `guess` against the candidate gives
+/// the same pattern. `after` must be in the same order as `before`.
+fn divergences<'a>(
+    guess: &Guess<'a>,
+    before: &[&'a str],
+    after: &[&'a str],
+    rule: Duplicates,
+) -> Vec<Divergence<'a>> {
+    let mut after = after.iter().peekable();
+    let mut found = Vec::new();
+    for &candidate in before {
+        let kept = after.next_if(|&&word| word == candidate).is_some();
+        let actual = Correctness::compute_with(candidate.as_bytes(), guess.word.as_bytes(), rule);
+        if kept != (actual == guess.mask) {
+            found.push(Divergence {
+                guess: *guess,
+                candidate,
+                kept,
+                actual,
+            });
+        }
+    }
+    found
+}
+
+/// The first guess played unless another is chosen.
+pub const OPENER: &str = "salet";
+
+type Observer<'a, 'b> = Box<dyn FnMut(&GuessEvent<'a>) + 'b>;
+type Verifier<'a, 'b> = Box<dyn FnMut(&Divergence<'a>) + 'b>;
+
+pub struct Guesser<'a, 'b> {
+    words: &'a [&'a str],
+    dictionary: Cow<'a, [&'a str]>,
+    guesses: Option<&'a [&'a str]>,
+    /// The only words that may be guessed, if restricted.
+    allowed: Option<&'b HashSet<&'a str>>,
+    exclusions: Cow<'b, HashSet<&'a str>>,
+    model: &'b AnswerModel<'a>,
+    strategy: &'b dyn Strategy,
+    cache: Option<(&'b mut ReductionCache<'a>, PoolId)>,
+    /// The guess already chosen for each pool of candidates in the cache.
+    choices: Option<&'b mut HashMap<PoolId, &'a str>>,
+    /// A plan to play while the game follows it, ahead of the strategy.
+    tree: Option<&'b DecisionTree>,
+    /// The words to open with, in order, before the strategy takes over.
+    opening: Vec<&'a str>,
+    limit: Option<usize>,
+    observer: Option<Observer<'a, 'b>>,
+    verifier: Option<Verifier<'a, 'b>>,
+    history: Vec<Guess<'a>>,
+    /// Guesses played before the guesser took over, applied on every reset,
+    /// and how many candidates each left.
+    given: Vec<Guess<'a>>,
+    given_remaining: Vec<usize>,
+    knowledge: KnowledgeState,
+    /// How the feedback colors repeated letters.
+    duplicates: Duplicates,
+    timings: Timings,
+    /// The candidates from before the last narrowing, kept to narrow into
+    /// next time rather than allocating afresh.
+    spare: Vec<&'a str>,
+}
+
+impl<'a, 'b> Guesser<'a, 'b> {
+    pub fn new(
+        dictionary: &'a [&'a str],
+        exclusions: &'b HashSet<&'a str>,
+        model: &'b AnswerModel<'a>,
+        strategy: &'b dyn Strategy,
     ) -> Self {
         Self {
-            answer,
+            words: dictionary,
             dictionary: Cow::Borrowed(dictionary),
-            exclusions,
-            history: [None; 6],
+            guesses: None,
+            allowed: None,
+            exclusions: Cow::Borrowed(exclusions),
+            model,
+            strategy,
+            cache: None,
+            choices: None,
+            tree: None,
+            opening: vec![OPENER],
+            limit: Some(6),
+            observer: None,
+            verifier: None,
+            history: Vec::new(),
+            given: Vec::new(),
+            given_remaining: Vec::new(),
+            knowledge: KnowledgeState::default(),
+            duplicates: Duplicates::Official,
+            timings: Timings::default(),
+            spare: Vec::new(),
         }
     }
 
-    pub(crate) fn solve(&mut self) -> Option<usize> {
-        let mut current_word = "salet";
+    /// Allow any of `guesses` to be played as a probe, rather than only the
+    /// remaining candidates.
+    pub fn with_guesses(mut self, guesses: &'a [&'a str]) -> Self {
+        self.guesses = Some(guesses);
+        self
+    }
 
-        for i in 0..6 {
-            let guess = Guess::check(self.answer, current_word);
+    /// Only ever guess words in `allowed`, such as the words a player knows,
+    /// though any word may still be the answer. Once the answer is certain
+    /// it's played regardless, since there's nothing else left to try.
+    pub fn with_allowed(mut self, allowed: &'b HashSet<&'a str>) -> Self {
+        self.allowed = Some(allowed);
+        self
+    }
 
-            if guess.is_correct() {
-                return Some(i + 1);
-            }
+    /// Share candidate reductions through `cache`, which must have been built
+    /// over the same dictionary as this guesser.
+    pub fn with_cache(mut self, cache: &'b mut ReductionCache<'a>) -> Self {
+        self.cache = Some((cache, ReductionCache::ROOT));
+        self
+    }
 
-            match &mut self.dictionary {
-                Cow::Borrowed(_) => {
-                    self.dictionary = Cow::Owned(
-                        self.dictionary
-                            .iter()
-                            .filter_map(|word| {
-                                (guess.matches(word) && !self.exclusions.contains(word))
-                                    .then_some(*word)
-                            })
-                            .collect(),
-                    );
-                },
-                Cow::Owned(dict) => {
-                    dict.retain(|word| guess.matches(word) && !self.exclusions.contains(word))
-                },
-            };
+    /// Remember the strategy's choice for each set of candidates in
+    /// `choices`, and play it again whenever the same candidates come up,
+    /// however they were reached. Strategies choose by the candidates alone,
+    /// so this only saves time; it needs a cache to tell the sets apart, and
+    /// is skipped while anything is excluded.
+    pub fn with_choices(mut self, choices: &'b mut HashMap<PoolId, &'a str>) -> Self {
+        self.choices = Some(choices);
+        self
+    }
 
-            self.history[i] = Some(guess);
+    /// Start every game from `given`, guesses already played elsewhere with
+    /// the feedback they got, as if the guesser had played them itself.
+    pub fn with_given(mut self, given: &[(&'a str, [Correctness; 5])]) -> Self {
+        self.given = given
+            .iter()
+            .map(|&(word, mask)| Guess { word, mask })
+            .collect();
+        self.reset();
+        self
+    }
+
+    /// Play the guesses `tree` plans for as long as the game follows one of
+    /// its paths, leaving the strategy only games that stray from it. The tree
+    /// must have been built over the same lists as this guesser, and is
+    /// skipped while anything is excluded, since it may plan to play an
+    /// excluded answer.
+    pub fn with_tree(mut self, tree: &'b DecisionTree) -> Self {
+        self.tree = Some(tree);
+        self
+    }
+
+    /// Open with `word` rather than [`OPENER`]; `None` leaves the first guess
+    /// to the strategy, as for alphabets the default can't be spelled in.
+    pub fn with_opener(mut self, word: Option<&'a str>) -> Self {
+        self.opening = word.into_iter().collect();
+        self
+    }
+
+    /// Open with each of `words` in turn, then leave the rest to the
+    /// strategy. Words after the first are skipped once the answer is found
+    /// or certain.
+    pub fn with_opening(mut self, words: &[&'a str]) -> Self {
+        self.opening = words.to_vec();
+        self
+    }
+
+    /// Change the number of guesses allowed; `None` keeps guessing until the
+    /// answer is found or no candidates remain.
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Read feedback as a clone colored by `rule` gives it, rather than as
+    /// the official game does. A cache given with
+    /// [`with_cache`](Self::with_cache) must reduce by the same rule.
+    pub fn with_duplicates(mut self, rule: Duplicates) -> Self {
+        self.duplicates = rule;
+        self.reset();
+        self
+    }
+
+    /// Call `observer` after each guess, once its feedback has been applied.
+    pub fn with_observer(mut self, observer: impl FnMut(&GuessEvent<'a>) + 'b) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Check every narrowing against the brute-force definition of a match,
+    /// calling `verifier` with each candidate kept or dropped wrongly. This
+    /// recomputes a pattern per candidate, so it's meant for debugging.
+    pub fn with_verifier(mut self, verifier: impl FnMut(&Divergence<'a>) + 'b) -> Self {
+        self.verifier = Some(Box::new(verifier));
+        self
+    }
 
-            if self.dictionary.is_empty() {
+    /// Play against `oracle` until the answer is found, the limit is reached,
+    /// no candidates remain, or the oracle stops giving feedback.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(strategy = self.strategy.name()))
+    )]
+    pub fn solve(&mut self, oracle: &mut dyn Oracle) -> SolveResult<'a> {
+        let start = Instant::now();
+        let mut remaining = self.given_remaining.clone();
+        remaining.truncate(self.history.len());
+
+        while let Some(word) = self.next_guess() {
+            let Some(mask) = oracle.feedback(word) else {
                 break;
+            };
+            remaining.push(self.apply_feedback(word, mask));
+        }
+
+        SolveResult {
+            path: self.history.clone(),
+            remaining,
+            solved: self.history.last().is_some_and(Guess::is_correct),
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// The word to play next, or `None` once the answer has been found, the
+    /// limit is reached, or no candidates remain. Each call asks the strategy
+    /// afresh, so callers should hold on to the result.
+    pub fn next_guess(&mut self) -> Option<&'a str> {
+        let last = self.history.last();
+        let planned = self
+            .opening
+            .get(self.history.len())
+            .copied()
+            .filter(|word| self.given.is_empty() && self.playable(word));
+        if let (None, Some(opener)) = (last, planned) {
+            return Some(opener);
+        }
+
+        let exhausted = self.limit.is_some_and(|limit| self.history.len() >= limit);
+        if last.is_some_and(Guess::is_correct) || exhausted || self.dictionary.is_empty() {
+            return None;
+        }
+        if let (Some(word), 2..) = (planned, self.dictionary.len()) {
+            return Some(word);
+        }
+        if let Some(word) = self.planned_by_tree() {
+            return Some(word);
+        }
+
+        let pool = match (&self.cache, &self.choices) {
+            (Some((_, pool)), Some(_)) if self.exclusions.is_empty() => Some(*pool),
+            _ => None,
+        };
+        if let (Some(pool), Some(choices)) = (pool, &self.choices) {
+            if let Some(&word) = choices.get(&pool) {
+                return Some(word);
             }
+        }
 
-            current_word = self.dictionary[0];
+        let guesses = self.guesses.unwrap_or(&self.dictionary);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("choose", candidates = self.dictionary.len()).entered();
+        let start = Instant::now();
+        let word = match self.allowed {
+            Some(allowed) if self.dictionary.len() > 1 => self.choose_allowed(guesses, allowed),
+            _ => self.strategy.choose_within(
+                guesses,
+                &self.dictionary,
+                self.model,
+                self.turns_left(),
+            ),
+        };
+        self.timings.scoring += start.elapsed();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(guess = word, elapsed = ?start.elapsed(), "chose");
+        if let (Some(pool), Some(choices)) = (pool, &mut self.choices) {
+            choices.insert(pool, word);
         }
+        Some(word)
+    }
 
-        None
+    /// The tree's next guess, as the guesser's own copy of the word, if the
+    /// game hasn't strayed from it and the word may be played.
+    fn planned_by_tree(&self) -> Option<&'a str> {
+        let tree = self.tree.filter(|_| self.exclusions.is_empty())?;
+        let planned = tree.next(&self.history)?;
+        self.guesses
+            .unwrap_or_default()
+            .iter()
+            .chain(self.words)
+            .copied()
+            .find(|&word| word == planned)
+            .filter(|word| self.playable(word))
     }
 
-    pub(crate) fn guessed_words(&self) -> Vec<&str> {
-        self.history
+    /// Whether `word` may be guessed.
+    fn playable(&self, word: &str) -> bool {
+        self.allowed.is_none_or(|allowed| allowed.contains(word))
+    }
+
+    /// The strategy's choice among the `allowed` words. If it would rather
+    /// play a candidate that isn't allowed, its choice among the allowed
+    /// candidates is played instead, and failing any of those, the allowed
+    /// word that tells the candidates apart best. When no allowed word can
+    /// tell them apart at all, the strategy has its way.
+    fn choose_allowed(&self, guesses: &[&'a str], allowed: &HashSet<&'a str>) -> &'a str {
+        let candidates = &self.dictionary[..];
+        let playable: Vec<&str> = guesses
             .iter()
-            .filter_map(|og| og.map(|g| g.word))
-            .collect()
+            .copied()
+            .filter(|word| allowed.contains(word))
+            .collect();
+        let turns = self.turns_left();
+        let word = self
+            .strategy
+            .choose_within(&playable, candidates, self.model, turns);
+        if allowed.contains(word) {
+            return word;
+        }
+
+        let answers: Vec<&str> = candidates
+            .iter()
+            .copied()
+            .filter(|word| allowed.contains(word))
+            .collect();
+        if !answers.is_empty() {
+            let word = self
+                .strategy
+                .choose_within(&answers, &answers, self.model, turns);
+            if allowed.contains(word) {
+                return word;
+            }
+        }
+
+        // Without probes, the guesses were only the candidates, none of which
+        // may be played; any allowed word that narrows them will have to do.
+        let probes: Vec<&str> = self
+            .guesses
+            .unwrap_or(self.words)
+            .iter()
+            .copied()
+            .filter(|word| allowed.contains(word))
+            .collect();
+        let probe = Entropy::default().choose(&probes, candidates, self.model);
+        let distribution = self.model.distribution(candidates);
+        if probes.is_empty()
+            || Suggestion::evaluate(probe, candidates, &distribution).information == 0.0
+        {
+            word
+        } else {
+            probe
+        }
+    }
+
+    /// How many more guesses may be played, if there's a limit.
+    pub fn turns_left(&self) -> Option<usize> {
+        Some(self.limit?.saturating_sub(self.history.len()))
+    }
+
+    /// The chance the strategy finds the answer within the guesses left, as
+    /// estimated by [`odds::win_probability`] from `samples` answers, or
+    /// `None` if there's no limit on guesses.
+    pub fn win_probability(&self, samples: usize) -> Option<f64> {
+        let turns = self.turns_left()?;
+        if self.history.last().is_some_and(Guess::is_correct) {
+            return Some(1.0);
+        }
+        Some(odds::win_probability(
+            self.strategy,
+            self.guesses,
+            &self.dictionary,
+            self.model,
+            turns,
+            samples,
+        ))
+    }
+
+    /// Why `word` is worth playing now, beside the best of the guesses the
+    /// strategy is offered, as ranked by `scoring`.
+    pub fn explain(&self, word: &'a str, scoring: Scoring) -> Explanation<'a> {
+        let guesses = self.guesses.unwrap_or(&self.dictionary);
+        hint::explain(
+            word,
+            guesses,
+            &self.dictionary,
+            self.model,
+            scoring,
+            hint::EXPLAINED,
+        )
+    }
+
+    /// What playing `word` now would do, without playing it: every feedback
+    /// it could get, and the candidates and knowledge each would leave.
+    pub fn simulate(&self, word: &'a str) -> Simulation<'a> {
+        hint::simulate(word, &self.knowledge, &self.dictionary, self.model)
+    }
+
+    /// Record the feedback for playing `word`, narrowing the candidates to
+    /// those consistent with it. Returns how many candidates remain.
+    pub fn apply_feedback(&mut self, word: &'a str, mask: [Correctness; 5]) -> usize {
+        let guess = Guess { word, mask };
+        if guess.is_correct() {
+            self.record(guess, 1);
+            return 1;
+        }
+
+        self.narrow(&guess);
+        let remaining = self.dictionary.len();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(guess = %format_args!("{guess:#}"), remaining, "narrowed");
+        self.record(guess, remaining);
+        remaining
+    }
+
+    /// Start a new game from the full word list, narrowed by any guesses the
+    /// guesser was given, keeping the configuration, any exclusions, and the
+    /// buffers from the last game. Solving many answers with one guesser this
+    /// way saves reallocating for each.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.knowledge = KnowledgeState::new().with_duplicates(self.duplicates);
+        self.timings = Timings::default();
+        if let Some((_, pool)) = &mut self.cache {
+            *pool = ReductionCache::ROOT;
+        }
+        if let Cow::Owned(old) = std::mem::replace(&mut self.dictionary, Cow::Borrowed(self.words))
+        {
+            self.spare = old;
+        }
+        self.given_remaining.clear();
+        for index in 0..self.given.len() {
+            let guess = self.given[index];
+            let remaining = self.apply_feedback(guess.word, guess.mask);
+            self.given_remaining.push(remaining);
+        }
+    }
+
+    /// Never consider `word` a candidate again, as when it was the answer
+    /// to an earlier puzzle. Like the exclusions the guesser was made with,
+    /// this applies from the first narrowing on.
+    pub fn exclude(&mut self, word: &'a str) {
+        self.exclusions.to_mut().insert(word);
+    }
+
+    /// Take back the last guess, restoring the candidates from before it was
+    /// played. Returns the guess, or `None` if nothing has been played.
+    pub fn undo(&mut self) -> Option<Guess<'a>> {
+        let last = self.history.pop()?;
+        self.rebuild();
+        Some(last)
+    }
+
+    /// The likeliest fix when no candidates remain: the one tile in one entry
+    /// that, given another color, lets some word fit all the feedback again.
+    /// Of the fixes that do, the one leaving the most model weight wins, and
+    /// ties go to the latest entry. Returns the entry's index and the guess
+    /// as corrected, or `None` if no single tile would do.
+    pub fn correction(&self) -> Option<(usize, Guess<'a>)> {
+        let mut best: Option<(f64, usize, Guess<'a>)> = None;
+        let mut amended = self.history.clone();
+        for (index, guess) in self.history.iter().enumerate().rev() {
+            for position in 0..5 {
+                for color in [
+                    Correctness::Correct,
+                    Correctness::Misplaced,
+                    Correctness::Wrong,
+                ] {
+                    if guess.mask[position] == color {
+                        continue;
+                    }
+                    let mut fixed = *guess;
+                    fixed.mask[position] = color;
+                    amended[index] = fixed;
+                    let knowledge = KnowledgeState::from_history_with(&amended, self.duplicates);
+                    let fitting = self
+                        .words
+                        .iter()
+                        .filter(|word| knowledge.allows(word) && !self.exclusions.contains(*word));
+                    let mut found = false;
+                    let mut weight = 0.0;
+                    for word in fitting {
+                        found = true;
+                        weight += self.model.weight(word);
+                    }
+                    if found && best.as_ref().is_none_or(|&(most, ..)| weight > most) {
+                        best = Some((weight, index, fixed));
+                    }
+                }
+            }
+            amended[index] = *guess;
+        }
+        best.map(|(_, index, fixed)| (index, fixed))
+    }
+
+    /// Replace the feedback entered for guess `index` with `mask`, keeping
+    /// every guess since, as when an entry turns out to have been mistyped.
+    /// Returns how many candidates remain, or `None` if there's no such guess.
+    pub fn amend(&mut self, index: usize, mask: [Correctness; 5]) -> Option<usize> {
+        self.history.get_mut(index)?.mask = mask;
+        self.rebuild();
+        Some(self.dictionary.len())
+    }
+
+    /// Recompute what's known and the candidates from the history alone.
+    fn rebuild(&mut self) {
+        self.knowledge = KnowledgeState::from_history_with(&self.history, self.duplicates);
+
+        // Rather than keeping a snapshot per turn, rebuild from the history:
+        // the cache has to replay its reductions, but otherwise the words are
+        // filtered once against everything still known.
+        let start = Instant::now();
+        match &mut self.cache {
+            Some((cache, pool)) => {
+                *pool = ReductionCache::ROOT;
+                for guess in &self.history {
+                    *pool = cache.reduce(*pool, guess);
+                }
+                self.dictionary = Cow::Owned(
+                    cache
+                        .words(*pool)
+                        .filter(|word| !self.exclusions.contains(word))
+                        .collect(),
+                );
+            },
+            None => {
+                self.dictionary = Cow::Owned(
+                    self.words
+                        .iter()
+                        .copied()
+                        .filter(|word| self.allows(word))
+                        .collect(),
+                )
+            },
+        }
+        self.timings.filtering += start.elapsed();
+    }
+
+    /// Whether `word` is still a candidate given everything known.
+    #[inline]
+    fn allows(&self, word: &str) -> bool {
+        self.knowledge.allows(word) && !self.exclusions.contains(word)
+    }
+
+    /// Fold `guess` into what's known and drop the candidates that no longer
+    /// fit.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn narrow(&mut self, guess: &Guess<'a>) {
+        let before = self.verifier.is_some().then(|| self.dictionary.to_vec());
+        let start = Instant::now();
+        self.knowledge.add(guess);
+        let mut narrowed = std::mem::take(&mut self.spare);
+        narrowed.clear();
+        match (&self.dictionary, &mut self.cache) {
+            (_, Some((cache, pool))) => {
+                // The cache is shared between answers, so exclusions are
+                // applied on top of the cached pool rather than stored in it.
+                *pool = cache.reduce(*pool, guess);
+                narrowed.extend(
+                    cache
+                        .words(*pool)
+                        .filter(|word| !self.exclusions.contains(word)),
+                );
+            },
+            (Cow::Borrowed(words), None) => {
+                narrowed.extend(words.iter().copied().filter(|word| self.allows(word)));
+            },
+            (Cow::Owned(_), None) => {
+                let mut dictionary = std::mem::take(self.dictionary.to_mut());
+                dictionary.retain(|word| self.allows(word));
+                narrowed = dictionary;
+            },
+        };
+        if let Cow::Owned(old) = std::mem::replace(&mut self.dictionary, Cow::Owned(narrowed)) {
+            self.spare = old;
+        }
+        self.timings.filtering += start.elapsed();
+
+        if let (Some(before), Some(verifier)) = (before, &mut self.verifier) {
+            for divergence in divergences(guess, &before, &self.dictionary, self.duplicates) {
+                verifier(&divergence);
+            }
+        }
+    }
+
+    /// The earliest guess whose feedback contradicts the latest one's, which
+    /// is usually the entry to correct when no candidates remain.
+    pub fn conflict(&self) -> Option<usize> {
+        let (latest, earlier) = self.history.split_last()?;
+        earlier.iter().position(|guess| {
+            !KnowledgeState::from_history_with(&[*guess, *latest], self.duplicates).is_consistent()
+        })
+    }
+
+    /// How the feedback colors repeated letters, for a host answering this
+    /// guesser to color them the same way.
+    pub fn duplicates(&self) -> Duplicates {
+        self.duplicates
+    }
+
+    /// Everything the guesses so far say about the answer.
+    pub fn knowledge(&self) -> &KnowledgeState {
+        &self.knowledge
+    }
+
+    /// The answers still consistent with every guess so far.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.dictionary
+    }
+
+    /// The candidates with each one's chance of being the answer, likeliest
+    /// first.
+    pub fn ranked(&self) -> Vec<(&'a str, f64)> {
+        let distribution = self.model.distribution(&self.dictionary);
+        let mut ranked: Vec<_> = self.dictionary.iter().copied().zip(distribution).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+
+    fn record(&mut self, guess: Guess<'a>, remaining: usize) {
+        self.history.push(guess);
+        if let Some(observer) = &mut self.observer {
+            observer(&GuessEvent {
+                turn: self.history.len(),
+                guess,
+                remaining,
+            });
+        }
+    }
+
+    pub fn timings(&self) -> Timings {
+        self.timings
+    }
+
+    /// The word this guesser opens with, if it doesn't leave it to the strategy.
+    pub fn opener(&self) -> Option<&'a str> {
+        self.opening.first().copied()
+    }
+
+    /// Every guess made so far, with its feedback, in order.
+    pub fn history(&self) -> &[Guess<'a>] {
+        &self.history
     }
 }
 
@@ -208,6 +860,409 @@ mod tests {
         assert_eq!(Correctness::compute("party", "tardy"), mask![M C C W C]);
     }
 
+    #[test]
+    fn batch() {
+        let answers = [
+            "stare", "tares", "chomp", "party", "ccccc", "eerie", "abbey",
+        ];
+        for word in ["tardy", "ccccg", "geese", "babes", "stare"] {
+            let expected: Vec<_> = answers
+                .iter()
+                .map(|answer| Correctness::compute(answer, word))
+                .collect();
+            assert_eq!(Correctness::compute_batch(word, &answers), expected);
+            assert_eq!(
+                Correctness::compute_packed(word, &Packed::new(&answers)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let guess = Guess::check("racer", "tares");
+        assert_eq!(format!("{guess:#}"), "tares ⬛🟩🟨🟩⬛");
+        assert!(format!("{guess}").contains(" A "));
+    }
+
+    #[test]
+    fn history() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        let mut oracle = crate::game::KnownAnswer::new("catch");
+        let result = guesser.solve(&mut oracle);
+        assert!(result.solved);
+        assert_eq!(result.guesses(), Some(3));
+        assert_eq!(result.remaining, vec![3, 2, 1]);
+
+        let history: Vec<(&str, [Correctness; 5])> = guesser
+            .history()
+            .iter()
+            .map(|g| (g.word(), g.mask()))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                ("salet", mask![W C W W M]),
+                ("hatch", mask![W C C C C]),
+                ("catch", mask![C C C C C]),
+            ]
+        );
+    }
+
+    #[test]
+    fn observer() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+
+        let mut events = Vec::new();
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_observer(|event| events.push((event.turn, event.guess.word(), event.remaining)));
+        guesser.solve(&mut crate::game::KnownAnswer::new("match"));
+        drop(guesser);
+
+        assert_eq!(
+            events,
+            vec![
+                (1, "salet", 3),
+                (2, "hatch", 2),
+                (3, "catch", 1),
+                (4, "match", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn step_by_step() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        assert_eq!(guesser.next_guess(), Some("salet"));
+        assert_eq!(guesser.apply_feedback("salet", mask![W C W W M]), 3);
+        assert_eq!(guesser.next_guess(), Some("hatch"));
+        assert_eq!(guesser.apply_feedback("hatch", mask![W C C C C]), 2);
+        assert_eq!(guesser.candidates(), ["catch", "match"]);
+        assert_eq!(
+            guesser.knowledge().fixed(),
+            [None, Some(b'a'), Some(b't'), Some(b'c'), Some(b'h')]
+        );
+
+        // Feedback needn't follow the suggestion.
+        assert_eq!(guesser.apply_feedback("match", mask![C C C C C]), 1);
+        assert_eq!(guesser.next_guess(), None);
+    }
+
+    #[test]
+    fn opener() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser =
+            Guesser::new(&dictionary, &exclusions, &model, &strategy).with_opener(Some("match"));
+
+        let result = guesser.solve(&mut crate::game::KnownAnswer::new("catch"));
+        let words: Vec<&str> = result.path.iter().map(|g| g.word()).collect();
+        assert_eq!(words, vec!["match", "hatch", "catch"]);
+    }
+
+    #[test]
+    fn naive_duplicates() {
+        // Against "cigar", the first "a" of "carat" is yellow under the naive
+        // rule, which the official rule would read as a second "a".
+        let dictionary = ["carat", "cigar", "comma"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("carat", 3), ("cigar", 2), ("comma", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opener(None)
+            .with_duplicates(Duplicates::Naive);
+
+        let mut oracle = crate::game::KnownAnswer {
+            answer: "cigar",
+            duplicates: guesser.duplicates(),
+        };
+        let result = guesser.solve(&mut oracle);
+        assert_eq!(result.path[0].mask(), mask![C M M C W]);
+        assert_eq!(result.guesses(), Some(2));
+
+        // The official rule gives that feedback to nothing left.
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+        assert_eq!(guesser.apply_feedback("carat", mask![C M M C W]), 0);
+    }
+
+    #[test]
+    fn allowed() {
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 4), ("catch", 3), ("match", 2), ("patch", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let allowed = HashSet::from(["match", "patch", "chomp"]);
+        fn solve<'a>(guesser: &mut Guesser<'a, '_>, answer: &'a str) -> Vec<&'a str> {
+            let result = guesser.solve(&mut crate::game::KnownAnswer::new(answer));
+            result.path.iter().map(|g| g.word()).collect()
+        }
+
+        // The opener and the likelier candidates are passed over.
+        let mut guesser =
+            Guesser::new(&dictionary, &exclusions, &model, &strategy).with_allowed(&allowed);
+        assert_eq!(solve(&mut guesser, "patch"), ["match", "patch"]);
+        // When no allowed word helps, the strategy's choice is played anyway.
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opener(Some("patch"))
+            .with_allowed(&allowed);
+        assert_eq!(solve(&mut guesser, "hatch"), ["patch", "match", "hatch"]);
+        // With probes, an allowed one splits the last two candidates, and
+        // then the answer is certain.
+        let guesses = ["hatch", "catch", "match", "patch", "chomp"];
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_guesses(&guesses)
+            .with_opener(Some("patch"))
+            .with_allowed(&allowed);
+        assert_eq!(
+            solve(&mut guesser, "hatch"),
+            ["patch", "match", "chomp", "hatch"]
+        );
+    }
+
+    #[test]
+    fn opening() {
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opening(&["match", "patch", "catch"]);
+        let mut solve = |answer| {
+            guesser.reset();
+            let result = guesser.solve(&mut crate::game::KnownAnswer::new(answer));
+            result.path.iter().map(|g| g.word()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(solve("hatch"), ["match", "patch", "catch", "hatch"]);
+        assert_eq!(solve("patch"), ["match", "patch"]);
+
+        // Once the answer is certain, the rest of the opening is skipped.
+        let dictionary = ["hatch", "catch"];
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opening(&["chomp", "match"]);
+        let result = guesser.solve(&mut crate::game::KnownAnswer::new("hatch"));
+        let words: Vec<&str> = result.path.iter().map(|g| g.word()).collect();
+        assert_eq!(words, ["chomp", "hatch"]);
+    }
+
+    #[test]
+    fn choices() {
+        struct Counting(std::cell::Cell<usize>);
+        impl Strategy for Counting {
+            fn name(&self) -> &'static str {
+                "counting"
+            }
+
+            fn choose<'a>(
+                &self,
+                guesses: &[&'a str],
+                candidates: &[&'a str],
+                model: &AnswerModel,
+            ) -> &'a str {
+                self.0.set(self.0.get() + 1);
+                crate::strategy::Likeliest.choose(guesses, candidates, model)
+            }
+        }
+
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = Counting(Default::default());
+        let mut cache = ReductionCache::new(&dictionary);
+        let mut choices = HashMap::new();
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opener(Some("match"))
+            .with_cache(&mut cache)
+            .with_choices(&mut choices);
+        let mut solve = |answer| {
+            guesser.reset();
+            let result = guesser.solve(&mut crate::game::KnownAnswer::new(answer));
+            result.path.iter().map(|g| g.word()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(solve("patch"), ["match", "hatch", "catch", "patch"]);
+        assert_eq!(solve("catch"), ["match", "hatch", "catch"]);
+        assert_eq!(solve("hatch"), ["match", "hatch"]);
+        // Each set of candidates was only chosen for once.
+        assert_eq!(strategy.0.get(), 3);
+    }
+
+    #[test]
+    fn tree() {
+        let mut tree = DecisionTree::new();
+        tree.insert(&["match", "patch", "catch"]);
+        tree.insert(&["match", "patch"]);
+        tree.insert(&["match"]);
+
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opener(Some("match"))
+            .with_tree(&tree);
+        let mut solve = |answer| {
+            guesser.reset();
+            let result = guesser.solve(&mut crate::game::KnownAnswer::new(answer));
+            result.path.iter().map(|g| g.word()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(solve("catch"), ["match", "patch", "catch"]);
+        // Past the end of the plan, the strategy takes over.
+        assert_eq!(solve("hatch"), ["match", "patch", "catch", "hatch"]);
+
+        // The plan may not account for what's excluded, so it's set aside.
+        let exclusions = HashSet::from(["patch"]);
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+            .with_opener(Some("match"))
+            .with_tree(&tree);
+        let result = guesser.solve(&mut crate::game::KnownAnswer::new("catch"));
+        let words: Vec<&str> = result.path.iter().map(|g| g.word()).collect();
+        assert_eq!(words, ["match", "hatch", "catch"]);
+    }
+
+    #[test]
+    fn given() {
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let given = [("latch", mask![W C C C C])];
+        let mut guesser =
+            Guesser::new(&dictionary, &exclusions, &model, &strategy).with_given(&given);
+        assert_eq!(guesser.candidates().len(), 4);
+
+        let result = guesser.solve(&mut crate::game::KnownAnswer::new("catch"));
+        let words: Vec<&str> = result.path.iter().map(|g| g.word()).collect();
+        assert_eq!(words, ["latch", "hatch", "catch"]);
+        assert_eq!(result.remaining, [4, 3, 1]);
+
+        // Every new game starts from the same guesses.
+        guesser.reset();
+        assert_eq!(guesser.history().len(), 1);
+        assert_eq!(guesser.candidates().len(), 4);
+    }
+
+    #[test]
+    fn reuse() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser =
+            Guesser::new(&dictionary, &exclusions, &model, &strategy).with_opener(Some("match"));
+        fn solve<'a>(guesser: &mut Guesser<'a, '_>, answer: &'a str) -> Vec<&'a str> {
+            guesser.reset();
+            let result = guesser.solve(&mut crate::game::KnownAnswer::new(answer));
+            result.path.iter().map(|g| g.word()).collect()
+        }
+
+        assert_eq!(solve(&mut guesser, "catch"), ["match", "hatch", "catch"]);
+        assert_eq!(solve(&mut guesser, "catch"), ["match", "hatch", "catch"]);
+        // Once excluded, a word is never a candidate again.
+        guesser.exclude("hatch");
+        assert_eq!(solve(&mut guesser, "catch"), ["match", "catch"]);
+        assert!(exclusions.is_empty());
+    }
+
+    #[test]
+    fn undo() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([]);
+        let strategy = crate::strategy::Likeliest;
+        let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        assert!(guesser.undo().is_none());
+        guesser.apply_feedback("salet", mask![W C W W M]);
+        guesser.apply_feedback("hatch", mask![W C C C C]);
+        assert_eq!(guesser.candidates(), ["catch", "match"]);
+
+        assert_eq!(guesser.conflict(), None);
+        guesser.apply_feedback("catch", mask![W W W W W]);
+        assert_eq!(guesser.candidates(), [] as [&str; 0]);
+        assert_eq!(guesser.conflict(), Some(0));
+        guesser.undo();
+
+        assert_eq!(guesser.undo().map(|g| g.word()), Some("hatch"));
+        assert_eq!(guesser.candidates(), dictionary);
+        assert_eq!(guesser.history().len(), 1);
+    }
+
+    #[test]
+    fn verify() {
+        let dictionary = [
+            "eerie", "geese", "egret", "sheep", "sweet", "leave", "reset", "tepee",
+        ];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([]);
+        let strategy = crate::strategy::Likeliest;
+
+        let mut found = Vec::new();
+        for answer in dictionary {
+            let mut guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy)
+                .with_opener(Some("geese"))
+                .with_limit(None)
+                .with_verifier(|d| found.push(d.to_string()));
+            assert!(
+                guesser
+                    .solve(&mut crate::game::KnownAnswer::new(answer))
+                    .solved
+            );
+        }
+        assert!(found.is_empty(), "{found:?}");
+
+        // A filter that kept "sheep" and dropped "eerie" is caught both ways.
+        let guess = Guess::check("eerie", "geese");
+        let found = divergences(
+            &guess,
+            &dictionary,
+            &["sheep", "tepee"],
+            Duplicates::Official,
+        );
+        let found: Vec<_> = found.iter().map(|d| (d.candidate, d.kept)).collect();
+        assert_eq!(found, vec![("eerie", false), ("sheep", true)]);
+        assert_eq!(
+            divergences(&guess, &dictionary, &[], Duplicates::Official)[0].to_string(),
+            "geese ⬛🟩🟨⬛🟩 against eerie: dropped, but it gives ⬛🟩🟨⬛🟩"
+        );
+    }
+
+    #[test]
+    fn contradictions() {
+        let guess = |word, mask| Guess { word, mask };
+        let green_a = guess("salet", mask![W C W W W]);
+
+        // Another letter where "a" was green.
+        assert!(green_a.contradicts(&guess("chart", mask![W C W W W])));
+        assert!(green_a.contradicts(&guess("crane", mask![W W W W W])));
+        assert!(green_a.contradicts(&guess("madam", mask![W M W W W])));
+        // "a" was gray, so there's only the one, and "t" can't be there too.
+        assert!(guess("salet", mask![W W W W C]).contradicts(&guess("taunt", mask![M W W W M])));
+        assert!(!green_a.contradicts(&guess("hatch", mask![W C W W W])));
+        assert!(!green_a.contradicts(&guess("tramp", mask![W W C W W])));
+
+        // Nothing depends on the words being spelled with letters.
+        let sevens = guess("77777", mask![W W W W C]);
+        assert!(sevens.contradicts(&guess("17377", mask![W W W C C])));
+        assert!(!sevens.contradicts(&guess("10007", mask![W W W W C])));
+    }
+
     #[test]
     fn plausibility_imply() {
         let answer = "imply";
@@ -239,6 +1294,15 @@ mod tests {
         assert!(!guess.matches("races"));
     }
 
+    #[test]
+    fn plausibility_repeated_gray() {
+        // The second "a" is gray, so there's exactly one, and not at the end.
+        let guess = Guess::check("cigar", "karma");
+        assert!(guess.matches("cigar"));
+        assert!(!guess.matches("ultra"));
+        assert!(!guess.matches("zebra"));
+    }
+
     #[test]
     fn plausibility_requires_misplaced() {
         let answer = "islet";
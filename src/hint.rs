@@ -0,0 +1,544 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    engine::PATTERNS,
+    feedback::Feedback,
+    guesser::{Correctness, Guess},
+    knowledge::KnowledgeState,
+    model::AnswerModel,
+    packed::Packed,
+    parallel,
+};
+
+/// How many bits of uncertainty each further guess clears on average, once
+/// the opener is played: the entropy strategy's benchmark games take about
+/// `1 + log2(n) / 4` more guesses from a state with `n` candidates left.
+const BITS_PER_GUESS: f64 = 4.0;
+
+/// How many bits a guess's chance of being the answer is worth under
+/// [`Scoring::Combined`] unless another weight is given.
+pub const ANSWER_WEIGHT: f64 = 2.0;
+
+/// What a greedy strategy maximizes when ranking guesses.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Scoring {
+    /// Expected information from the feedback.
+    #[default]
+    Information,
+    /// Fewest candidates expected to remain after the feedback.
+    Remaining,
+    /// Expected information plus the chance of being the answer, weighted
+    /// as this many bits. The higher the weight, the more a likely answer
+    /// is preferred over a slightly more informative probe.
+    Combined(f64),
+}
+
+impl Scoring {
+    /// Look up a scoring by name. The combined scoring takes its weight as a
+    /// suffix, as in `combined:3`, or uses [`ANSWER_WEIGHT`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.split_once(':') {
+            Some(("combined", weight)) => match weight.parse() {
+                Ok(weight) if weight >= 0.0 => Some(Scoring::Combined(weight)),
+                _ => None,
+            },
+            Some(_) => None,
+            None => match name {
+                "information" | "info" => Some(Scoring::Information),
+                "remaining" => Some(Scoring::Remaining),
+                "combined" => Some(Scoring::Combined(ANSWER_WEIGHT)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Score a suggestion; higher is better.
+    ///
+    /// Neither measure alone can separate the final two candidates, so the
+    /// answer probability breaks ties, and also favours a candidate over a
+    /// probe word that can't win outright.
+    #[inline]
+    pub fn score(self, suggestion: &Suggestion) -> f64 {
+        match self {
+            Scoring::Information => suggestion.information + suggestion.probability,
+            // Guessing the answer leaves nothing to find, so that case isn't
+            // counted as a remaining candidate.
+            Scoring::Remaining => -(suggestion.expected_remaining - suggestion.probability),
+            Scoring::Combined(weight) => suggestion.information + weight * suggestion.probability,
+        }
+    }
+}
+
+/// A candidate next guess, with the numbers behind it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion<'a> {
+    pub word: &'a str,
+    /// Expected information from the feedback, in bits.
+    pub information: f64,
+    /// Expected number of candidates left after the feedback.
+    pub expected_remaining: f64,
+    /// Probability that the guess is the answer.
+    pub probability: f64,
+    /// Expected number of guesses to find the answer if this one is played,
+    /// counting it, with the rest estimated from how many candidates each
+    /// feedback leaves by [`guesses_left`].
+    pub expected_guesses: f64,
+}
+
+impl<'a> Suggestion<'a> {
+    /// Score playing `word` against `candidates`, whose probabilities are
+    /// given by `distribution`.
+    pub fn evaluate(word: &'a str, candidates: &[&str], distribution: &[f64]) -> Self {
+        Self::from_codes(
+            word,
+            Correctness::compute_many(word, candidates),
+            distribution,
+        )
+    }
+
+    /// [`evaluate`](Self::evaluate) against candidates packed with
+    /// [`Packed::new`], for scoring many guesses against the same ones.
+    pub fn evaluate_packed(word: &'a str, candidates: &Packed, distribution: &[f64]) -> Self {
+        let answers = candidates.iter().map(|answer| &answer[..]);
+        Self::from_codes(
+            word,
+            Correctness::compute_many_bytes(word.as_bytes(), answers),
+            distribution,
+        )
+    }
+
+    /// Score playing `word` in a variant that gives feedback of type `F`.
+    pub fn evaluate_with<F: Feedback>(
+        word: &'a str,
+        candidates: &[&str],
+        distribution: &[f64],
+    ) -> Self {
+        Self::from_patterns(word, F::compute_batch(word, candidates), distribution)
+    }
+
+    /// [`from_patterns`](Self::from_patterns) for patterns given by their
+    /// codes, which can be counted in an array rather than a map.
+    fn from_codes(word: &'a str, codes: Vec<u8>, distribution: &[f64]) -> Self {
+        let solved = Correctness::encode([Correctness::Correct; 5]);
+        let mut buckets = [(0.0, 0usize); PATTERNS];
+        let mut probability = 0.0;
+
+        for (code, &p) in codes.into_iter().zip(distribution) {
+            let bucket = &mut buckets[code as usize];
+            bucket.0 += p;
+            bucket.1 += 1;
+            if code == solved {
+                probability = p;
+            }
+        }
+
+        Self::from_buckets(word, buckets.iter().copied(), probability)
+    }
+
+    /// Score playing `word` given the feedback it gets from each candidate.
+    fn from_patterns<F: Feedback>(word: &'a str, patterns: Vec<F>, distribution: &[f64]) -> Self {
+        let mut buckets: HashMap<F, (f64, usize)> = HashMap::new();
+        let mut probability = 0.0;
+
+        for (pattern, &p) in patterns.into_iter().zip(distribution) {
+            let bucket = buckets.entry(pattern).or_default();
+            bucket.0 += p;
+            bucket.1 += 1;
+            if pattern.is_solved() {
+                probability = p;
+            }
+        }
+
+        Self::from_buckets(word, buckets.into_values(), probability)
+    }
+
+    /// Score playing `word` given the total probability and count of the
+    /// candidates giving each feedback, and its own chance of winning.
+    fn from_buckets(
+        word: &'a str,
+        buckets: impl Iterator<Item = (f64, usize)>,
+        probability: f64,
+    ) -> Self {
+        let mut information = 0.0;
+        let mut expected_remaining = 0.0;
+        let mut expected_guesses = 1.0;
+        for (p, count) in buckets {
+            if p > 0.0 {
+                information -= p * p.log2();
+            }
+            expected_remaining += p * count as f64;
+            expected_guesses += p * guesses_left(count);
+        }
+        // Winning outright leaves nothing to find.
+        expected_guesses -= probability * guesses_left(1);
+
+        Self {
+            word,
+            information,
+            expected_remaining,
+            probability,
+            expected_guesses,
+        }
+    }
+}
+
+/// About how many guesses it takes to find the answer among `n` candidates.
+pub fn guesses_left(n: usize) -> f64 {
+    match n {
+        0 => 0.0,
+        n => 1.0 + (n as f64).log2() / BITS_PER_GUESS,
+    }
+}
+
+/// What playing `word` risks, for the objectives that look past the average:
+/// the most candidates any one feedback leaves, and the chance of not finding
+/// the answer in `turns` more guesses afterwards. By [`guesses_left`], that
+/// many guesses can find the answer among at most `2^(4 × (turns - 1))`
+/// candidates; past that, the answer is taken to be found only as often as
+/// it's among that many of them.
+pub fn risk(word: &str, candidates: &Packed, distribution: &[f64], turns: usize) -> (usize, f64) {
+    let answers = candidates.iter().map(|answer| &answer[..]);
+    let codes = Correctness::compute_many_bytes(word.as_bytes(), answers);
+    let mut buckets = [(0.0, 0usize); PATTERNS];
+    for (code, &p) in codes.into_iter().zip(distribution) {
+        let bucket = &mut buckets[code as usize];
+        bucket.0 += p;
+        bucket.1 += 1;
+    }
+
+    let capacity = match turns {
+        0 => 0.0,
+        turns => (BITS_PER_GUESS * (turns - 1) as f64).exp2(),
+    };
+    // Winning outright can't fail.
+    let solved = Correctness::encode([Correctness::Correct; 5]) as usize;
+    let largest = buckets.iter().map(|&(_, count)| count).max().unwrap_or(0);
+    let failure = buckets
+        .iter()
+        .enumerate()
+        .filter(|&(code, &(_, count))| code != solved && count > 0)
+        .map(|(_, &(p, count))| p * (1.0 - capacity / count as f64).max(0.0))
+        .sum();
+    (largest, failure)
+}
+
+/// The `k` best next guesses, best first.
+pub fn suggest<'a>(
+    guesses: &[&'a str],
+    candidates: &[&str],
+    model: &AnswerModel,
+    scoring: Scoring,
+    k: usize,
+) -> Vec<Suggestion<'a>> {
+    let distribution = model.distribution(candidates);
+    let work = guesses.len() * candidates.len();
+
+    let packed = Packed::new(candidates);
+    let mut suggestions = parallel::map(guesses, work, |&word| {
+        Suggestion::evaluate_packed(word, &packed, &distribution)
+    });
+    // A stable sort keeps the guess list's order among equal scores.
+    suggestions.sort_by(|a, b| scoring.score(b).total_cmp(&scoring.score(a)));
+    suggestions.truncate(k);
+    suggestions
+}
+
+/// How many alternatives an [`Explanation`] lists.
+pub const EXPLAINED: usize = 3;
+
+/// Why a guess is worth playing: its numbers beside those of the best
+/// alternatives, as `--explain` shows them. A strategy that doesn't score
+/// guesses greedily may choose one the scoring ranks below them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation<'a> {
+    pub chosen: Suggestion<'a>,
+    /// The most candidates the chosen guess can leave.
+    pub worst: usize,
+    /// How many candidates there were to tell apart.
+    pub candidates: usize,
+    /// The best other guesses by `scoring`, best first, with the most
+    /// candidates each can leave.
+    pub alternatives: Vec<(Suggestion<'a>, usize)>,
+    pub scoring: Scoring,
+}
+
+/// Explain playing `word` against `candidates`, comparing it with the best
+/// `k` others of `guesses` by `scoring`.
+pub fn explain<'a>(
+    word: &'a str,
+    guesses: &[&'a str],
+    candidates: &[&str],
+    model: &AnswerModel,
+    scoring: Scoring,
+    k: usize,
+) -> Explanation<'a> {
+    let distribution = model.distribution(candidates);
+    let alternatives = suggest(guesses, candidates, model, scoring, k + 1)
+        .into_iter()
+        .filter(|s| s.word != word)
+        .take(k)
+        .map(|s| {
+            let worst = worst(s.word, candidates);
+            (s, worst)
+        })
+        .collect();
+    Explanation {
+        chosen: Suggestion::evaluate(word, candidates, &distribution),
+        worst: worst(word, candidates),
+        candidates: candidates.len(),
+        alternatives,
+        scoring,
+    }
+}
+
+/// The most of `candidates` that can give the same feedback to `word`.
+fn worst(word: &str, candidates: &[&str]) -> usize {
+    let mut buckets = [0usize; PATTERNS];
+    for code in Correctness::compute_many(word, candidates) {
+        buckets[code as usize] += 1;
+    }
+    buckets.into_iter().max().unwrap_or(0)
+}
+
+/// One feedback a guess could get, and where it would leave the game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome<'a> {
+    pub mask: [Correctness; 5],
+    /// The chance of this feedback, from the candidates that give it.
+    pub probability: f64,
+    /// The candidates that would be left.
+    pub candidates: Vec<&'a str>,
+    /// Everything that would be known about the answer.
+    pub knowledge: KnowledgeState,
+}
+
+/// What playing a guess would do, without playing it: its numbers, and every
+/// feedback it could get, likeliest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation<'a> {
+    pub summary: Suggestion<'a>,
+    pub outcomes: Vec<Outcome<'a>>,
+}
+
+/// What playing `word` would do against `candidates`, with `knowledge` what's
+/// known before it. Ties in probability keep the order of the first candidate
+/// giving each feedback.
+pub fn simulate<'a>(
+    word: &'a str,
+    knowledge: &KnowledgeState,
+    candidates: &[&'a str],
+    model: &AnswerModel,
+) -> Simulation<'a> {
+    let distribution = model.distribution(candidates);
+    let mut outcomes: Vec<Outcome<'a>> = Vec::new();
+    let mut index = [usize::MAX; PATTERNS];
+    let codes = Correctness::compute_many(word, candidates);
+    for ((&answer, code), &p) in candidates.iter().zip(codes).zip(&distribution) {
+        let slot = &mut index[code as usize];
+        if *slot == usize::MAX {
+            *slot = outcomes.len();
+            let mut after = knowledge.clone();
+            after.add(&Guess::check(answer, word));
+            outcomes.push(Outcome {
+                mask: Correctness::decode(code).expect("a computed code decodes"),
+                probability: 0.0,
+                candidates: Vec::new(),
+                knowledge: after,
+            });
+        }
+        let outcome = &mut outcomes[*slot];
+        outcome.probability += p;
+        outcome.candidates.push(answer);
+    }
+    outcomes.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+
+    Simulation {
+        summary: Suggestion::evaluate(word, candidates, &distribution),
+        outcomes,
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    /// The chosen guess's line, then a line for each alternative.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |f: &mut fmt::Formatter<'_>, s: &Suggestion, worst: usize| {
+            writeln!(
+                f,
+                "{:.2} score, {:.2} bits, at worst {worst} left, {:.1}% the answer, \
+                 {:.2} guesses expected",
+                self.scoring.score(s),
+                s.information,
+                s.probability * 100.0,
+                s.expected_guesses
+            )
+        };
+        write!(
+            f,
+            "{}: of {} candidates, ",
+            self.chosen.word, self.candidates
+        )?;
+        line(f, &self.chosen, self.worst)?;
+        for (s, worst) in &self.alternatives {
+            write!(f, "  vs {}: ", s.word)?;
+            line(f, s, *worst)?;
+        }
+        Ok(())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn evaluate() {
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let distribution = [0.25; 4];
+
+        let chomp = Suggestion::evaluate("chomp", &candidates, &distribution);
+        assert_eq!(chomp.information, 2.0);
+        assert_eq!(chomp.expected_remaining, 1.0);
+        assert_eq!(chomp.probability, 0.0);
+        // Every candidate is told apart, leaving one more guess.
+        assert_eq!(chomp.expected_guesses, 2.0);
+
+        // One bucket of three and one of one.
+        let hatch = Suggestion::evaluate("hatch", &candidates, &distribution);
+        assert_eq!(hatch.expected_remaining, 0.25 + 0.75 * 3.0);
+        assert_eq!(hatch.probability, 0.25);
+        assert_eq!(hatch.expected_guesses, 1.0 + 0.75 * guesses_left(3));
+    }
+
+    #[test]
+    fn top_k() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let guesses = ["hatch", "chomp", "catch"];
+
+        let words: Vec<&str> = suggest(&guesses, &candidates, &model, Scoring::Information, 2)
+            .iter()
+            .map(|s| s.word)
+            .collect();
+        assert_eq!(words, vec!["chomp", "hatch"]);
+    }
+
+    #[test]
+    fn explained() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let guesses = ["hatch", "chomp", "catch"];
+
+        let explanation = explain(
+            "hatch",
+            &guesses,
+            &candidates,
+            &model,
+            Scoring::Information,
+            1,
+        );
+        assert_eq!(explanation.candidates, 4);
+        assert_eq!(explanation.worst, 3);
+        assert_eq!(explanation.chosen.probability, 0.25);
+        let (chomp, worst) = &explanation.alternatives[0];
+        assert_eq!((chomp.word, *worst), ("chomp", 1));
+        assert_eq!(explanation.alternatives.len(), 1);
+
+        let text = explanation.to_string();
+        assert!(text.starts_with("hatch: of 4 candidates, "));
+        assert!(text.contains("at worst 3 left, 25.0% the answer, 2.05 guesses expected\n"));
+        assert!(text.contains("  vs chomp: 2.00 score, 2.00 bits, at worst 1 left"));
+    }
+
+    #[test]
+    fn risks() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let packed = Packed::new(&candidates);
+        let distribution = model.distribution(&candidates);
+
+        // "hatch" leaves the other three together, and one guess can only
+        // find one of them; "chomp" tells them all apart.
+        assert_eq!(risk("hatch", &packed, &distribution, 1), (3, 0.5));
+        assert_eq!(risk("chomp", &packed, &distribution, 1), (1, 0.0));
+        // With no guesses after it, only the answer itself is safe.
+        assert_eq!(risk("chomp", &packed, &distribution, 0), (1, 1.0));
+        assert_eq!(risk("hatch", &packed, &distribution, 0), (3, 0.75));
+    }
+
+    #[test]
+    fn objectives_disagree() {
+        // One guess is more informative; the other leaves fewer candidates.
+        let skewed = Suggestion {
+            word: "x",
+            information: 1.0,
+            expected_remaining: 2.0,
+            probability: 0.0,
+            expected_guesses: 0.0,
+        };
+        let even = Suggestion {
+            word: "y",
+            information: 0.9,
+            expected_remaining: 1.5,
+            probability: 0.0,
+            expected_guesses: 0.0,
+        };
+
+        assert!(Scoring::Information.score(&skewed) > Scoring::Information.score(&even));
+        assert!(Scoring::Remaining.score(&skewed) < Scoring::Remaining.score(&even));
+    }
+
+    #[test]
+    fn combined() {
+        let probe = Suggestion {
+            word: "chomp",
+            information: 2.0,
+            expected_remaining: 1.0,
+            probability: 0.0,
+            expected_guesses: 0.0,
+        };
+        let candidate = Suggestion {
+            word: "hatch",
+            information: 1.8,
+            expected_remaining: 1.3,
+            probability: 0.25,
+            expected_guesses: 0.0,
+        };
+
+        // A weight of one is the information scoring's own tie-breaker.
+        let information = Scoring::Information;
+        assert_eq!(
+            Scoring::Combined(1.0).score(&candidate),
+            information.score(&candidate)
+        );
+        assert!(Scoring::Combined(0.5).score(&probe) > Scoring::Combined(0.5).score(&candidate));
+        assert!(Scoring::Combined(2.0).score(&probe) < Scoring::Combined(2.0).score(&candidate));
+
+        assert_eq!(
+            Scoring::by_name("combined"),
+            Some(Scoring::Combined(ANSWER_WEIGHT))
+        );
+        assert_eq!(
+            Scoring::by_name("combined:3.5"),
+            Some(Scoring::Combined(3.5))
+        );
+        assert_eq!(Scoring::by_name("combined:-1"), None);
+        assert_eq!(Scoring::by_name("remaining:2"), None);
+    }
+
+    #[test]
+    fn simulated() {
+        let model = AnswerModel::new([("hatch", 4), ("catch", 3), ("match", 2), ("patch", 1)]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let knowledge = KnowledgeState::default();
+
+        let simulation = simulate("hatch", &knowledge, &candidates, &model);
+        assert_eq!(simulation.summary.probability, 0.4);
+        assert_eq!(simulation.outcomes.len(), 2);
+        // The other three share one feedback, together likelier than hatch.
+        let others = &simulation.outcomes[0];
+        assert_eq!(others.candidates, ["catch", "match", "patch"]);
+        assert!((others.probability - 0.6).abs() < 1e-9);
+        assert!(others.knowledge.allows("catch") && !others.knowledge.allows("hatch"));
+        assert_eq!(simulation.outcomes[1].mask, [Correctness::Correct; 5]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+/// One daily puzzle solved unattended, as kept in the history store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub number: usize,
+    pub answer: String,
+    /// The guesses taken, if the answer was found within the limit.
+    pub guesses: Option<usize>,
+    pub words: Vec<String>,
+}
+
+impl Entry {
+    /// Parse a line written by the [`Display`](fmt::Display) impl.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let number = fields.next()?.parse().ok()?;
+        let answer = fields.next()?.to_string();
+        let guesses = match fields.next()? {
+            "-" => None,
+            n => Some(n.parse().ok()?),
+        };
+
+        Some(Self {
+            number,
+            answer,
+            guesses,
+            words: fields.map(str::to_string).collect(),
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    /// Renders the entry as one line: the puzzle number, the answer, the
+    /// guesses taken or `-`, then each guess.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.number, self.answer)?;
+        match self.guesses {
+            Some(n) => write!(f, "{n}")?,
+            None => write!(f, "-")?,
+        }
+        for word in &self.words {
+            write!(f, " {word}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every entry in a history store, in the order recorded. Lines that don't
+/// parse are skipped.
+pub fn load(r: impl BufRead) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for line in r.lines() {
+        entries.extend(Entry::parse(&line?));
+    }
+    Ok(entries)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let entry = Entry {
+            number: 1944,
+            answer: "plump".to_string(),
+            guesses: Some(2),
+            words: vec!["salet".to_string(), "plump".to_string()],
+        };
+        assert_eq!(entry.to_string(), "1944 plump 2 salet plump");
+        assert_eq!(Entry::parse(&entry.to_string()), Some(entry));
+
+        let failed = Entry::parse("7 cigar - salet").unwrap();
+        assert_eq!(failed.guesses, None);
+        assert_eq!(failed.to_string(), "7 cigar - salet");
+    }
+
+    #[test]
+    fn skips_junk() {
+        let entries = load(&b"1 rebut 3 salet brute rebut\n\nnope\n2 sissy x\n"[..]).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].answer, "rebut");
+    }
+}
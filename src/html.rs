@@ -0,0 +1,190 @@
+use std::fmt::Write;
+
+use crate::{
+    guesser::{Correctness, Guess},
+    pattern,
+    report::{Outcome, Report, HARDEST, MAX_GUESSES},
+};
+
+/// The page's look, after the server's UI, with the game's colors for tiles
+/// and a bar per distribution row.
+const STYLE: &str = "
+  body { font-family: system-ui, sans-serif; max-width: 52rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+  h1 { font-size: 1.4rem; }
+  h2 { font-size: 1.1rem; margin-top: 2rem; }
+  table { border-collapse: collapse; }
+  td, th { padding: 0.15rem 0.6rem; text-align: right; }
+  td:first-child, th:first-child { text-align: left; }
+  td.words { text-align: left; font-family: monospace; }
+  .bar { background: #6aaa64; color: white; padding: 0 0.3rem; min-width: 1.5rem; text-align: right; }
+  .bar.failed { background: #787c7e; }
+  details { margin: 0.2rem 0; }
+  summary { cursor: pointer; font-family: monospace; }
+  .row { display: flex; gap: 0.2rem; margin: 0.2rem 0 0.2rem 1rem; align-items: center; }
+  .row span { margin-left: 0.5rem; color: #666; font-size: 0.9rem; }
+  b { width: 1.6rem; height: 1.6rem; display: flex; align-items: center; justify-content: center;
+      color: white; background: #787c7e; border-radius: 0.15rem; }
+  b.g { background: #6aaa64; }
+  b.y { background: #c9b458; }
+  .contrast b.g, .contrast .bar { background: #f5793a; }
+  .contrast b.y { background: #85c0f9; }
+";
+
+/// A whole benchmark run as one HTML page with nothing to fetch: the summary,
+/// the guess distribution, the hardest answers, and how every answer was
+/// solved, each behind a fold. `label` gives the text of each tile, so
+/// multi-letter tiles show as typed.
+pub fn report(strategy: &str, report: &Report, label: impl Fn(u8) -> String) -> String {
+    let word = |word: &str| escape(&word.bytes().map(&label).collect::<String>());
+    let mut page = String::new();
+    let contrast = if pattern::palette().high_contrast {
+        " class=\"contrast\""
+    } else {
+        ""
+    };
+    let _ = write!(
+        page,
+        "<!doctype html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>wordle benchmark: {strategy}</title>\n<style>{STYLE}</style>\n</head>\n\
+         <body{contrast}>\n<h1>wordle benchmark: {strategy}</h1>\n",
+        strategy = escape(strategy),
+    );
+
+    let outcomes = report.outcomes();
+    let worst = report
+        .worst_case()
+        .map_or_else(|| "none found".to_string(), |n| n.to_string());
+    let per_solve = match outcomes.len() {
+        0 => "-".to_string(),
+        n => format!("{:?}", report.elapsed() / n as u32),
+    };
+    page.push_str("<table>\n");
+    for (name, value) in [
+        ("answers", outcomes.len().to_string()),
+        ("average score", format!("{:.4}", report.average())),
+        ("missed", report.failures().count().to_string()),
+        ("worst case", worst),
+        ("wall clock", format!("{:?}", report.elapsed())),
+        ("per solve", per_solve),
+    ] {
+        let _ = writeln!(page, "<tr><th>{name}</th><td>{value}</td></tr>");
+    }
+    page.push_str("</table>\n");
+
+    page.push_str("<h2>guess distribution</h2>\n<table>\n");
+    let distribution = report.distribution();
+    let most = distribution.iter().copied().max().unwrap_or(0).max(1);
+    for (index, &count) in distribution.iter().enumerate() {
+        let (name, class) = match index {
+            MAX_GUESSES => ("failed".to_string(), "bar failed"),
+            _ => ((index + 1).to_string(), "bar"),
+        };
+        let width = count * 100 / most;
+        let _ = writeln!(
+            page,
+            "<tr><th>{name}</th><td style=\"width: 40rem\"><div class=\"{class}\" \
+             style=\"width: {width}%\">{count}</div></td></tr>"
+        );
+    }
+    page.push_str("</table>\n");
+
+    page.push_str("<h2>hardest answers</h2>\n<table>\n");
+    page.push_str("<tr><th>answer</th><th>guesses</th><th>path</th></tr>\n");
+    for outcome in report.hardest(HARDEST) {
+        let path: Vec<String> = outcome.result.path.iter().map(|g| word(g.word())).collect();
+        let _ = writeln!(
+            page,
+            "<tr><td>{}</td><td>{}</td><td class=\"words\">{}</td></tr>",
+            word(outcome.answer),
+            guesses(outcome),
+            path.join(" ")
+        );
+    }
+    page.push_str("</table>\n");
+
+    page.push_str("<h2>every answer</h2>\n");
+    for outcome in outcomes {
+        let _ = writeln!(
+            page,
+            "<details><summary>{} ({})</summary>",
+            word(outcome.answer),
+            guesses(outcome)
+        );
+        for (guess, left) in outcome.result.path.iter().zip(&outcome.result.remaining) {
+            let _ = writeln!(
+                page,
+                "<div class=\"row\">{}<span>{left} left</span></div>",
+                tiles(guess, &label)
+            );
+        }
+        page.push_str("</details>\n");
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+/// The guesses an answer took, or `-` if it was never found.
+fn guesses(outcome: &Outcome) -> String {
+    outcome
+        .guesses()
+        .map_or_else(|| "-".to_string(), |n| n.to_string())
+}
+
+/// A guess as a row of colored tiles.
+fn tiles(guess: &Guess, label: impl Fn(u8) -> String) -> String {
+    guess
+        .word()
+        .bytes()
+        .zip(guess.mask())
+        .map(|(symbol, c)| {
+            let class = match c {
+                Correctness::Correct => " class=\"g\"",
+                Correctness::Misplaced => " class=\"y\"",
+                Correctness::Wrong => "",
+            };
+            format!("<b{class}>{}</b>", escape(&label(symbol).to_uppercase()))
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::outcome;
+
+    #[test]
+    fn page() {
+        let mut outcome = outcome("cigar", Some(2));
+        outcome.result.path[0] = Guess::check("cigar", "raise");
+        outcome.result.remaining[0] = 12;
+        let mut report = Report::default();
+        report.push(outcome);
+        let page = super::report("a<b", &report, |symbol| char::from(symbol).to_string());
+
+        assert!(page.starts_with("<!doctype html>"));
+        assert!(page.contains("<title>wordle benchmark: a&lt;b</title>"));
+        assert!(page.contains("<tr><th>average score</th><td>2.0000</td></tr>"));
+        assert!(page.contains("<details><summary>cigar (2)</summary>"));
+        // "raise": the r, a, and i are in the answer, but elsewhere.
+        assert!(page.contains(
+            "<div class=\"row\"><b class=\"y\">R</b><b class=\"y\">A</b><b class=\"y\">I</b>\
+             <b>S</b><b>E</b><span>12 left</span></div>"
+        ));
+        assert!(page.ends_with("</html>\n"));
+    }
+}
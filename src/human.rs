@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead},
+};
+
+use crate::report::Report;
+
+/// How many guesses people took on average for each answer, from a dataset
+/// such as the daily game's published statistics.
+#[derive(Clone, Debug, Default)]
+pub struct HumanScores {
+    averages: HashMap<String, f64>,
+}
+
+impl HumanScores {
+    /// Read a CSV of `word,average` lines. A header line, blank lines, and
+    /// any columns past the second are skipped. A word listed twice keeps its
+    /// last average.
+    pub fn load(r: impl BufRead) -> io::Result<Self> {
+        let mut scores = Self::default();
+        for (index, line) in r.lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split(',').map(|field| field.trim().trim_matches('"'));
+            let (Some(word), Some(average)) = (fields.next(), fields.next()) else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(invalid(&line));
+            };
+            match average.parse::<f64>() {
+                Ok(average) if average.is_finite() => {
+                    scores.averages.insert(word.to_lowercase(), average);
+                },
+                Err(_) if index == 0 => {},
+                _ => return Err(invalid(&line)),
+            }
+        }
+        Ok(scores)
+    }
+
+    /// The average guesses people took for `word`, if the data has it.
+    pub fn average(&self, word: &str) -> Option<f64> {
+        self.averages.get(word).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.averages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.averages.is_empty()
+    }
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad human score line: {line}"),
+    )
+}
+
+/// An answer both the solver and people played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair {
+    pub word: String,
+    /// The solver's guesses, scored as
+    /// [`Outcome::score`](crate::report::Outcome::score) does.
+    pub solver: usize,
+    /// People's average guesses.
+    pub human: f64,
+    /// How much harder the word was for the solver than for people, in
+    /// standard deviations of each side's scores: positive where the solver
+    /// struggled and people didn't.
+    pub disagreement: f64,
+}
+
+/// How the solver's sense of which answers are hard matches people's.
+#[derive(Clone, Debug, Default)]
+pub struct Comparison {
+    /// Every answer in both, the solver's hardest relative to people's
+    /// first.
+    pub pairs: Vec<Pair>,
+    /// Pearson's correlation between the two scores.
+    pub correlation: f64,
+    /// Spearman's correlation, of the scores' ranks, which doesn't mind the
+    /// solver's scores being whole numbers.
+    pub rank_correlation: f64,
+}
+
+/// Compare the answers in `report`, shown by `decode`, with how people did
+/// on them.
+pub fn compare(
+    report: &Report,
+    human: &HumanScores,
+    decode: impl Fn(&str) -> String,
+) -> Comparison {
+    let mut pairs: Vec<Pair> = report
+        .outcomes()
+        .iter()
+        .filter_map(|outcome| {
+            let word = decode(outcome.answer);
+            let human = human.average(&word)?;
+            Some(Pair {
+                word,
+                solver: outcome.score(),
+                human,
+                disagreement: 0.0,
+            })
+        })
+        .collect();
+    if pairs.is_empty() {
+        return Comparison::default();
+    }
+
+    let solver: Vec<f64> = pairs.iter().map(|pair| pair.solver as f64).collect();
+    let human: Vec<f64> = pairs.iter().map(|pair| pair.human).collect();
+    let (solver_z, human_z) = (standardized(&solver), standardized(&human));
+    for (pair, (s, h)) in pairs.iter_mut().zip(solver_z.iter().zip(&human_z)) {
+        pair.disagreement = s - h;
+    }
+    pairs.sort_by(|a, b| {
+        b.disagreement
+            .total_cmp(&a.disagreement)
+            .then_with(|| a.word.cmp(&b.word))
+    });
+
+    Comparison {
+        correlation: pearson(&solver, &human),
+        rank_correlation: pearson(&ranks(&solver), &ranks(&human)),
+        pairs,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// `values` less their mean, over their standard deviation, or all zero if
+/// they don't vary.
+fn standardized(values: &[f64]) -> Vec<f64> {
+    let mean = mean(values);
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    let deviation = variance.sqrt();
+    values
+        .iter()
+        .map(|v| {
+            if deviation > 0.0 {
+                (v - mean) / deviation
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// The correlation of `a` and `b`, or zero if either doesn't vary.
+fn pearson(a: &[f64], b: &[f64]) -> f64 {
+    let (a, b) = (standardized(a), standardized(b));
+    a.iter().zip(&b).map(|(a, b)| a * b).sum::<f64>() / a.len() as f64
+}
+
+/// The rank of each of `values`, from one, with tied values sharing the
+/// average of the ranks they span.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&i| values[i] == values[order[start]])
+                .count();
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Print the correlations and the `top` answers each side found hardest
+/// compared with the other.
+pub fn print(comparison: &Comparison, answers: usize, top: usize) {
+    let pairs = &comparison.pairs;
+    println!("{} of {answers} answers have human scores", pairs.len());
+    if pairs.is_empty() {
+        return;
+    }
+    println!("correlation: {:.3}", comparison.correlation);
+    println!("rank correlation: {:.3}", comparison.rank_correlation);
+
+    let line = |pair: &Pair| {
+        println!(
+            "  {}: solver {}, people {:.2} ({:+.2})",
+            pair.word, pair.solver, pair.human, pair.disagreement
+        );
+    };
+    println!();
+    println!("harder for the solver:");
+    pairs
+        .iter()
+        .take(top)
+        .filter(|pair| pair.disagreement > 0.0)
+        .for_each(line);
+    println!("harder for people:");
+    pairs
+        .iter()
+        .rev()
+        .take(top)
+        .filter(|pair| pair.disagreement < 0.0)
+        .for_each(line);
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::outcome;
+
+    #[test]
+    fn load() {
+        let csv = "word,average\ncigar,3.8\n\n\"REBUT\",4.2,extra\n";
+        let scores = HumanScores::load(csv.as_bytes()).unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores.average("cigar"), Some(3.8));
+        assert_eq!(scores.average("rebut"), Some(4.2));
+        assert!(HumanScores::load("cigar,3.8\nrebut,lots\n".as_bytes()).is_err());
+        assert!(HumanScores::load("cigar,3.8\nrebut\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn correlations() {
+        assert_eq!(ranks(&[3.0, 1.0, 3.0, 2.0]), [3.5, 1.0, 3.5, 2.0]);
+        assert!((pearson(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]) - 1.0).abs() < 1e-9);
+        assert!((pearson(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]) + 1.0).abs() < 1e-9);
+        assert_eq!(pearson(&[1.0, 1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn disagreements() {
+        let mut report = Report::default();
+        for (answer, guesses) in [("cigar", 2), ("rebut", 3), ("sissy", 4), ("humph", 5)] {
+            report.push(outcome(answer, Some(guesses)));
+        }
+        // People agree on all but humph, which they found easiest.
+        let human = HumanScores::load("cigar,3\nrebut,4\nsissy,5\nhumph,2\n".as_bytes()).unwrap();
+        let comparison = compare(&report, &human, str::to_string);
+        assert_eq!(comparison.pairs.len(), 4);
+        assert_eq!(comparison.pairs[0].word, "humph");
+        assert!(comparison.pairs[0].disagreement > 0.0);
+        assert!(comparison.correlation < 0.5);
+        assert!(comparison.rank_correlation < 0.5);
+    }
+}
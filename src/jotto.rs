@@ -0,0 +1,198 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    io::{BufRead, Write},
+};
+
+use crate::{
+    feedback::Feedback,
+    guesser::Correctness,
+    locale::{Language, Message},
+    normalize,
+};
+
+/// The feedback in Jotto, or Wordle played Mastermind-style: how many of the
+/// guess's letters are in the right place and how many more are in the
+/// answer elsewhere, without saying which.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub placed: u8,
+    pub elsewhere: u8,
+}
+
+impl Score {
+    /// The score for playing `word` when the answer is `answer`, counting
+    /// repeated letters as Wordle's colors do.
+    pub fn compute(answer: &str, word: &str) -> Self {
+        Self::from_mask(Correctness::compute(answer, word))
+    }
+
+    fn from_mask(mask: [Correctness; 5]) -> Self {
+        let count = |c| mask.iter().filter(|&&m| m == c).count() as u8;
+        Self {
+            placed: count(Correctness::Correct),
+            elsewhere: count(Correctness::Misplaced),
+        }
+    }
+
+    /// A score as written by [`Display`](fmt::Display), as `2/1`, or with the
+    /// counts apart, as `2 1` or `21`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let digits: Vec<u8> = text
+            .chars()
+            .filter(|c| !matches!(c, '/' | ',' | ' '))
+            .map(|c| c.to_digit(10).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        let [placed, elsewhere] = digits[..] else {
+            return None;
+        };
+        (placed + elsewhere <= 5).then_some(Self { placed, elsewhere })
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.placed, self.elsewhere)
+    }
+}
+
+impl Feedback for Score {
+    #[inline]
+    fn compute(answer: &str, word: &str) -> Self {
+        Score::compute(answer, word)
+    }
+
+    fn compute_batch(word: &str, answers: &[&str]) -> Vec<Self> {
+        Correctness::compute_batch(word, answers)
+            .into_iter()
+            .map(Self::from_mask)
+            .collect()
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.placed == 5
+    }
+}
+
+/// Hosts a game of Jotto for a human: reads their guesses and answers each
+/// with its score alone.
+pub struct Host<'a, R, W> {
+    answer: &'a str,
+    words: HashSet<&'a str>,
+    history: Vec<(&'a str, Score)>,
+    language: Language,
+    input: R,
+    output: W,
+}
+
+impl<'a, R: BufRead, W: Write> Host<'a, R, W> {
+    /// Host a game for `answer`, accepting it or any of `words` as a guess.
+    pub fn new(answer: &'a str, words: &[&'a str], input: R, output: W) -> Self {
+        Self {
+            answer,
+            words: words.iter().copied().chain([answer]).collect(),
+            history: Vec::new(),
+            language: Language::default(),
+            input,
+            output,
+        }
+    }
+
+    /// Prompt and explain in `language`.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Every guess so far, with its score, in order.
+    pub fn history(&self) -> &[(&'a str, Score)] {
+        &self.history
+    }
+
+    /// Play until the answer is guessed, returning how many guesses that
+    /// took, or `None` if the input ends first.
+    pub fn play(&mut self) -> Option<usize> {
+        loop {
+            let prompt = self.language.template(Message::GuessPrompt);
+            write!(self.output, "{prompt}").ok()?;
+            self.output.flush().ok()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+
+            let word = normalize::input(&line).to_lowercase();
+            let Some(&word) = self.words.get(word.as_str()) else {
+                let reason = match word.chars().count() {
+                    0 => self.language.template(Message::EmptyGuess).to_string(),
+                    _ => self.language.text(
+                        Message::Rejected,
+                        &[
+                            ("word", &word),
+                            ("reason", &self.language.template(Message::NotInList)),
+                        ],
+                    ),
+                };
+                writeln!(self.output, "{reason}").ok()?;
+                continue;
+            };
+
+            let score = Score::compute(self.answer, word);
+            self.history.push((word, score));
+            writeln!(self.output, "{word} {score}").ok()?;
+            if score.is_solved() {
+                return Some(self.history.len());
+            }
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::{feedback::Solver, model::AnswerModel};
+
+    #[test]
+    fn compute() {
+        assert_eq!(Score::compute("cigar", "cigar").to_string(), "5/0");
+        assert_eq!(Score::compute("cigar", "rebut").to_string(), "0/1");
+        // Only one of the guess's two l's counts, as in Wordle.
+        assert_eq!(Score::compute("lemon", "llama").to_string(), "1/1");
+        assert_eq!(Score::parse("1/2"), Some(Score::compute("cigar", "crane")));
+        assert_eq!(Score::parse("4 0"), Score::parse("40"));
+        assert_eq!(Score::parse("4/2"), None);
+        assert_eq!(Score::parse("1"), None);
+    }
+
+    #[test]
+    fn solves() {
+        let model = AnswerModel::new([]);
+        let candidates = ["catch", "hatch", "match", "cigar"];
+        let mut solver = Solver::<Score>::new(&candidates);
+        // Hatch scores the same against catch as against match.
+        assert_eq!(solver.apply("hatch", Score::parse("4/0").unwrap()), 2);
+        assert_eq!(
+            solver.solve("match", "catch", &candidates, &model, None),
+            Some(3)
+        );
+        assert_eq!(solver.candidates(), ["match"]);
+    }
+
+    #[test]
+    fn host() {
+        let words = ["catch", "hatch", "match"];
+        let input = "chomp\nHATCH\nmatch\n".as_bytes();
+        let mut output = Vec::new();
+        let mut host = Host::new("match", &words, input, &mut output);
+        assert_eq!(host.play(), Some(2));
+        assert_eq!(host.history()[0], ("hatch", Score::parse("4/0").unwrap()));
+        drop(host);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("chomp: not in word list\n"));
+        assert!(output.contains("hatch 4/0\n"));
+    }
+}
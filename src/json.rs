@@ -0,0 +1,362 @@
+use std::{error::Error, fmt};
+
+/// A JSON value, enough of one to save and load the files this crate writes.
+///
+/// Objects keep their keys in order, so what's written reads back the same.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The value under `key`, if this is an object that has one.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// The number, if it's a whole one that fits.
+    pub fn as_usize(&self) -> Option<usize> {
+        self.as_f64()
+            .filter(|n| n.fract() == 0.0 && *n >= 0.0 && *n <= usize::MAX as f64)
+            .map(|n| n as usize)
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl fmt::Display for Value {
+    /// Writes compact JSON, with no whitespace between tokens.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Number(n) if n.is_finite() => write!(f, "{n}"),
+            // JSON has no infinities or NaN.
+            Value::Number(_) => write!(f, "null"),
+            Value::String(s) => write!(f, "{}", quote(s)),
+            Value::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            },
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{value}", quote(key))?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+/// `text` as a quoted JSON string.
+pub fn quote(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Where a document stopped being valid JSON.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset of the first character that doesn't fit.
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON at byte {}", self.offset)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Read a single JSON value, with nothing but whitespace after it.
+pub fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error());
+    }
+    Ok(value)
+}
+
+struct Parser<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self) -> ParseError {
+        ParseError { offset: self.pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.text[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        match self.peek().ok_or_else(|| self.error())? {
+            b'n' => self.expect("null").map(|()| Value::Null),
+            b't' => self.expect("true").map(|()| Value::Bool(true)),
+            b'f' => self.expect("false").map(|()| Value::Bool(false)),
+            b'"' => self.string().map(Value::String),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        },
+                        _ => return Err(self.error()),
+                    }
+                }
+            },
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() != Some(b'"') {
+                        return Err(self.error());
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    self.expect(":")?;
+                    fields.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(fields));
+                        },
+                        _ => return Err(self.error()),
+                    }
+                }
+            },
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        self.text[start..self.pos]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| ParseError { offset: start })
+    }
+
+    /// Read a quoted string, starting at its opening quote.
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let c = rest.chars().next().ok_or_else(|| self.error())?;
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(s);
+                },
+                '\\' => {
+                    self.pos += 1;
+                    let escaped = match self.peek().ok_or_else(|| self.error())? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            self.pos += 1;
+                            let mut code = self.hex4()?;
+                            // A surrogate pair spells one character outside
+                            // the basic plane.
+                            if (0xd800..0xdc00).contains(&code) {
+                                self.expect("\\u")?;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(self.error());
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            s.push(char::from_u32(code).ok_or_else(|| self.error())?);
+                            continue;
+                        },
+                        _ => return Err(self.error()),
+                    };
+                    self.pos += 1;
+                    s.push(escaped);
+                },
+                c if c.is_control() => return Err(self.error()),
+                c => {
+                    self.pos += c.len_utf8();
+                    s.push(c);
+                },
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error())?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error())?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = Value::Object(vec![
+            ("word".to_string(), "cig\"ar\n".into()),
+            ("guesses".to_string(), Value::Number(3.0)),
+            ("average".to_string(), Value::Number(3.6145)),
+            (
+                "path".to_string(),
+                Value::Array(vec!["salet".into(), Value::Bool(true), Value::Null]),
+            ),
+            ("empty".to_string(), Value::Object(Vec::new())),
+        ]);
+        let text = value.to_string();
+        assert_eq!(
+            text,
+            r#"{"word":"cig\"ar\n","guesses":3,"average":3.6145,"path":["salet",true,null],"empty":{}}"#
+        );
+        assert_eq!(parse(&text), Ok(value));
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote("Wordle 2/6\n\n🟩"), r#""Wordle 2/6\n\n🟩""#);
+        assert_eq!(quote("a \"b\" \\ \t"), r#""a \"b\" \\ \u0009""#);
+    }
+
+    #[test]
+    fn reading() {
+        let value =
+            parse(" { \"a\" : [ 1 , -2.5e1 ] , \"b\":\"\\u00e9\\ud83d\\ude00\" } ").unwrap();
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap()[1].as_f64(),
+            Some(-25.0)
+        );
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap()[0].as_usize(),
+            Some(1)
+        );
+        assert_eq!(value.get("b").unwrap().as_str(), Some("é😀"));
+        assert_eq!(value.get("c"), None);
+
+        assert_eq!(parse("[1,]"), Err(ParseError { offset: 3 }));
+        assert_eq!(parse("{\"a\" 1}"), Err(ParseError { offset: 5 }));
+        assert_eq!(parse("\"open"), Err(ParseError { offset: 5 }));
+        assert_eq!(parse("true false"), Err(ParseError { offset: 5 }));
+    }
+}
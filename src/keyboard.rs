@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crossterm::style::Color;
+
+use crate::{
+    console,
+    guesser::{Correctness, Guess},
+};
+
+/// The rows of a QWERTY keyboard, as the game shows it.
+pub const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// What's known about a letter, from least to most.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Key {
+    #[default]
+    Unknown,
+    Absent,
+    /// In the answer, but not yet placed.
+    Present,
+    /// Known to be at some position in the answer.
+    Placed,
+}
+
+impl Key {
+    /// The text and background colors of a key.
+    pub fn colors(self) -> (Color, Color) {
+        match self.correctness() {
+            Some(c) => c.colors(),
+            None => (Color::Black, Color::Grey),
+        }
+    }
+
+    /// The tile a key is colored like, if anything is known about it.
+    pub fn correctness(self) -> Option<Correctness> {
+        match self {
+            Key::Unknown => None,
+            Key::Absent => Some(Correctness::Wrong),
+            Key::Present => Some(Correctness::Misplaced),
+            Key::Placed => Some(Correctness::Correct),
+        }
+    }
+}
+
+/// Everything the feedback so far says about each letter, as the game's
+/// on-screen keyboard shows it.
+#[derive(Clone, Debug)]
+pub struct Keyboard {
+    keys: [Key; 128],
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self {
+            keys: [Key::Unknown; 128],
+        }
+    }
+}
+
+impl Keyboard {
+    pub fn from_history(history: &[Guess]) -> Self {
+        let mut keyboard = Self::default();
+        for guess in history {
+            keyboard.record(guess);
+        }
+        keyboard
+    }
+
+    /// Fold in what `guess` says. A letter only ever becomes better known, so
+    /// a gray for a repeated letter doesn't hide a green or yellow.
+    pub fn record(&mut self, guess: &Guess) {
+        for (letter, c) in guess.word().bytes().zip(guess.mask()) {
+            let key = match c {
+                Correctness::Correct => Key::Placed,
+                Correctness::Misplaced => Key::Present,
+                Correctness::Wrong => Key::Absent,
+            };
+            if let Some(known) = self.keys.get_mut(letter as usize) {
+                *known = key.max(*known);
+            }
+        }
+    }
+
+    pub fn key(&self, letter: u8) -> Key {
+        self.keys.get(letter as usize).copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Keyboard {
+    /// Renders the keyboard with colored keys, or with `{:#}`, marking placed
+    /// letters `[A]`, present ones `(A)`, and absent ones with a dot.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (indent, row) in ROWS.iter().enumerate() {
+            write!(f, "{:indent$}", "", indent = indent * 2)?;
+            for letter in row.bytes() {
+                let key = self.key(letter);
+                let upper = letter.to_ascii_uppercase() as char;
+                if !f.alternate() {
+                    match key.correctness() {
+                        Some(c) => f.write_str(&c.paint(upper))?,
+                        None => {
+                            let (foreground, background) = key.colors();
+                            f.write_str(&console::paint(
+                                format!(" {upper} "),
+                                foreground,
+                                background,
+                            ))?
+                        },
+                    }
+                    continue;
+                }
+
+                match key {
+                    Key::Unknown => write!(f, " {upper} ")?,
+                    Key::Absent => write!(f, " · ")?,
+                    Key::Present => write!(f, "({upper})")?,
+                    Key::Placed => write!(f, "[{upper}]")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn knowledge() {
+        let keyboard = Keyboard::from_history(&[
+            Guess::check("catch", "tacit"),
+            Guess::check("catch", "hatch"),
+        ]);
+
+        assert_eq!(keyboard.key(b'a'), Key::Placed);
+        // Yellow in "tacit", then green in "hatch".
+        assert_eq!(keyboard.key(b't'), Key::Placed);
+        assert_eq!(keyboard.key(b'h'), Key::Placed);
+        assert_eq!(keyboard.key(b'i'), Key::Absent);
+        assert_eq!(keyboard.key(b'z'), Key::Unknown);
+    }
+
+    #[test]
+    fn repeated_gray_letter() {
+        // The second "e" is gray, but the first is green.
+        let keyboard = Keyboard::from_history(&[Guess::check("eagle", "eerie")]);
+        assert_eq!(keyboard.key(b'e'), Key::Placed);
+    }
+
+    #[test]
+    fn display() {
+        let keyboard = Keyboard::from_history(&[Guess::check("cigar", "tares")]);
+        let plain = format!("{keyboard:#}");
+        assert!(plain.starts_with(" Q  W  · (R) ·  Y "));
+        assert!(plain.contains("(A) ·  D "));
+        assert_eq!(plain.lines().count(), 3);
+    }
+}
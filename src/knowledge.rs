@@ -0,0 +1,330 @@
+use crate::{
+    engine::Duplicates,
+    guesser::{Correctness, Guess},
+};
+
+/// Everything a sequence of guesses says about the answer, as constraints
+/// that can be queried directly.
+///
+/// Each guess narrows what's known: a green fixes a position, a yellow or gray
+/// rules its letter out of that position, and the colors a letter gets across
+/// a guess bound how many times it appears. A gray means the answer has no
+/// more copies of that letter than the guess has non-gray ones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnowledgeState {
+    fixed: [Option<u8>; 5],
+    /// A bitset of the symbols known not to be at each position.
+    excluded_at: [u128; 5],
+    min: [u8; 128],
+    max: [u8; 128],
+    /// Symbols any guess has said something about, in the order first seen.
+    letters: Vec<u8>,
+    /// Two guesses fixed different letters at the same position.
+    clash: bool,
+    /// How the feedback folded in colors repeated letters.
+    duplicates: Duplicates,
+}
+
+impl Default for KnowledgeState {
+    fn default() -> Self {
+        Self {
+            fixed: [None; 5],
+            excluded_at: [0; 5],
+            min: [0; 128],
+            max: [5; 128],
+            letters: Vec::new(),
+            clash: false,
+            duplicates: Duplicates::Official,
+        }
+    }
+}
+
+impl KnowledgeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read feedback as colored by `rule`, rather than as the official game
+    /// colors it. Set before any feedback is added.
+    pub fn with_duplicates(mut self, rule: Duplicates) -> Self {
+        self.duplicates = rule;
+        self
+    }
+
+    pub fn from_history(history: &[Guess]) -> Self {
+        Self::from_history_with(history, Duplicates::Official)
+    }
+
+    /// [`from_history`](Self::from_history) with feedback colored by `rule`.
+    pub fn from_history_with(history: &[Guess], rule: Duplicates) -> Self {
+        let mut state = Self::default().with_duplicates(rule);
+        for guess in history {
+            state.add(guess);
+        }
+        state
+    }
+
+    /// Fold in what `guess`'s feedback says.
+    pub fn add(&mut self, guess: &Guess) {
+        let word = guess.word().as_bytes();
+        let mask = guess.mask();
+
+        for (i, (&letter, &m)) in word.iter().zip(&mask).enumerate() {
+            let letter = self.note(letter);
+            match m {
+                Correctness::Correct => match self.fixed[i] {
+                    Some(known) if known != letter => self.clash = true,
+                    _ => self.fixed[i] = Some(letter),
+                },
+                _ => self.excluded_at[i] |= 1 << letter,
+            }
+        }
+
+        // Under the naive rule a yellow only says the letter is somewhere, so
+        // only the greens count copies; a gray still caps them.
+        let naive = self.duplicates == Duplicates::Naive;
+        for &letter in word {
+            let letter = letter & 0x7f;
+            let (mut greens, mut yellows, mut capped) = (0, 0, false);
+            for (&l, &m) in word.iter().zip(&mask) {
+                if l & 0x7f == letter {
+                    match m {
+                        Correctness::Correct => greens += 1,
+                        Correctness::Misplaced => yellows += 1,
+                        Correctness::Wrong => capped = true,
+                    }
+                }
+            }
+            let seen = if naive {
+                greens.max(yellows.min(1))
+            } else {
+                greens + yellows
+            };
+
+            let index = letter as usize;
+            self.min[index] = self.min[index].max(seen);
+            if capped {
+                self.max[index] = self.max[index].min(seen);
+            }
+        }
+    }
+
+    /// Record that `letter` is at position `i`, counting from zero.
+    pub fn fix(&mut self, i: usize, letter: u8) {
+        let letter = self.note(letter);
+        match self.fixed[i] {
+            Some(known) if known != letter => self.clash = true,
+            _ => self.fixed[i] = Some(letter),
+        }
+
+        let placed = self.fixed.iter().filter(|&&f| f == Some(letter)).count() as u8;
+        self.min[letter as usize] = self.min[letter as usize].max(placed);
+    }
+
+    /// Record that `letter` appears at least `count` times.
+    pub fn require(&mut self, letter: u8, count: usize) {
+        let letter = self.note(letter) as usize;
+        self.min[letter] = self.min[letter].max(count as u8);
+    }
+
+    /// Record that the answer has no more copies of `letter` than are already
+    /// known to be there, which is none unless it's been fixed or required.
+    pub fn exclude(&mut self, letter: u8) {
+        let letter = self.note(letter) as usize;
+        self.max[letter] = self.max[letter].min(self.min[letter]);
+    }
+
+    /// Record that `letter` isn't at position `i`, counting from zero.
+    pub fn exclude_at(&mut self, i: usize, letter: u8) {
+        let letter = self.note(letter);
+        self.excluded_at[i] |= 1 << letter;
+    }
+
+    /// Start tracking `letter`, returning it as an index into the tables.
+    fn note(&mut self, letter: u8) -> u8 {
+        let letter = letter & 0x7f;
+        if !self.letters.contains(&letter) {
+            self.letters.push(letter);
+        }
+        letter
+    }
+
+    /// The letter known to be at each position.
+    pub fn fixed(&self) -> [Option<u8>; 5] {
+        self.fixed
+    }
+
+    /// Letters known to be in the answer, with the fewest times each appears.
+    pub fn required(&self) -> impl Iterator<Item = (u8, usize)> + '_ {
+        self.letters
+            .iter()
+            .filter(|&&l| self.min[l as usize] > 0)
+            .map(|&l| (l, self.min[l as usize] as usize))
+    }
+
+    /// Letters known not to be in the answer at all.
+    pub fn excluded(&self) -> impl Iterator<Item = u8> + '_ {
+        self.letters
+            .iter()
+            .copied()
+            .filter(|&l| self.max[l as usize] == 0)
+    }
+
+    /// Letters known not to be at position `i`, besides the excluded ones.
+    pub fn excluded_at(&self, i: usize) -> impl Iterator<Item = u8> + '_ {
+        self.letters
+            .iter()
+            .copied()
+            .filter(move |&l| self.excluded_at[i] & (1 << l) != 0 && self.max[l as usize] > 0)
+    }
+
+    /// The fewest and most times `letter` can appear in the answer.
+    pub fn count(&self, letter: u8) -> (usize, usize) {
+        let index = (letter & 0x7f) as usize;
+        (self.min[index] as usize, self.max[index] as usize)
+    }
+
+    /// Whether any word at all could satisfy every constraint. This only
+    /// looks at the constraints themselves, not at a word list.
+    pub fn is_consistent(&self) -> bool {
+        if self.clash {
+            return false;
+        }
+
+        for (i, fixed) in self.fixed.iter().enumerate() {
+            if let &Some(letter) = fixed {
+                if self.excluded_at[i] & (1 << letter) != 0 || self.max[letter as usize] == 0 {
+                    return false;
+                }
+            }
+        }
+
+        let mut needed = 0;
+        for &letter in &self.letters {
+            let (min, max) = self.count(letter);
+            let placed = self.fixed.iter().filter(|&&f| f == Some(letter)).count();
+            if min > max || placed > max {
+                return false;
+            }
+            needed += min.max(placed);
+        }
+
+        needed <= 5
+    }
+
+    /// Whether `word` could be the answer given everything known.
+    #[inline]
+    pub fn allows(&self, word: &str) -> bool {
+        self.allows_bytes(word.as_bytes())
+    }
+
+    /// [`allows`](Self::allows) for a word as bytes, as
+    /// [`Packed`](crate::packed::Packed) stores them.
+    pub fn allows_bytes(&self, word: &[u8]) -> bool {
+        if word.len() != 5 {
+            return false;
+        }
+
+        let mut counts = [0u8; 128];
+        for (i, &letter) in word.iter().enumerate() {
+            if letter >= 128 || self.excluded_at[i] & (1 << letter) != 0 {
+                return false;
+            }
+            if self.fixed[i].is_some_and(|f| f != letter) {
+                return false;
+            }
+            counts[letter as usize] += 1;
+        }
+
+        self.letters.iter().all(|&l| {
+            let count = counts[l as usize];
+            self.min[l as usize] <= count && count <= self.max[l as usize]
+        })
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn accumulates() {
+        let state = KnowledgeState::from_history(&[
+            Guess::check("catch", "tacit"),
+            Guess::check("catch", "hatch"),
+        ]);
+
+        assert_eq!(
+            state.fixed(),
+            [None, Some(b'a'), Some(b't'), Some(b'c'), Some(b'h')]
+        );
+        let mut required: Vec<(u8, usize)> = state.required().collect();
+        required.sort();
+        assert_eq!(required, vec![(b'a', 1), (b'c', 1), (b'h', 1), (b't', 1)]);
+        assert_eq!(state.excluded().collect::<Vec<_>>(), vec![b'i']);
+        assert_eq!(state.excluded_at(0).collect::<Vec<_>>(), vec![b't', b'h']);
+        // The second "t" in "tacit" was gray, so there's only the one.
+        assert_eq!(state.count(b't'), (1, 1));
+        assert_eq!(state.count(b'c'), (1, 5));
+        assert!(state.is_consistent());
+    }
+
+    #[test]
+    fn allows_exactly_what_gives_the_same_feedback() {
+        let words = [
+            "catch", "match", "hatch", "tacit", "eerie", "eagle", "geese", "karma", "ultra",
+            "cigar",
+        ];
+        for rule in [Duplicates::Official, Duplicates::Naive] {
+            for answer in words {
+                for word in words {
+                    let guess = Guess::check_with(answer, word, rule);
+                    let state = KnowledgeState::from_history_with(&[guess], rule);
+                    for candidate in words {
+                        assert_eq!(
+                            state.allows(candidate),
+                            Guess::check_with(candidate, word, rule).mask() == guess.mask(),
+                            "{candidate} after {guess:#} ({rule:?})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clues() {
+        // The third letter is "a", there's an "r" but not first, and no "s",
+        // "t", or "e".
+        let mut state = KnowledgeState::new();
+        state.fix(2, b'a');
+        state.require(b'r', 1);
+        state.exclude_at(0, b'r');
+        for letter in [b's', b't', b'e'] {
+            state.exclude(letter);
+        }
+
+        assert!(state.allows("chard"));
+        assert!(!state.allows("roach"));
+        assert!(!state.allows("shard"));
+        assert!(!state.allows("chain"));
+
+        // A gray for a letter that's also green caps it at the known copies.
+        state.exclude(b'a');
+        assert_eq!(state.count(b'a'), (1, 1));
+        assert!(!state.allows("arava"));
+    }
+
+    #[test]
+    fn inconsistent() {
+        let guess = |answer, word| Guess::check(answer, word);
+        let mut state = KnowledgeState::from_history(&[guess("catch", "hatch")]);
+        state.add(&guess("hatch", "hatch"));
+        assert!(!state.is_consistent());
+
+        // Exactly two "e"s, then at least three.
+        let state =
+            KnowledgeState::from_history(&[guess("sheep", "eerie"), guess("geese", "eerie")]);
+        assert!(!state.is_consistent());
+    }
+}
@@ -0,0 +1,502 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, BufRead, BufReader, Write},
+    net::TcpListener,
+};
+
+use crate::{
+    guesser::{Correctness, Guess},
+    json::{self, Value},
+    normalize::Normalizer,
+    pattern::format_code,
+    server::{Request, Response},
+    stats::GUESSES,
+};
+
+/// The page served at `/` for the shared daily game, which talks to the
+/// `/daily` endpoints.
+const PAGE: &str = include_str!("ui/daily.html");
+
+/// The longest name a player may go by.
+const NAME: usize = 20;
+
+/// A player's record across the days they've played.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Player {
+    pub played: usize,
+    pub wins: usize,
+    /// The guesses taken over every win, for the average.
+    pub guesses: usize,
+    pub streak: usize,
+    pub max_streak: usize,
+    /// The day of the last win, in days since 1970-01-01, which a streak
+    /// must follow on from.
+    pub last_win: Option<i64>,
+}
+
+impl Player {
+    /// The wins in a row up to `day`, which a day without a win breaks.
+    pub fn streak_on(&self, day: i64) -> usize {
+        match self.last_win {
+            Some(last) if last >= day - 1 => self.streak,
+            _ => 0,
+        }
+    }
+
+    fn record(&mut self, day: i64, guesses: Option<usize>) {
+        self.played += 1;
+        match guesses {
+            Some(n) => {
+                self.wins += 1;
+                self.guesses += n;
+                self.streak = self.streak_on(day - 1) + 1;
+                self.max_streak = self.max_streak.max(self.streak);
+                self.last_win = Some(day);
+            },
+            None => self.streak = 0,
+        }
+    }
+}
+
+/// A daily game shared by everyone on a server: one hidden word a day, the
+/// same for every player, with each player's guesses for the day and their
+/// record across days for a leaderboard.
+///
+/// The endpoints are
+///
+/// - `POST /daily/guess`, with a `player` and a `word`: its feedback, and
+///   the answer once the player's game is over;
+/// - `POST /daily/game`, with a `player`: their guesses so far today;
+/// - `GET /daily/leaderboard`: every player's record, today's best first.
+pub struct League<'a, F> {
+    /// The puzzle number and answer for a day, if there's a puzzle that day.
+    answer: F,
+    words: HashSet<&'a str>,
+    normalizer: Normalizer,
+    players: BTreeMap<String, Player>,
+    /// The day the games in `games` were played.
+    day: i64,
+    games: HashMap<String, Vec<Guess<'a>>>,
+    ui: bool,
+}
+
+impl<'a, F: Fn(i64) -> Option<(usize, &'a str)>> League<'a, F> {
+    /// A league whose answer on each day is given by `answer`, accepting any
+    /// of `words` as a guess.
+    pub fn new(answer: F, words: &[&'a str]) -> Self {
+        Self {
+            answer,
+            words: words.iter().copied().collect(),
+            normalizer: Normalizer::default(),
+            players: BTreeMap::new(),
+            day: i64::MIN,
+            games: HashMap::new(),
+            ui: false,
+        }
+    }
+
+    /// Read words with `normalizer`, as the word lists were loaded.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Also serve the bundled page at `/`.
+    pub fn with_ui(mut self, ui: bool) -> Self {
+        self.ui = ui;
+        self
+    }
+
+    /// Every player's record so far.
+    pub fn players(&self) -> &BTreeMap<String, Player> {
+        &self.players
+    }
+
+    /// Answer `request`, made on `day`. Games from an earlier day are set
+    /// aside, and anyone who left one unfinished is recorded as having lost
+    /// it, which breaks their streak.
+    pub fn handle(&mut self, request: &Request, day: i64) -> Response {
+        self.roll_over(day);
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/" | "/index.html") if self.ui => {
+                return Response {
+                    status: 200,
+                    content_type: "text/html; charset=utf-8",
+                    body: PAGE.to_string(),
+                };
+            },
+            ("POST", "/daily/guess") => self.guess(&request.body),
+            ("POST", "/daily/game") => self.game(&request.body),
+            ("GET", "/daily/leaderboard") => Ok(self.leaderboard()),
+            (_, "/daily/guess" | "/daily/game") => return Response::error(405, "use POST"),
+            (_, "/daily/leaderboard") => return Response::error(405, "use GET"),
+            _ => return Response::error(404, "not found"),
+        };
+        match result {
+            Ok(value) => Response::json(200, &value),
+            Err(message) => Response::error(400, &message),
+        }
+    }
+
+    fn roll_over(&mut self, day: i64) {
+        if day == self.day {
+            return;
+        }
+        let unfinished: Vec<String> = self
+            .games
+            .iter()
+            .filter(|(_, history)| !is_over(history))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in unfinished {
+            self.players.entry(name).or_default().record(self.day, None);
+        }
+        self.games.clear();
+        self.day = day;
+    }
+
+    fn guess(&mut self, body: &str) -> Result<Value, String> {
+        let request = json::parse(body).map_err(|e| e.to_string())?;
+        let name = player(&request)?;
+        let typed = request
+            .get("word")
+            .and_then(Value::as_str)
+            .ok_or("a guess needs a word")?;
+        let (_, answer) = (self.answer)(self.day).ok_or("there's no puzzle today")?;
+        let word = self
+            .normalizer
+            .word(typed)
+            .into_iter()
+            .find_map(|word| self.word(&word, answer))
+            .ok_or_else(|| format!("{typed}: not in word list"))?;
+
+        let history = self.games.entry(name.to_string()).or_default();
+        if is_over(history) {
+            return Err("today's game is over; come back tomorrow".to_string());
+        }
+        history.push(Guess::check(answer, word));
+        if is_over(history) {
+            let solved = history.last().filter(|guess| guess.is_correct());
+            let guesses = solved.map(|_| history.len());
+            self.players
+                .entry(name.to_string())
+                .or_default()
+                .record(self.day, guesses);
+        }
+        self.game(body)
+    }
+
+    fn game(&self, body: &str) -> Result<Value, String> {
+        let request = json::parse(body).map_err(|e| e.to_string())?;
+        let name = player(&request)?;
+        let (number, answer) = (self.answer)(self.day).ok_or("there's no puzzle today")?;
+        let history = self.games.get(name).map_or(&[][..], Vec::as_slice);
+        let rows = history
+            .iter()
+            .map(|guess| {
+                Value::Object(vec![
+                    ("word".to_string(), self.decode(guess.word())),
+                    ("feedback".to_string(), feedback(guess)),
+                ])
+            })
+            .collect();
+        let mut fields = vec![
+            ("number".to_string(), Value::Number(number as f64)),
+            ("rows".to_string(), Value::Array(rows)),
+            ("over".to_string(), Value::Bool(is_over(history))),
+        ];
+        if is_over(history) {
+            fields.push(("answer".to_string(), self.decode(answer)));
+        }
+        Ok(Value::Object(fields))
+    }
+
+    /// Every player, those who solved today's puzzle in the fewest guesses
+    /// first, then by streak.
+    fn leaderboard(&self) -> Value {
+        let today = |name: &str| {
+            let history = self.games.get(name)?;
+            let solved = history.last().is_some_and(Guess::is_correct);
+            solved.then_some(history.len())
+        };
+        let mut names: Vec<&String> = self.players.keys().chain(self.games.keys()).collect();
+        names.sort();
+        names.dedup();
+        names.sort_by_key(|name| {
+            let streak = self.players.get(*name).map_or(0, |p| p.streak_on(self.day));
+            (today(name).unwrap_or(usize::MAX), std::cmp::Reverse(streak))
+        });
+
+        let number = (self.answer)(self.day).map_or(Value::Null, |(n, _)| Value::Number(n as f64));
+        let players = names
+            .into_iter()
+            .map(|name| {
+                let record = self.players.get(name).cloned().unwrap_or_default();
+                let average = match record.wins {
+                    0 => Value::Null,
+                    wins => Value::Number(record.guesses as f64 / wins as f64),
+                };
+                Value::Object(vec![
+                    ("player".to_string(), name.as_str().into()),
+                    (
+                        "today".to_string(),
+                        today(name).map_or(Value::Null, |n| Value::Number(n as f64)),
+                    ),
+                    ("played".to_string(), Value::Number(record.played as f64)),
+                    ("wins".to_string(), Value::Number(record.wins as f64)),
+                    ("average".to_string(), average),
+                    (
+                        "streak".to_string(),
+                        Value::Number(record.streak_on(self.day) as f64),
+                    ),
+                    (
+                        "max_streak".to_string(),
+                        Value::Number(record.max_streak as f64),
+                    ),
+                ])
+            })
+            .collect();
+        Value::Object(vec![
+            ("number".to_string(), number),
+            ("players".to_string(), Value::Array(players)),
+        ])
+    }
+
+    /// `word` as it's stored, if it may be played on a day `answer` is the
+    /// answer.
+    fn word(&self, word: &str, answer: &'a str) -> Option<&'a str> {
+        match self.words.get(word) {
+            Some(&word) => Some(word),
+            None => (word == answer).then_some(answer),
+        }
+    }
+
+    fn decode(&self, word: &str) -> Value {
+        Value::String(self.normalizer.tiles().decode(word).into_owned())
+    }
+
+    /// Write every record, then today's games, as lines of words:
+    /// `player <name> <played> <wins> <guesses> <streak> <max-streak>
+    /// <last-win or ->`, then `game <day> <name> <word>...`.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        for (name, p) in &self.players {
+            let last_win = p
+                .last_win
+                .map_or_else(|| "-".to_string(), |d| d.to_string());
+            writeln!(
+                w,
+                "player {name} {} {} {} {} {} {last_win}",
+                p.played, p.wins, p.guesses, p.streak, p.max_streak
+            )?;
+        }
+        for (name, history) in &self.games {
+            let words: Vec<&str> = history.iter().map(Guess::word).collect();
+            writeln!(w, "game {} {name} {}", self.day, words.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// Read what [`save`](Self::save) wrote. Today's games are replayed
+    /// against today's answer once the first request says what day it is.
+    pub fn load(&mut self, r: impl BufRead) -> io::Result<()> {
+        for line in r.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                ["player", name, played, wins, guesses, streak, max_streak, last_win] => {
+                    let number = |field: &str| field.parse().map_err(|_| invalid(&line));
+                    let last_win = match last_win {
+                        "-" => None,
+                        day => Some(day.parse().map_err(|_| invalid(&line))?),
+                    };
+                    let record = Player {
+                        played: number(played)?,
+                        wins: number(wins)?,
+                        guesses: number(guesses)?,
+                        streak: number(streak)?,
+                        max_streak: number(max_streak)?,
+                        last_win,
+                    };
+                    self.players.insert(name.to_string(), record);
+                },
+                ["game", day, name, ref words @ ..] => {
+                    let day = day.parse().map_err(|_| invalid(&line))?;
+                    let Some((_, answer)) = (self.answer)(day) else {
+                        continue;
+                    };
+                    let history = words
+                        .iter()
+                        .map(|word| match self.word(word, answer) {
+                            Some(word) => Ok(Guess::check(answer, word)),
+                            None => Err(invalid(&line)),
+                        })
+                        .collect::<io::Result<_>>()?;
+                    self.day = day;
+                    self.games.insert(name.to_string(), history);
+                },
+                [] => {},
+                _ => return Err(invalid(&line)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a player's game for the day is over: solved, or out of guesses.
+fn is_over(history: &[Guess]) -> bool {
+    history.last().is_some_and(Guess::is_correct) || history.len() >= GUESSES
+}
+
+fn feedback(guess: &Guess) -> Value {
+    let code = Correctness::encode(guess.mask());
+    Value::String(format_code(code).unwrap_or_default())
+}
+
+/// The player a request is for, whose name must be one short word.
+fn player(request: &Value) -> Result<&str, String> {
+    let name = request
+        .get("player")
+        .and_then(Value::as_str)
+        .ok_or("a request needs a player")?;
+    let fits = (1..=NAME).contains(&name.chars().count())
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !fits {
+        return Err(format!(
+            "a player's name is 1 to {NAME} letters, digits, dashes, or underscores"
+        ));
+    }
+    Ok(name)
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad league line: {line}"),
+    )
+}
+
+/// Answer connections on `listener` one at a time for `league`, with `today`
+/// saying what day each request is made on, and `save` called after every
+/// guess, until the listener fails. A connection that fails only drops that
+/// connection.
+pub fn serve<'a, F: Fn(i64) -> Option<(usize, &'a str)>>(
+    listener: TcpListener,
+    league: &mut League<'a, F>,
+    today: impl Fn() -> i64,
+    mut save: impl FnMut(&League<'a, F>) -> io::Result<()>,
+) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let response = match Request::read(BufReader::new(&stream)) {
+            Ok(request) => {
+                let response = league.handle(&request, today());
+                if request.path == "/daily/guess" && response.status == 200 {
+                    if let Err(e) = save(league) {
+                        eprintln!("warning: couldn't save the league: {e}");
+                    }
+                }
+                response
+            },
+            Err(e) => Response::error(400, &e.to_string()),
+        };
+        if let Err(e) = response.write(&stream) {
+            eprintln!("warning: couldn't answer a request: {e}");
+        }
+    }
+    Ok(())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn guess(player: &str, word: &str) -> Request {
+        post(
+            "/daily/guess",
+            &format!(r#"{{"player": "{player}", "word": "{word}"}}"#),
+        )
+    }
+
+    #[allow(dead_code)]
+    const WORDS: [&str; 4] = ["hatch", "catch", "match", "cigar"];
+
+    #[allow(dead_code)]
+    fn answer(day: i64) -> Option<(usize, &'static str)> {
+        let number = usize::try_from(day).ok()?;
+        Some((number, ["catch", "cigar"][number % 2]))
+    }
+
+    #[test]
+    fn shared_game() {
+        let mut league = League::new(answer, &WORDS);
+        let response = league.handle(&guess("ann", "hatch"), 0);
+        let value = json::parse(&response.body).unwrap();
+        let rows = value.get("rows").unwrap().as_array().unwrap();
+        assert_eq!(rows[0].get("feedback").unwrap().as_str(), Some("BGGGG"));
+        assert_eq!(value.get("over").unwrap().as_bool(), Some(false));
+        assert_eq!(value.get("answer"), None);
+
+        league.handle(&guess("ann", "catch"), 0);
+        league.handle(&guess("bo", "catch"), 0);
+        let response = league.handle(&guess("bo", "match"), 0);
+        assert_eq!(response.status, 400);
+        assert_eq!(league.handle(&guess("bo b", "match"), 0).status, 400);
+
+        let board = league.handle(
+            &Request {
+                method: "GET".to_string(),
+                path: "/daily/leaderboard".to_string(),
+                body: String::new(),
+            },
+            0,
+        );
+        let board = json::parse(&board.body).unwrap();
+        let players = board.get("players").unwrap().as_array().unwrap();
+        let names: Vec<&str> = players
+            .iter()
+            .map(|p| p.get("player").unwrap().as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["bo", "ann"]);
+
+        // Ann wins the next day too; Bo starts and never finishes, which
+        // breaks their streak once the day is over.
+        league.handle(&guess("ann", "cigar"), 1);
+        league.handle(&guess("bo", "hatch"), 1);
+        league.handle(&post("/daily/game", r#"{"player": "ann"}"#), 2);
+        assert_eq!(league.players()["ann"].streak_on(2), 2);
+        assert_eq!(league.players()["bo"].streak_on(2), 0);
+        assert_eq!(league.players()["bo"].played, 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut league = League::new(answer, &WORDS);
+        let body = r#"{"player": "ann", "word": "hatch"}"#;
+        league.handle(&post("/daily/guess", body), 2);
+        let body = r#"{"player": "bo", "word": "catch"}"#;
+        league.handle(&post("/daily/guess", body), 2);
+
+        let mut saved = Vec::new();
+        league.save(&mut saved).unwrap();
+        let mut loaded = League::new(answer, &WORDS);
+        loaded.load(&saved[..]).unwrap();
+        assert_eq!(loaded.players(), league.players());
+        let game = loaded.handle(&post("/daily/game", r#"{"player": "ann"}"#), 2);
+        let rows = json::parse(&game.body).unwrap();
+        assert_eq!(rows.get("rows").unwrap().as_array().unwrap().len(), 1);
+
+        assert!(loaded.load("player ann 1\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! A Wordle solver, with a command line to benchmark, play, and assist.
+//!
+//! Without the default `std` feature, only the [`engine`] is built, with no
+//! dependencies beyond `core` and `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod engine;
+
+// Everything but the engine needs the `std` feature, which the optional
+// modules' features all enable.
+#[cfg(feature = "std")]
+pub mod alphabet;
+#[cfg(feature = "std")]
+pub mod anneal;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod benchmark;
+#[cfg(feature = "bot")]
+pub mod bot;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod chain;
+#[cfg(feature = "charts")]
+pub mod chart;
+#[cfg(feature = "std")]
+pub mod clusters;
+#[cfg(feature = "std")]
+pub mod compare;
+#[cfg(feature = "std")]
+pub mod compete;
+#[cfg(feature = "std")]
+pub mod console;
+#[cfg(feature = "std")]
+pub mod corpus;
+#[cfg(feature = "std")]
+pub mod daily;
+#[cfg(feature = "std")]
+pub mod deck;
+#[cfg(feature = "std")]
+pub mod dictionary;
+#[cfg(feature = "std")]
+pub mod duel;
+#[cfg(feature = "line-editing")]
+pub mod editor;
+#[cfg(feature = "std")]
+pub mod feedback;
+#[cfg(feature = "std")]
+pub mod frequency;
+#[cfg(feature = "std")]
+pub mod game;
+#[cfg(feature = "std")]
+pub mod guesser;
+#[cfg(feature = "std")]
+pub mod hint;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod html;
+#[cfg(feature = "std")]
+pub mod human;
+#[cfg(feature = "std")]
+pub mod jotto;
+#[cfg(feature = "std")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod keyboard;
+#[cfg(feature = "std")]
+pub mod knowledge;
+#[cfg(feature = "std")]
+pub mod league;
+#[cfg(feature = "std")]
+pub mod locale;
+#[cfg(feature = "std")]
+pub mod matrix;
+#[cfg(feature = "memory")]
+pub mod memory;
+#[cfg(feature = "std")]
+pub mod model;
+#[cfg(feature = "std")]
+pub mod morphology;
+#[cfg(feature = "std")]
+pub mod normalize;
+#[cfg(feature = "std")]
+pub mod odds;
+#[cfg(feature = "std")]
+pub mod open;
+#[cfg(feature = "std")]
+pub mod optimal;
+#[cfg(feature = "std")]
+pub mod packed;
+#[cfg(feature = "std")]
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod pareto;
+#[cfg(feature = "std")]
+pub mod pattern;
+#[cfg(feature = "std")]
+pub mod peaks;
+#[cfg(all(feature = "std", unix))]
+pub mod plugin;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "std")]
+pub mod race;
+#[cfg(feature = "std")]
+pub mod random;
+#[cfg(feature = "std")]
+pub mod rate;
+#[cfg(feature = "std")]
+pub mod regret;
+#[cfg(feature = "std")]
+pub mod repl;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod rpc;
+#[cfg(feature = "std")]
+pub mod schedule;
+#[cfg(feature = "std")]
+pub mod server;
+#[cfg(feature = "std")]
+pub mod share;
+#[cfg(feature = "std")]
+pub mod significance;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+#[cfg(feature = "std")]
+pub mod spelling;
+#[cfg(feature = "std")]
+pub mod split;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod strategy;
+#[cfg(feature = "std")]
+pub mod tiles;
+#[cfg(feature = "std")]
+pub mod timezone;
+#[cfg(feature = "std")]
+pub mod transcript;
+#[cfg(feature = "std")]
+pub mod tree;
+#[cfg(feature = "std")]
+pub mod tune;
+#[cfg(feature = "std")]
+pub mod typing;
+#[cfg(feature = "std")]
+pub mod waffle;
+#[cfg(feature = "std")]
+pub mod weights;
@@ -0,0 +1,429 @@
+use std::fmt;
+
+/// A language the interactive modes can speak, to match the word list being
+/// played. Messages a language hasn't translated yet are shown in English.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+}
+
+/// Everything the interactive modes say to the player. Each message is a
+/// template whose `{name}` placeholders are filled in by [`Language::text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// The solver's guess, as `assist` shows it: `{word}`.
+    Suggestion,
+    FeedbackPrompt,
+    /// Feedback that didn't parse, with the parser's `{error}`.
+    FeedbackHelp,
+    /// The `{latest}` entry rules out every word, because of guess `{number}`
+    /// (`{earlier}`).
+    Contradiction,
+    NoWordsFit,
+    UndoHint,
+    /// Guess `{number}` would fit if its feedback had been `{fixed}`.
+    Correction,
+    /// Guess `{number}` was corrected to `{fixed}`, leaving `{count}`
+    /// candidates.
+    Fixed,
+    NothingToFix,
+    /// `{guess}` was taken back.
+    Undid,
+    NothingToUndo,
+    CantUndo,
+    /// `{count}` candidates remain after a guess.
+    Remaining,
+    /// A `{percent}` chance of solving with the `{turns}` guesses left.
+    Odds,
+    /// The candidate at `{rank}` in a listing, `{word}`, with a `{percent}`
+    /// chance of being the answer.
+    Ranked,
+    /// A listing stopped with `{count}` candidates still to show.
+    MoreCandidates,
+    CantList,
+    /// No listed word fits, so letter sequences that do are suggested.
+    OpenVocabulary,
+    /// The letter sequence at `{rank}` in a listing, `{word}`.
+    Sequence,
+    /// Solved in `{count}` guesses.
+    Solved,
+    Unsolved,
+    GuessPrompt,
+    EmptyGuess,
+    /// The guess had `{typed}` letters, not `{length}`.
+    WrongLength,
+    NotInList,
+    /// `{word}` can't be played, for `{reason}`.
+    Rejected,
+    /// `{word}` can't be played, for `{reason}`, but is near `{suggestions}`.
+    DidYouMean,
+    /// `{word}` breaks guess `{number}` (`{guess}`) in Anti-Wordle.
+    DoesntFit,
+    AvoidRules,
+    /// The `{answer}` was found on guess `{count}`, after lasting `{lasted}`.
+    Found,
+    /// The game ended without finding `{answer}`.
+    Reveal,
+    /// The player took `{you}` guesses to the solver's `{solver}`.
+    RaceWon,
+    /// The solver took `{solver}` guesses to the player's `{you}`.
+    RaceLost,
+    /// Both took `{you}` guesses.
+    RaceTied,
+    /// A timed game took `{total}`.
+    Time,
+    /// A timed win beat every one before it.
+    PersonalBest,
+}
+
+impl Language {
+    /// The languages `--language` accepts.
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+    ];
+
+    /// The ISO 639-1 code for the language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+        }
+    }
+
+    /// The language for an ISO 639-1 code, ignoring any region, so "es-MX"
+    /// and "de_AT" are read as "es" and "de".
+    pub fn by_code(code: &str) -> Option<Self> {
+        let code = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// `message` with its placeholders filled in from `args`.
+    pub fn text(self, message: Message, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut text = self.template(message).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        text
+    }
+
+    /// The template for `message`, falling back to English.
+    pub fn template(self, message: Message) -> &'static str {
+        let translated = match self {
+            Language::English => None,
+            Language::Spanish => spanish(message),
+            Language::French => french(message),
+            Language::German => german(message),
+        };
+        translated.unwrap_or_else(|| english(message))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::Suggestion => "guess: {word}",
+        Message::FeedbackPrompt => "feedback> ",
+        Message::FeedbackHelp => {
+            "{error}; enter e.g. GYBBG, 21002, or 🟩🟨⬛⬛🟩, or undo, or list for the candidates"
+        },
+        Message::Contradiction => "no words fit: {latest} contradicts guess {number} ({earlier})",
+        Message::NoWordsFit => "no words fit those entries",
+        Message::UndoHint => "undo to correct an entry",
+        Message::Correction => "guess {number} was likely {fixed}; fix to correct it, or undo",
+        Message::Fixed => "corrected guess {number} to {fixed}; {count} candidates remain",
+        Message::NothingToFix => "nothing to fix",
+        Message::Undid => "undid {guess}",
+        Message::NothingToUndo => "nothing to undo",
+        Message::CantUndo => "can't undo against this host",
+        Message::Remaining => "{count} candidates remain",
+        Message::Odds => "{percent}% chance to solve in time, with {turns} to go",
+        Message::Ranked => "{rank}. {word} {percent}%",
+        Message::MoreCandidates => "{count} more; list to see them",
+        Message::CantList => "can't list candidates against this host",
+        Message::OpenVocabulary => {
+            "no listed word fits; guessing letter sequences that do, or undo to go back"
+        },
+        Message::Sequence => "{rank}. {word}",
+        Message::Solved => "solved in {count}",
+        Message::Unsolved => "no solution found",
+        Message::GuessPrompt => "guess> ",
+        Message::EmptyGuess => "enter a word to guess",
+        Message::WrongLength => "guesses are {length} letters, not {typed}",
+        Message::NotInList => "not in word list",
+        Message::Rejected => "{word}: {reason}",
+        Message::DidYouMean => "{word}: {reason}; did you mean {suggestions}?",
+        Message::DoesntFit => "{word} doesn't fit guess {number} ({guess})",
+        Message::AvoidRules => "avoid the answer: every guess must fit all the feedback so far",
+        Message::Found => "found {answer} on guess {count}; you lasted {lasted}",
+        Message::Reveal => "the answer was {answer}",
+        Message::RaceWon => "you win, {you} guesses to the solver's {solver}",
+        Message::RaceLost => "the solver wins, {solver} guesses to your {you}",
+        Message::RaceTied => "a tie, {you} guesses each",
+        Message::Time => "time: {total}",
+        Message::PersonalBest => "a new personal best!",
+    }
+}
+
+// Typed commands such as "undo" stay in English in every language, so the
+// translations mention them as they must be typed.
+
+fn spanish(message: Message) -> Option<&'static str> {
+    Some(match message {
+        Message::Suggestion => "intento: {word}",
+        Message::FeedbackPrompt => "colores> ",
+        Message::FeedbackHelp => {
+            "{error}; escribe p. ej. GYBBG, 21002 o 🟩🟨⬛⬛🟩, o undo para deshacer, o list para ver las candidatas"
+        },
+        Message::Contradiction => {
+            "ninguna palabra encaja: {latest} contradice el intento {number} ({earlier})"
+        },
+        Message::NoWordsFit => "ninguna palabra encaja con esas entradas",
+        Message::UndoHint => "escribe undo para corregir una entrada",
+        Message::Correction => {
+            "el intento {number} era seguramente {fixed}; escribe fix para corregirlo, o undo"
+        },
+        Message::Fixed => "intento {number} corregido a {fixed}; quedan {count} candidatas",
+        Message::NothingToFix => "no hay nada que corregir",
+        Message::Undid => "deshecho: {guess}",
+        Message::NothingToUndo => "no hay nada que deshacer",
+        Message::CantUndo => "no se puede deshacer con este anfitrión",
+        Message::Remaining => "quedan {count} candidatas",
+        Message::Odds => "{percent}% de probabilidad de resolverlo a tiempo (quedan {turns})",
+        Message::Ranked => "{rank}. {word} {percent}%",
+        Message::MoreCandidates => "{count} más; escribe list para verlas",
+        Message::CantList => "no se pueden listar candidatas con este anfitrión",
+        Message::OpenVocabulary => {
+            "ninguna palabra de la lista encaja; se proponen secuencias de letras que sí, o escribe undo para volver"
+        },
+        Message::Sequence => "{rank}. {word}",
+        Message::Solved => "resuelto en {count}",
+        Message::Unsolved => "no se encontró solución",
+        Message::GuessPrompt => "intento> ",
+        Message::EmptyGuess => "escribe una palabra",
+        Message::WrongLength => "los intentos tienen {length} letras, no {typed}",
+        Message::NotInList => "no está en la lista de palabras",
+        Message::Rejected => "{word}: {reason}",
+        Message::DidYouMean => "{word}: {reason}; ¿quisiste decir {suggestions}?",
+        Message::DoesntFit => "{word} no encaja con el intento {number} ({guess})",
+        Message::AvoidRules => {
+            "evita la respuesta: cada intento debe encajar con todas las pistas hasta ahora"
+        },
+        Message::Found => "encontraste {answer} en el intento {count}; aguantaste {lasted}",
+        Message::Reveal => "la respuesta era {answer}",
+        Message::RaceWon => "ganas tú, {you} intentos contra {solver} del solucionador",
+        Message::RaceLost => "gana el solucionador, {solver} intentos contra tus {you}",
+        Message::RaceTied => "empate, {you} intentos cada uno",
+        Message::Time => "tiempo: {total}",
+        Message::PersonalBest => "¡nuevo récord personal!",
+    })
+}
+
+fn french(message: Message) -> Option<&'static str> {
+    Some(match message {
+        Message::Suggestion => "essai : {word}",
+        Message::FeedbackPrompt => "couleurs> ",
+        Message::FeedbackHelp => {
+            "{error} ; saisissez par ex. GYBBG, 21002 ou 🟩🟨⬛⬛🟩, ou undo pour annuler, ou list pour voir les candidats"
+        },
+        Message::Contradiction => {
+            "aucun mot ne convient : {latest} contredit l'essai {number} ({earlier})"
+        },
+        Message::NoWordsFit => "aucun mot ne correspond à ces saisies",
+        Message::UndoHint => "saisissez undo pour corriger une saisie",
+        Message::Correction => {
+            "l'essai {number} était sans doute {fixed} ; saisissez fix pour le corriger, ou undo"
+        },
+        Message::Fixed => "essai {number} corrigé en {fixed} ; il reste {count} candidats",
+        Message::NothingToFix => "rien à corriger",
+        Message::Undid => "annulé : {guess}",
+        Message::NothingToUndo => "rien à annuler",
+        Message::CantUndo => "impossible d'annuler avec cet hôte",
+        Message::Remaining => "il reste {count} candidats",
+        Message::Odds => "{percent} % de chances de trouver à temps (encore {turns})",
+        Message::Ranked => "{rank}. {word} {percent} %",
+        Message::MoreCandidates => "encore {count} ; saisissez list pour les voir",
+        Message::CantList => "impossible de lister les candidats avec cet hôte",
+        Message::OpenVocabulary => {
+            "aucun mot de la liste ne correspond ; essai de suites de lettres qui conviennent, ou undo pour revenir"
+        },
+        Message::Sequence => "{rank}. {word}",
+        Message::Solved => "trouvé en {count}",
+        Message::Unsolved => "aucune solution trouvée",
+        Message::GuessPrompt => "essai> ",
+        Message::EmptyGuess => "saisissez un mot",
+        Message::WrongLength => "les essais font {length} lettres, pas {typed}",
+        Message::NotInList => "pas dans la liste de mots",
+        Message::Rejected => "{word} : {reason}",
+        Message::DidYouMean => "{word} : {reason} ; vouliez-vous dire {suggestions} ?",
+        Message::DoesntFit => "{word} ne respecte pas l'essai {number} ({guess})",
+        Message::AvoidRules => {
+            "évitez la réponse : chaque essai doit respecter tous les indices obtenus"
+        },
+        Message::Found => "{answer} trouvé à l'essai {count} ; vous avez tenu {lasted}",
+        Message::Reveal => "la réponse était {answer}",
+        Message::RaceWon => "vous gagnez, {you} essais contre {solver} pour le solveur",
+        Message::RaceLost => "le solveur gagne, {solver} essais contre vos {you}",
+        Message::RaceTied => "égalité, {you} essais chacun",
+        Message::Time => "temps : {total}",
+        Message::PersonalBest => "nouveau record personnel !",
+    })
+}
+
+fn german(message: Message) -> Option<&'static str> {
+    Some(match message {
+        Message::Suggestion => "Versuch: {word}",
+        Message::FeedbackPrompt => "Farben> ",
+        Message::FeedbackHelp => {
+            "{error}; gib z. B. GYBBG, 21002 oder 🟩🟨⬛⬛🟩 ein, oder undo zum Rückgängigmachen, oder list für die Kandidaten"
+        },
+        Message::Contradiction => {
+            "kein Wort passt: {latest} widerspricht Versuch {number} ({earlier})"
+        },
+        Message::NoWordsFit => "kein Wort passt zu diesen Eingaben",
+        Message::UndoHint => "gib undo ein, um eine Eingabe zu korrigieren",
+        Message::Correction => {
+            "Versuch {number} war wohl {fixed}; gib fix zum Korrigieren ein, oder undo"
+        },
+        Message::Fixed => "Versuch {number} zu {fixed} korrigiert; {count} Kandidaten übrig",
+        Message::NothingToFix => "nichts zu korrigieren",
+        Message::Undid => "rückgängig gemacht: {guess}",
+        Message::NothingToUndo => "nichts rückgängig zu machen",
+        Message::CantUndo => "bei diesem Gastgeber geht kein Rückgängigmachen",
+        Message::Remaining => "{count} Kandidaten übrig",
+        Message::Odds => "{percent}% Chance, es rechtzeitig zu lösen (noch {turns})",
+        Message::Ranked => "{rank}. {word} {percent}%",
+        Message::MoreCandidates => "{count} weitere; gib list ein, um sie zu sehen",
+        Message::CantList => "bei diesem Gastgeber lassen sich keine Kandidaten auflisten",
+        Message::OpenVocabulary => {
+            "kein Wort der Liste passt; es werden passende Buchstabenfolgen versucht, oder gib undo ein, um zurückzugehen"
+        },
+        Message::Sequence => "{rank}. {word}",
+        Message::Solved => "gelöst in {count}",
+        Message::Unsolved => "keine Lösung gefunden",
+        Message::GuessPrompt => "Versuch> ",
+        Message::EmptyGuess => "gib ein Wort ein",
+        Message::WrongLength => "Versuche haben {length} Buchstaben, nicht {typed}",
+        Message::NotInList => "nicht in der Wortliste",
+        Message::Rejected => "{word}: {reason}",
+        Message::DidYouMean => "{word}: {reason}; meintest du {suggestions}?",
+        Message::DoesntFit => "{word} passt nicht zu Versuch {number} ({guess})",
+        Message::AvoidRules => {
+            "vermeide die Lösung: jeder Versuch muss zu allen bisherigen Hinweisen passen"
+        },
+        Message::Found => "{answer} in Versuch {count} gefunden; du hast {lasted} durchgehalten",
+        Message::Reveal => "die Lösung war {answer}",
+        Message::RaceWon => "du gewinnst, {you} Versuche gegen {solver} des Lösers",
+        Message::RaceLost => "der Löser gewinnt, {solver} Versuche gegen deine {you}",
+        Message::RaceTied => "unentschieden, je {you} Versuche",
+        Message::Time => "Zeit: {total}",
+        Message::PersonalBest => "neue persönliche Bestzeit!",
+    })
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    const MESSAGES: [Message; 36] = [
+        Message::Suggestion,
+        Message::FeedbackPrompt,
+        Message::FeedbackHelp,
+        Message::Contradiction,
+        Message::NoWordsFit,
+        Message::UndoHint,
+        Message::Correction,
+        Message::Fixed,
+        Message::NothingToFix,
+        Message::Undid,
+        Message::NothingToUndo,
+        Message::CantUndo,
+        Message::Remaining,
+        Message::Odds,
+        Message::Ranked,
+        Message::MoreCandidates,
+        Message::CantList,
+        Message::OpenVocabulary,
+        Message::Sequence,
+        Message::Solved,
+        Message::Unsolved,
+        Message::GuessPrompt,
+        Message::EmptyGuess,
+        Message::WrongLength,
+        Message::NotInList,
+        Message::Rejected,
+        Message::DidYouMean,
+        Message::DoesntFit,
+        Message::AvoidRules,
+        Message::Found,
+        Message::Reveal,
+        Message::RaceWon,
+        Message::RaceLost,
+        Message::RaceTied,
+        Message::Time,
+        Message::PersonalBest,
+    ];
+
+    /// The `{name}` placeholders in `template`, sorted.
+    #[allow(dead_code)]
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn codes() {
+        assert_eq!(Language::by_code("es"), Some(Language::Spanish));
+        assert_eq!(Language::by_code("de-AT"), Some(Language::German));
+        assert_eq!(Language::by_code("FR_ca"), Some(Language::French));
+        assert_eq!(Language::by_code("xx"), None);
+        assert_eq!(Language::default(), Language::English);
+    }
+
+    #[test]
+    fn texts() {
+        let count: &dyn fmt::Display = &3;
+        assert_eq!(
+            Language::English.text(Message::Solved, &[("count", count)]),
+            "solved in 3"
+        );
+        assert_eq!(
+            Language::Spanish.text(Message::Remaining, &[("count", count)]),
+            "quedan 3 candidatas"
+        );
+    }
+
+    #[test]
+    fn translations_fill_the_same_placeholders() {
+        for language in Language::ALL {
+            for message in MESSAGES {
+                assert_eq!(
+                    placeholders(language.template(message)),
+                    placeholders(english(message)),
+                    "{language} {message:?}"
+                );
+            }
+        }
+    }
+}
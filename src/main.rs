@@ -1,45 +1,2241 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+};
 
-mod guesser;
+use wordle::{
+    alphabet::Alphabet,
+    anneal, audit, benchmark, cache, chain, clusters, compare, compete, console, corpus, daily,
+    deck,
+    dictionary::{self, Dictionary},
+    duel, engine, feedback, frequency, game, guesser, hint, history, html, human, jotto, knowledge,
+    league,
+    locale::{Language, Message},
+    matrix, model, morphology,
+    normalize::Normalizer,
+    open, optimal, parallel, pareto, pattern, peaks, pool, race, random, rate, regret, repl,
+    report, rpc, schedule, server, share, split, stats, strategy,
+    tiles::Tileset,
+    timezone, transcript, tree, tune, typing, waffle, weights,
+};
 
-static ANSWERS: &str = include_str!("../answers.txt");
-static DICTIONARY: &str = include_str!("../corpus/word-counts.txt");
+mod cli;
 
-fn main() {
-    let answers: Vec<&str> = ANSWERS.split_ascii_whitespace().collect();
-    let dictionary: Vec<&str> = {
-        let mut pairs: Vec<(&str, usize)> = DICTIONARY
-            .split('\n')
-            .filter_map(|pair| match pair.split_once(' ') {
-                Some((word, count_str)) => count_str.parse().map(|c| (word, c)).ok(),
-                _ => None,
-            })
-            .collect();
-        pairs.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
-        pairs.into_iter().map(|(word, _)| word).collect()
+// The lists are deflated by the build script, and inflated at startup.
+#[cfg(feature = "embedded-wordlists")]
+static ANSWERS: Option<fn() -> Dictionary> =
+    Some(|| Dictionary::inflate(include_bytes!(concat!(env!("OUT_DIR"), "/answers.txt.z"))));
+#[cfg(feature = "embedded-wordlists")]
+static DICTIONARY: Option<fn() -> Dictionary> = Some(|| {
+    Dictionary::inflate_compact(include_bytes!(concat!(
+        env!("OUT_DIR"),
+        "/word-counts.bin.z"
+    )))
+});
+#[cfg(feature = "embedded-wordlists")]
+static GUESSES: Option<fn() -> Dictionary> = Some(|| {
+    Dictionary::inflate(include_bytes!(concat!(
+        env!("OUT_DIR"),
+        "/valid-words.txt.z"
+    )))
+});
+#[cfg(feature = "embedded-tree")]
+static TREE: Option<&str> = Some(include_str!("../corpus/tree.txt"));
+#[cfg(not(feature = "embedded-tree"))]
+static TREE: Option<&str> = None;
+
+// Without the embedded lists, every list has to be given on the command line.
+#[cfg(not(feature = "embedded-wordlists"))]
+static ANSWERS: Option<fn() -> Dictionary> = None;
+#[cfg(not(feature = "embedded-wordlists"))]
+static DICTIONARY: Option<fn() -> Dictionary> = None;
+#[cfg(not(feature = "embedded-wordlists"))]
+static GUESSES: Option<fn() -> Dictionary> = None;
+
+/// How many of the most frequent entries `prune` lists as removed.
+const PRUNED: usize = 20;
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("error: {message}");
+    std::process::exit(2);
+}
+
+/// Read a word list from `path` (`-` for stdin) in `format`, or whatever
+/// format it appears to be in, falling back to the embedded copy. A build
+/// without one requires the list be given with `flag`.
+fn load(
+    path: Option<&str>,
+    flag: &str,
+    embedded: Option<Dictionary>,
+    format: Option<dictionary::Format>,
+) -> Dictionary {
+    let dictionary = match (path, embedded) {
+        (Some("-"), _) => Dictionary::from_reader_as(std::io::stdin().lock(), format),
+        (Some(path), _) => Dictionary::from_file_as(path, format),
+        (None, Some(embedded)) => return embedded,
+        (None, None) => fail(format!(
+            "{flag} is required in a build without embedded word lists"
+        )),
     };
 
-    let mut count = 0;
-    let mut score = 0;
-    let mut wrong = 0;
-    let mut exclusions: HashSet<&str> = HashSet::new();
+    dictionary.unwrap_or_else(|e| fail(format!("couldn't read {}: {e}", path.unwrap_or("-"))))
+}
+
+fn save_cache(cache: &cache::ReductionCache, path: &Path) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut w = BufWriter::new(File::create(path)?);
+    cache.save(&mut w)?;
+    w.flush()
+}
+
+/// `path` if given, or else `name` in the home directory.
+fn file_or_home(path: Option<&String>, name: &str) -> Option<PathBuf> {
+    path.map(PathBuf::from).or_else(|| {
+        let home = std::env::var_os("HOME")?;
+        Some(Path::new(&home).join(name))
+    })
+}
+
+/// Where player statistics are kept: `--stats`, or a file in the home
+/// directory, unless `--no-stats` was given.
+fn stats_path(options: &cli::Options) -> Option<PathBuf> {
+    if !options.keep_stats {
+        return None;
+    }
+    file_or_home(options.stats.as_ref(), ".wordle-stats")
+}
+
+fn append_history(entry: &history::Entry, path: &Path) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{entry}")
+}
+
+fn save_weights(weights: &weights::LetterWeights, path: &Path) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    weights.save(&mut w)?;
+    w.flush()
+}
+
+fn save_tree(tree: &tree::DecisionTree, path: &Path) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    tree.save(&mut w)?;
+    w.flush()
+}
+
+fn save_run(run: &compare::Run, path: &Path) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    run.save(&mut w)?;
+    w.flush()
+}
+
+/// Draw a chart to the `--chart` path with `draw`, if one was given.
+#[cfg(feature = "charts")]
+fn save_chart(path: &Option<String>, draw: impl FnOnce(&Path) -> std::io::Result<()>) {
+    if let Some(path) = path {
+        match draw(Path::new(path)) {
+            Ok(()) => println!("wrote {path}"),
+            Err(e) => fail(format!("couldn't write {path}: {e}")),
+        }
+    }
+}
+
+/// The average guesses over every answer, with each one missed counted one
+/// past the limit, so a search can't buy a lower average by giving up on the
+/// hard words.
+fn mean_score(report: &report::Report) -> f64 {
+    let total: usize = report.outcomes().iter().map(|o| o.score()).sum();
+    total as f64 / report.outcomes().len() as f64
+}
+
+fn save_profile(params: &[(String, f64)], comment: &str, path: &Path) -> std::io::Result<()> {
+    tune::save_profile(params, comment, BufWriter::new(File::create(path)?))
+}
+
+fn save_stats(stats: &stats::Stats, path: &Path) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    stats.save(&mut w)?;
+    w.flush()
+}
+
+/// Add words to the study deck at `path` with `add`, keeping the cards
+/// already there.
+fn update_deck(path: &str, add: impl FnOnce(&mut deck::Deck)) -> std::io::Result<()> {
+    let format = deck::Format::by_path(path);
+    let mut cards = match File::open(path) {
+        Ok(file) => deck::Deck::load(BufReader::new(file), format)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => deck::Deck::default(),
+        Err(e) => return Err(e),
+    };
+    add(&mut cards);
+    let mut w = BufWriter::new(File::create(path)?);
+    cards.save(&mut w, format)?;
+    w.flush()
+}
+
+/// Read the records kept at `path` into `league`, unless there are none yet.
+fn load_league<'a, F: Fn(i64) -> Option<(usize, &'a str)>>(
+    league: &mut league::League<'a, F>,
+    path: &str,
+) {
+    match File::open(path) {
+        Ok(file) => league
+            .load(BufReader::new(file))
+            .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}"))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+        Err(e) => fail(format!("couldn't read {path}: {e}")),
+    }
+}
+
+/// Write `league`'s records to `path`. They're written beside it first and
+/// renamed into place, so a crash or a full disk mid-write leaves the last
+/// records whole rather than a torn file the next start can't read.
+fn save_league<'a, F: Fn(i64) -> Option<(usize, &'a str)>>(
+    league: &league::League<'a, F>,
+    path: &str,
+) -> std::io::Result<()> {
+    let temporary = format!("{path}.tmp");
+    let mut w = BufWriter::new(File::create(&temporary)?);
+    league.save(&mut w)?;
+    w.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&temporary, path)
+}
+
+/// A seed that differs from run to run, for when `--seed` isn't given.
+fn clock_seed() -> u64 {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+    now.map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn copy_grid(grid: &str) {
+    match share::copy(grid) {
+        Ok(()) => println!("copied the share grid to the clipboard"),
+        Err(e) => eprintln!("warning: couldn't copy the share grid: {e}"),
+    }
+}
+
+/// How many of the guesses passed over a trace lists for each turn.
+const TRACED: usize = 3;
+
+/// Everything a benchmark run needs besides the strategy.
+struct Bench<'a> {
+    answers: &'a [&'a str],
+    dictionary: &'a [&'a str],
+    guesses: &'a [&'a str],
+    excluded: &'a [&'a str],
+    /// The only words that may be guessed, if restricted.
+    allowed: Option<&'a HashSet<&'a str>>,
+    model: &'a model::AnswerModel<'a>,
+    scoring: hint::Scoring,
+    alphabet: &'a Alphabet,
+    /// How the game being played colors repeated letters.
+    duplicates: engine::Duplicates,
+    options: &'a cli::Options,
+}
+
+impl<'a> Bench<'a> {
+    /// A guesser configured from the command-line options.
+    fn guesser<'b>(
+        &self,
+        strategy: &'b dyn strategy::Strategy,
+        exclusions: &'b HashSet<&'a str>,
+    ) -> guesser::Guesser<'a, 'b> {
+        let mut guesser = guesser::Guesser::new(self.dictionary, exclusions, self.model, strategy)
+            .with_duplicates(self.duplicates);
+        if self.options.probe {
+            guesser = guesser.with_guesses(self.guesses);
+        }
+        if self.options.unlimited {
+            guesser = guesser.with_limit(None);
+        }
+        if let Some(allowed) = self.allowed {
+            guesser = guesser.with_allowed(allowed);
+        }
+        if !self.alphabet.spells(guesser::OPENER) {
+            guesser = guesser.with_opener(None);
+        }
+        if self.options.verify {
+            guesser = guesser.with_verifier(|divergence| eprintln!("verify: {divergence}"));
+        }
+        guesser
+    }
+
+    /// Solve `answer` from scratch with `guesser`, reusing what it allocated
+    /// for earlier answers.
+    fn solve(
+        &self,
+        guesser: &mut guesser::Guesser<'a, '_>,
+        answer: &'a str,
+    ) -> report::Outcome<'a> {
+        guesser.reset();
+        let result = self.play(guesser, answer);
+        report::Outcome {
+            answer,
+            result,
+            timings: guesser.timings(),
+        }
+    }
+
+    /// Play `answer` with a freshly reset `guesser`, tracing it as the
+    /// options ask.
+    fn play(
+        &self,
+        guesser: &mut guesser::Guesser<'a, '_>,
+        answer: &'a str,
+    ) -> guesser::SolveResult<'a> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("answer", answer).entered();
+        let (result, transcript) = if self.options.trace || self.options.failures_only {
+            let (result, transcript) = self.trace(guesser, answer);
+            (result, Some(transcript))
+        } else {
+            let duplicates = self.duplicates;
+            (
+                guesser.solve(&mut game::KnownAnswer { answer, duplicates }),
+                None,
+            )
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(guesses = ?result.guesses(), solved = result.solved, "done");
+
+        if let Some(transcript) = transcript {
+            let solved = result.guesses().is_some_and(|n| n <= report::MAX_GUESSES);
+            if !self.options.failures_only || !solved {
+                eprint!("{transcript}");
+            }
+        }
+        result
+    }
+
+    /// Solve `answer` a guess at a time, with a transcript of each guess, its
+    /// feedback, how many candidates it eliminated, and the best of the
+    /// guesses it was chosen over.
+    fn trace(
+        &self,
+        guesser: &mut guesser::Guesser<'a, '_>,
+        answer: &'a str,
+    ) -> (guesser::SolveResult<'a>, String) {
+        let start = std::time::Instant::now();
+        let mut transcript = format!("{answer}:\n");
+        let mut remaining = Vec::new();
+        while let Some(word) = guesser.next_guess() {
+            let candidates = guesser.candidates();
+            let guesses = if self.options.probe {
+                self.guesses
+            } else {
+                candidates
+            };
+            let suggestions =
+                hint::suggest(guesses, candidates, self.model, self.scoring, TRACED + 1);
+            let alternatives: Vec<String> = suggestions
+                .iter()
+                .filter(|s| s.word != word)
+                .take(TRACED)
+                .map(|s| format!("{} ({:.2})", s.word, self.scoring.score(s)))
+                .collect();
+
+            let before = candidates.len();
+            let guess = guesser::Guess::check_with(answer, word, self.duplicates);
+            let after = guesser.apply_feedback(word, guess.mask());
+            remaining.push(after);
+            transcript += &format!(
+                "  {}. {guess:#}  {before} -> {after} candidates (-{}); over {}\n",
+                remaining.len(),
+                before.saturating_sub(after),
+                alternatives.join(", ")
+            );
+        }
+
+        let path = guesser.history().to_vec();
+        let result = guesser::SolveResult {
+            solved: path.last().is_some_and(guesser::Guess::is_correct),
+            path,
+            remaining,
+            elapsed: start.elapsed(),
+        };
+        (result, transcript)
+    }
+
+    fn run(
+        &self,
+        strategy: &dyn strategy::Strategy,
+        cache: &mut cache::ReductionCache<'a>,
+    ) -> report::Report<'a> {
+        self.run_from(strategy, cache, &[])
+    }
 
-    for answer in answers.iter() {
-        let mut guesser = crate::guesser::Guesser::new(answer, &dictionary, &exclusions);
+    /// Solve every answer as [`run`](Self::run) does, but opening with the
+    /// words in `opening` if there are any.
+    fn run_from(
+        &self,
+        strategy: &dyn strategy::Strategy,
+        cache: &mut cache::ReductionCache<'a>,
+        opening: &[&'a str],
+    ) -> report::Report<'a> {
+        let exclusions: HashSet<&str> = self.excluded.iter().copied().collect();
+        let mut guesser = self.guesser(strategy, &exclusions).with_cache(cache);
+        if !opening.is_empty() {
+            guesser = guesser.with_opening(opening);
+        }
 
-        match guesser.solve() {
-            Some(guess_count) => {
-                count += 1;
-                score += guess_count;
-                exclusions.insert(answer);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "benchmark",
+            strategy = strategy.name(),
+            answers = self.answers.len()
+        )
+        .entered();
+        benchmark::run_following(
+            &mut guesser,
+            self.answers,
+            |guesser, answer| self.play(guesser, answer),
+            |outcome| {
+                if self.options.follow.is_some() {
+                    println!("{}", outcome.line());
+                }
             },
+        )
+    }
+
+    /// Solve the answers in consecutive chains of `length`, where each answer
+    /// is the first guess of the next puzzle in its chain.
+    fn run_chains(
+        &self,
+        strategy: &dyn strategy::Strategy,
+        cache: &mut cache::ReductionCache<'a>,
+        length: usize,
+    ) -> chain::ChainReport<'a> {
+        let mut report = chain::ChainReport::default();
+        let exclusions: HashSet<&str> = self.excluded.iter().copied().collect();
+        let mut guesser = self.guesser(strategy, &exclusions).with_cache(cache);
+        let opener = guesser.opener();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("chains", strategy = strategy.name(), length).entered();
+        let start = std::time::Instant::now();
+        for answers in self.answers.chunks(length) {
+            let mut chain = chain::Chain::default();
+            for answer in answers {
+                guesser = guesser.with_opener(chain.opener().or(opener));
+                let outcome = self.solve(&mut guesser, answer);
+                if outcome.solved() {
+                    guesser.exclude(answer);
+                }
+                chain.push(outcome);
+            }
+            report.push(chain);
+        }
+
+        report.set_elapsed(start.elapsed());
+        report
+    }
+
+    /// Solve every answer from scratch, with no exclusions or guess limit,
+    /// returning each answer with how it was solved, in order. Answers are
+    /// grouped by the feedback the opener gets, and the groups are solved in
+    /// parallel, each with its own strategy and cache.
+    fn build_tree(&self, params: &strategy::Params) -> Vec<(&'a str, guesser::SolveResult<'a>)> {
+        let exclusions = HashSet::new();
+        let strategy = strategy_by_name(&self.options.strategy, params);
+        let Some(opener) = self.guesser(strategy.as_ref(), &exclusions).next_guess() else {
+            return Vec::new();
+        };
+
+        // Each answer keeps its place in the list, for following the
+        // branches' progress in input order.
+        let mut branches: HashMap<[guesser::Correctness; 5], Vec<(usize, &'a str)>> =
+            HashMap::new();
+        for (i, &answer) in self.answers.iter().enumerate() {
+            let pattern = guesser::Correctness::compute_with(
+                answer.as_bytes(),
+                opener.as_bytes(),
+                self.duplicates,
+            );
+            branches.entry(pattern).or_default().push((i, answer));
+        }
+        let branches: Vec<Vec<(usize, &'a str)>> = branches.into_values().collect();
+
+        let follow = self
+            .options
+            .follow
+            .map(|order| parallel::Follow::new(order, |line: String| println!("{line}")));
+        let work = self.answers.len() * self.dictionary.len();
+        let solved = parallel::map(&branches, work, |answers| {
+            let strategy = strategy_by_name(&self.options.strategy, params);
+            let mut cache =
+                cache::ReductionCache::new(self.dictionary).with_duplicates(self.duplicates);
+            let mut choices = HashMap::new();
+            let mut guesser = self
+                .guesser(strategy.as_ref(), &exclusions)
+                .with_cache(&mut cache)
+                .with_choices(&mut choices)
+                .with_opener(Some(opener))
+                .with_limit(None);
+            answers
+                .iter()
+                .map(|&(i, answer)| {
+                    let outcome = self.solve(&mut guesser, answer);
+                    if let Some(follow) = &follow {
+                        follow.done(i, outcome.line());
+                    }
+                    (answer, outcome.result)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut solved: Vec<_> = solved.into_iter().flatten().collect();
+        solved.sort_unstable_by_key(|&(answer, _)| answer);
+        solved
+    }
+}
+
+/// Solve every answer in a variant of the game with feedback `F`, and
+/// summarize the results under `name`, writing feedback with `show`.
+fn run_variant<F: feedback::Feedback>(bench: &Bench, name: &str, show: impl Fn(&F) -> String) {
+    let guesses = if bench.options.probe {
+        bench.guesses
+    } else {
+        bench.dictionary
+    };
+    let limit = (!bench.options.unlimited).then_some(report::MAX_GUESSES);
+
+    // Every game starts from the same candidates, so the opener only needs
+    // to be worked out once.
+    let opener = feedback::Solver::<F>::new(bench.dictionary)
+        .best_guess(guesses, bench.model)
+        .unwrap_or_else(|| fail("no candidates to solve for"));
+
+    let start = std::time::Instant::now();
+    let mut distribution = [0; report::MAX_GUESSES + 1];
+    let mut failures = Vec::new();
+    for &answer in bench.answers {
+        let mut solver = feedback::Solver::<F>::new(bench.dictionary);
+        match solver.solve(answer, opener, guesses, bench.model, limit) {
+            Some(n) if n <= report::MAX_GUESSES => distribution[n - 1] += 1,
             _ => {
-                println!("{answer}: {:?}", guesser.guessed_words());
-                wrong += 1;
+                distribution[report::MAX_GUESSES] += 1;
+                failures.push((answer, solver));
             },
+        }
+    }
+
+    let solved: usize = distribution[..report::MAX_GUESSES].iter().sum();
+    let total: usize = (1..).zip(&distribution).map(|(n, count)| n * count).sum();
+    let missed = distribution[report::MAX_GUESSES];
+    let average = (total - missed * (report::MAX_GUESSES + 1)) as f32 / solved as f32;
+
+    println!("strategy: {name}");
+    println!("opener: {opener}");
+    println!("average score: {average}");
+    println!("missed words: {missed}");
+    for (n, count) in (1..).zip(&distribution[..report::MAX_GUESSES]) {
+        println!("  solved in {n}: {count}");
+    }
+
+    println!("failures:");
+    for (answer, solver) in failures {
+        let path: Vec<String> = solver
+            .history()
+            .iter()
+            .map(|(word, feedback)| format!("{word} {}", show(feedback)))
+            .collect();
+        println!("  {answer}: {}", path.join(", "));
+    }
+
+    println!();
+    println!("wall clock: {:?}", start.elapsed());
+}
+
+/// What the clues given on the command line say about the answer.
+fn clues(options: &cli::Options) -> knowledge::KnowledgeState {
+    let mut knowledge = knowledge::KnowledgeState::new();
+    if let cli::Command::Match(pattern) = &options.command {
+        for (position, slot) in pattern.iter().enumerate() {
+            if let &Some(letter) = slot {
+                knowledge.fix(position, letter);
+            }
+        }
+    }
+    for &(position, letter) in &options.green {
+        knowledge.fix(position, letter);
+    }
+    for &(position, letter) in &options.yellow {
+        knowledge.require(letter, 1);
+        if let Some(position) = position {
+            knowledge.exclude_at(position, letter);
+        }
+    }
+    // Grays go last, since they only cap letters at what's known.
+    for &letter in &options.gray {
+        knowledge.exclude(letter);
+    }
+    knowledge
+}
+
+/// `words` as they're typed, for completion.
+fn decoded(words: &[&str], tileset: &Tileset) -> Vec<String> {
+    words
+        .iter()
+        .map(|word| tileset.decode(word).into_owned())
+        .collect()
+}
+
+/// Stdin and stdout for an interactive mode. At a terminal, with the
+/// `line-editing` feature, typing gets history, editing, and completion of
+/// the words `completions` lists.
+fn terminal(completions: impl FnOnce() -> Vec<String>) -> (Box<dyn BufRead>, Box<dyn Write>) {
+    #[cfg(feature = "line-editing")]
+    if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        match wordle::editor::LineEditor::new(completions()) {
+            Ok(editor) => {
+                let (input, output) = editor.split();
+                return (Box::new(input), Box::new(output));
+            },
+            Err(e) => eprintln!("warning: no line editing: {e}"),
+        }
+    }
+    #[cfg(not(feature = "line-editing"))]
+    let _ = completions;
+
+    (
+        Box::new(std::io::stdin().lock()),
+        Box::new(std::io::stdout()),
+    )
+}
+
+/// Suggest guesses for a game played elsewhere, reading its feedback from
+/// stdin.
+fn assist<'a>(
+    bench: &Bench<'a>,
+    strategy: &dyn strategy::Strategy,
+    given: &[(&'a str, [guesser::Correctness; 5])],
+    language: Language,
+    explain: Option<hint::Scoring>,
+    open: Option<open::OpenVocabulary>,
+) {
+    let exclusions: HashSet<&str> = bench.excluded.iter().copied().collect();
+    let mut guesser = bench
+        .guesser(strategy, &exclusions)
+        .with_observer(|event| {
+            if !event.guess.is_correct() {
+                let count = event.remaining;
+                println!(
+                    "{}",
+                    language.text(Message::Remaining, &[("count", &count)])
+                );
+            }
+        })
+        .with_given(given);
+
+    // Feedback is typed rather than words, so there's nothing to complete.
+    let (input, output) = terminal(Vec::new);
+    let mut oracle = game::Interactive::new(input, output)
+        .with_keyboard(true)
+        .with_language(language)
+        .with_explanations(explain)
+        .with_corrections(bench.options.fix_typos)
+        .with_open_vocabulary(open)
+        .with_odds(true);
+    match oracle.assist(&mut guesser) {
+        Some(n) => println!("{}", language.text(Message::Solved, &[("count", &n)])),
+        None => println!("{}", language.template(Message::Unsolved)),
+    }
+}
+
+/// A share grid read from stdin, with at least one row.
+fn read_grid() -> audit::Grid {
+    let text = std::io::read_to_string(std::io::stdin())
+        .unwrap_or_else(|e| fail(format!("couldn't read the grid: {e}")));
+    let grid = audit::Grid::parse(&text).unwrap_or_else(|e| fail(e));
+    if grid.rows.is_empty() {
+        fail("no grid rows to check");
+    }
+    grid
+}
+
+fn print_cache_stats(cache: &cache::ReductionCache) {
+    let (hits, misses) = cache.stats();
+    println!("  reduction cache: {hits} hits, {misses} misses");
+    #[cfg(feature = "memory")]
+    println!(
+        "  peak heap: {}",
+        wordle::memory::human(wordle::memory::peak())
+    );
+}
+
+fn strategy_by_name(name: &str, params: &strategy::Params) -> Box<dyn strategy::Strategy> {
+    #[cfg(unix)]
+    if let Some(path) = name.strip_prefix(wordle::plugin::PREFIX) {
+        let plugin =
+            wordle::plugin::Plugin::load(path).unwrap_or_else(|e| fail(format!("{path}: {e}")));
+        return Box::new(plugin);
+    }
+    strategy::by_name(name, params).unwrap_or_else(|| fail(format!("unknown strategy: {name}")))
+}
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: wordle::memory::Counting = wordle::memory::Counting;
+
+fn main() {
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    let options = cli::Options::parse(std::env::args().skip(1)).unwrap_or_else(|e| fail(e));
+    if let Some(jobs) = options.jobs {
+        parallel::set_threads(jobs);
+    }
+    #[cfg(not(feature = "charts"))]
+    if options.chart.is_some() {
+        fail("--chart needs wordle built with the charts feature");
+    }
+    console::init();
+    pattern::set_palette(pattern::Palette {
+        high_contrast: options.high_contrast,
+        symbols: options.symbols,
+    });
+    let duplicates = match &options.duplicates {
+        Some(name) => engine::Duplicates::by_name(name)
+            .unwrap_or_else(|| fail(format!("unknown duplicate rule: {name}"))),
+        None => engine::Duplicates::default(),
+    };
+    let language = match &options.language {
+        Some(code) => {
+            Language::by_code(code).unwrap_or_else(|| fail(format!("unknown language: {code}")))
+        },
+        None => Language::default(),
+    };
+
+    let scoring = hint::Scoring::by_name(&options.scoring)
+        .unwrap_or_else(|| fail(format!("unknown scoring: {}", options.scoring)));
+    let explain = options.explain.then_some(scoring);
+    let weights = options.weights.as_ref().map(|path| {
+        File::open(path)
+            .and_then(|file| weights::LetterWeights::load(BufReader::new(file)))
+            .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")))
+    });
+    let reference = options.reference.as_ref().map(|path| {
+        File::open(path)
+            .and_then(|file| tree::DecisionTree::load(BufReader::new(file)))
+            .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")))
+    });
+    let mut params = strategy::Params {
+        scoring,
+        weights,
+        endgame: options.endgame,
+        budget: options.budget,
+        smoothing: options.smoothing,
+        ..strategy::Params::default()
+    };
+    if let Some(name) = &options.objective {
+        params.objective = strategy::Objective::by_name(name)
+            .unwrap_or_else(|| fail(format!("unknown objective: {name}")));
+    }
+    if let Some(name) = &options.layout {
+        params.layout = typing::Layout::by_name(name)
+            .unwrap_or_else(|| fail(format!("unknown layout: {name}")));
+    }
+    let profile = options.profile.as_ref().map_or_else(Vec::new, |path| {
+        File::open(path)
+            .and_then(|file| tune::load_profile(BufReader::new(file)))
+            .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")))
+    });
+    for (key, value) in profile.iter().chain(&options.params) {
+        params.set(key, *value).unwrap_or_else(|e| fail(e));
+    }
+
+    let list_format = options.list_format.as_ref().map(|name| {
+        dictionary::Format::by_name(name)
+            .unwrap_or_else(|| fail(format!("unknown list format: {name}")))
+    });
+    // Folding happens as lists load, so everything after sees plain vowels.
+    let fold = |dictionary: Dictionary| {
+        if options.fold_accents {
+            dictionary.fold_accents()
+        } else {
+            dictionary
+        }
+    };
+    let answers_list = fold(load(
+        options.answers.as_deref(),
+        "--answers",
+        ANSWERS.map(|inflate| inflate()),
+        list_format,
+    ));
+
+    let guesses_list = fold(load(
+        options.guesses.as_deref(),
+        "--guesses",
+        GUESSES.map(|inflate| inflate()),
+        list_format,
+    ));
+    let exclude_list = fold(load(
+        options.exclude.as_deref(),
+        "--exclude",
+        Some(Dictionary::embedded("")),
+        list_format,
+    ));
+    let corpus = fold(load(
+        options.dictionary.as_deref(),
+        "--dictionary",
+        DICTIONARY.map(|inflate| inflate()),
+        list_format,
+    ));
+
+    // Multi-letter tiles are declared by the answer list or with --tiles, and
+    // stand for symbols none of the lists otherwise use.
+    let mut declared: Vec<String> = answers_list
+        .declared_tiles()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    declared.extend(options.tiles.iter().cloned());
+    let reserved = match &options.alphabet {
+        Some(symbols) => Alphabet::new(symbols).unwrap_or_else(|e| fail(e)),
+        None => Alphabet::infer_lists([&answers_list, &guesses_list, &corpus]),
+    };
+    let tileset = Tileset::new(declared.iter().map(String::as_str), &reserved)
+        .unwrap_or_else(|| fail("too many tiles to give each a symbol"));
+    let prepare = |dictionary: Dictionary| fold(dictionary).encode_tiles(&tileset);
+
+    let answers_list = answers_list.encode_tiles(&tileset);
+    let guesses_list = guesses_list.encode_tiles(&tileset);
+    let exclude_list = exclude_list.encode_tiles(&tileset);
+    let corpus = corpus.encode_tiles(&tileset);
+
+    // Without an explicit alphabet, whatever the lists are spelled with is
+    // assumed, so number variants work from their own lists alone.
+    let alphabet = match &options.alphabet {
+        Some(_) if !tileset.is_empty() => {
+            let symbols: String = tileset.symbols().map(char::from).collect();
+            Alphabet::new(&format!("{reserved}{symbols}")).unwrap_or_else(|e| fail(e))
+        },
+        Some(_) => reserved,
+        None => Alphabet::infer_lists([&answers_list, &guesses_list, &corpus]),
+    };
+
+    let mut answers: Vec<&str> = answers_list.words().collect();
+    let skipped = answers.len();
+    answers.retain(|word| alphabet.spells(word));
+    let skipped = skipped - answers.len();
+    if skipped > 0 {
+        eprintln!("skipping {skipped} answers not spelled with {alphabet}");
+    }
+
+    let mut guesses: Vec<&str> = guesses_list.words().collect();
+    guesses.retain(|word| alphabet.spells(word));
+    let excluded: Vec<&str> = exclude_list.words().collect();
+    let mut pairs = corpus.by_frequency();
+    pairs.retain(|(word, _)| alphabet.spells(word));
+    let mut model = model::AnswerModel::new(pairs.iter().copied())
+        .with_smoothing(params.smoothing)
+        .with_inflection_weight(options.inflection_weight);
+    if let Some(center) = options.sigmoid {
+        model = model.with_sigmoid(center, options.sigmoid_width);
+    }
+
+    // Letter statistics counted over a fixed list of words stand in for
+    // trained weights, so the two can't both be given.
+    if let Some(name) = &options.freq_source {
+        let source = frequency::Source::by_name(name)
+            .unwrap_or_else(|| fail(format!("unknown frequency source: {name}")));
+        if params.weights.is_some() {
+            fail("--weights and --freq-source both set the letter weights");
+        }
+        let counted: Vec<(&str, usize)> = match source {
+            frequency::Source::Answers => answers.iter().map(|&word| (word, 1)).collect(),
+            frequency::Source::Dictionary => guesses.iter().map(|&word| (word, 1)).collect(),
+            frequency::Source::Corpus => pairs.clone(),
         };
+        params.weights = Some(weights::LetterWeights::train(counted));
     }
 
-    println!("average score: {}", score as f32 / count as f32);
-    println!("missed words: {}", wrong);
+    // The model keeps the full corpus, so only what may be guessed is cut.
+    if let Some(top_n) = options.top_n {
+        pairs.truncate(top_n);
+        let frequent: HashSet<&str> = pairs.iter().map(|&(word, _)| word).collect();
+        guesses.retain(|word| frequent.contains(word));
+    }
+    let mut dictionary: Vec<&str> = if options.answer_pool {
+        let mut pool = answers.clone();
+        pool.sort_by(|a, b| model.weight(b).total_cmp(&model.weight(a)));
+        pool
+    } else {
+        pairs.iter().map(|&(word, _)| word).collect()
+    };
+    // A weight of zero rules plurals and past tenses out as answers, though
+    // they may still be probed with.
+    if options.inflection_weight == 0.0 {
+        dictionary.retain(|word| !morphology::is_inflected(word));
+    }
+
+    // Restricting what may be guessed leaves the answers alone.
+    let allow_list = options
+        .allow_guesses
+        .as_deref()
+        .map(|path| prepare(load(Some(path), "--allow-guesses", None, list_format)));
+    let deny_list = options
+        .deny_guesses
+        .as_deref()
+        .map(|path| prepare(load(Some(path), "--deny-guesses", None, list_format)));
+    let allowed: Option<HashSet<&str>> = (allow_list.is_some() || deny_list.is_some()).then(|| {
+        let mut allowed: HashSet<&str> = match &allow_list {
+            Some(list) => list.words().filter(|word| alphabet.spells(word)).collect(),
+            None => dictionary.iter().chain(&guesses).copied().collect(),
+        };
+        for word in deny_list.iter().flat_map(|list| list.words()) {
+            allowed.remove(word);
+        }
+        allowed
+    });
+
+    let cache_dir = match &options.cache_dir {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None if options.preset.is_some_and(cli::Preset::caches) => {
+            file_or_home(None, ".cache/wordle")
+        },
+        None => None,
+    };
+    let cache_path = cache_dir.map(|dir| {
+        // Reductions worked out under one duplicate rule don't hold under
+        // the other, so each keeps its own file.
+        let fingerprint = cache::ReductionCache::fingerprint(&dictionary);
+        let rule = match duplicates {
+            engine::Duplicates::Official => "",
+            engine::Duplicates::Naive => "-naive",
+        };
+        dir.join(format!("reductions-{fingerprint:016x}{rule}.bin"))
+    });
+    let mut cache = cache_path
+        .as_ref()
+        .and_then(|path| File::open(path).ok())
+        .and_then(|file| cache::ReductionCache::load(&dictionary, BufReader::new(file)).ok())
+        .unwrap_or_else(|| cache::ReductionCache::new(&dictionary))
+        .with_duplicates(duplicates);
+
+    // Splitting and sampling only narrow what's benchmarked; the daily
+    // puzzles and hosted games still draw on every answer. Both sides must
+    // agree on the split, and reruns on the sample, so they're seeded with 0
+    // unless --seed says otherwise. Each answer solved is ruled out for the
+    // rest of a run, so fewer answers average a little worse than all of them;
+    // compare runs over the same sample, not a sample against a full run.
+    let seed = options.seed.unwrap_or(0);
+    let mut benchmarked = match &options.split {
+        Some(name) => {
+            let split = split::Split::by_name(name)
+                .unwrap_or_else(|| fail(format!("unknown split: {name}")));
+            split.select(&answers, seed)
+        },
+        None => answers.clone(),
+    };
+    if let Some(n) = options.sample {
+        benchmarked = split::sample(&benchmarked, n, seed);
+    }
+
+    let bench = Bench {
+        answers: &benchmarked,
+        dictionary: &dictionary,
+        guesses: &guesses,
+        excluded: &excluded,
+        allowed: allowed.as_ref(),
+        model: &model,
+        scoring,
+        alphabet: &alphabet,
+        duplicates,
+        options: &options,
+    };
+
+    // Words read to solve are typed by hand, so they're normalized as the
+    // game normalizes guesses rather than loaded as a list.
+    let normalizer = Normalizer::new()
+        .with_folded_accents(options.fold_accents)
+        .with_tiles(tileset.clone());
+    let solve_words: Vec<String> = if options.command == cli::Command::Solve {
+        load(Some("-"), "", None, list_format)
+            .words()
+            .map(|word| {
+                let [exact, lowered] = normalizer.word(word);
+                if alphabet.spells(&exact) {
+                    exact
+                } else {
+                    lowered
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Played at the defaults, solving replays the embedded tree rather than
+    // searching, falling back on the strategy for words the tree never saw.
+    let solving = matches!(
+        options.command,
+        cli::Command::Solve | cli::Command::Daily | cli::Command::Auto | cli::Command::Archive(_)
+    );
+    let embedded_tree = TREE
+        .filter(|_| solving && options.plays_by_default())
+        .map(|text| {
+            tree::DecisionTree::load(text.as_bytes())
+                .unwrap_or_else(|e| fail(format!("the embedded tree is corrupt: {e}")))
+        });
+    let solver = match embedded_tree {
+        Some(_) => "the embedded tree",
+        None => options.strategy.as_str(),
+    };
+
+    // Guesses played elsewhere are typed too, and must be words the guesser
+    // knows to be played.
+    let lookup = |word: &str| {
+        let [exact, lowered] = normalizer.word(word);
+        guesses
+            .iter()
+            .chain(&dictionary)
+            .copied()
+            .find(|&known| known == exact || known == lowered)
+    };
+    let known =
+        |word: &str| lookup(word).unwrap_or_else(|| fail(format!("{word}: not in the word list")));
+    let given: Vec<(&str, [guesser::Correctness; 5])> = options
+        .given
+        .iter()
+        .map(|(word, mask)| (known(word), *mask))
+        .collect();
+
+    // The guesses a hint draws on, which a game is rated against too.
+    let hint_guesses = || {
+        let mut words = if options.probe {
+            guesses.clone()
+        } else {
+            dictionary.clone()
+        };
+        if let Some(allowed) = &allowed {
+            words.retain(|word| allowed.contains(word));
+        }
+        words
+    };
+
+    // Every word a grid's rows could have been played with, the most
+    // frequent first, to give as examples.
+    let legal = || {
+        let mut words = guesses.clone();
+        words.extend(answers.iter().filter(|answer| !guesses.contains(answer)));
+        words.sort_by(|a, b| model.weight(b).total_cmp(&model.weight(a)));
+        words
+    };
+
+    // The answers a hosted game or a schedule may draw on.
+    let hosted_pool = || {
+        let blocklist = prepare(load(
+            options.blocklist.as_deref(),
+            "--blocklist",
+            Some(Dictionary::embedded("")),
+            list_format,
+        ));
+        let mut filter = pool::AnswerFilter::new()
+            .with_min_count(options.min_count)
+            .with_blocklist(blocklist.words());
+        if !options.proper_nouns {
+            filter = filter.without_proper_nouns();
+        }
+        let mut pool = filter.select(&answers, &model);
+        if let Some(name) = &options.difficulty {
+            let difficulty = pool::Difficulty::by_name(name)
+                .unwrap_or_else(|| fail(format!("unknown difficulty: {name}")));
+            pool = difficulty.band(&pool, &model);
+        }
+        if pool.is_empty() {
+            fail("no answers pass the filters");
+        }
+        pool
+    };
+    let zone = || match &options.timezone {
+        Some(name) => timezone::TimeZone::parse(name).unwrap_or_else(|e| fail(e)),
+        None => timezone::TimeZone::local(),
+    };
+
+    // A private schedule stands in for the published answers, so its words
+    // are typed and must be answers.
+    let schedule = options.schedule.as_ref().map(|path| {
+        File::open(path)
+            .and_then(|file| schedule::Schedule::load(BufReader::new(file)))
+            .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")))
+    });
+    let scheduled = |number: usize| {
+        let typed = schedule.as_ref()?.answer(number)?;
+        let [exact, lowered] = normalizer.word(typed);
+        let known = answers
+            .iter()
+            .copied()
+            .find(|&known| known == exact || known == lowered);
+        Some(known.unwrap_or_else(|| fail(format!("{typed}: not in the answer list"))))
+    };
+
+    // A shared daily game's puzzle number and answer on a day.
+    let daily_answer = |day: i64| match &schedule {
+        Some(schedule) => {
+            let number = schedule.number(day)?;
+            Some((number, scheduled(number)?))
+        },
+        None => {
+            let number = daily::number(day)?;
+            Some((number, daily::answer(number, &answers)?))
+        },
+    };
+
+    // Past the word lists, the answer could be any word in the corpus, or
+    // failing that any letters that look like one.
+    let open_vocabulary = || {
+        options.open_vocabulary.then(|| {
+            open::OpenVocabulary::new(dictionary.iter().chain(&guesses).copied())
+                .with_words(pairs.iter().map(|&(word, _)| word))
+        })
+    };
+
+    // A schedule sets the day's answer for everyone playing it.
+    let hosted_answer = || match &schedule {
+        Some(schedule) => {
+            let today = daily::today(&zone());
+            let number = schedule
+                .number(today)
+                .unwrap_or_else(|| fail("the schedule has no puzzle for today"));
+            scheduled(number).unwrap_or_else(|| fail("the schedule is empty"))
+        },
+        None => {
+            let pool = hosted_pool();
+            let seed = options.seed.unwrap_or_else(clock_seed);
+            pool[random::Rng::new(seed).below(pool.len())]
+        },
+    };
+
+    match &options.command {
+        cli::Command::Benchmark => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let report = bench.run(strategy.as_ref(), &mut cache);
+            report.print(strategy.name(), options.verbosity);
+            if let Some(path) = &options.save_report {
+                let run =
+                    compare::Run::new(strategy.name(), &report, |w| tileset.decode(w).into_owned());
+                if let Err(e) = save_run(&run, Path::new(path)) {
+                    fail(format!("couldn't write {path}: {e}"));
+                }
+            }
+            #[cfg(feature = "charts")]
+            save_chart(&options.chart, |path| {
+                wordle::chart::Bars::distribution(&[(strategy.name(), &report)]).save(path)
+            });
+            if let Some(path) = &options.html_report {
+                let page = html::report(strategy.name(), &report, |symbol| {
+                    match tileset.tile(symbol) {
+                        Some(tile) => tile.to_string(),
+                        None => char::from(symbol).to_string(),
+                    }
+                });
+                match std::fs::write(path, page) {
+                    Ok(()) => println!("wrote {path}"),
+                    Err(e) => fail(format!("couldn't write {path}: {e}")),
+                }
+            }
+            if let Some(path) = &options.deck {
+                let added = update_deck(path, |cards| {
+                    cards.record_report(&report, |w| tileset.decode(w).into_owned())
+                });
+                match added {
+                    Ok(()) => println!("wrote {path}"),
+                    Err(e) => fail(format!("couldn't write {path}: {e}")),
+                }
+            }
+            if options.verbosity != report::Verbosity::Quiet {
+                match &reference {
+                    Some(tree) => regret::Regret::against(&report, |answer| {
+                        tree.path(&tileset.decode(answer)).map(<[_]>::len)
+                    }),
+                    None => regret::Regret::counting(&report),
+                }
+                .print();
+                print_cache_stats(&cache);
+            }
+        },
+        cli::Command::Chain => {
+            if options.chain_length == 0 {
+                fail("--chain-length must be at least 1");
+            }
+            let strategy = strategy_by_name(&options.strategy, &params);
+            bench
+                .run_chains(strategy.as_ref(), &mut cache, options.chain_length)
+                .print(strategy.name(), options.verbosity);
+            if options.verbosity != report::Verbosity::Quiet {
+                print_cache_stats(&cache);
+            }
+        },
+        cli::Command::Peaks => run_variant(&bench, "peaks", |peaks: &[peaks::Peak; 5]| {
+            peaks.iter().map(|p| p.to_string()).collect()
+        }),
+        cli::Command::Jotto(false) => {
+            run_variant(&bench, "jotto", |score: &jotto::Score| score.to_string())
+        },
+        cli::Command::Jotto(true) => {
+            let answer = hosted_answer();
+            let (input, output) = terminal(|| decoded(&guesses, &tileset));
+            let mut host =
+                jotto::Host::new(answer, &guesses, input, output).with_language(language);
+            let text = match host.play() {
+                Some(n) => language.text(Message::Solved, &[("count", &n)]),
+                None => language.text(Message::Reveal, &[("answer", &tileset.decode(answer))]),
+            };
+            println!("{text}");
+        },
+        cli::Command::Waffle => {
+            let text = std::io::read_to_string(std::io::stdin())
+                .unwrap_or_else(|e| fail(format!("couldn't read the grid: {e}")));
+            let puzzle = waffle::Puzzle::parse(&text).unwrap_or_else(|e| fail(e));
+            let solution = puzzle
+                .solve(&dictionary)
+                .unwrap_or_else(|| fail("no words in the dictionary fit the grid"));
+
+            let words = waffle::words(&solution);
+            println!("rows: {}", words[..3].join(", "));
+            println!("columns: {}", words[3..].join(", "));
+            let swaps = waffle::swaps(puzzle.letters(), &solution);
+            println!("swaps: {}", swaps.len());
+            let mut letters = *puzzle.letters();
+            for (a, b) in swaps {
+                let (from, to) = (waffle::position(a), waffle::position(b));
+                println!(
+                    "  {from} {} <-> {to} {}",
+                    letters[a] as char, letters[b] as char
+                );
+                letters.swap(a, b);
+            }
+        },
+        cli::Command::Assist => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            assist(
+                &bench,
+                strategy.as_ref(),
+                &given,
+                language,
+                explain,
+                open_vocabulary(),
+            );
+        },
+        cli::Command::Repl => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let guesser = bench.guesser(strategy.as_ref(), &exclusions);
+            let (input, output) = terminal(|| decoded(&guesses, &tileset));
+            repl::Repl::new(guesser, &guesses, input, output)
+                .with_normalizer(normalizer.clone())
+                .run();
+        },
+        cli::Command::Compete => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let guesser = bench.guesser(strategy.as_ref(), &exclusions);
+            let stdin = std::io::stdin();
+            let result = compete::Competitor::new(guesser)
+                .with_normalizer(normalizer.clone())
+                .run(stdin.lock(), std::io::stdout());
+            if let Err(e) = result {
+                fail(format!("couldn't talk to the referee: {e}"));
+            }
+        },
+        cli::Command::Adversary => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = HashSet::new();
+            let mut guesser = bench.guesser(strategy.as_ref(), &exclusions);
+
+            let mut oracle = game::Adversarial::new(&dictionary).with_duplicates(duplicates);
+            let result = guesser.solve(&mut oracle);
+            for guess in &result.path {
+                println!("{}", tileset.render(guess, true));
+            }
+            match result.guesses() {
+                Some(n) => println!("solved in {n}"),
+                None => println!("not solved; {} words remain", oracle.candidates().len()),
+            }
+        },
+        cli::Command::Play | cli::Command::Anti | cli::Command::Race | cli::Command::Speedrun => {
+            let answer = hosted_answer();
+            let policy = match &options.accept {
+                Some(name) => game::Policy::by_name(name)
+                    .unwrap_or_else(|| fail(format!("unknown guess policy: {name}"))),
+                None => game::Policy::default(),
+            };
+            // Typos are corrected to the most frequent words first.
+            let mut playable = match policy {
+                game::Policy::Dictionary => {
+                    let mut words: Vec<&str> = pairs.iter().map(|&(word, _)| word).collect();
+                    words.extend(guesses.iter().filter(|word| model.count(word) == 0));
+                    words
+                },
+                game::Policy::Strict | game::Policy::Open => guesses.clone(),
+            };
+            playable.sort_by(|a, b| model.weight(b).total_cmp(&model.weight(a)));
+
+            let timed = options.command == cli::Command::Speedrun;
+            let goal = if options.command == cli::Command::Anti {
+                println!("{}", language.template(Message::AvoidRules));
+                game::Goal::Avoid
+            } else {
+                game::Goal::Find
+            };
+            // In a race, the solver plays first, out of sight, and its colors
+            // are shown beside the player's as they catch up.
+            let rival = match options.command {
+                cli::Command::Race => {
+                    let strategy = strategy_by_name(&options.strategy, &params);
+                    let exclusions = HashSet::new();
+                    let mut guesser = bench.guesser(strategy.as_ref(), &exclusions);
+                    Some(guesser.solve(&mut game::KnownAnswer { answer, duplicates }))
+                },
+                _ => None,
+            };
+            let (input, output) = terminal(|| decoded(&playable, &tileset));
+            let mut host = game::Host::new(answer, &playable, input, output)
+                .with_goal(goal)
+                .with_rival(rival.as_ref().map_or(&[], |result| &result.path[..]))
+                .with_timer(timed)
+                .with_keyboard(true)
+                .with_folded_accents(options.fold_accents)
+                .with_tiles(tileset.clone())
+                .with_language(language)
+                .with_duplicates(duplicates);
+            if policy == game::Policy::Open {
+                host = host.with_validator(|word| alphabet.spells(word));
+            }
+            let result = host.play();
+            let shown = tileset.decode(answer);
+            let text = match (result, goal) {
+                (Some(n), game::Goal::Find) => language.text(Message::Solved, &[("count", &n)]),
+                (Some(n), game::Goal::Avoid) => language.text(
+                    Message::Found,
+                    &[("answer", &shown), ("count", &n), ("lasted", &(n - 1))],
+                ),
+                (None, _) => language.text(Message::Reveal, &[("answer", &shown)]),
+            };
+            println!("{text}");
+            let time: std::time::Duration = host.times().iter().sum();
+            if timed {
+                let total = share::seconds(time);
+                println!("{}", language.text(Message::Time, &[("total", &total)]));
+            }
+            if let Some(rival) = &rival {
+                println!();
+                print!("{}", race::boards(host.history(), &rival.path, &tileset));
+                let solver = rival.guesses().filter(|_| rival.solved);
+                println!("{}", race::verdict(result, solver, language));
+            }
+            if options.copy {
+                let mut grid = share::grid("Wordle", host.history());
+                if timed {
+                    grid = format!("{grid}\n{}", share::timing(host.times()));
+                }
+                copy_grid(&grid);
+            }
+            if let (Some(path), game::Goal::Find) = (&options.deck, goal) {
+                if let Err(e) = update_deck(path, |cards| cards.record(&shown, result)) {
+                    eprintln!("warning: couldn't save {path}: {e}");
+                }
+            }
+
+            // Only games played to find the answer are comparable.
+            if let (Some(path), game::Goal::Find) = (stats_path(&options), goal) {
+                // A file that can't be read is left alone rather than reset.
+                let loaded = match File::open(&path) {
+                    Ok(file) => stats::Stats::load(BufReader::new(file)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Default::default()),
+                    Err(e) => Err(e),
+                };
+                match loaded {
+                    Ok(mut stats) => {
+                        stats.record(result);
+                        if timed && stats.record_time(result, time) {
+                            println!("{}", language.template(Message::PersonalBest));
+                        }
+                        println!();
+                        print!("{stats}");
+                        if let Err(e) = save_stats(&stats, &path) {
+                            eprintln!("warning: couldn't save {}: {e}", path.display());
+                        }
+                    },
+                    Err(e) => eprintln!("warning: couldn't read {}: {e}", path.display()),
+                }
+            }
+        },
+        cli::Command::Daily | cli::Command::Auto | cli::Command::Archive(_) => {
+            let day = daily::today(&zone());
+            let today = match &schedule {
+                Some(schedule) => schedule
+                    .number(day)
+                    .unwrap_or_else(|| fail("the schedule has no puzzle for today")),
+                None => daily::number(day)
+                    .unwrap_or_else(|| fail("the clock is set before the first puzzle")),
+            };
+            let number = match options.command {
+                cli::Command::Archive(number) if number > today => {
+                    fail(format!("puzzle {number} isn't out yet; today's is {today}"))
+                },
+                cli::Command::Archive(number) => number,
+                _ => today,
+            };
+            let answer = match &schedule {
+                Some(_) => scheduled(number),
+                None => daily::answer(number, &answers),
+            }
+            .unwrap_or_else(|| fail(format!("the answer list has no puzzle {number}")));
+
+            let strategy = strategy_by_name(&options.strategy, &params);
+            if options.assist && options.command != cli::Command::Auto {
+                println!("{}", daily::title(number));
+                assist(
+                    &bench,
+                    strategy.as_ref(),
+                    &given,
+                    language,
+                    explain,
+                    open_vocabulary(),
+                );
+                return;
+            }
+
+            // Cron may run more often than daily, so each puzzle is only
+            // solved and posted once.
+            let history_path = match options.command {
+                cli::Command::Auto => Some(
+                    file_or_home(options.history.as_ref(), ".wordle-history")
+                        .unwrap_or_else(|| fail("no history store; pass --history")),
+                ),
+                _ => None,
+            };
+            if let Some(path) = &history_path {
+                let recorded = match File::open(path) {
+                    Ok(file) => history::load(BufReader::new(file))
+                        .unwrap_or_else(|e| fail(format!("couldn't read {}: {e}", path.display()))),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                    Err(e) => fail(format!("couldn't read {}: {e}", path.display())),
+                };
+                if recorded.iter().any(|entry| entry.number == number) {
+                    println!("puzzle {number} is already recorded");
+                    return;
+                }
+            }
+
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let mut guesser = bench
+                .guesser(strategy.as_ref(), &exclusions)
+                .with_cache(&mut cache);
+            if let Some(tree) = &embedded_tree {
+                // The tree probes, so its guesses must be playable.
+                guesser = guesser.with_guesses(&guesses).with_tree(tree);
+            }
+            let outcome = bench.solve(&mut guesser, answer);
+            println!(
+                "{} {}",
+                tileset.decode(answer),
+                tileset.decode(&outcome.words())
+            );
+
+            let remaining: Vec<String> = outcome
+                .result
+                .remaining
+                .iter()
+                .map(usize::to_string)
+                .collect();
+            let grid = share::grid(&daily::title(number), &outcome.result.path);
+            let message = format!(
+                "{grid}\n\nsolved with {solver}; candidates left after each guess: {}",
+                remaining.join(", ")
+            );
+            println!();
+            println!("{message}");
+
+            if options.copy {
+                copy_grid(&grid);
+            }
+            if let Some(url) = &options.webhook {
+                if let Err(e) = share::post(url, &message) {
+                    fail(format!("couldn't post to the webhook: {e}"));
+                }
+            }
+            // Recorded last, so a failed post is retried on the next run.
+            if let Some(path) = &history_path {
+                let entry = history::Entry {
+                    number,
+                    answer: answer.to_string(),
+                    guesses: outcome.guesses().filter(|_| outcome.solved()),
+                    words: outcome
+                        .result
+                        .path
+                        .iter()
+                        .map(|g| g.word().to_string())
+                        .collect(),
+                };
+                if let Err(e) = append_history(&entry, path) {
+                    fail(format!("couldn't record to {}: {e}", path.display()));
+                }
+            }
+            if options.command == cli::Command::Auto && !outcome.solved() {
+                std::process::exit(1);
+            }
+        },
+        cli::Command::Solve => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let mut guesser = bench
+                .guesser(strategy.as_ref(), &exclusions)
+                .with_cache(&mut cache)
+                .with_given(&given);
+            if let Some(tree) = &embedded_tree {
+                // The tree probes, so its guesses must be playable.
+                guesser = guesser.with_guesses(&guesses).with_tree(tree);
+            }
+            let mut grids = Vec::new();
+            for word in solve_words.iter().map(String::as_str) {
+                if word.chars().count() != 5 {
+                    let length = word.chars().count();
+                    eprintln!("skipping {}: {length} letters, not 5", tileset.decode(word));
+                    continue;
+                }
+
+                let outcome = bench.solve(&mut guesser, word);
+                let guesses = match outcome.guesses() {
+                    Some(n) if outcome.solved() => n.to_string(),
+                    _ => "-".to_string(),
+                };
+                println!(
+                    "{} {guesses} {}",
+                    tileset.decode(word),
+                    tileset.decode(&outcome.words())
+                );
+                grids.push(share::grid("Wordle", &outcome.result.path));
+            }
+            if options.copy && !grids.is_empty() {
+                copy_grid(&grids.join("\n\n"));
+            }
+        },
+        cli::Command::Audit(target) => {
+            let grid = read_grid();
+
+            // A target that reads as a number, as shared like #1,234, is a
+            // puzzle; anything else is the answer itself.
+            let number = match target {
+                Some(target) => target.trim_start_matches('#').replace(',', "").parse().ok(),
+                None => grid.number,
+            };
+            let typed;
+            let answer = match (target, number) {
+                (_, Some(number)) => daily::answer(number, &answers)
+                    .unwrap_or_else(|| fail(format!("the answer list has no puzzle {number}"))),
+                (Some(word), None) => {
+                    let [exact, lowered] = normalizer.word(word);
+                    typed = if alphabet.spells(&exact) {
+                        exact
+                    } else {
+                        lowered
+                    };
+                    typed.as_str()
+                },
+                (None, None) => fail("the grid names no puzzle; give the answer or its number"),
+            };
+
+            let audit = audit::Audit::new(&grid, answer, &legal());
+            println!("answer: {}", tileset.decode(answer));
+            audit.print(|word| tileset.decode(word).into_owned());
+            if !audit.is_possible() {
+                std::process::exit(1);
+            }
+        },
+        cli::Command::Infer => {
+            let grid = read_grid();
+            let readings = audit::readings(&grid, &answers, &legal(), &model);
+            audit::print_readings(&readings, options.top_k, |word| {
+                tileset.decode(word).into_owned()
+            });
+        },
+        cli::Command::Query | cli::Command::Match(_) => {
+            let knowledge = clues(&options);
+            if !knowledge.is_consistent() {
+                fail("those clues contradict each other");
+            }
+            for &(word, count) in &pairs {
+                if knowledge.allows(word) && !excluded.contains(&word) {
+                    println!("{} {count}", tileset.decode(word));
+                }
+            }
+        },
+        cli::Command::Hint => {
+            let guesses = hint_guesses();
+            let suggestions = hint::suggest(&guesses, &dictionary, &model, scoring, options.top_k);
+
+            println!(
+                "{:<8} {:>8} {:>12} {:>10} {:>8}",
+                "guess", "bits", "remaining", "p(answer)", "guesses"
+            );
+            for s in suggestions {
+                println!(
+                    "{:<8} {:>8.4} {:>12.2} {:>10.6} {:>8.3}",
+                    tileset.decode(s.word),
+                    s.information,
+                    s.expected_remaining,
+                    s.probability,
+                    s.expected_guesses
+                );
+            }
+        },
+        cli::Command::Import(paths) => {
+            let paths = if paths.is_empty() {
+                vec!["-".to_string()]
+            } else {
+                paths.clone()
+            };
+            let mut transcripts = Vec::new();
+            for path in &paths {
+                let text = match path.as_str() {
+                    "-" => std::io::read_to_string(std::io::stdin()),
+                    _ => std::fs::read_to_string(path),
+                };
+                let text = text.unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")));
+                transcripts.extend(transcript::parse(&text));
+            }
+
+            let guesses = hint_guesses();
+            let rater = rate::Rater::new(&guesses, &dictionary, &model);
+            // Each player's games by the month of the puzzle, or `-` if the
+            // header had no number, and over all of them.
+            let mut tallies: BTreeMap<(&str, String), rate::Tally> = BTreeMap::new();
+            let mut skipped = 0;
+            for transcript in &transcripts {
+                let game: Option<Vec<(&str, [guesser::Correctness; 5])>> =
+                    transcript.game().and_then(|game| {
+                        game.iter()
+                            .map(|&(word, mask)| Some((lookup(word)?, mask)))
+                            .collect()
+                    });
+                let Some(rating) = game.and_then(|game| rater.rate(&game).ok()) else {
+                    skipped += 1;
+                    continue;
+                };
+                let month = match transcript.number {
+                    Some(number) => schedule::format_date(daily::day(number))[..7].to_string(),
+                    None => "-".to_string(),
+                };
+                for period in [month, "all".to_string()] {
+                    tallies
+                        .entry((&transcript.player, period))
+                        .or_default()
+                        .add(&rating);
+                }
+            }
+
+            println!(
+                "{:<16} {:<8} {:>6} {:>6} {:>6}",
+                "player", "month", "games", "skill", "luck"
+            );
+            for ((player, period), tally) in &tallies {
+                let player = if player.is_empty() { "-" } else { player };
+                let luck = tally
+                    .luck()
+                    .map_or("-".to_string(), |luck| format!("{luck:.0}"));
+                println!(
+                    "{player:<16} {period:<8} {:>6} {:>6.0} {luck:>6}",
+                    tally.games,
+                    tally.skill()
+                );
+            }
+            println!(
+                "rated {} of {} games",
+                transcripts.len() - skipped,
+                transcripts.len()
+            );
+            if skipped > 0 {
+                println!(
+                    "skipped {skipped} missing a word, with a word not in the list, or with \
+                     feedback no answer fits"
+                );
+            }
+        },
+        cli::Command::Rate(game) => {
+            let words: Vec<&str> = game.iter().map(|(word, _)| known(word)).collect();
+            let answer = words[words.len() - 1];
+            let game: Vec<(&str, [guesser::Correctness; 5])> = words
+                .iter()
+                .zip(game)
+                .map(|(&word, (_, mask))| {
+                    (
+                        word,
+                        mask.unwrap_or_else(|| {
+                            guesser::Correctness::compute_with(
+                                answer.as_bytes(),
+                                word.as_bytes(),
+                                duplicates,
+                            )
+                        }),
+                    )
+                })
+                .collect();
+            let guesses = hint_guesses();
+            let rater = rate::Rater::new(&guesses, &dictionary, &model);
+            let rating = rater
+                .rate(&game)
+                .unwrap_or_else(|n| fail(format!("no answer fits the feedback to guess {n}")));
+
+            println!(
+                "{:<8} {:>8} {:>8} {:>6} {:>6}  best",
+                "guess", "before", "after", "skill", "luck"
+            );
+            let luck =
+                |luck: Option<f64>| luck.map_or("-".to_string(), |luck| format!("{luck:.0}"));
+            for turn in &rating.turns {
+                println!(
+                    "{:<8} {:>8} {:>8} {:>6.0} {:>6}  {}",
+                    tileset.decode(turn.chosen.word),
+                    turn.candidates,
+                    turn.left,
+                    turn.skill,
+                    luck(turn.luck),
+                    tileset.decode(turn.best.word)
+                );
+            }
+            println!("skill {:.0}, luck {}", rating.skill(), luck(rating.luck()));
+        },
+        cli::Command::Serve if options.league.is_some() => {
+            let path = options.league.as_deref().unwrap_or_default();
+            if options.stdio {
+                fail("a league is served over HTTP, not --stdio");
+            }
+            let zone = zone();
+            let words = legal();
+            let mut league = league::League::new(daily_answer, &words)
+                .with_normalizer(normalizer.clone())
+                .with_ui(options.ui);
+            load_league(&mut league, path);
+
+            let listener = TcpListener::bind((options.bind.as_str(), options.port))
+                .unwrap_or_else(|e| fail(format!("couldn't listen on port {}: {e}", options.port)));
+            println!(
+                "serving the daily game at http://{}:{}/",
+                options.bind, options.port
+            );
+            let today = || daily::today(&zone);
+            let served = league::serve(listener, &mut league, today, |league| {
+                save_league(league, path)
+            });
+            if let Err(e) = served {
+                fail(format!("server stopped: {e}"));
+            }
+        },
+        #[cfg(feature = "bot")]
+        cli::Command::Bot(channels) => {
+            let token = std::env::var("DISCORD_TOKEN")
+                .unwrap_or_else(|_| fail("set DISCORD_TOKEN to the bot's token"));
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let api = server::Api::new(
+                || bench.guesser(strategy.as_ref(), &exclusions),
+                &guesses,
+                &model,
+            )
+            .with_probes(options.probe.then_some(&guesses[..]))
+            .with_scoring(scoring)
+            .with_normalizer(normalizer.clone());
+            let words = legal();
+            let mut league =
+                league::League::new(daily_answer, &words).with_normalizer(normalizer.clone());
+            if let Some(path) = &options.league {
+                load_league(&mut league, path);
+            }
+
+            let zone = zone();
+            let discord = wordle::bot::Discord::new(&token);
+            println!("answering commands in {} channels", channels.len());
+            let ran = wordle::bot::run(&discord, channels, wordle::bot::INTERVAL, |message| {
+                let text = wordle::bot::reply(
+                    &api,
+                    &mut league,
+                    &message.author,
+                    &message.content,
+                    daily::today(&zone),
+                )?;
+                if let Some(path) = &options.league {
+                    if let Err(e) = save_league(&league, path) {
+                        eprintln!("warning: couldn't save the league: {e}");
+                    }
+                }
+                Some(text)
+            });
+            if let Err(e) = ran {
+                fail(format!("couldn't read the channels: {e}"));
+            }
+        },
+        #[cfg(not(feature = "bot"))]
+        cli::Command::Bot(_) => fail("bot needs wordle built with the bot feature"),
+        cli::Command::Serve => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let exclusions: HashSet<&str> = excluded.iter().copied().collect();
+            let api = server::Api::new(
+                || bench.guesser(strategy.as_ref(), &exclusions),
+                &guesses,
+                &model,
+            )
+            .with_probes(options.probe.then_some(&guesses[..]))
+            .with_scoring(scoring)
+            .with_normalizer(normalizer.clone())
+            .with_ui(options.ui);
+
+            if options.stdio {
+                let stdin = std::io::stdin().lock();
+                if let Err(e) = rpc::Session::new(api).run(stdin, std::io::stdout().lock()) {
+                    fail(format!("couldn't serve over stdio: {e}"));
+                }
+                return;
+            }
+            let address = (options.bind.as_str(), options.port);
+            let listener = TcpListener::bind(address)
+                .unwrap_or_else(|e| fail(format!("couldn't listen on port {}: {e}", options.port)));
+            if options.ui {
+                println!(
+                    "serving the solver at http://{}:{}/",
+                    options.bind, options.port
+                );
+            } else {
+                println!(
+                    "serving suggestions at http://{}:{}/suggest",
+                    options.bind, options.port
+                );
+            }
+            if let Err(e) = server::serve(listener, &api) {
+                fail(format!("server stopped: {e}"));
+            }
+        },
+        cli::Command::Duel(a, b) => {
+            let (a, b) = (strategy_by_name(a, &params), strategy_by_name(b, &params));
+            let a_report = bench.run(a.as_ref(), &mut cache);
+            let b_report = bench.run(b.as_ref(), &mut cache);
+            duel::print(a.name(), &a_report, b.name(), &b_report);
+            #[cfg(feature = "charts")]
+            save_chart(&options.chart, |path| {
+                let runs = [(a.name(), &a_report), (b.name(), &b_report)];
+                wordle::chart::Bars::distribution(&runs).save(path)
+            });
+            print_cache_stats(&cache);
+        },
+        cli::Command::Compare(a, b) => {
+            let load = |path: &str| {
+                File::open(path)
+                    .and_then(|file| compare::Run::load(BufReader::new(file)))
+                    .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")))
+            };
+            compare::print(a, &load(a), b, &load(b));
+        },
+        cli::Command::LetterStats(over_dictionary) => {
+            let words: &[&str] = if *over_dictionary {
+                &dictionary
+            } else {
+                bench.answers
+            };
+            let frequency = frequency::PositionFrequency::count(words.iter().copied());
+            let label = |symbol: u8| match tileset.tile(symbol) {
+                Some(tile) => tile.to_string(),
+                None => char::from(symbol).to_string(),
+            };
+            println!("letters by position over {} words", frequency.words());
+            print!("{}", frequency.table(options.heatmap, label));
+        },
+        cli::Command::Clusters => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let report = bench.run(strategy.as_ref(), &mut cache);
+            let turns = options.depth.unwrap_or(clusters::TURNS);
+            let families = clusters::families(&report, turns);
+            println!("{}", report.summary(strategy.name()));
+            clusters::print(
+                &families,
+                turns,
+                report.outcomes().len(),
+                options.top_k,
+                |w| tileset.decode(w).into_owned(),
+            );
+        },
+        cli::Command::Humans(path) => {
+            let human = File::open(path)
+                .and_then(|file| human::HumanScores::load(BufReader::new(file)))
+                .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")));
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let report = bench.run(strategy.as_ref(), &mut cache);
+            let comparison = human::compare(&report, &human, |w| tileset.decode(w).into_owned());
+            println!("{}", report.summary(strategy.name()));
+            human::print(&comparison, report.outcomes().len(), options.top_k);
+        },
+        cli::Command::Snapshot(path) => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let report = bench.run(strategy.as_ref(), &mut cache);
+            let run =
+                compare::Run::new(strategy.name(), &report, |w| tileset.decode(w).into_owned());
+            if let Err(e) = save_run(&run, Path::new(path)) {
+                fail(format!("couldn't write {path}: {e}"));
+            }
+            println!("{}; wrote {path}", report.summary(strategy.name()));
+        },
+        cli::Command::Check(path) => {
+            let baseline = File::open(path)
+                .and_then(|file| compare::Run::load(BufReader::new(file)))
+                .unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")));
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let report = bench.run(strategy.as_ref(), &mut cache);
+            let run =
+                compare::Run::new(strategy.name(), &report, |w| tileset.decode(w).into_owned());
+            if options.verbosity == report::Verbosity::Quiet {
+                println!("{}", report.summary(strategy.name()));
+            } else {
+                compare::print(path, &baseline, "current", &run);
+            }
+
+            let tolerance = compare::Tolerance {
+                average: options.average_tolerance,
+                failures: options.failure_tolerance,
+            };
+            let regressions = compare::regressions(&baseline, &run, tolerance);
+            if !regressions.is_empty() {
+                for regression in regressions {
+                    eprintln!("regression: {regression}");
+                }
+                std::process::exit(1);
+            }
+            println!("no regressions against {path}");
+        },
+        cli::Command::Pareto(strategies) => {
+            // Each strategy starts from an empty cache, so none is timed on
+            // reductions another already paid for.
+            let points: Vec<pareto::Point> = strategies
+                .iter()
+                .map(|name| {
+                    let strategy = strategy_by_name(name, &params);
+                    let mut cache =
+                        cache::ReductionCache::new(&dictionary).with_duplicates(duplicates);
+                    let report = bench.run(strategy.as_ref(), &mut cache);
+                    eprintln!("{}", report.summary(strategy.name()));
+                    pareto::Point::new(name, &report)
+                })
+                .collect();
+            pareto::print(&points);
+            #[cfg(feature = "charts")]
+            save_chart(&options.chart, |path| {
+                wordle::chart::Frontier::new(&points).save(path)
+            });
+        },
+        cli::Command::Matrix(openers, strategies) => {
+            let strategies: Vec<_> = strategies
+                .iter()
+                .map(|name| strategy_by_name(name, &params))
+                .collect();
+            // Openers are played from the word lists, so they're checked
+            // against them as guesses typed into a game would be, before any
+            // of the runs start.
+            let openers: Vec<&str> = openers
+                .iter()
+                .map(|opener| {
+                    let [exact, lowered] = normalizer.word(opener);
+                    bench
+                        .guesses
+                        .iter()
+                        .chain(bench.dictionary)
+                        .find(|&&word| word == exact || word == lowered)
+                        .copied()
+                        .unwrap_or_else(|| fail(format!("{opener}: not in word list")))
+                })
+                .collect();
+
+            let mut matrix = matrix::Matrix::new(strategies.iter().map(|s| s.name()));
+            for opener in openers {
+                let cells = strategies
+                    .iter()
+                    .map(|s| matrix::Cell::new(&bench.run_from(s.as_ref(), &mut cache, &[opener])))
+                    .collect();
+                matrix.push(&tileset.decode(opener), cells);
+            }
+            matrix.print();
+            #[cfg(feature = "charts")]
+            save_chart(&options.chart, |path| {
+                wordle::chart::Bars::matrix(&matrix).save(path)
+            });
+            print_cache_stats(&cache);
+        },
+        cli::Command::Corpus(paths) => {
+            // Words are kept as they'd be typed, so the corpus reads back
+            // the same whatever the tiles or accent folding.
+            let mut builder = corpus::CorpusBuilder::new();
+            let mut add = |word: &str, count: usize| {
+                let [_, word] = normalizer.word(word);
+                if alphabet.spells(&word) {
+                    builder.add(&tileset.decode(&word), count);
+                }
+            };
+
+            let paths = if paths.is_empty() {
+                vec!["-".to_string()]
+            } else {
+                paths.clone()
+            };
+            for path in &paths {
+                let r: Box<dyn BufRead> = match path.as_str() {
+                    "-" => Box::new(std::io::stdin().lock()),
+                    _ => match File::open(path) {
+                        Ok(file) => Box::new(BufReader::new(file)),
+                        Err(e) => fail(format!("couldn't read {path}: {e}")),
+                    },
+                };
+                for line in r.lines() {
+                    let line = line.unwrap_or_else(|e| fail(format!("couldn't read {path}: {e}")));
+                    if options.frequency_list {
+                        if let Some((word, count)) = corpus::parse_count(&line) {
+                            add(word, count);
+                        }
+                    } else {
+                        corpus::tokens(&line).for_each(|word| add(word, 1));
+                    }
+                }
+            }
+
+            eprintln!("{} words", builder.len());
+            let mut w = BufWriter::new(std::io::stdout().lock());
+            if let Err(e) = builder.save(&mut w).and_then(|()| w.flush()) {
+                fail(format!("couldn't write the corpus: {e}"));
+            }
+        },
+        cli::Command::Prune => {
+            // Answers are always legal guesses, even if the guess list
+            // leaves them out.
+            let legal: HashSet<String> = guesses_list
+                .words()
+                .chain(answers_list.words())
+                .map(|word| tileset.decode(word).into_owned())
+                .collect();
+
+            let mut builder = corpus::CorpusBuilder::new();
+            for (word, count) in corpus.counts() {
+                builder.add(&tileset.decode(word), count);
+            }
+            let total = builder.len();
+            let removed = builder.retain(|word| legal.contains(word));
+
+            eprintln!(
+                "removed {} of {total} entries that aren't legal guesses",
+                removed.len()
+            );
+            for (word, count) in removed.iter().take(PRUNED) {
+                eprintln!("  {word} {count}");
+            }
+            if removed.len() > PRUNED {
+                eprintln!("  and {} more", removed.len() - PRUNED);
+            }
+
+            let mut w = BufWriter::new(std::io::stdout().lock());
+            if let Err(e) = builder.save(&mut w).and_then(|()| w.flush()) {
+                fail(format!("couldn't write the corpus: {e}"));
+            }
+        },
+        cli::Command::Train(path) => {
+            // The full corpus, not just what --top-n leaves to guess.
+            let mut training = corpus.by_frequency();
+            training.retain(|(word, _)| alphabet.spells(word));
+            if options.answers_only {
+                let answers: HashSet<&str> = answers.iter().copied().collect();
+                training.retain(|(word, _)| answers.contains(word));
+            }
+            if training.is_empty() {
+                fail("no corpus words to train on");
+            }
+
+            let weights = weights::LetterWeights::train(training.iter().copied());
+            if let Err(e) = save_weights(&weights, Path::new(path)) {
+                fail(format!("couldn't write {path}: {e}"));
+            }
+            println!("trained on {} words; wrote {path}", training.len());
+        },
+        cli::Command::Openings(length) => {
+            let strategy = strategy_by_name(&options.strategy, &params);
+            let pool: &[&str] = if options.probe { &guesses } else { &dictionary };
+            let mut rng = random::Rng::new(options.seed.unwrap_or(1));
+            let mut initial = Vec::new();
+            if alphabet.spells(guesser::OPENER) {
+                initial.push(guesser::OPENER);
+            }
+            while initial.len() < *length {
+                let word = pool[rng.below(pool.len())];
+                if !initial.contains(&word) {
+                    initial.push(word);
+                }
+            }
+
+            let describe = |sequence: &[&str]| {
+                let words: Vec<_> = sequence.iter().map(|w| tileset.decode(w)).collect();
+                words.join(" ")
+            };
+            let (best, cost) = anneal::Annealer::new(rng.next_u64())
+                .with_steps(options.steps)
+                .search(
+                    pool,
+                    initial,
+                    |sequence| mean_score(&bench.run_from(strategy.as_ref(), &mut cache, sequence)),
+                    |step, sequence, cost| {
+                        println!("step {step}: {cost:.4} with {}", describe(sequence))
+                    },
+                );
+            println!(
+                "best opening: {} ({cost:.4} guesses on average)",
+                describe(&best)
+            );
+            if options.verbosity != report::Verbosity::Quiet {
+                print_cache_stats(&cache);
+            }
+        },
+        cli::Command::Tune(path) => {
+            let settings = |genome: &[f64]| -> Vec<(String, f64)> {
+                let keys = tune::KNOBS.iter().map(|knob| knob.key.to_string());
+                keys.zip(genome.iter().copied()).collect()
+            };
+            let describe = |genome: &[f64]| {
+                let settings: Vec<String> = settings(genome)
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value:.3}"))
+                    .collect();
+                settings.join(" ")
+            };
+            let fitness = |genome: &[f64]| {
+                let mut params = params.clone();
+                for (key, value) in settings(genome) {
+                    params.set(&key, value).unwrap_or_else(|e| fail(e));
+                }
+                let strategy = strategy_by_name(&options.strategy, &params);
+                let model = model.clone().with_smoothing(params.smoothing);
+                let bench = Bench {
+                    model: &model,
+                    ..bench
+                };
+                let mut cache = cache::ReductionCache::new(&dictionary).with_duplicates(duplicates);
+                mean_score(&bench.run(strategy.as_ref(), &mut cache))
+            };
+
+            let seed = options.seed.unwrap_or(1);
+            let (best, score) = tune::Tuner::new(tune::KNOBS, seed)
+                .with_population(options.population)
+                .with_generations(options.generations)
+                .evolve(fitness, |generation, genome, score| {
+                    println!(
+                        "generation {generation}: {score:.4} with {}",
+                        describe(genome)
+                    );
+                });
+
+            let comment = format!(
+                "tuned for {} over {} answers: {score:.4} guesses on average",
+                options.strategy,
+                bench.answers.len()
+            );
+            if let Err(e) = save_profile(&settings(&best), &comment, Path::new(path)) {
+                fail(format!("couldn't write {path}: {e}"));
+            }
+            println!("best: {score:.4} with {}; wrote {path}", describe(&best));
+        },
+        cli::Command::Optimal => {
+            let depth = match options.depth {
+                Some(depth) => depth,
+                None if options.unlimited => usize::MAX,
+                None => report::MAX_GUESSES,
+            };
+            let probes = options.probe.then_some(bench.guesses);
+            let mut search = optimal::Search::uniform(probes, bench.answers).with_depth(depth);
+
+            let start = std::time::Instant::now();
+            let Some((average, opener)) = search.solve(bench.answers) else {
+                fail(format!(
+                    "no way to find every answer within {depth} guesses"
+                ));
+            };
+            let worst = search.worst(bench.answers).unwrap_or(depth);
+            println!("optimal opener: {}", tileset.decode(opener));
+            println!(
+                "average: {average:.4} guesses over {} answers",
+                bench.answers.len()
+            );
+            println!("worst case: {worst} guesses");
+            println!("searched in {:.2?}", start.elapsed());
+        },
+        cli::Command::BuildTree(path) => {
+            let start = std::time::Instant::now();
+            let mut tree = tree::DecisionTree::new();
+            let mut guesses = 0;
+            for (answer, result) in bench.build_tree(&params) {
+                if !result.solved {
+                    eprintln!("couldn't solve {}", tileset.decode(answer));
+                    continue;
+                }
+                let words: Vec<_> = result
+                    .path
+                    .iter()
+                    .map(|g| tileset.decode(g.word()))
+                    .collect();
+                guesses += words.len();
+                tree.insert(&words);
+            }
+            if tree.is_empty() {
+                fail("no answers to build a tree for");
+            }
+
+            if let Err(e) = save_tree(&tree, Path::new(path)) {
+                fail(format!("couldn't write {path}: {e}"));
+            }
+            println!(
+                "{} answers, {:.4} guesses on average, in {:.2?}; wrote {path}",
+                tree.len(),
+                guesses as f64 / tree.len() as f64,
+                start.elapsed()
+            );
+        },
+        cli::Command::Schedule(first, last, path) => {
+            let pool = hosted_pool();
+            let seed = options.seed.unwrap_or_else(clock_seed);
+            let days = last - first + 1;
+            let drawn =
+                schedule::Schedule::generate(*first, *last, &pool, seed).unwrap_or_else(|| {
+                    fail(format!(
+                        "only {} answers pass the filters, too few for {days} days",
+                        pool.len()
+                    ))
+                });
+            let saved = File::create(path).and_then(|file| {
+                let mut w = BufWriter::new(file);
+                drawn.save(&mut w, |word| tileset.decode(word).into_owned())?;
+                w.flush()
+            });
+            match saved {
+                Ok(()) => println!(
+                    "{days} answers from {} to {}; wrote {path}",
+                    schedule::format_date(*first),
+                    schedule::format_date(*last)
+                ),
+                Err(e) => fail(format!("couldn't write {path}: {e}")),
+            }
+        },
+    }
+
+    if let Some(path) = cache_path {
+        if let Err(e) = save_cache(&cache, &path) {
+            eprintln!("warning: couldn't save {}: {e}", path.display());
+        }
+    }
 }
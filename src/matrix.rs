@@ -0,0 +1,111 @@
+use crate::report::Report;
+
+/// How one opener did with one strategy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub average: f32,
+    pub failures: usize,
+}
+
+impl Cell {
+    pub fn new(report: &Report) -> Self {
+        Self {
+            average: report.average(),
+            failures: report.failures().count(),
+        }
+    }
+}
+
+/// Runs of every opener with every strategy over the same answers, one row
+/// per opener and one column per strategy.
+#[derive(Clone, Debug, Default)]
+pub struct Matrix {
+    strategies: Vec<String>,
+    rows: Vec<(String, Vec<Cell>)>,
+}
+
+impl Matrix {
+    pub fn new(strategies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            strategies: strategies.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add the row for `opener`, with a cell per strategy in column order.
+    pub fn push(&mut self, opener: &str, cells: Vec<Cell>) {
+        assert_eq!(cells.len(), self.strategies.len());
+        self.rows.push((opener.to_string(), cells));
+    }
+
+    /// The strategies, in column order.
+    pub fn strategies(&self) -> &[String] {
+        &self.strategies
+    }
+
+    /// Each opener with its cells, in the order they were pushed.
+    pub fn rows(&self) -> &[(String, Vec<Cell>)] {
+        &self.rows
+    }
+
+    /// The opener with the lowest average score under the strategy in
+    /// `column`, breaking ties by fewer failures.
+    pub fn best(&self, column: usize) -> Option<&str> {
+        self.rows
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                let (a, b) = (a[column], b[column]);
+                a.average
+                    .total_cmp(&b.average)
+                    .then(a.failures.cmp(&b.failures))
+            })
+            .map(|(opener, _)| opener.as_str())
+    }
+
+    /// Print the average score and, in parentheses, the failures for every
+    /// pair, then the best opener for each strategy.
+    pub fn print(&self) {
+        let width = |name: &str| name.len().max(12);
+
+        print!("{:<8}", "opener");
+        for strategy in &self.strategies {
+            print!(" {strategy:>w$}", w = width(strategy));
+        }
+        println!();
+
+        for (opener, cells) in &self.rows {
+            print!("{opener:<8}");
+            for (strategy, cell) in self.strategies.iter().zip(cells) {
+                let cell = format!("{:.4} ({})", cell.average, cell.failures);
+                print!(" {cell:>w$}", w = width(strategy));
+            }
+            println!();
+        }
+
+        println!();
+        println!("best opener:");
+        for (column, strategy) in self.strategies.iter().enumerate() {
+            if let Some(opener) = self.best(column) {
+                println!("  {strategy}: {opener}");
+            }
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn best() {
+        let cell = |average, failures| Cell { average, failures };
+        let mut matrix = Matrix::new(["likeliest", "entropy"]);
+        assert_eq!(matrix.best(0), None);
+
+        matrix.push("salet", vec![cell(3.6, 4), cell(3.5, 0)]);
+        matrix.push("crane", vec![cell(3.5, 9), cell(3.5, 1)]);
+        matrix.push("trace", vec![cell(3.5, 2), cell(3.7, 0)]);
+        assert_eq!(matrix.best(0), Some("trace"));
+        assert_eq!(matrix.best(1), Some("salet"));
+    }
+}
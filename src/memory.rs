@@ -0,0 +1,114 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Bytes allocated through [`Counting`] and not yet freed.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The most [`CURRENT`] has been since the peak was last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the bytes in use and their peak.
+///
+/// Install it in a binary with `#[global_allocator]` to make [`current`] and
+/// [`peak`] mean anything; every allocation pays for two atomic updates.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new
+    }
+}
+
+fn grow(bytes: usize) {
+    let current = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// The bytes of heap in use now.
+pub fn current() -> usize {
+    CURRENT.load(Ordering::Relaxed)
+}
+
+/// The most bytes of heap in use at once since [`reset_peak`], or since the
+/// start.
+pub fn peak() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// Start measuring the peak again from what's in use now, as before a run.
+pub fn reset_peak() {
+    PEAK.store(current(), Ordering::Relaxed);
+}
+
+/// `bytes` in the largest binary unit that keeps it at least one.
+pub fn human(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn counts() {
+        // The test binary doesn't install the allocator, so only what's
+        // allocated through it here is counted.
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+        unsafe {
+            let ptr = Counting.alloc(layout);
+            assert!(current() >= 4096 && peak() >= current());
+            let ptr = Counting.realloc(ptr, layout, 8192);
+            assert!(peak() >= 8192);
+            Counting.dealloc(ptr, Layout::from_size_align(8192, 8).unwrap());
+        }
+        reset_peak();
+        assert_eq!(peak(), current());
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(human(512), "512 B");
+        assert_eq!(human(1536), "1.5 KiB");
+        assert_eq!(human(300 << 20), "300.0 MiB");
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use crate::morphology;
+
+/// A probability model over candidate answers, derived from corpus counts.
+///
+/// Each word's weight is its corpus count plus an additive smoothing term, so
+/// that words with very low (or missing) counts are unlikely rather than
+/// impossible. Probabilities are always taken relative to a candidate set.
+#[derive(Clone)]
+pub struct AnswerModel<'a> {
+    counts: HashMap<&'a str, usize>,
+    smoothing: f64,
+    inflection_weight: f64,
+    sigmoid: Option<Sigmoid<'a>>,
+}
+
+/// A prior that a word is a plausible answer, from its frequency rank: close
+/// to one for common words and falling smoothly to zero past `center`, as in
+/// 3Blue1Brown's Wordle solver.
+#[derive(Clone)]
+struct Sigmoid<'a> {
+    /// Each word's rank by corpus count, from zero for the most common.
+    ranks: HashMap<&'a str, usize>,
+    center: f64,
+    width: f64,
+}
+
+impl Sigmoid<'_> {
+    fn prior(&self, word: &str) -> f64 {
+        let rank = self.ranks.get(word).copied().unwrap_or(self.ranks.len());
+        1.0 / (1.0 + ((rank as f64 - self.center) / self.width).exp())
+    }
+}
+
+impl<'a> AnswerModel<'a> {
+    pub fn new(counts: impl IntoIterator<Item = (&'a str, usize)>) -> Self {
+        Self {
+            counts: counts.into_iter().collect(),
+            smoothing: 0.0,
+            inflection_weight: 1.0,
+            sigmoid: None,
+        }
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Scale the weight of words that look like plurals or past tenses by
+    /// `weight`, for answer lists that avoid them.
+    pub fn with_inflection_weight(mut self, weight: f64) -> Self {
+        self.inflection_weight = weight;
+        self
+    }
+
+    /// Weigh words by a sigmoid over their frequency rank rather than by
+    /// their counts, so the few hundred commonest words don't dwarf the rest
+    /// while obscure ones still fade out. Words ranked `center` get half the
+    /// weight of the commonest, and `width` sets how many ranks the fall
+    /// takes.
+    pub fn with_sigmoid(mut self, center: usize, width: f64) -> Self {
+        let mut ranked: Vec<(&str, usize)> = self.counts.iter().map(|(&w, &c)| (w, c)).collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        self.sigmoid = Some(Sigmoid {
+            ranks: ranked
+                .into_iter()
+                .enumerate()
+                .map(|(rank, (word, _))| (word, rank))
+                .collect(),
+            center: center as f64,
+            width,
+        });
+        self
+    }
+
+    /// How many times `word` appears in the corpus.
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.get(word).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn weight(&self, word: &str) -> f64 {
+        let weight = match &self.sigmoid {
+            Some(sigmoid) => sigmoid.prior(word),
+            None => self.count(word) as f64,
+        } + self.smoothing;
+        if self.inflection_weight != 1.0 && morphology::is_inflected(word) {
+            return weight * self.inflection_weight;
+        }
+        weight
+    }
+
+    /// The probability of each candidate being the answer, in the same order.
+    ///
+    /// If no candidate carries any weight, they are treated as equally likely.
+    pub fn distribution(&self, candidates: &[&str]) -> Vec<f64> {
+        let weights: Vec<f64> = candidates.iter().map(|word| self.weight(word)).collect();
+        let total: f64 = weights.iter().sum();
+
+        if total <= 0.0 {
+            let uniform = 1.0 / candidates.len() as f64;
+            return vec![uniform; candidates.len()];
+        }
+
+        weights.into_iter().map(|w| w / total).collect()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn normalized() {
+        let model = AnswerModel::new([("cigar", 3), ("rebut", 1)]);
+        assert_eq!(model.distribution(&["cigar", "rebut"]), vec![0.75, 0.25]);
+    }
+
+    #[test]
+    fn smoothed() {
+        let model = AnswerModel::new([("cigar", 3), ("rebut", 1)]).with_smoothing(1.0);
+        assert_eq!(
+            model.distribution(&["cigar", "rebut", "sissy"]),
+            vec![4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0]
+        );
+    }
+
+    #[test]
+    fn inflections() {
+        let model = AnswerModel::new([("cigar", 1), ("cards", 3)]).with_inflection_weight(0.5);
+        assert_eq!(model.weight("cards"), 1.5);
+        assert_eq!(model.distribution(&["cigar", "cards"]), vec![0.4, 0.6]);
+    }
+
+    #[test]
+    fn sigmoid() {
+        let counts = [("which", 9000), ("cigar", 30), ("rebut", 20), ("xylyl", 1)];
+        let model = AnswerModel::new(counts).with_sigmoid(2, 0.5);
+        // Counts put "which" 300 times ahead of "cigar"; ranks barely do.
+        assert!(model.weight("which") > 0.98);
+        assert!(model.weight("which") / model.weight("cigar") < 1.2);
+        assert_eq!(model.weight("rebut"), 0.5);
+        assert!(model.weight("xylyl") < 0.2);
+        // Words the corpus lacks rank below everything in it.
+        assert!(model.weight("qajaq") < model.weight("xylyl"));
+    }
+
+    #[test]
+    fn uniform_without_counts() {
+        let model = AnswerModel::new([]);
+        assert_eq!(model.distribution(&["cigar", "rebut"]), vec![0.5, 0.5]);
+    }
+}
@@ -0,0 +1,52 @@
+//! Rough tests for inflected word forms, which some answer lists avoid.
+//!
+//! These look only at spelling, so they misjudge some words either way; they
+//! are meant for weighting answers, not for linguistics.
+
+/// Whether `word` looks like a simple plural, such as "cards" or "boxes".
+///
+/// Words ending in a double "s", or in "us", "is", or "os", are usually
+/// singular ("abyss", "bonus", "oasis", "chaos"), so they don't count.
+pub fn is_simple_plural(word: &str) -> bool {
+    word.ends_with('s')
+        && !["ss", "us", "is", "os"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+}
+
+/// Whether `word` looks like a regular past tense, such as "baked" or
+/// "aimed". Words ending in "eed" ("greed", "speed") are left out.
+pub fn is_regular_past(word: &str) -> bool {
+    word.ends_with("ed") && !word.ends_with("eed")
+}
+
+/// Whether `word` looks like either a simple plural or a regular past tense.
+#[inline]
+pub fn is_inflected(word: &str) -> bool {
+    is_simple_plural(word) || is_regular_past(word)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn plurals() {
+        for word in ["cards", "boxes", "plays"] {
+            assert!(is_simple_plural(word), "{word}");
+        }
+        for word in ["abyss", "bonus", "oasis", "chaos", "cigar"] {
+            assert!(!is_simple_plural(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn past_tenses() {
+        assert!(is_regular_past("baked"));
+        assert!(is_regular_past("aimed"));
+        assert!(!is_regular_past("greed"));
+        assert!(!is_regular_past("cigar"));
+        assert!(is_inflected("aimed"));
+        assert!(!is_inflected("speed"));
+    }
+}
@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use crate::tiles::Tileset;
+
+/// Accented vowels and the plain ones they fold to.
+const ACCENTED: [(&str, char); 12] = [
+    ("àáâãäåā", 'a'),
+    ("èéêëē", 'e'),
+    ("ìíîïī", 'i'),
+    ("òóôõöō", 'o'),
+    ("ùúûüū", 'u'),
+    ("ýÿ", 'y'),
+    ("ÀÁÂÃÄÅĀ", 'A'),
+    ("ÈÉÊËĒ", 'E'),
+    ("ÌÍÎÏĪ", 'I'),
+    ("ÒÓÔÕÖŌ", 'O'),
+    ("ÙÚÛÜŪ", 'U'),
+    ("ÝŸ", 'Y'),
+];
+
+/// The plain vowel for an accented one, or `c` itself.
+pub fn fold_accent(c: char) -> char {
+    ACCENTED
+        .iter()
+        .find(|(accented, _)| accented.contains(c))
+        .map_or(c, |&(_, plain)| plain)
+}
+
+/// `text` with accented vowels folded to plain ones, as many non-English
+/// clones match them. Other letters, such as "ñ" and "ç", are kept, since
+/// those games treat them as letters of their own.
+pub fn fold_accents(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().map(fold_accent).collect())
+}
+
+/// `c` as it was most likely meant: fullwidth forms as plain ASCII, curly
+/// quotes as straight ones, and dashes as hyphens. Invisible characters
+/// become `None`.
+pub fn plain_char(c: char) -> Option<char> {
+    match c {
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0),
+        '\u{3000}' => Some(' '),
+        '‘' | '’' | '‚' | '‛' | '′' => Some('\''),
+        '“' | '”' | '„' | '″' => Some('"'),
+        '‐' | '‑' | '‒' | '–' | '—' | '−' => Some('-'),
+        '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => None,
+        c => Some(c),
+    }
+}
+
+/// `input` as typed, with [`plain_char`] applied and surrounding whitespace
+/// trimmed.
+pub fn input(input: &str) -> String {
+    input
+        .chars()
+        .filter_map(plain_char)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Turns typed words into the form word lists were loaded in.
+#[derive(Clone, Debug, Default)]
+pub struct Normalizer {
+    fold_accents: bool,
+    tiles: Tileset,
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold accented vowels, as [`Dictionary::fold_accents`] does.
+    ///
+    /// [`Dictionary::fold_accents`]: crate::dictionary::Dictionary::fold_accents
+    pub fn with_folded_accents(mut self, fold: bool) -> Self {
+        self.fold_accents = fold;
+        self
+    }
+
+    /// Encode `tiles`, as [`Dictionary::encode_tiles`] does.
+    ///
+    /// [`Dictionary::encode_tiles`]: crate::dictionary::Dictionary::encode_tiles
+    pub fn with_tiles(mut self, tiles: Tileset) -> Self {
+        self.tiles = tiles;
+        self
+    }
+
+    pub fn tiles(&self) -> &Tileset {
+        &self.tiles
+    }
+
+    /// The ways `typed` could be meant, best first: as typed, in case the
+    /// alphabet has capitals of its own, then lowercased. Tiles are encoded
+    /// last, since their symbols are capitals.
+    pub fn word(&self, typed: &str) -> [String; 2] {
+        let typed = input(typed);
+        let typed = if self.fold_accents {
+            fold_accents(&typed)
+        } else {
+            Cow::Borrowed(typed.as_str())
+        };
+
+        [
+            self.tiles.encode(&typed).into_owned(),
+            self.tiles.encode(&typed.to_lowercase()).into_owned(),
+        ]
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn accents() {
+        assert_eq!(fold_accents("árbol"), "arbol");
+        assert_eq!(fold_accents("CAFÉ élève"), "CAFE eleve");
+        assert_eq!(fold_accents("niño"), "niño");
+        assert!(matches!(fold_accents("cigar"), Cow::Borrowed("cigar")));
+    }
+
+    #[test]
+    fn typed() {
+        assert_eq!(input("  ｃｉｇａｒ\u{3000}"), "cigar");
+        assert_eq!(input("rock’n\u{200b}roll – yes"), "rock'nroll - yes");
+
+        let normalizer = Normalizer::new();
+        assert_eq!(normalizer.word(" CIGAR\n"), ["CIGAR", "cigar"]);
+        assert_eq!(normalizer.word("ÉLÈVE")[1], "élève");
+
+        let tiles = Tileset::new(["ll"], &Default::default()).unwrap();
+        let normalizer = Normalizer::new()
+            .with_folded_accents(true)
+            .with_tiles(tiles);
+        assert_eq!(normalizer.word("LLÁVES"), ["LLAVES", "Aaves"]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{guesser::Correctness, model::AnswerModel, strategy::Strategy};
+
+/// How many answers are played out when estimating the odds, unless there
+/// are fewer candidates than that.
+pub const SAMPLES: usize = 100;
+
+/// The chance that `strategy` finds the answer among `candidates` within
+/// `turns` guesses, weighing each candidate by its probability under
+/// `model`. The strategy may play any of `guesses`, or only the candidates
+/// left if there are none.
+///
+/// Each answer is played out along the decision tree the strategy builds,
+/// so the strategy chooses at each state only once, however many answers
+/// reach it. Above `samples` candidates, only an evenly spread sample of
+/// them are played out, though the strategy still chooses among them all.
+pub fn win_probability<'a>(
+    strategy: &dyn Strategy,
+    guesses: Option<&[&'a str]>,
+    candidates: &[&'a str],
+    model: &AnswerModel,
+    turns: usize,
+    samples: usize,
+) -> f64 {
+    if candidates.is_empty() {
+        return 0.0;
+    }
+    let step = candidates.len().div_ceil(samples.max(1));
+    let sampled: HashSet<&str> = candidates.iter().step_by(step).copied().collect();
+
+    let mut found = Vec::new();
+    let tree = Tree {
+        strategy,
+        guesses,
+        model,
+        sampled: &sampled,
+    };
+    tree.play(candidates, turns, &mut found);
+
+    let probability: HashMap<&str, f64> = candidates
+        .iter()
+        .copied()
+        .zip(model.distribution(candidates))
+        .collect();
+    let total: f64 = sampled.iter().map(|word| probability[word]).sum();
+    let won: f64 = found.iter().map(|word| probability[word]).sum();
+    won / total
+}
+
+/// What's needed to walk the strategy's decision tree.
+struct Tree<'t, 'a> {
+    strategy: &'t dyn Strategy,
+    guesses: Option<&'t [&'a str]>,
+    model: &'t AnswerModel<'t>,
+    sampled: &'t HashSet<&'t str>,
+}
+
+impl<'a> Tree<'_, 'a> {
+    /// Collect into `found` the sampled answers among `candidates` that are
+    /// found within `turns` guesses.
+    fn play(&self, candidates: &[&'a str], turns: usize, found: &mut Vec<&'a str>) {
+        if turns == 0 || !candidates.iter().any(|word| self.sampled.contains(word)) {
+            return;
+        }
+        let word = match candidates {
+            [only] => only,
+            _ => {
+                let guesses = self.guesses.unwrap_or(candidates);
+                self.strategy
+                    .choose_within(guesses, candidates, self.model, Some(turns))
+            },
+        };
+
+        let solved = Correctness::encode([Correctness::Correct; 5]);
+        let mut buckets: HashMap<u8, Vec<&'a str>> = HashMap::new();
+        for (code, &answer) in Correctness::compute_many(word, candidates)
+            .into_iter()
+            .zip(candidates)
+        {
+            buckets.entry(code).or_default().push(answer);
+        }
+        for (code, bucket) in buckets {
+            if code == solved {
+                found.extend(
+                    bucket
+                        .into_iter()
+                        .filter(|word| self.sampled.contains(word)),
+                );
+            } else {
+                self.play(&bucket, turns - 1, found);
+            }
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::strategy::Likeliest;
+
+    #[test]
+    fn odds() {
+        let model = AnswerModel::new([]);
+        // Every guess among these tells the answer apart from only itself.
+        let candidates = ["hatch", "catch", "match", "patch", "latch"];
+        let odds =
+            |turns, samples| win_probability(&Likeliest, None, &candidates, &model, turns, samples);
+
+        assert_eq!(odds(0, SAMPLES), 0.0);
+        assert_eq!(odds(1, SAMPLES), 0.2);
+        assert_eq!(odds(2, SAMPLES), 0.4);
+        assert_eq!(odds(5, SAMPLES), 1.0);
+        // A sample of two answers is enough when all are found.
+        assert_eq!(odds(5, 2), 1.0);
+    }
+}
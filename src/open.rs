@@ -0,0 +1,224 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::knowledge::KnowledgeState;
+
+/// Guesses for an answer no word list has, as when a clone picks its own
+/// words: any string of letters that fits every entry could be the answer.
+///
+/// Words from a secondary list that fit come first, in the list's order.
+/// After them come letter sequences, likeliest first by how often each letter
+/// appears at each position in the words the vocabulary learned from.
+#[derive(Clone, Debug)]
+pub struct OpenVocabulary {
+    words: Vec<String>,
+    /// The log of how often each symbol appears at each position, smoothed
+    /// so that no symbol is ruled out.
+    weights: [[f64; 128]; 5],
+    /// Every symbol seen in the words learned from.
+    symbols: Vec<u8>,
+}
+
+/// A sequence being built, with its weight so far and the most the rest of
+/// it could add.
+struct Partial {
+    bound: f64,
+    weight: f64,
+    letters: Vec<u8>,
+}
+
+impl PartialEq for Partial {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Partial {}
+
+impl PartialOrd for Partial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Partial {
+    /// The highest bound first, then the earliest sequence, so the search is
+    /// deterministic.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound
+            .total_cmp(&other.bound)
+            .then_with(|| other.letters.cmp(&self.letters))
+    }
+}
+
+impl OpenVocabulary {
+    /// A vocabulary that generates sequences looking like `model`'s words.
+    pub fn new<'w>(model: impl IntoIterator<Item = &'w str>) -> Self {
+        let mut counts = [[0u32; 128]; 5];
+        let mut seen = [false; 128];
+        let mut total = 0;
+        for word in model.into_iter().filter(|w| w.len() == 5 && w.is_ascii()) {
+            for (i, letter) in word.bytes().enumerate() {
+                counts[i][letter as usize] += 1;
+                seen[letter as usize] = true;
+            }
+            total += 1;
+        }
+
+        let symbols: Vec<u8> = (0..128).filter(|&s| seen[s as usize]).collect();
+        let mut weights = [[0.0; 128]; 5];
+        for (weights, counts) in weights.iter_mut().zip(&counts) {
+            for (weight, &count) in weights.iter_mut().zip(counts) {
+                *weight = ((count as f64 + 1.0) / (total + symbols.len()) as f64).ln();
+            }
+        }
+
+        Self {
+            words: Vec::new(),
+            weights,
+            symbols,
+        }
+    }
+
+    /// Try the words of a secondary list, such as a larger dictionary, before
+    /// any sequences.
+    pub fn with_words<'w>(mut self, words: impl IntoIterator<Item = &'w str>) -> Self {
+        self.words = words.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Up to `limit` answers that fit `knowledge`, likeliest first.
+    pub fn candidates(&self, knowledge: &KnowledgeState, limit: usize) -> Vec<String> {
+        let mut found: Vec<String> = self
+            .words
+            .iter()
+            .filter(|word| knowledge.allows(word))
+            .take(limit)
+            .cloned()
+            .collect();
+        if found.len() >= limit || !knowledge.is_consistent() {
+            return found;
+        }
+
+        // Each position's choices, which take in letters known to be in the
+        // answer even if the words learned from never had them, and the best
+        // weight any of them has, which bounds how well the rest of a
+        // sequence can do.
+        let required: Vec<(u8, usize)> = knowledge.required().collect();
+        let mut symbols = self.symbols.clone();
+        for &(letter, _) in &required {
+            if !symbols.contains(&letter) {
+                symbols.push(letter);
+            }
+        }
+        let fixed = knowledge.fixed();
+        let choices: Vec<Vec<u8>> = (0..5)
+            .map(|i| match fixed[i] {
+                Some(letter) => vec![letter],
+                None => {
+                    let excluded: Vec<u8> = knowledge.excluded_at(i).collect();
+                    symbols
+                        .iter()
+                        .copied()
+                        .filter(|s| knowledge.count(*s).1 > 0 && !excluded.contains(s))
+                        .collect()
+                },
+            })
+            .collect();
+        // A letter known to be in place costs nothing.
+        let weight = |i: usize, s: u8| match fixed[i] {
+            Some(_) => 0.0,
+            None => self.weights[i][s as usize],
+        };
+        let best: Vec<f64> = (0..5)
+            .map(|i| {
+                choices[i]
+                    .iter()
+                    .map(|&s| weight(i, s))
+                    .fold(f64::NEG_INFINITY, f64::max)
+            })
+            .collect();
+        let rest = |from: usize| best[from..].iter().sum::<f64>();
+
+        // Best first, with a bound that never underestimates, so complete
+        // sequences come off the heap in order of weight.
+        let mut heap = BinaryHeap::from([Partial {
+            bound: rest(0),
+            weight: 0.0,
+            letters: Vec::new(),
+        }]);
+        while let Some(partial) = heap.pop() {
+            let i = partial.letters.len();
+            if i == 5 {
+                let word = String::from_utf8_lossy(&partial.letters).into_owned();
+                if knowledge.allows_bytes(&partial.letters) && !found.contains(&word) {
+                    found.push(word);
+                    if found.len() >= limit {
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            for &s in &choices[i] {
+                let mut letters = partial.letters.clone();
+                letters.push(s);
+                let count = |l: u8| letters.iter().filter(|&&x| x == l).count();
+                // Too many copies of a letter, or too few places left for
+                // the letters still owed, can't lead anywhere.
+                let owed: usize = required
+                    .iter()
+                    .map(|&(l, min)| min.saturating_sub(count(l)))
+                    .sum();
+                if count(s) > knowledge.count(s).1 || owed > 4 - i {
+                    continue;
+                }
+                let weight = partial.weight + weight(i, s);
+                heap.push(Partial {
+                    bound: weight + rest(i + 1),
+                    weight,
+                    letters,
+                });
+            }
+        }
+        found
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::guesser::Guess;
+
+    #[test]
+    fn words_first() {
+        let open = OpenVocabulary::new(["hatch", "catch", "match"]).with_words(["latch", "hutch"]);
+        let knowledge = KnowledgeState::from_history(&[Guess::check("latch", "match")]);
+        let found = open.candidates(&knowledge, 3);
+        assert_eq!(found[0], "latch");
+        assert_eq!(found.len(), 3);
+        // Sequences fill in after the words, each fitting the feedback.
+        assert!(found[1..].iter().all(|word| knowledge.allows(word)));
+        assert!(!found[1..].contains(&"latch".to_string()));
+    }
+
+    #[test]
+    fn likeliest_sequences() {
+        let open = OpenVocabulary::new(["hatch", "catch", "match", "patch"]);
+        // Everything but the first letter is known, and m was ruled out.
+        let knowledge = KnowledgeState::from_history(&[Guess::check("zatch", "match")]);
+        let found = open.candidates(&knowledge, 2);
+        assert_eq!(found, ["catch", "hatch"]);
+        assert!(open
+            .candidates(&knowledge, 100)
+            .iter()
+            .all(|word| word.ends_with("atch") && !word.starts_with('m')));
+
+        // Feedback no word could give leaves nothing to guess.
+        let knowledge = KnowledgeState::from_history(&[
+            Guess::check("zatch", "match"),
+            Guess::check("zzzzz", "catch"),
+        ]);
+        assert!(open.candidates(&knowledge, 5).is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::guesser::Correctness;
+
+/// The best guess and its expected guesses, if there is a way to finish.
+type Solution<'a> = Option<(f64, &'a str)>;
+
+/// A way of finding every candidate: its expected guesses, the guess to
+/// play first, and the most guesses any candidate takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Plan<'a> {
+    expected: f64,
+    guess: &'a str,
+    worst: usize,
+}
+
+/// An exhaustive expectimax search for the guesses that find the answer in
+/// the fewest expected guesses, optionally within a bounded number of them.
+///
+/// Every allowed guess is tried at every turn, so the result is provably
+/// optimal, but the cost grows quickly with the number of candidates. The
+/// search cuts off any guess whose lower bound can't beat the best found so
+/// far, and remembers the best guess for every set of candidates it solves.
+///
+/// Sets are remembered by their sorted words, so the same candidates reached
+/// by guesses in a different order are only solved once, and a plan found
+/// with more guesses to spare is reused with fewer when it fits in them.
+/// Guesses that split the candidates exactly as an earlier one does are
+/// skipped, since they can only tie with it.
+pub struct Search<'a, 'g> {
+    /// Every word that may be played, or `None` if only candidates may be.
+    probes: Option<&'g [&'a str]>,
+    /// Each candidate's probability of being the answer, at the start.
+    probability: HashMap<&'a str, f64>,
+    /// The most guesses any answer may take, counting the next one.
+    depth: usize,
+    /// The plan for each sorted set of candidates at each depth solved so
+    /// far, or `None` if they can't be solved in time.
+    memo: HashMap<Vec<&'a str>, Vec<(usize, Option<Plan<'a>>)>>,
+}
+
+impl<'a, 'g> Search<'a, 'g> {
+    /// Search over guesses from `probes`, or only the candidates if `None`,
+    /// weighing each answer by `probability`.
+    pub fn new(probes: Option<&'g [&'a str]>, probability: HashMap<&'a str, f64>) -> Self {
+        Self {
+            probes,
+            probability,
+            depth: usize::MAX,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Weigh every one of `candidates` equally, so the expected guesses are
+    /// the average over them.
+    pub fn uniform(probes: Option<&'g [&'a str]>, candidates: &[&'a str]) -> Self {
+        let probability = candidates.iter().map(|&word| (word, 1.0)).collect();
+        Self::new(probes, probability)
+    }
+
+    /// Only consider ways of finding every answer within `depth` guesses.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// The fewest expected guesses to find the answer among `candidates`,
+    /// counting the next one, and the guess that achieves it. `None` if no
+    /// way of guessing finds every candidate within the depth.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(candidates = candidates.len()))
+    )]
+    pub fn solve(&mut self, candidates: &[&'a str]) -> Solution<'a> {
+        let plan = self.solve_within(&sorted(candidates), self.depth)?;
+        Some((plan.expected, plan.guess))
+    }
+
+    /// The most guesses any of `candidates` takes when each turn plays the
+    /// guess [`solve`](Self::solve) chooses.
+    pub fn worst(&mut self, candidates: &[&'a str]) -> Option<usize> {
+        let plan = self.solve_within(&sorted(candidates), self.depth)?;
+        Some(plan.worst)
+    }
+
+    /// The plan for `candidates`, which must be sorted, within `depth`.
+    fn solve_within(&mut self, candidates: &[&'a str], depth: usize) -> Option<Plan<'a>> {
+        if depth == 0 || candidates.is_empty() {
+            return None;
+        }
+        if let [only] = candidates {
+            return Some(Plan {
+                expected: 1.0,
+                guess: only,
+                worst: 1,
+            });
+        }
+        if depth == 1 {
+            return None;
+        }
+
+        // Every candidate can be found by guessing them in turn, so more
+        // guesses than candidates is as good as unlimited.
+        let depth = depth.min(candidates.len());
+        if let Some(solved) = self.recall(candidates, depth) {
+            return solved;
+        }
+
+        let probability = self.normalized(candidates);
+        let p = |word: &str| probability.get(word).copied().unwrap_or(0.0);
+
+        // Partition the candidates under every guess up front, so the most
+        // promising guesses are searched first and the rest can be cut off
+        // by their lower bounds.
+        let guesses = self.probes.unwrap_or(candidates);
+        let mut options: Vec<(f64, &'a str, Vec<Vec<&'a str>>)> = Vec::new();
+        let mut partitions: HashSet<Vec<Vec<&'a str>>> = HashSet::new();
+        for &guess in guesses {
+            let mut buckets: HashMap<[Correctness; 5], Vec<&'a str>> = HashMap::new();
+            for &answer in candidates {
+                if answer != guess {
+                    buckets
+                        .entry(Correctness::compute(answer, guess))
+                        .or_default()
+                        .push(answer);
+                }
+            }
+            // A guess that can't win and doesn't split anything is wasted,
+            // as is one that leaves more than one candidate on the last turn.
+            // Buckets are sorted so sums, and so ties, come out the same
+            // every run.
+            let mut buckets: Vec<Vec<&str>> = buckets.into_values().collect();
+            buckets.sort_unstable();
+            if buckets.len() == 1 && buckets[0].len() == candidates.len() {
+                continue;
+            }
+            if depth == 2 && buckets.iter().any(|bucket| bucket.len() > 1) {
+                continue;
+            }
+            // A guess that's a candidate is never in its own buckets, so two
+            // guesses only split alike if both or neither can win.
+            if !partitions.insert(buckets.clone()) {
+                continue;
+            }
+
+            // Every answer not found now takes at least one more guess, and
+            // two more unless it's the likeliest left in its bucket.
+            let mut bound = 1.0;
+            for bucket in &buckets {
+                let total: f64 = bucket.iter().map(|w| p(w)).sum();
+                let likeliest = bucket.iter().map(|w| p(w)).fold(0.0, f64::max);
+                bound += match bucket.len() {
+                    1 => total,
+                    _ => 2.0 * total - likeliest,
+                };
+            }
+            options.push((bound, guess, buckets));
+        }
+        options.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut best: Option<Plan<'a>> = None;
+        for (bound, guess, buckets) in options {
+            if best.is_some_and(|best| bound >= best.expected) {
+                break;
+            }
+
+            let mut expected = 1.0;
+            let mut worst = 1;
+            for bucket in buckets {
+                let total: f64 = bucket.iter().map(|w| p(w)).sum();
+                let Some(solved) = self.solve_within(&bucket, depth - 1) else {
+                    expected = f64::INFINITY;
+                    break;
+                };
+                expected += total * solved.expected;
+                worst = worst.max(1 + solved.worst);
+                if best.is_some_and(|best| expected >= best.expected) {
+                    break;
+                }
+            }
+            if expected.is_finite() && best.is_none_or(|best| expected < best.expected) {
+                best = Some(Plan {
+                    expected,
+                    guess,
+                    worst,
+                });
+            }
+        }
+
+        self.memo
+            .entry(candidates.to_vec())
+            .or_default()
+            .push((depth, best));
+        best
+    }
+
+    /// What's already known of `candidates` within `depth`, if enough is.
+    /// Failing with more guesses means failing with fewer, and the best plan
+    /// with more guesses is still the best with fewer if it fits in them.
+    fn recall(&self, candidates: &[&'a str], depth: usize) -> Option<Option<Plan<'a>>> {
+        let solved = self.memo.get(candidates)?;
+        solved.iter().find_map(|&(within, plan)| match plan {
+            _ if within == depth => Some(plan),
+            None if within > depth => Some(None),
+            Some(found) if within > depth && found.worst <= depth => Some(plan),
+            _ => None,
+        })
+    }
+
+    /// The probability of each of `candidates` given that the answer is one
+    /// of them, evenly split if none has any.
+    fn normalized(&self, candidates: &[&'a str]) -> HashMap<&'a str, f64> {
+        let p = |word: &&str| self.probability.get(word).copied().unwrap_or(0.0);
+        let total: f64 = candidates.iter().map(p).sum();
+        candidates
+            .iter()
+            .map(|word| {
+                if total > 0.0 {
+                    (*word, p(word) / total)
+                } else {
+                    (*word, 1.0 / candidates.len() as f64)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `candidates` in a canonical order, so that the same set is always
+/// remembered under the same key.
+fn sorted<'a>(candidates: &[&'a str]) -> Vec<&'a str> {
+    let mut sorted = candidates.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn expected_guesses() {
+        let candidates = ["hatch", "catch"];
+        let probability = [("hatch", 0.75), ("catch", 0.25)].into_iter().collect();
+        let mut search = Search::new(None, probability);
+        // Guess the likelier first: one guess 3/4 of the time, else two.
+        assert_eq!(search.solve(&candidates), Some((1.25, "hatch")));
+
+        let candidates = ["hatch", "catch", "match", "patch", "latch"];
+        let mut search = Search::uniform(None, &candidates);
+        // Candidates only ever rule themselves out, so this is 1 + 2 + ... + 5
+        // guesses spread over five answers.
+        assert_eq!(search.solve(&candidates).unwrap().0, 3.0);
+        assert_eq!(search.worst(&candidates), Some(5));
+    }
+
+    #[test]
+    fn depth() {
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+
+        // Only a probe finds all four within two guesses.
+        let mut search = Search::uniform(Some(&guesses), &candidates).with_depth(2);
+        assert_eq!(search.solve(&candidates), Some((2.0, "chomp")));
+        assert_eq!(search.worst(&candidates), Some(2));
+
+        let mut search = Search::uniform(None, &candidates).with_depth(3);
+        assert_eq!(search.solve(&candidates), None);
+        assert_eq!(search.worst(&candidates), None);
+        let mut search = Search::uniform(None, &candidates).with_depth(4);
+        assert_eq!(search.solve(&candidates).unwrap().0, 2.5);
+    }
+
+    #[test]
+    fn transpositions() {
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        let mut search = Search::uniform(Some(&guesses), &candidates).with_depth(4);
+        let solved = search.solve(&candidates);
+
+        // The same set in another order is the same state.
+        let mut shuffled = candidates;
+        shuffled.reverse();
+        let before = search.memo.len();
+        assert_eq!(search.solve(&shuffled), solved);
+        assert_eq!(search.memo.len(), before);
+
+        // The plan found with four guesses to spare takes at most two, so it
+        // answers for two without searching again.
+        let entries = search.memo[&sorted(&candidates)].len();
+        assert_eq!(search.worst(&candidates), Some(2));
+        let mut search_at_two = Search::uniform(Some(&guesses), &candidates).with_depth(2);
+        assert_eq!(search_at_two.solve(&candidates), solved);
+        search.depth = 2;
+        assert_eq!(search.solve(&candidates), solved);
+        assert_eq!(search.memo[&sorted(&candidates)].len(), entries);
+    }
+}
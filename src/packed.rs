@@ -0,0 +1,66 @@
+/// Words stored back to back in one buffer, five bytes each.
+///
+/// A `&[&str]` is a list of pointers into wherever each word happens to
+/// live, so every word a hot loop looks at is a separate load from somewhere
+/// else in memory. Packing the words first means a loop over them reads one
+/// contiguous block instead, and a word is found by its index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Packed {
+    words: Vec<[u8; 5]>,
+}
+
+impl Packed {
+    /// Pack `words`, in order. Words are expected to be five bytes; any that
+    /// are shorter are padded with zeros, which no alphabet contains.
+    pub fn new(words: &[&str]) -> Self {
+        Self {
+            words: words
+                .iter()
+                .map(|word| {
+                    let mut packed = [0; 5];
+                    for (slot, byte) in packed.iter_mut().zip(word.bytes()) {
+                        *slot = byte;
+                    }
+                    packed
+                })
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> &[u8; 5] {
+        &self.words[i]
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[u8; 5]> + '_ {
+        self.words.iter()
+    }
+
+    /// Every word, back to back.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.words.as_flattened()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn contiguous() {
+        let packed = Packed::new(&["cigar", "rebut", "sis"]);
+        assert_eq!(packed.len(), 3);
+        assert_eq!(packed.get(1), b"rebut");
+        assert_eq!(packed.get(2), b"sis\0\0");
+        assert_eq!(&packed.as_bytes()[..10], b"cigarrebut");
+        assert!(Packed::new(&[]).is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Instant,
+};
+
+/// Below this much work (guesses × candidates), spawning threads costs more
+/// than it saves.
+const PARALLEL_THRESHOLD: usize = 50_000;
+
+/// Items each thread scores between checks of a deadline in [`best_until`].
+const BATCH_PER_THREAD: usize = 32;
+
+/// The thread count set with [`set_threads`], or zero for the default.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of worker threads to use for parallel work: as many as set
+/// with [`set_threads`], or else one per available core.
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    }
+}
+
+/// Use at most `threads` worker threads for parallel work from now on. One
+/// keeps all the work on the calling thread, for repeatable timings.
+pub fn set_threads(threads: NonZeroUsize) {
+    THREADS.store(threads.get(), Ordering::Relaxed);
+}
+
+/// Apply `f` to every item, in order, splitting the items across threads when
+/// `work` is large enough to be worth it.
+pub fn map<T, R, F>(items: &[T], work: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads();
+    if threads <= 1 || work < PARALLEL_THRESHOLD || items.len() < threads {
+        return items.iter().map(f).collect();
+    }
+
+    map_across(items, threads, &f)
+}
+
+fn map_across<T, R, F>(items: &[T], threads: usize, f: &F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+/// The order a [`Follow`] hands results on in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Each as soon as it's done, whichever thread finished it.
+    #[default]
+    Completion,
+    /// Each once every earlier item's has been handed on, holding back any
+    /// that finish early.
+    Input,
+}
+
+/// Hands on results from any thread as items complete, so a long run can
+/// show its progress rather than waiting for every item.
+pub struct Follow<M, S> {
+    order: Order,
+    pending: Mutex<Pending<M, S>>,
+}
+
+struct Pending<M, S> {
+    /// The index of the earliest item not yet handed on.
+    next: usize,
+    /// Results that finished ahead of an earlier item, by index.
+    held: BTreeMap<usize, M>,
+    sink: S,
+}
+
+impl<M, S: FnMut(M)> Follow<M, S> {
+    /// Hand each result to `sink`, in `order`.
+    pub fn new(order: Order, sink: S) -> Self {
+        Self {
+            order,
+            pending: Mutex::new(Pending {
+                next: 0,
+                held: BTreeMap::new(),
+                sink,
+            }),
+        }
+    }
+
+    /// Record that the item at `index` is done with `result`, handing on
+    /// whatever its order now allows.
+    pub fn done(&self, index: usize, result: M) {
+        let mut pending = self.pending.lock().expect("follow lock poisoned");
+        let pending = &mut *pending;
+        if self.order == Order::Completion {
+            (pending.sink)(result);
+            return;
+        }
+
+        pending.held.insert(index, result);
+        while let Some(result) = pending.held.remove(&pending.next) {
+            (pending.sink)(result);
+            pending.next += 1;
+        }
+    }
+}
+
+/// The item with the highest score, or `None` if there are no items.
+///
+/// `work` is an estimate of the cost of scoring every item, used to decide
+/// whether to split the items across threads. Ties always go to the earliest
+/// item, so the result is the same however the work is divided.
+pub fn best_by<T, F>(items: &[T], work: usize, score: F) -> Option<&T>
+where
+    T: Sync,
+    F: Fn(&T) -> f64 + Sync,
+{
+    let threads = threads();
+    if threads <= 1 || work < PARALLEL_THRESHOLD || items.len() < threads {
+        return best_in(items, &score);
+    }
+
+    best_across(items, threads, &score)
+}
+
+/// The item with the highest score, as [`best_by`], but scoring `items` in
+/// order, a batch at a time, and stopping once `deadline` has passed. At
+/// least one batch is always scored, so the earliest items are always
+/// considered; put the likeliest best first.
+pub fn best_until<T, F>(items: &[T], work: usize, deadline: Option<Instant>, score: F) -> Option<&T>
+where
+    T: Sync,
+    F: Fn(&T) -> f64 + Sync,
+{
+    let Some(deadline) = deadline else {
+        return best_by(items, work, score);
+    };
+
+    let batch = threads() * BATCH_PER_THREAD;
+    let work_per_item = work / items.len().max(1);
+    let mut best: Option<(&T, f64)> = None;
+    for chunk in items.chunks(batch) {
+        if let Some(item) = best_by(chunk, work_per_item * chunk.len(), &score) {
+            let score = score(item);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((item, score));
+            }
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+    }
+
+    best.map(|(item, _)| item)
+}
+
+/// Score `items` split across `threads` threads.
+fn best_across<'a, T, F>(items: &'a [T], threads: usize, score: &F) -> Option<&'a T>
+where
+    T: Sync,
+    F: Fn(&T) -> f64 + Sync,
+{
+    let chunk_size = items.len().div_ceil(threads);
+    let bests: Vec<Option<(&T, f64)>> = thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || best_in(chunk, score).map(|item| (item, score(item)))))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("scoring thread panicked"))
+            .collect()
+    });
+
+    let mut best: Option<(&T, f64)> = None;
+    for (item, score) in bests.into_iter().flatten() {
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((item, score));
+        }
+    }
+
+    best.map(|(item, _)| item)
+}
+
+fn best_in<'a, T>(items: &'a [T], score: &impl Fn(&T) -> f64) -> Option<&'a T> {
+    let mut best: Option<(&T, f64)> = None;
+    for item in items {
+        let score = score(item);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((item, score));
+        }
+    }
+
+    best.map(|(item, _)| item)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn earliest_tie_wins() {
+        let items: Vec<usize> = (0..1000).collect();
+        let score = |&i: &usize| (i % 10) as f64;
+
+        assert_eq!(best_by(&items, 0, score), Some(&9));
+        assert_eq!(best_across(&items, 4, &score), Some(&9));
+        assert_eq!(best_across(&items, 7, &score), Some(&9));
+    }
+
+    #[test]
+    fn map_in_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let expected: Vec<usize> = items.iter().map(|i| i * 2).collect();
+
+        assert_eq!(map(&items, 0, |i| i * 2), expected);
+        assert_eq!(map_across(&items, 3, &|i| i * 2), expected);
+    }
+
+    #[test]
+    fn best_until_deadline() {
+        let items: Vec<usize> = (0..100_000).collect();
+        let score = |&i: &usize| i as f64;
+        assert_eq!(best_until(&items, 0, None, score), Some(&99_999));
+
+        // Past the deadline, only the first batch is scored.
+        let past = Some(Instant::now());
+        let best = *best_until(&items, 0, past, score).unwrap();
+        assert_eq!(best, threads() * BATCH_PER_THREAD - 1);
+    }
+
+    #[test]
+    fn follow_orders() {
+        let mut seen = Vec::new();
+        let follow = Follow::new(Order::Input, |i| seen.push(i));
+        for i in [2, 0, 3, 1, 4] {
+            follow.done(i, i);
+        }
+        drop(follow);
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+
+        let mut seen = Vec::new();
+        let follow = Follow::new(Order::Completion, |i| seen.push(i));
+        for i in [2, 0, 3, 1] {
+            follow.done(i, i);
+        }
+        drop(follow);
+        assert_eq!(seen, [2, 0, 3, 1]);
+
+        // From worker threads, every result still arrives once.
+        let items: Vec<usize> = (0..100).collect();
+        let seen = Mutex::new(Vec::new());
+        let follow = Follow::new(Order::Input, |i| seen.lock().unwrap().push(i));
+        map_across(&items, 4, &|&i| follow.done(i, i));
+        assert_eq!(*seen.lock().unwrap(), items);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(best_by(&[] as &[usize], usize::MAX, |_| 0.0), None);
+    }
+}
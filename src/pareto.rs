@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::report::Report;
+
+/// One strategy's run, placed by every measure there is to trade off.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+    pub name: String,
+    pub average: f32,
+    pub failures: usize,
+    /// The wall-clock time of the run over the answers solved.
+    pub per_solve: Duration,
+}
+
+impl Point {
+    pub fn new(name: &str, report: &Report) -> Self {
+        let answers = report.outcomes().len().max(1);
+        Self {
+            name: name.to_string(),
+            average: report.average(),
+            failures: report.failures().count(),
+            per_solve: report.elapsed() / answers as u32,
+        }
+    }
+
+    /// Whether this is no worse than `other` on any measure, and better on
+    /// at least one.
+    pub fn dominates(&self, other: &Point) -> bool {
+        let no_worse = self.average <= other.average
+            && self.failures <= other.failures
+            && self.per_solve <= other.per_solve;
+        let better = self.average < other.average
+            || self.failures < other.failures
+            || self.per_solve < other.per_solve;
+        no_worse && better
+    }
+}
+
+/// For each point, the first point that dominates it, or `None` if none
+/// does and it's on the Pareto frontier.
+pub fn dominated_by(points: &[Point]) -> Vec<Option<usize>> {
+    points
+        .iter()
+        .map(|point| points.iter().position(|other| other.dominates(point)))
+        .collect()
+}
+
+/// Print every point's measures and what, if anything, beats it on all of
+/// them, then the strategies on the frontier: those each worth picking for
+/// some weighing of score, failures, and speed.
+pub fn print(points: &[Point]) {
+    let width = points
+        .iter()
+        .map(|point| point.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    println!(
+        "{:<width$} {:>10} {:>8} {:>12}  frontier",
+        "strategy", "average", "missed", "per solve"
+    );
+
+    let dominated = dominated_by(points);
+    for (point, by) in points.iter().zip(&dominated) {
+        let verdict = match by {
+            Some(other) => format!("beaten by {}", points[*other].name),
+            None => "yes".to_string(),
+        };
+        println!(
+            "{:<width$} {:>10.4} {:>8} {:>12}  {verdict}",
+            point.name,
+            point.average,
+            point.failures,
+            format!("{:.2?}", point.per_solve),
+        );
+    }
+
+    let frontier: Vec<&str> = points
+        .iter()
+        .zip(&dominated)
+        .filter(|(_, by)| by.is_none())
+        .map(|(point, _)| point.name.as_str())
+        .collect();
+    println!();
+    println!("pareto-optimal: {}", frontier.join(", "));
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn point(name: &str, average: f32, failures: usize, millis: u64) -> Point {
+        Point {
+            name: name.to_string(),
+            average,
+            failures,
+            per_solve: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn frontier() {
+        let points = [
+            point("likeliest", 3.6, 16, 1),
+            point("entropy", 3.5, 5, 4),
+            point("rollout", 3.5, 6, 40),
+            point("coverage", 3.7, 20, 1),
+            point("twin", 3.5, 5, 4),
+        ];
+        assert_eq!(dominated_by(&points), [None, None, Some(1), Some(0), None]);
+        assert!(!points[1].dominates(&points[4]));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crossterm::style::Color;
+
+use crate::{console, guesser::Correctness};
+
+/// Why a feedback pattern couldn't be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidTile(char),
+    WrongLength(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidTile(c) => write!(f, "invalid feedback tile: {c:?}"),
+            ParseError::WrongLength(n) => write!(f, "feedback needs 5 tiles, got {n}"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Renders a feedback pattern as a row of share-grid emoji.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tiles(pub [Correctness; 5]);
+
+impl fmt::Display for Tiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0 {
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Correctness {
+    /// The share-grid emoji for this tile, in the current [`palette`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.emoji(palette()))
+    }
+}
+
+/// How tiles are drawn, for players who can't easily tell green from
+/// yellow. The same palette colors share grids, the terminal board, and the
+/// keyboard, so set it once with [`set_palette`] before drawing anything.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Palette {
+    /// Orange and blue in place of green and yellow, as in the game's
+    /// high-contrast mode.
+    pub high_contrast: bool,
+    /// Mark each colored tile with a shape as well as its color: `■` for
+    /// correct, `▲` for misplaced, and `·` for wrong. Share grids are emoji,
+    /// which have no room for a mark, so this only changes boards on screen.
+    pub symbols: bool,
+}
+
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+
+/// The palette tiles are drawn in: as set with [`set_palette`], or else the
+/// game's own colors.
+pub fn palette() -> Palette {
+    let bits = PALETTE.load(Ordering::Relaxed);
+    Palette {
+        high_contrast: bits & 1 != 0,
+        symbols: bits & 2 != 0,
+    }
+}
+
+pub fn set_palette(palette: Palette) {
+    let bits = u8::from(palette.high_contrast) | u8::from(palette.symbols) << 1;
+    PALETTE.store(bits, Ordering::Relaxed);
+}
+
+impl Correctness {
+    /// The share-grid emoji for this tile in `palette`.
+    pub fn emoji(self, palette: Palette) -> &'static str {
+        match (self, palette.high_contrast) {
+            (Correctness::Correct, false) => "🟩",
+            (Correctness::Misplaced, false) => "🟨",
+            (Correctness::Correct, true) => "🟧",
+            (Correctness::Misplaced, true) => "🟦",
+            (Correctness::Wrong, _) => "⬛",
+        }
+    }
+
+    /// The shape that marks this tile for [`Palette::symbols`].
+    pub fn symbol(self) -> char {
+        match self {
+            Correctness::Correct => '■',
+            Correctness::Misplaced => '▲',
+            Correctness::Wrong => '·',
+        }
+    }
+
+    /// The text and background colors of a tile in the current [`palette`].
+    pub fn colors(self) -> (Color, Color) {
+        self.colors_in(palette())
+    }
+
+    /// The text and background colors of a tile in `palette`.
+    pub fn colors_in(self, palette: Palette) -> (Color, Color) {
+        match (self, palette.high_contrast) {
+            (Correctness::Correct, false) => (Color::Black, Color::DarkGreen),
+            (Correctness::Misplaced, false) => (Color::Black, Color::DarkYellow),
+            (Correctness::Correct, true) => (Color::Black, Color::AnsiValue(208)),
+            (Correctness::Misplaced, true) => (Color::Black, Color::AnsiValue(39)),
+            (Correctness::Wrong, _) => (Color::White, Color::DarkGrey),
+        }
+    }
+
+    /// `label` on this tile's color, three columns wide, as the terminal
+    /// board draws it in the current [`palette`].
+    pub fn paint(self, label: impl fmt::Display) -> String {
+        self.paint_in(label, palette())
+    }
+
+    /// `label` on this tile's color in `palette`. Tiles are marked with
+    /// their shapes when the terminal can't show colors, whatever the
+    /// palette, so they can still be told apart.
+    pub fn paint_in(self, label: impl fmt::Display, palette: Palette) -> String {
+        let marked = palette.symbols || !console::colors();
+        let mark = if marked { self.symbol() } else { ' ' };
+        let (foreground, background) = self.colors_in(palette);
+        console::paint(format!(" {label}{mark}"), foreground, background)
+    }
+}
+
+impl Correctness {
+    /// Parse a single feedback tile.
+    ///
+    /// Accepts color letters (`G`reen, `Y`ellow, `B`lack or `X`), the letters
+    /// of the `mask!` notation (`C`, `M`, `W`), in either case, digits (`2`
+    /// correct, `1` misplaced, `0` wrong), and the share-grid emoji, including
+    /// the high-contrast orange and blue squares, as well as the
+    /// [`Palette::symbols`] marks.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'G' | 'g' | 'C' | 'c' | '2' | '🟩' | '🟧' | '■' => Some(Correctness::Correct),
+            'Y' | 'y' | 'M' | 'm' | '1' | '🟨' | '🟦' | '▲' => Some(Correctness::Misplaced),
+            'B' | 'b' | 'X' | 'x' | 'W' | 'w' | '0' | '.' | '-' | '·' | '⬛' | '⬜' => {
+                Some(Correctness::Wrong)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Parse a feedback pattern such as `GYBBG`, `21002`, or `🟩🟨⬛⬛🟩`.
+/// Whitespace is ignored, as are emoji variation selectors.
+pub fn parse(s: &str) -> Result<[Correctness; 5], ParseError> {
+    let tiles: Vec<Correctness> = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{fe0f}')
+        .map(|c| Correctness::from_char(c).ok_or(ParseError::InvalidTile(c)))
+        .collect::<Result<_, _>>()?;
+
+    let len = tiles.len();
+    tiles.try_into().map_err(|_| ParseError::WrongLength(len))
+}
+
+/// Parse a feedback pattern, in any form [`parse`] reads, into its code.
+pub fn parse_code(s: &str) -> Result<u8, ParseError> {
+    parse(s).map(Correctness::encode)
+}
+
+/// The pattern with `code` in letters, such as `GYBBG`, which [`parse`]
+/// reads back, or `None` if no pattern has that code.
+pub fn format_code(code: u8) -> Option<String> {
+    let mask = Correctness::decode(code)?;
+    Some(
+        mask.iter()
+            .map(|c| match c {
+                Correctness::Correct => 'G',
+                Correctness::Misplaced => 'Y',
+                Correctness::Wrong => 'B',
+            })
+            .collect(),
+    )
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+    #[test]
+    fn letters() {
+        assert_eq!(parse("GYBBG"), Ok([C, M, W, W, C]));
+        assert_eq!(parse("gybxw"), Ok([C, M, W, W, W]));
+        assert_eq!(parse(" G Y B B G\n"), Ok([C, M, W, W, C]));
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(parse("21002"), Ok([C, M, W, W, C]));
+    }
+
+    #[test]
+    fn emoji() {
+        assert_eq!(parse("🟩🟨⬛⬜🟩"), Ok([C, M, W, W, C]));
+        assert_eq!(parse("🟧🟦⬛⬛🟧"), Ok([C, M, W, W, C]));
+        assert_eq!(parse("⬛\u{fe0f}⬛⬛⬛🟩"), Ok([W, W, W, W, C]));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Tiles([C, M, W, W, C]).to_string(), "🟩🟨⬛⬛🟩");
+        assert_eq!(
+            parse(&Tiles([C, M, W, W, C]).to_string()),
+            Ok([C, M, W, W, C])
+        );
+    }
+
+    #[test]
+    fn palettes() {
+        let contrast = Palette {
+            high_contrast: true,
+            symbols: false,
+        };
+        let tiles: String = [C, M, W].iter().map(|c| c.emoji(contrast)).collect();
+        assert_eq!(tiles, "🟧🟦⬛");
+        assert_eq!(parse(&format!("{tiles}🟧🟧")), Ok([C, M, W, C, C]));
+        assert_eq!(
+            C.paint_in('A', Palette::default()),
+            "\x1b[48;5;2m\x1b[38;5;0m A \x1b[49m\x1b[39m"
+        );
+
+        let symbols = Palette {
+            symbols: true,
+            ..contrast
+        };
+        assert_eq!(
+            M.paint_in('A', symbols),
+            "\x1b[48;5;39m\x1b[38;5;0m A▲\x1b[49m\x1b[39m"
+        );
+        assert_eq!(parse("■▲··■"), Ok([C, M, W, W, C]));
+    }
+
+    #[test]
+    fn codes() {
+        assert_eq!(
+            parse_code("21002"),
+            Ok(Correctness::encode([C, M, W, W, C]))
+        );
+        assert_eq!(parse_code("🟩🟨⬛⬛🟩"), parse_code("GYBBG"));
+        assert_eq!(
+            format_code(parse_code("gybbg").unwrap()).as_deref(),
+            Some("GYBBG")
+        );
+        assert_eq!(format_code(0).as_deref(), Some("BBBBB"));
+        assert_eq!(format_code(243), None);
+        assert_eq!(parse_code("GYBB"), Err(ParseError::WrongLength(4)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("GYBB"), Err(ParseError::WrongLength(4)));
+        assert_eq!(parse("GYBBGG"), Err(ParseError::WrongLength(6)));
+        assert_eq!(parse("GYBBZ"), Err(ParseError::InvalidTile('Z')));
+        assert_eq!(parse("🟥⬛⬛⬛⬛"), Err(ParseError::InvalidTile('🟥')));
+    }
+}
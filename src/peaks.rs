@@ -0,0 +1,83 @@
+use std::fmt;
+
+use crate::feedback::Feedback;
+
+/// A tile in Wordle Peaks, which says where the answer's letter at that
+/// position falls in the alphabet relative to the guess's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Peak {
+    Correct,
+    /// The answer's letter comes before the guess's.
+    Earlier,
+    /// The answer's letter comes after the guess's.
+    Later,
+}
+
+impl Peak {
+    pub fn compute(answer: &str, word: &str) -> [Self; 5] {
+        let mut peaks = [Peak::Correct; 5];
+        for (peak, (a, g)) in peaks.iter_mut().zip(answer.bytes().zip(word.bytes())) {
+            *peak = match a.cmp(&g) {
+                std::cmp::Ordering::Equal => Peak::Correct,
+                std::cmp::Ordering::Less => Peak::Earlier,
+                std::cmp::Ordering::Greater => Peak::Later,
+            };
+        }
+        peaks
+    }
+}
+
+impl fmt::Display for Peak {
+    /// `=` for a match, otherwise which way the answer's letter lies.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Peak::Correct => "=",
+            Peak::Earlier => "<",
+            Peak::Later => ">",
+        })
+    }
+}
+
+impl Feedback for [Peak; 5] {
+    #[inline]
+    fn compute(answer: &str, word: &str) -> Self {
+        Peak::compute(answer, word)
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.iter().all(|&p| p == Peak::Correct)
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::{feedback::Solver, model::AnswerModel};
+    #[allow(unused_imports)]
+    use Peak::{Correct as C, Earlier as E, Later as L};
+
+    #[test]
+    fn compute() {
+        assert_eq!(Peak::compute("cigar", "cigar"), [C; 5]);
+        assert_eq!(Peak::compute("cigar", "rebut"), [E, L, L, E, E]);
+        let tiles: String = Peak::compute("cigar", "salet")
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(tiles, "<><<<");
+    }
+
+    #[test]
+    fn narrows_by_range() {
+        // Only "m" lies between "h" and "p" in the first position.
+        let model = AnswerModel::new([]);
+        let candidates = ["catch", "hatch", "match", "patch"];
+        let mut solver = Solver::<[Peak; 5]>::new(&candidates);
+
+        assert_eq!(solver.apply("hatch", [L, C, C, C, C]), 2);
+        assert_eq!(solver.apply("patch", [E, C, C, C, C]), 1);
+        assert_eq!(solver.best_guess(&candidates, &model), Some("match"));
+    }
+}
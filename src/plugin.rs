@@ -0,0 +1,280 @@
+//! Strategies loaded at run time from a shared library, so a new guessing
+//! policy can be benchmarked without patching the crate.
+//!
+//! A plugin is a `cdylib` (or any shared object) exporting two functions
+//! with the C ABI:
+//!
+//! ```c
+//! // The strategy's name, as reports print it. Read once, when loaded.
+//! const char *wordle_strategy_name(void);
+//!
+//! // The index in `guesses` of the word to play next. `guesses` holds
+//! // `guess_count` words and `candidates` holds `candidate_count`, each five
+//! // bytes back to back with no terminators; `probabilities` gives each
+//! // candidate's chance of being the answer. `turns` is how many guesses are
+//! // left counting this one, or 0 if the game has no limit.
+//! size_t wordle_strategy_choose(const uint8_t *guesses, size_t guess_count,
+//!                               const uint8_t *candidates, size_t candidate_count,
+//!                               const double *probabilities, size_t turns);
+//! ```
+//!
+//! The buffers are only valid for the length of the call. A plugin is never
+//! unloaded. An index past the end of `guesses` plays the first candidate
+//! instead, with a warning the first time.
+
+use std::{
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{model::AnswerModel, packed::Packed, strategy::Strategy};
+
+/// The name a strategy is given as, as in `--strategy plugin:./libmine.so`.
+pub const PREFIX: &str = "plugin:";
+
+const NAME_SYMBOL: &CStr = c"wordle_strategy_name";
+const CHOOSE_SYMBOL: &CStr = c"wordle_strategy_choose";
+
+/// Resolve every symbol when loading, so a missing one fails then rather
+/// than mid-game.
+const RTLD_NOW: c_int = 2;
+
+extern "C" {
+    fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlerror() -> *mut c_char;
+}
+
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type ChooseFn = unsafe extern "C" fn(
+    guesses: *const u8,
+    guess_count: usize,
+    candidates: *const u8,
+    candidate_count: usize,
+    probabilities: *const f64,
+    turns: usize,
+) -> usize;
+
+/// Why a plugin couldn't be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// The library couldn't be opened, with the loader's reason.
+    Open(String),
+    /// The library doesn't export this function.
+    Missing(&'static str),
+    /// The name it gave isn't UTF-8, or is empty.
+    BadName,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Open(reason) => write!(f, "couldn't load the plugin: {reason}"),
+            LoadError::Missing(symbol) => write!(f, "the plugin doesn't export {symbol}"),
+            LoadError::BadName => f.write_str("the plugin's name isn't UTF-8 text"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// A strategy whose choices come from a shared library.
+pub struct Plugin {
+    name: &'static str,
+    choose: ChooseFn,
+    /// Whether it's been caught choosing a guess that isn't there.
+    warned: AtomicBool,
+}
+
+impl Plugin {
+    /// Load the plugin at `path`. A path without a slash is looked up where
+    /// the system looks for libraries, so a plugin in the current directory
+    /// is given as `./libmine.so`.
+    pub fn load(path: &str) -> Result<Self, LoadError> {
+        let path = CString::new(path).map_err(|_| LoadError::Open("bad path".to_string()))?;
+        // SAFETY: the path is a valid C string. Loading runs the library's
+        // initializers, which is the trust a plugin asks for.
+        let handle = unsafe { dlopen(path.as_ptr(), RTLD_NOW) };
+        if handle.is_null() {
+            return Err(LoadError::Open(last_error()));
+        }
+
+        let symbol = |name: &'static CStr| {
+            // SAFETY: the handle is open and is never closed.
+            let address = unsafe { dlsym(handle, name.as_ptr()) };
+            if address.is_null() {
+                Err(LoadError::Missing(
+                    name.to_str().expect("symbol names are ASCII"),
+                ))
+            } else {
+                Ok(address)
+            }
+        };
+        // SAFETY: the ABI above gives these symbols these signatures.
+        let name: NameFn = unsafe { std::mem::transmute(symbol(NAME_SYMBOL)?) };
+        let choose: ChooseFn = unsafe { std::mem::transmute(symbol(CHOOSE_SYMBOL)?) };
+
+        // SAFETY: the name is a C string the plugin keeps, if it keeps to the
+        // ABI; it's copied at once all the same.
+        let name = unsafe {
+            let name = name();
+            if name.is_null() {
+                return Err(LoadError::BadName);
+            }
+            CStr::from_ptr(name)
+        };
+        let name = name.to_str().map_err(|_| LoadError::BadName)?;
+        if name.is_empty() {
+            return Err(LoadError::BadName);
+        }
+        // The name outlives any report it's printed in, as the library does.
+        Ok(Self {
+            name: Box::leak(name.to_string().into_boxed_str()),
+            choose,
+            warned: AtomicBool::new(false),
+        })
+    }
+}
+
+impl Strategy for Plugin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        self.choose_within(guesses, candidates, model, None)
+    }
+
+    fn choose_within<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> &'a str {
+        let packed_guesses = Packed::new(guesses);
+        let packed_candidates = Packed::new(candidates);
+        let probabilities = model.distribution(candidates);
+        // SAFETY: every buffer holds as many items as the count beside it,
+        // and outlives the call.
+        let index = unsafe {
+            (self.choose)(
+                packed_guesses.as_bytes().as_ptr(),
+                guesses.len(),
+                packed_candidates.as_bytes().as_ptr(),
+                candidates.len(),
+                probabilities.as_ptr(),
+                turns.unwrap_or(0),
+            )
+        };
+        match guesses.get(index) {
+            Some(word) => word,
+            None => {
+                if !self.warned.swap(true, Ordering::Relaxed) {
+                    eprintln!(
+                        "warning: {} chose guess {index} of {}, playing a candidate instead",
+                        self.name,
+                        guesses.len()
+                    );
+                }
+                candidates[0]
+            },
+        }
+    }
+}
+
+/// The loader's reason for the last failure.
+fn last_error() -> String {
+    // SAFETY: a non-null result is a C string, valid until the next call.
+    unsafe {
+        let reason = dlerror();
+        if reason.is_null() {
+            return "unknown error".to_string();
+        }
+        CStr::from_ptr(reason).to_string_lossy().into_owned()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn missing() {
+        let Err(LoadError::Open(reason)) = Plugin::load("/nonexistent/libwordle.so") else {
+            panic!("loaded a plugin that isn't there");
+        };
+        assert!(!reason.is_empty());
+    }
+
+    /// Chooses the guess that's the likeliest candidate, reading every
+    /// buffer the way a plugin would.
+    #[allow(dead_code)]
+    unsafe extern "C" fn likeliest(
+        guesses: *const u8,
+        guess_count: usize,
+        candidates: *const u8,
+        candidate_count: usize,
+        probabilities: *const f64,
+        _turns: usize,
+    ) -> usize {
+        let guesses = std::slice::from_raw_parts(guesses, guess_count * 5);
+        let candidates = std::slice::from_raw_parts(candidates, candidate_count * 5);
+        let probabilities = std::slice::from_raw_parts(probabilities, candidate_count);
+        let best = (0..candidate_count)
+            .max_by(|&a, &b| probabilities[a].total_cmp(&probabilities[b]))
+            .unwrap();
+        let word = &candidates[best * 5..best * 5 + 5];
+        guesses.chunks(5).position(|guess| guess == word).unwrap()
+    }
+
+    /// Chooses the guess numbered by the turns left, which may not be there.
+    #[allow(dead_code)]
+    unsafe extern "C" fn turns(
+        _guesses: *const u8,
+        _guess_count: usize,
+        _candidates: *const u8,
+        _candidate_count: usize,
+        _probabilities: *const f64,
+        turns: usize,
+    ) -> usize {
+        turns
+    }
+
+    #[allow(dead_code)]
+    fn stub(choose: ChooseFn) -> Plugin {
+        Plugin {
+            name: "stub",
+            choose,
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    #[test]
+    fn choices() {
+        let model = AnswerModel::new([("hatch", 1), ("catch", 1), ("match", 4)]);
+        let candidates = ["hatch", "catch", "match"];
+        let guesses = ["chomp", "catch", "match", "hatch"];
+        let plugin = stub(likeliest);
+        assert_eq!(plugin.choose(&guesses, &candidates, &model), "match");
+
+        let plugin = stub(turns);
+        assert_eq!(plugin.choose(&guesses, &candidates, &model), "chomp");
+        assert_eq!(
+            plugin.choose_within(&guesses, &candidates, &model, Some(3)),
+            "hatch"
+        );
+        // A guess that isn't there falls back to a candidate.
+        assert_eq!(
+            plugin.choose_within(&guesses, &candidates, &model, Some(4)),
+            "hatch"
+        );
+        assert!(plugin.warned.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::model::AnswerModel;
+
+/// Which words a hosted game may pick as its answer.
+///
+/// By default every word may be; each rule only ever takes words away.
+#[derive(Clone, Debug, Default)]
+pub struct AnswerFilter {
+    min_count: usize,
+    /// Blocked words, lowercased.
+    blocked: HashSet<String>,
+    no_proper_nouns: bool,
+}
+
+impl AnswerFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave out words seen fewer than `count` times in the corpus.
+    pub fn with_min_count(mut self, count: usize) -> Self {
+        self.min_count = count;
+        self
+    }
+
+    /// Leave out `words`, whatever their case.
+    pub fn with_blocklist<'a>(mut self, words: impl IntoIterator<Item = &'a str>) -> Self {
+        self.blocked
+            .extend(words.into_iter().map(str::to_ascii_lowercase));
+        self
+    }
+
+    /// Leave out capitalized entries, which are usually names.
+    pub fn without_proper_nouns(mut self) -> Self {
+        self.no_proper_nouns = true;
+        self
+    }
+
+    /// Whether `word`, seen `count` times in the corpus, may be the answer.
+    pub fn allows(&self, word: &str, count: usize) -> bool {
+        if count < self.min_count {
+            return false;
+        }
+        if self.no_proper_nouns && word.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return false;
+        }
+        !self.blocked.contains(&word.to_ascii_lowercase())
+    }
+
+    /// The words in `words` that may be the answer, in the same order.
+    pub fn select<'w>(&self, words: &[&'w str], model: &AnswerModel) -> Vec<&'w str> {
+        words
+            .iter()
+            .copied()
+            .filter(|word| self.allows(word, model.count(word)))
+            .collect()
+    }
+}
+
+/// How obscure a hosted game's answer may be, as a band of the answer pool
+/// by corpus frequency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    /// The most common third of the pool.
+    Easy,
+    /// The middle third.
+    Medium,
+    /// The least common third.
+    Hard,
+}
+
+impl Difficulty {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// The words in `words` within this band, from most to least common.
+    /// Each band gets at least one word of a non-empty pool.
+    pub fn band<'w>(self, words: &[&'w str], model: &AnswerModel) -> Vec<&'w str> {
+        let mut ranked = words.to_vec();
+        ranked.sort_by_key(|word| std::cmp::Reverse(model.count(word)));
+
+        let third = ranked.len().div_ceil(3);
+        let start = match self {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => third.min(ranked.len() - third),
+            Difficulty::Hard => ranked.len() - third,
+        };
+        ranked[start..start + third].to_vec()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn unfiltered() {
+        let model = AnswerModel::new([("cigar", 3)]);
+        let words = ["cigar", "rebut", "Paris"];
+        assert_eq!(AnswerFilter::new().select(&words, &model), words);
+    }
+
+    #[test]
+    fn rules() {
+        let model = AnswerModel::new([("cigar", 3), ("rebut", 1), ("Paris", 5), ("sissy", 4)]);
+        let words = ["cigar", "rebut", "Paris", "sissy"];
+
+        let filter = AnswerFilter::new().with_min_count(2);
+        assert_eq!(filter.select(&words, &model), ["cigar", "Paris", "sissy"]);
+
+        let filter = filter.without_proper_nouns();
+        assert_eq!(filter.select(&words, &model), ["cigar", "sissy"]);
+
+        let filter = filter.with_blocklist(["SISSY"]);
+        assert_eq!(filter.select(&words, &model), ["cigar"]);
+    }
+
+    #[test]
+    fn difficulty() {
+        let model = AnswerModel::new([("cigar", 3), ("rebut", 1), ("sissy", 4), ("humph", 2)]);
+        let words = ["cigar", "rebut", "sissy", "humph"];
+        assert_eq!(Difficulty::Easy.band(&words, &model), ["sissy", "cigar"]);
+        assert_eq!(Difficulty::Medium.band(&words, &model), ["humph", "rebut"]);
+        assert_eq!(Difficulty::Hard.band(&words, &model), ["humph", "rebut"]);
+
+        assert_eq!(Difficulty::Medium.band(&["cigar"], &model), ["cigar"]);
+        assert!(Difficulty::Easy.band(&[], &model).is_empty());
+        assert_eq!(Difficulty::by_name("medium"), Some(Difficulty::Medium));
+        assert_eq!(Difficulty::by_name("brutal"), None);
+    }
+}
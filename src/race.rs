@@ -0,0 +1,114 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use crate::{
+    guesser::Guess,
+    locale::{Language, Message},
+    tiles::Tileset,
+};
+
+/// The space between the player's board and the solver's.
+const GAP: &str = "   ";
+
+/// A guess's colors without its letters, so the player can see how the
+/// solver is doing without having its guesses given away.
+pub fn masked(guess: &Guess) -> String {
+    guess.mask().iter().map(|c| c.paint(' ')).collect()
+}
+
+/// `row` beside `rival`, a solver's row for the same turn, if it got that
+/// far.
+pub fn beside(row: &str, rival: Option<&str>) -> String {
+    match rival {
+        Some(rival) => format!("{row}{GAP}{rival}"),
+        None => row.to_string(),
+    }
+}
+
+/// The player's board and the solver's side by side, both revealed, row by
+/// row until the longer of the two games ends.
+pub fn boards(player: &[Guess], solver: &[Guess], tiles: &Tileset) -> String {
+    let blank = " ".repeat(player.iter().map(|g| width(g, tiles)).max().unwrap_or(0));
+    let mut boards = String::new();
+    for row in 0..player.len().max(solver.len()) {
+        let left = player
+            .get(row)
+            .map_or_else(|| blank.clone(), |g| tiles.render(g, false));
+        let right = solver.get(row).map(|g| tiles.render(g, false));
+        let _ = writeln!(boards, "{}", beside(&left, right.as_deref()).trim_end());
+    }
+    boards
+}
+
+/// Who won, by the guesses each took; a game never solved loses to one that
+/// was, and two unsolved games tie.
+pub fn verdict(player: Option<usize>, solver: Option<usize>, language: Language) -> String {
+    let shown = |guesses: Option<usize>| guesses.map_or_else(|| "-".to_string(), |n| n.to_string());
+    let (you, rival) = (shown(player), shown(solver));
+    let unsolved_last = |guesses: Option<usize>| guesses.unwrap_or(usize::MAX);
+    let message = match unsolved_last(player).cmp(&unsolved_last(solver)) {
+        Ordering::Less => Message::RaceWon,
+        Ordering::Greater => Message::RaceLost,
+        Ordering::Equal => Message::RaceTied,
+    };
+    language.text(message, &[("you", &you), ("solver", &rival)])
+}
+
+/// How many columns `guess` takes on the board: each tile is its label with
+/// a column either side.
+fn width(guess: &Guess, tiles: &Tileset) -> usize {
+    guess
+        .word()
+        .bytes()
+        .map(|symbol| tiles.tile(symbol).map_or(1, |tile| tile.chars().count()) + 2)
+        .sum()
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn winner() {
+        let english = Language::English;
+        assert_eq!(
+            verdict(Some(3), Some(4), english),
+            "you win, 3 guesses to the solver's 4"
+        );
+        assert_eq!(
+            verdict(None, Some(6), english),
+            "the solver wins, 6 guesses to your -"
+        );
+        assert_eq!(verdict(Some(4), Some(4), english), "a tie, 4 guesses each");
+    }
+
+    #[test]
+    fn side_by_side() {
+        let tiles = Tileset::default();
+        let player = [
+            Guess::check("cigar", "tares"),
+            Guess::check("cigar", "cigar"),
+        ];
+        let solver = [
+            Guess::check("cigar", "salet"),
+            Guess::check("cigar", "crony"),
+            Guess::check("cigar", "cigar"),
+        ];
+        let boards = boards(&player, &solver, &tiles);
+        let rows: Vec<&str> = boards.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            format!(
+                "{}{GAP}{}",
+                tiles.render(&player[0], false),
+                tiles.render(&solver[0], false)
+            )
+        );
+        // The player was done a row early, so the solver's last row is alone.
+        assert_eq!(
+            rows[2],
+            format!("{}{GAP}{}", " ".repeat(15), tiles.render(&solver[2], false))
+        );
+        assert!(!masked(&solver[0]).contains('S'));
+    }
+}
@@ -0,0 +1,88 @@
+/// A small, fast, seedable pseudo-random number generator (SplitMix64).
+///
+/// This is not suitable for anything security-related; it exists so that
+/// sampling and search are reproducible from a seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed float in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniformly distributed integer in `[0, n)`. `n` must be non-zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize
+    }
+
+    /// An index chosen with probability proportional to its weight.
+    pub fn weighted(&mut self, weights: &[f64]) -> usize {
+        let total: f64 = weights.iter().sum();
+        let mut target = self.next_f64() * total;
+        for (i, &w) in weights.iter().enumerate() {
+            if target < w {
+                return i;
+            }
+            target -= w;
+        }
+        weights.len() - 1
+    }
+
+    /// Shuffle `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let a: Vec<u64> = (0..5)
+            .scan(Rng::new(7), |rng, _| Some(rng.next_u64()))
+            .collect();
+        let b: Vec<u64> = (0..5)
+            .scan(Rng::new(7), |rng, _| Some(rng.next_u64()))
+            .collect();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn in_range() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(6) < 6);
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+        }
+        assert_eq!(rng.weighted(&[0.0, 1.0, 0.0]), 1);
+    }
+
+    #[test]
+    fn shuffle_permutes() {
+        let mut items: Vec<usize> = (0..50).collect();
+        Rng::new(3).shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,243 @@
+use std::{cell::OnceCell, cmp::Ordering};
+
+use crate::{
+    engine::PATTERNS, guesser::Correctness, hint::Suggestion, model::AnswerModel, packed::Packed,
+    parallel,
+};
+
+/// How one guess of a finished game measures up, in the manner of the NYT's
+/// WordleBot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Turn<'a> {
+    /// The guess as played.
+    pub chosen: Suggestion<'a>,
+    /// The guess expected to finish soonest from the same position.
+    pub best: Suggestion<'a>,
+    /// How many candidates there were before the guess.
+    pub candidates: usize,
+    /// How many its feedback left, none if it was the answer.
+    pub left: usize,
+    /// How close the guess came to the best, from 0 to 100: the best's
+    /// expected guesses as a share of the chosen one's.
+    pub skill: f64,
+    /// How favorable its feedback was, from 0 to 100: the chance another
+    /// feedback would have left more candidates, counting those that leave
+    /// as many at half. `None` if only one feedback was possible.
+    pub luck: Option<f64>,
+}
+
+/// A finished game's guesses, rated one by one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating<'a> {
+    pub turns: Vec<Turn<'a>>,
+}
+
+impl Rating<'_> {
+    /// The mean skill over every guess.
+    pub fn skill(&self) -> f64 {
+        mean(self.turns.iter().map(|turn| turn.skill)).unwrap_or(0.0)
+    }
+
+    /// The mean luck over the guesses whose feedback wasn't certain, or
+    /// `None` if none was in doubt.
+    pub fn luck(&self) -> Option<f64> {
+        mean(self.turns.iter().filter_map(|turn| turn.luck))
+    }
+}
+
+/// Rates finished games against the best of a list of guesses.
+pub struct Rater<'a, 'm> {
+    guesses: Vec<&'a str>,
+    candidates: Vec<&'a str>,
+    model: &'m AnswerModel<'a>,
+    /// The best opening guess, which is the slowest to find and the same for
+    /// every game.
+    opening: OnceCell<Suggestion<'a>>,
+}
+
+impl<'a, 'm> Rater<'a, 'm> {
+    /// Rate against the best of `guesses` for whichever of `candidates` are
+    /// left at each turn, with probabilities from `model`.
+    pub fn new(guesses: &[&'a str], candidates: &[&'a str], model: &'m AnswerModel<'a>) -> Self {
+        Self {
+            guesses: guesses.to_vec(),
+            candidates: candidates.to_vec(),
+            model,
+            opening: OnceCell::new(),
+        }
+    }
+
+    /// Rate each guess in `game`, played with the feedback beside it. Fails
+    /// with the number of the first guess whose feedback no candidate gives,
+    /// counted from 1.
+    pub fn rate(&self, game: &[(&'a str, [Correctness; 5])]) -> Result<Rating<'a>, usize> {
+        let mut candidates = self.candidates.clone();
+        let mut turns = Vec::new();
+        for (number, &(word, mask)) in game.iter().enumerate() {
+            if candidates.is_empty() {
+                return Err(number);
+            }
+            let distribution = self.model.distribution(&candidates);
+            let packed = Packed::new(&candidates);
+            let chosen = Suggestion::evaluate_packed(word, &packed, &distribution);
+            let best = match number {
+                0 => self
+                    .opening
+                    .get_or_init(|| self.best(&packed, &distribution))
+                    .clone(),
+                _ => self.best(&packed, &distribution),
+            };
+            // The game may have been played with a word outside the list.
+            let best = if chosen.expected_guesses < best.expected_guesses {
+                chosen.clone()
+            } else {
+                best
+            };
+
+            // How many candidates each feedback leaves. Solving leaves none.
+            let codes = Correctness::compute_many(word, &candidates);
+            let solved = Correctness::encode([Correctness::Correct; 5]);
+            let mut counts = [0usize; PATTERNS];
+            for &code in codes.iter().filter(|&&code| code != solved) {
+                counts[code as usize] += 1;
+            }
+            let played = Correctness::encode(mask);
+            let left = counts[played as usize];
+            let luck = codes.iter().any(|&code| code != codes[0]).then(|| {
+                let favorable: f64 = codes
+                    .iter()
+                    .zip(&distribution)
+                    .map(|(&code, &p)| match counts[code as usize].cmp(&left) {
+                        Ordering::Greater => p,
+                        Ordering::Equal => p / 2.0,
+                        Ordering::Less => 0.0,
+                    })
+                    .sum();
+                100.0 * favorable
+            });
+
+            turns.push(Turn {
+                skill: 100.0 * best.expected_guesses / chosen.expected_guesses,
+                chosen,
+                best,
+                candidates: candidates.len(),
+                left,
+                luck,
+            });
+            candidates = candidates
+                .into_iter()
+                .zip(codes)
+                .filter(|&(_, code)| code == played)
+                .map(|(candidate, _)| candidate)
+                .collect();
+        }
+        if candidates.is_empty() {
+            return Err(game.len());
+        }
+        Ok(Rating { turns })
+    }
+
+    /// The guess expected to finish soonest against `candidates`.
+    fn best(&self, candidates: &Packed, distribution: &[f64]) -> Suggestion<'a> {
+        let work = self.guesses.len() * candidates.len();
+        parallel::map(&self.guesses, work, |&guess| {
+            Suggestion::evaluate_packed(guess, candidates, distribution)
+        })
+        .into_iter()
+        .min_by(|a, b| a.expected_guesses.total_cmp(&b.expected_guesses))
+        .expect("there is a guess to score")
+    }
+}
+
+/// The ratings of many games added up, as for one player over a month.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tally {
+    pub games: usize,
+    skill: f64,
+    luck: f64,
+    lucky: usize,
+}
+
+impl Tally {
+    pub fn add(&mut self, rating: &Rating) {
+        self.games += 1;
+        self.skill += rating.skill();
+        if let Some(luck) = rating.luck() {
+            self.luck += luck;
+            self.lucky += 1;
+        }
+    }
+
+    /// The mean of the games' skill.
+    pub fn skill(&self) -> f64 {
+        match self.games {
+            0 => 0.0,
+            games => self.skill / games as f64,
+        }
+    }
+
+    /// The mean of the games' luck, over those with any feedback in doubt.
+    pub fn luck(&self) -> Option<f64> {
+        (self.lucky > 0).then(|| self.luck / self.lucky as f64)
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn rated() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let guesses = ["hatch", "chomp", "catch", "match", "patch"];
+        let game = [
+            ("hatch", Correctness::compute("match", "hatch")),
+            ("catch", Correctness::compute("match", "catch")),
+            ("match", [Correctness::Correct; 5]),
+        ];
+        let rater = Rater::new(&guesses, &candidates, &model);
+        let rating = rater.rate(&game).unwrap();
+        assert_eq!(rating.turns.len(), 3);
+
+        // Chomp tells every candidate apart, so hatch falls short of it.
+        let first = &rating.turns[0];
+        assert_eq!(first.best.word, "chomp");
+        assert!(first.skill < 100.0);
+        assert_eq!((first.candidates, first.left), (4, 3));
+        // Three in four answers leave three, as this one did.
+        assert_eq!(first.luck, Some(37.5));
+
+        // Catch left match and patch, and the coin flip came up heads.
+        let last = &rating.turns[2];
+        assert_eq!((last.skill, last.luck, last.left), (100.0, Some(75.0), 0));
+        assert!(rating.skill() < 100.0);
+
+        // With one candidate left, guessing it is best and nothing is luck.
+        let game = [
+            ("chomp", Correctness::compute("match", "chomp")),
+            ("match", [Correctness::Correct; 5]),
+        ];
+        let rating = rater.rate(&game).unwrap();
+        assert_eq!(rating.turns[0].skill, 100.0);
+        assert_eq!((rating.turns[1].skill, rating.turns[1].luck), (100.0, None));
+        assert_eq!(rating.luck(), Some(50.0));
+
+        let mut tally = Tally::default();
+        tally.add(&rating);
+        // Guessing match outright was a long shot that paid off.
+        tally.add(&rater.rate(&game[1..]).unwrap());
+        assert_eq!((tally.games, tally.luck()), (2, Some((50.0 + 87.5) / 2.0)));
+        assert!(tally.skill() < 100.0);
+
+        // No candidate is all green for hatch after hatch got other colors.
+        let hatch = ("hatch", Correctness::compute("match", "hatch"));
+        let impossible = [hatch, ("hatch", [Correctness::Correct; 5])];
+        assert_eq!(rater.rate(&impossible), Err(2));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::report::Report;
+
+/// How many feedback patterns a guess can get short of solving: three
+/// colors at five positions, less all green.
+const UNSOLVED_PATTERNS: usize = 3usize.pow(5) - 1;
+
+/// How many of the answers with the most regret are listed.
+const REGRETTED: usize = 10;
+
+/// How far a run falls short of a lower bound on the guesses it needed.
+#[derive(Debug, PartialEq)]
+pub struct Regret<'a> {
+    /// The number of answers compared.
+    pub count: usize,
+    /// The fewest guesses the answers could have taken in total.
+    pub bound: usize,
+    /// The guesses the run took in total, with failures scored as one past
+    /// the limit.
+    pub taken: usize,
+    /// Each answer's guesses beyond its own bound, most first, where the
+    /// bound is known per answer.
+    pub answers: Vec<(&'a str, isize)>,
+}
+
+impl<'a> Regret<'a> {
+    /// Compare `report` with [`counting_bound`], which holds for any
+    /// strategy but says nothing about individual answers.
+    pub fn counting(report: &Report<'a>) -> Self {
+        Self {
+            count: report.outcomes().len(),
+            bound: counting_bound(report.outcomes().len()),
+            taken: report.outcomes().iter().map(|o| o.score()).sum(),
+            answers: Vec::new(),
+        }
+    }
+
+    /// Compare each answer in `report` with its own `bound`, such as its
+    /// guesses in an optimal tree. Answers without one are bounded by one
+    /// guess.
+    pub fn against(report: &Report<'a>, bound: impl Fn(&str) -> Option<usize>) -> Self {
+        let mut regret = Self {
+            count: report.outcomes().len(),
+            bound: 0,
+            taken: 0,
+            answers: Vec::new(),
+        };
+        for outcome in report.outcomes() {
+            let bound = bound(outcome.answer).unwrap_or(1);
+            regret.bound += bound;
+            regret.taken += outcome.score();
+            regret
+                .answers
+                .push((outcome.answer, outcome.score() as isize - bound as isize));
+        }
+        regret
+            .answers
+            .sort_by_key(|&(_, regret)| std::cmp::Reverse(regret));
+        regret
+    }
+
+    /// The guesses taken beyond the bound, in total. Negative if the run
+    /// beat a bound that wasn't truly optimal.
+    pub fn total(&self) -> isize {
+        self.taken as isize - self.bound as isize
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!(
+            "regret: {} guesses over a bound of {} ({:.4} per answer)",
+            self.total(),
+            self.bound,
+            self.total() as f64 / self.count.max(1) as f64
+        );
+        let regretted: Vec<_> = self
+            .answers
+            .iter()
+            .filter(|&&(_, regret)| regret > 0)
+            .take(REGRETTED)
+            .collect();
+        if !regretted.is_empty() {
+            println!("most regret:");
+            for (answer, regret) in regretted {
+                println!("  {answer} (+{regret})");
+            }
+        }
+    }
+}
+
+/// The fewest guesses that could find `answers` different answers in total.
+///
+/// Each guess leads to one next guess per pattern it can get, so at most one
+/// answer is found on the first guess, 242 on the second, 242² on the
+/// third, and so on; the bound fills those turns in order.
+pub fn counting_bound(answers: usize) -> usize {
+    let mut total = 0;
+    let mut left = answers;
+    let mut reachable = 1usize;
+    let mut guesses = 1;
+    while left > 0 {
+        let found = left.min(reachable);
+        total += found * guesses;
+        left -= found;
+        reachable = reachable.saturating_mul(UNSOLVED_PATTERNS);
+        guesses += 1;
+    }
+    total
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[cfg(test)]
+    use crate::report::outcome;
+
+    #[test]
+    fn counting() {
+        assert_eq!(counting_bound(0), 0);
+        assert_eq!(counting_bound(1), 1);
+        assert_eq!(counting_bound(243), 1 + 242 * 2);
+        assert_eq!(counting_bound(2309), 1 + 242 * 2 + 2066 * 3);
+    }
+
+    #[test]
+    fn against_a_tree() {
+        let mut tree = crate::tree::DecisionTree::new();
+        tree.insert(&["match", "hatch", "catch"]);
+        tree.insert(&["match", "hatch"]);
+
+        let mut report = Report::default();
+        for (answer, guesses) in [("catch", 4), ("hatch", 2), ("match", 1)] {
+            report.push(outcome(answer, Some(guesses)));
+        }
+
+        let regret = Regret::against(&report, |answer| tree.path(answer).map(<[_]>::len));
+        assert_eq!(regret.bound, 3 + 2 + 1);
+        assert_eq!(regret.taken, 7);
+        assert_eq!(regret.total(), 1);
+        assert_eq!(regret.answers[0], ("catch", 1));
+
+        let regret = Regret::counting(&report);
+        assert_eq!((regret.bound, regret.total()), (1 + 2 + 2, 2));
+    }
+}
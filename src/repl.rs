@@ -0,0 +1,253 @@
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+};
+
+use crate::{
+    guesser::{Correctness, Guesser},
+    normalize::{self, Normalizer},
+    pattern,
+};
+
+/// How many candidates `candidates` lists before summarizing the rest.
+const LISTED: usize = 20;
+
+const HELP: &str = "\
+commands:
+  guess <word>        play a word of your own next
+  feedback <pattern>  enter the feedback for the word being played
+  hint                show the word the solver would play
+  candidates          list the words that still fit
+  undo                take back the last feedback
+  reset               start a new game
+  quit                leave";
+
+/// A command in a session.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Play this word next, instead of the solver's choice.
+    Guess(String),
+    Feedback([Correctness; 5]),
+    Hint,
+    Candidates,
+    Undo,
+    Reset,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Read a command from a line of input. Returns `Ok(None)` for a blank
+    /// line or a comment starting with `#`, so sessions can be scripted.
+    pub fn parse(line: &str) -> Result<Option<Self>, String> {
+        let line = normalize::input(line);
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next().filter(|word| !word.starts_with('#')) else {
+            return Ok(None);
+        };
+        let argument = words.next();
+        if words.next().is_some() {
+            return Err(format!("too many arguments to {command}"));
+        }
+
+        let command = match (command.to_ascii_lowercase().as_str(), argument) {
+            ("guess", Some(word)) => Command::Guess(word.to_string()),
+            ("guess", None) => return Err("usage: guess <word>".to_string()),
+            ("feedback", Some(mask)) => {
+                Command::Feedback(pattern::parse(mask).map_err(|e| e.to_string())?)
+            },
+            ("feedback", None) => return Err("usage: feedback <pattern>".to_string()),
+            ("hint", None) => Command::Hint,
+            ("candidates", None) => Command::Candidates,
+            ("undo", None) => Command::Undo,
+            ("reset", None) => Command::Reset,
+            ("help", None) => Command::Help,
+            ("quit" | "exit", None) => Command::Quit,
+            (command, Some(_))
+                if ["hint", "candidates", "undo", "reset", "help"].contains(&command) =>
+            {
+                return Err(format!("{command} takes no arguments"));
+            },
+            (command, _) => return Err(format!("unknown command {command}; try help")),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// A session that steps a guesser through a game by commands, read from a
+/// human or a script.
+pub struct Repl<'a, 'b, R, W> {
+    guesser: Guesser<'a, 'b>,
+    /// The words that may be played with `guess`.
+    words: HashSet<&'a str>,
+    /// The word feedback applies to, if the player chose one.
+    pending: Option<&'a str>,
+    normalizer: Normalizer,
+    input: R,
+    output: W,
+}
+
+impl<'a, 'b, R: BufRead, W: Write> Repl<'a, 'b, R, W> {
+    /// Drive `guesser`, accepting any of `words` as a guess of the player's
+    /// own.
+    pub fn new(guesser: Guesser<'a, 'b>, words: &[&'a str], input: R, output: W) -> Self {
+        Self {
+            guesser,
+            words: words.iter().copied().collect(),
+            pending: None,
+            normalizer: Normalizer::default(),
+            input,
+            output,
+        }
+    }
+
+    /// Read guesses typed by the player with `normalizer`, as the word lists
+    /// were loaded.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Run commands until `quit` or the end of the input.
+    pub fn run(&mut self) -> Option<()> {
+        loop {
+            write!(self.output, "> ").ok()?;
+            self.output.flush().ok()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line).ok()? == 0 {
+                return Some(());
+            }
+
+            match Command::parse(&line) {
+                Ok(Some(Command::Quit)) => return Some(()),
+                Ok(Some(command)) => self.execute(command)?,
+                Ok(None) => {},
+                Err(e) => writeln!(self.output, "{e}").ok()?,
+            }
+        }
+    }
+
+    fn execute(&mut self, command: Command) -> Option<()> {
+        let tiles = self.normalizer.tiles();
+        match command {
+            Command::Guess(typed) => {
+                let [exact, lowered] = self.normalizer.word(&typed);
+                let word = [exact, lowered]
+                    .into_iter()
+                    .find_map(|word| self.words.get(word.as_str()).copied());
+                match word {
+                    Some(word) => {
+                        self.pending = Some(word);
+                        writeln!(self.output, "playing {}", tiles.decode(word)).ok()?;
+                    },
+                    None => writeln!(self.output, "{typed}: not in word list").ok()?,
+                }
+            },
+            Command::Feedback(mask) => {
+                let Some(word) = self.pending.take().or_else(|| self.guesser.next_guess()) else {
+                    return writeln!(self.output, "the game is over; undo or reset").ok();
+                };
+                let remaining = self.guesser.apply_feedback(word, mask);
+                let guess = *self.guesser.history().last()?;
+                if guess.is_correct() {
+                    let turns = self.guesser.history().len();
+                    writeln!(self.output, "solved in {turns}").ok()?;
+                } else if remaining == 0 {
+                    writeln!(self.output, "no words fit {guess:#}; undo to correct it").ok()?;
+                } else {
+                    let shown = tiles.render(&guess, true);
+                    writeln!(self.output, "{shown}: {remaining} candidates remain").ok()?;
+                }
+            },
+            Command::Hint => match self.pending.or_else(|| self.guesser.next_guess()) {
+                Some(word) => writeln!(self.output, "{}", tiles.decode(word)).ok()?,
+                None => writeln!(self.output, "no guess to suggest").ok()?,
+            },
+            Command::Candidates => {
+                let candidates = self.guesser.candidates();
+                let listed: Vec<_> = candidates
+                    .iter()
+                    .take(LISTED)
+                    .map(|word| tiles.decode(word))
+                    .collect();
+                write!(
+                    self.output,
+                    "{} candidates: {}",
+                    candidates.len(),
+                    listed.join(" ")
+                )
+                .ok()?;
+                if candidates.len() > LISTED {
+                    write!(self.output, " ...").ok()?;
+                }
+                writeln!(self.output).ok()?;
+            },
+            Command::Undo => {
+                self.pending = None;
+                match self.guesser.undo() {
+                    Some(guess) => writeln!(self.output, "undid {guess:#}").ok()?,
+                    None => writeln!(self.output, "nothing to undo").ok()?,
+                }
+            },
+            Command::Reset => {
+                self.pending = None;
+                self.guesser.reset();
+                writeln!(self.output, "new game").ok()?;
+            },
+            Command::Help => writeln!(self.output, "{HELP}").ok()?,
+            Command::Quit => {},
+        }
+        Some(())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Command::parse("guess CRANE\n"),
+            Ok(Some(Command::Guess("CRANE".to_string())))
+        );
+        assert_eq!(
+            Command::parse("feedback GYBBY"),
+            Ok(Some(Command::Feedback([C, M, W, W, M])))
+        );
+        assert_eq!(Command::parse("  Hint "), Ok(Some(Command::Hint)));
+        assert_eq!(Command::parse("# a comment"), Ok(None));
+        assert_eq!(Command::parse(""), Ok(None));
+        assert!(Command::parse("guess").is_err());
+        assert!(Command::parse("undo twice").is_err());
+        assert!(Command::parse("feedback GYB").is_err());
+        assert!(Command::parse("solve").is_err());
+    }
+
+    #[test]
+    fn session() {
+        let dictionary = ["hatch", "catch", "match"];
+        let exclusions = HashSet::new();
+        let model = crate::model::AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let guesser = Guesser::new(&dictionary, &exclusions, &model, &strategy);
+
+        let script = "hint\nguess match\nfeedback bgggg\ncandidates\nfeedback bbbbb\nundo\n\
+                      guess zzzzz\nfeedback ggggg\nreset\ncandidates\n";
+        let mut output = Vec::new();
+        Repl::new(guesser, &dictionary, script.as_bytes(), &mut output).run();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("> salet\n"));
+        assert!(output.contains("playing match"));
+        assert!(output.contains("2 candidates: hatch catch\n"));
+        assert!(output.contains("no words fit hatch ⬛⬛⬛⬛⬛"));
+        assert!(output.contains("undid hatch ⬛⬛⬛⬛⬛"));
+        assert!(output.contains("zzzzz: not in word list"));
+        assert!(output.contains("solved in 2"));
+        assert!(output.contains("3 candidates: hatch catch match\n"));
+    }
+}
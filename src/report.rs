@@ -0,0 +1,315 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::{
+    guesser::{SolveResult, Timings},
+    significance,
+};
+
+/// The number of guesses a solve may take before it counts as a failure.
+pub const MAX_GUESSES: usize = 6;
+
+/// How many of the hardest answers the worst-case section lists.
+pub const HARDEST: usize = 20;
+
+/// How much of a run to print.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Only the one-line summary, for scripts.
+    Quiet,
+    #[default]
+    Normal,
+    /// Everything, including how every answer was solved.
+    Verbose,
+}
+
+/// The result of solving a single answer.
+pub struct Outcome<'a> {
+    pub answer: &'a str,
+    pub result: SolveResult<'a>,
+    pub timings: Timings,
+}
+
+impl Outcome<'_> {
+    /// The number of guesses taken, if the answer was found at all.
+    #[inline]
+    pub fn guesses(&self) -> Option<usize> {
+        self.result.guesses()
+    }
+
+    /// The guessed words, space-separated.
+    pub fn words(&self) -> String {
+        let words: Vec<&str> = self.result.path.iter().map(|g| g.word()).collect();
+        words.join(" ")
+    }
+
+    /// The answer, the guesses taken, or `-` if it was never found, and the
+    /// guessed words, as verbose output lists each answer.
+    pub fn line(&self) -> String {
+        let guesses = self
+            .guesses()
+            .map_or_else(|| "-".to_string(), |n| n.to_string());
+        format!("{} ({guesses}): {}", self.answer, self.words())
+    }
+
+    #[inline]
+    pub fn solved(&self) -> bool {
+        self.guesses().is_some_and(|n| n <= MAX_GUESSES)
+    }
+
+    /// The guesses taken, with every failure scored as one past the limit so
+    /// outcomes can be compared.
+    #[inline]
+    pub fn score(&self) -> usize {
+        match self.guesses() {
+            Some(n) if n <= MAX_GUESSES => n,
+            _ => MAX_GUESSES + 1,
+        }
+    }
+}
+
+/// Results collected over a benchmark run.
+#[derive(Default)]
+pub struct Report<'a> {
+    outcomes: Vec<Outcome<'a>>,
+    elapsed: Duration,
+}
+
+impl<'a> Report<'a> {
+    pub fn push(&mut self, outcome: Outcome<'a>) {
+        self.outcomes.push(outcome);
+    }
+
+    /// Record the wall-clock time of the whole run.
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    /// The wall-clock time of the whole run.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn timings(&self) -> Timings {
+        let mut timings = Timings::default();
+        for outcome in &self.outcomes {
+            timings += outcome.timings;
+        }
+        timings
+    }
+
+    pub fn average(&self) -> f32 {
+        let (count, score) = self
+            .outcomes
+            .iter()
+            .filter(|o| o.solved())
+            .fold((0, 0), |(count, score), o| {
+                (count + 1, score + o.guesses().unwrap_or(0))
+            });
+
+        score as f32 / count as f32
+    }
+
+    /// A 95% confidence interval for [`average`](Self::average), by
+    /// resampling the answers, for judging a run over a sample against the
+    /// noise of which answers it happened to draw.
+    pub fn average_interval(&self) -> (f64, f64) {
+        significance::interval(&self.outcomes, |sample| {
+            let (count, score) = sample
+                .iter()
+                .filter(|o| o.solved())
+                .fold((0, 0), |(count, score), o| (count + 1, score + o.score()));
+            score as f64 / count as f64
+        })
+    }
+
+    /// The share of answers missed.
+    pub fn failure_rate(&self) -> f64 {
+        self.failures().count() as f64 / self.outcomes.len() as f64
+    }
+
+    /// A 95% confidence interval for [`failure_rate`](Self::failure_rate),
+    /// as for the average.
+    pub fn failure_rate_interval(&self) -> (f64, f64) {
+        significance::interval(&self.outcomes, |sample| {
+            sample.iter().filter(|o| !o.solved()).count() as f64 / sample.len() as f64
+        })
+    }
+
+    pub fn outcomes(&self) -> &[Outcome<'a>] {
+        &self.outcomes
+    }
+
+    /// How many answers were solved in each number of guesses, from one up to
+    /// the limit, with failures in the final slot.
+    pub fn distribution(&self) -> [usize; MAX_GUESSES + 1] {
+        let mut distribution = [0; MAX_GUESSES + 1];
+        for outcome in &self.outcomes {
+            distribution[outcome.score() - 1] += 1;
+        }
+        distribution
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &Outcome<'a>> {
+        self.outcomes.iter().filter(|o| !o.solved())
+    }
+
+    /// The most guesses any answer needed, counting answers that were only
+    /// found past the limit.
+    pub fn worst_case(&self) -> Option<usize> {
+        self.outcomes.iter().filter_map(|o| o.guesses()).max()
+    }
+
+    /// The `n` answers that took the most guesses, hardest first. Answers
+    /// that were never found come before everything else.
+    pub fn hardest(&self, n: usize) -> Vec<&Outcome<'a>> {
+        let mut outcomes: Vec<&Outcome> = self.outcomes.iter().collect();
+        outcomes.sort_by_key(|o| std::cmp::Reverse(o.guesses().unwrap_or(usize::MAX)));
+        outcomes.truncate(n);
+        outcomes
+    }
+
+    /// The average and misses on one line.
+    pub fn summary(&self, strategy: &str) -> String {
+        format!(
+            "{strategy}: average {:.4}, missed {} of {}",
+            self.average(),
+            self.failures().count(),
+            self.outcomes.len()
+        )
+    }
+
+    pub fn print(&self, strategy: &str, verbosity: Verbosity) {
+        if verbosity == Verbosity::Quiet {
+            println!("{}", self.summary(strategy));
+            return;
+        }
+        if verbosity == Verbosity::Verbose {
+            println!("answers:");
+            for outcome in &self.outcomes {
+                println!("  {}", outcome.line());
+            }
+            println!();
+        }
+
+        let mut overflow: BTreeMap<usize, usize> = BTreeMap::new();
+        for guesses in self.failures().filter_map(|o| o.guesses()) {
+            *overflow.entry(guesses).or_default() += 1;
+        }
+
+        println!("strategy: {strategy}");
+        println!("average score: {}", self.average());
+        let (low, high) = self.average_interval();
+        println!("  95% CI: {low:.4} to {high:.4}");
+        println!("missed words: {}", self.failures().count());
+        let (low, high) = self.failure_rate_interval();
+        println!(
+            "  failure rate: {:.2}% (95% CI {:.2}% to {:.2}%)",
+            100.0 * self.failure_rate(),
+            100.0 * low,
+            100.0 * high
+        );
+        for (guess_count, words) in overflow {
+            println!("  solved in {guess_count}: {words}");
+        }
+
+        println!();
+        match self.worst_case() {
+            Some(worst) => println!("worst case: {worst} guesses"),
+            None => println!("worst case: no answers found"),
+        }
+
+        println!("hardest answers:");
+        for outcome in self.hardest(HARDEST) {
+            println!("  {}", outcome.line());
+        }
+
+        println!("failures:");
+        for outcome in self.failures() {
+            println!("  {}: {}", outcome.answer, outcome.words());
+        }
+
+        let timings = self.timings();
+        println!();
+        println!("wall clock: {:?}", self.elapsed);
+        println!("  filtering: {:?}", timings.filtering);
+        println!("  scoring: {:?}", timings.scoring);
+        if !self.outcomes.is_empty() {
+            println!(
+                "  per solve: {:?}",
+                self.elapsed / self.outcomes.len() as u32
+            );
+        }
+    }
+}
+
+/// An outcome for `answer` that took `guesses`, or that gave up after the
+/// limit if `None`, for tests to build reports from. Every guess but a
+/// winning last one is "xxxxx".
+#[cfg(test)]
+pub(crate) fn outcome(answer: &str, guesses: Option<usize>) -> Outcome<'_> {
+    use crate::guesser::Guess;
+
+    let mut path = vec![Guess::check(answer, "xxxxx"); guesses.unwrap_or(MAX_GUESSES)];
+    if guesses.is_some() {
+        *path.last_mut().unwrap() = Guess::check(answer, answer);
+    }
+
+    Outcome {
+        answer,
+        result: SolveResult {
+            remaining: vec![1; path.len()],
+            path,
+            solved: guesses.is_some(),
+            elapsed: Duration::ZERO,
+        },
+        timings: Timings::default(),
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(test)]
+    fn report() -> Report<'static> {
+        let mut report = Report::default();
+        for (answer, guesses) in [
+            ("cigar", Some(3)),
+            ("rebut", None),
+            ("sissy", Some(7)),
+            ("humph", Some(5)),
+        ] {
+            report.push(outcome(answer, guesses));
+        }
+        report
+    }
+
+    #[test]
+    fn summary() {
+        let report = report();
+        assert_eq!(report.average(), 4.0);
+        assert_eq!(report.worst_case(), Some(7));
+        assert_eq!(report.distribution(), [0, 0, 1, 0, 1, 0, 2]);
+
+        let failures: Vec<&str> = report.failures().map(|o| o.answer).collect();
+        assert_eq!(failures, vec!["rebut", "sissy"]);
+        assert_eq!(report.failure_rate(), 0.5);
+        let (low, high) = report.failure_rate_interval();
+        assert!(low < 0.5 && 0.5 < high);
+        let (low, high) = report.average_interval();
+        assert!((3.0..=4.0).contains(&low) && (4.0..=5.0).contains(&high));
+
+        assert_eq!(report.outcomes()[0].line(), "cigar (3): xxxxx xxxxx cigar");
+    }
+
+    #[test]
+    fn hardest() {
+        let report = report();
+        let hardest: Vec<&str> = report.hardest(3).iter().map(|o| o.answer).collect();
+        assert_eq!(hardest, vec!["rebut", "sissy", "humph"]);
+        assert_eq!(
+            report.summary("likeliest"),
+            "likeliest: average 4.0000, missed 2 of 4"
+        );
+    }
+}
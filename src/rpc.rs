@@ -0,0 +1,283 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    guesser::{Correctness, Guesser},
+    json::{self, Value},
+    pattern,
+    server::Api,
+};
+
+/// How many suggestions `suggest` returns unless asked for a number.
+const SUGGESTED: usize = 5;
+
+// The error codes JSON-RPC 2.0 reserves.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+/// A JSON-RPC 2.0 error, to send back in place of a result.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub code: i32,
+    pub message: String,
+}
+
+impl Error {
+    fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+}
+
+/// A game driven by JSON-RPC 2.0 requests, one per line, for programs that
+/// run the solver as a child process. The methods are
+///
+/// - `suggest`, with an optional `top`: the candidates left, the solver's
+///   next guess, and the best guesses by score, as from the HTTP API;
+/// - `apply_feedback`, with a `word` and its `feedback`: how many candidates
+///   are left;
+/// - `candidates`, with an optional `limit`: the words that still fit;
+/// - `simulate`, with a `word`: each feedback playing it could get, with its
+///   chance and how many candidates it would leave, without playing it;
+/// - `reset`: start a new game.
+///
+/// Unlike the HTTP API, the game lasts across requests.
+pub struct Session<'a, 'm, 'b, F> {
+    api: Api<'a, 'm, F>,
+    guesser: Guesser<'a, 'b>,
+}
+
+impl<'a: 'b, 'm, 'b, F: Fn() -> Guesser<'a, 'b>> Session<'a, 'm, 'b, F> {
+    /// Play with the words, model, and scoring `api` answers with.
+    pub fn new(api: Api<'a, 'm, F>) -> Self {
+        let guesser = api.new_game();
+        Self { api, guesser }
+    }
+
+    /// Answer each request read from `input` on a line of `output`, until
+    /// `input` ends. Notifications, which have no `id`, get no answer.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle(&line) {
+                writeln!(output, "{response}")?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The response to the request in `line`, or `None` for a notification.
+    pub fn handle(&mut self, line: &str) -> Option<Value> {
+        let request = match json::parse(line) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = Error::new(PARSE_ERROR, e.to_string());
+                return Some(response(Value::Null, Err(error)));
+            },
+        };
+        let id = request.get("id").cloned();
+        let result = match request.get("method").and_then(Value::as_str) {
+            Some(method) => {
+                let params = request.get("params").unwrap_or(&Value::Null);
+                self.call(method, params)
+            },
+            None => Err(Error::new(INVALID_REQUEST, "a request needs a method")),
+        };
+        id.map(|id| response(id, result))
+    }
+
+    fn call(&mut self, method: &str, params: &Value) -> Result<Value, Error> {
+        let number = |key: &str, default: usize| match params.get(key) {
+            Some(n) => n
+                .as_usize()
+                .ok_or_else(|| Error::params(format!("{key} must be a whole number"))),
+            None => Ok(default),
+        };
+        let text = |key: &str| {
+            params
+                .get(key)
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::params(format!("{method} needs a {key}")))
+        };
+
+        match method {
+            "suggest" => {
+                let top = number("top", SUGGESTED)?;
+                let guess = self
+                    .guesser
+                    .next_guess()
+                    .map_or(Value::Null, |w| self.api.decode(w));
+                Ok(Value::Object(vec![
+                    ("candidates".to_string(), self.remaining()),
+                    ("guess".to_string(), guess),
+                    (
+                        "suggestions".to_string(),
+                        self.api.suggestions(&self.guesser, top),
+                    ),
+                ]))
+            },
+            "apply_feedback" => {
+                let word = self.api.word(text("word")?).map_err(Error::params)?;
+                let mask =
+                    pattern::parse(text("feedback")?).map_err(|e| Error::params(e.to_string()))?;
+                self.guesser.apply_feedback(word, mask);
+                Ok(Value::Object(vec![(
+                    "candidates".to_string(),
+                    self.remaining(),
+                )]))
+            },
+            "candidates" => {
+                let limit = number("limit", usize::MAX)?;
+                let candidates = self.guesser.candidates().iter().take(limit);
+                Ok(Value::Array(
+                    candidates.map(|w| self.api.decode(w)).collect(),
+                ))
+            },
+            "simulate" => {
+                let word = self.api.word(text("word")?).map_err(Error::params)?;
+                let simulation = self.guesser.simulate(word);
+                let outcomes = simulation.outcomes.iter().map(|outcome| {
+                    let code = Correctness::encode(outcome.mask);
+                    Value::Object(vec![
+                        (
+                            "feedback".to_string(),
+                            Value::String(pattern::format_code(code).unwrap_or_default()),
+                        ),
+                        (
+                            "probability".to_string(),
+                            Value::Number(outcome.probability),
+                        ),
+                        (
+                            "candidates".to_string(),
+                            Value::Number(outcome.candidates.len() as f64),
+                        ),
+                    ])
+                });
+                let summary = &simulation.summary;
+                Ok(Value::Object(vec![
+                    (
+                        "information".to_string(),
+                        Value::Number(summary.information),
+                    ),
+                    (
+                        "expected_remaining".to_string(),
+                        Value::Number(summary.expected_remaining),
+                    ),
+                    (
+                        "probability".to_string(),
+                        Value::Number(summary.probability),
+                    ),
+                    (
+                        "expected_guesses".to_string(),
+                        Value::Number(summary.expected_guesses),
+                    ),
+                    ("outcomes".to_string(), Value::Array(outcomes.collect())),
+                ]))
+            },
+            "reset" => {
+                self.guesser.reset();
+                Ok(Value::Null)
+            },
+            _ => Err(Error::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method: {method}"),
+            )),
+        }
+    }
+
+    fn remaining(&self) -> Value {
+        Value::Number(self.guesser.candidates().len() as f64)
+    }
+}
+
+/// A JSON-RPC 2.0 response to the request `id`.
+fn response(id: Value, result: Result<Value, Error>) -> Value {
+    let outcome = match result {
+        Ok(result) => ("result".to_string(), result),
+        Err(error) => (
+            "error".to_string(),
+            Value::Object(vec![
+                ("code".to_string(), Value::Number(error.code.into())),
+                ("message".to_string(), error.message.as_str().into()),
+            ]),
+        ),
+    };
+    Value::Object(vec![
+        ("jsonrpc".to_string(), "2.0".into()),
+        ("id".to_string(), id),
+        outcome,
+    ])
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::model::AnswerModel;
+    #[allow(unused_imports)]
+    use std::collections::HashSet;
+
+    #[test]
+    fn session() {
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 4), ("catch", 3), ("match", 2), ("patch", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let api = Api::new(
+            || Guesser::new(&dictionary, &exclusions, &model, &strategy).with_opener(None),
+            &dictionary,
+            &model,
+        );
+        let mut session = Session::new(api);
+
+        let input = r#"
+{"jsonrpc": "2.0", "id": 1, "method": "apply_feedback", "params": {"word": "hatch", "feedback": "bgggg"}}
+{"jsonrpc": "2.0", "id": 2, "method": "candidates", "params": {"limit": 2}}
+{"jsonrpc": "2.0", "id": 3, "method": "simulate", "params": {"word": "catch"}}
+{"jsonrpc": "2.0", "method": "reset"}
+{"jsonrpc": "2.0", "id": "s", "method": "suggest", "params": {"top": 1}}
+"#;
+        let mut output = Vec::new();
+        session.run(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        let simulate = json::parse(lines[2]).unwrap();
+        let outcomes = simulate.get("result").unwrap().get("outcomes").unwrap();
+        assert_eq!(outcomes.as_array().unwrap().len(), 2);
+        assert_eq!(
+            lines[..2],
+            [
+                r#"{"jsonrpc":"2.0","id":1,"result":{"candidates":3}}"#,
+                r#"{"jsonrpc":"2.0","id":2,"result":["catch","match"]}"#,
+            ]
+        );
+        let suggest = json::parse(lines[3]).unwrap();
+        let result = suggest.get("result").unwrap();
+        assert_eq!(result.get("candidates").unwrap().as_usize(), Some(4));
+        assert_eq!(result.get("guess").unwrap().as_str(), Some("hatch"));
+        assert_eq!(lines.len(), 4);
+
+        let mut code = |line: &str| {
+            let response = session.handle(line).unwrap();
+            let error = response.get("error").unwrap().get("code").unwrap();
+            error.as_f64().unwrap() as i32
+        };
+        assert_eq!(code("{"), PARSE_ERROR);
+        assert_eq!(code(r#"{"id": 1}"#), INVALID_REQUEST);
+        assert_eq!(code(r#"{"id": 1, "method": "solve"}"#), METHOD_NOT_FOUND);
+        let params = r#"{"word": "zzzzz", "feedback": "bbbbb"}"#;
+        let request = format!(r#"{{"id": 1, "method": "apply_feedback", "params": {params}}}"#);
+        assert_eq!(code(&request), INVALID_PARAMS);
+    }
+}
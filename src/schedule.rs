@@ -0,0 +1,150 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    random::Rng,
+    timezone::{civil_from_days, days_from_civil},
+};
+
+/// A private run of daily puzzles: an answer for each day from `start`, in
+/// days since 1970-01-01, numbered from 0 like the official puzzles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Schedule {
+    pub start: i64,
+    pub answers: Vec<String>,
+}
+
+impl Schedule {
+    /// An answer for every day from `start` to `end`, both included, drawn
+    /// from `pool` in an order shuffled by `seed` so none repeats. `None` if
+    /// the range is empty or longer than the pool.
+    pub fn generate(start: i64, end: i64, pool: &[&str], seed: u64) -> Option<Self> {
+        let days = usize::try_from(end - start + 1).ok().filter(|&n| n > 0)?;
+        if days > pool.len() {
+            return None;
+        }
+        let mut answers: Vec<String> = pool.iter().map(|word| word.to_string()).collect();
+        Rng::new(seed).shuffle(&mut answers);
+        answers.truncate(days);
+        Some(Self { start, answers })
+    }
+
+    /// The number of the puzzle on `day`, if the schedule covers it.
+    pub fn number(&self, day: i64) -> Option<usize> {
+        usize::try_from(day - self.start)
+            .ok()
+            .filter(|&number| number < self.answers.len())
+    }
+
+    /// The answer to puzzle `number`.
+    pub fn answer(&self, number: usize) -> Option<&str> {
+        self.answers.get(number).map(String::as_str)
+    }
+
+    /// Write a line per day: its date, then its answer as `show` renders it.
+    pub fn save(&self, mut w: impl Write, show: impl Fn(&str) -> String) -> io::Result<()> {
+        for (day, answer) in (self.start..).zip(&self.answers) {
+            writeln!(w, "{} {}", format_date(day), show(answer))?;
+        }
+        Ok(())
+    }
+
+    /// Read a schedule written by [`save`](Self::save). Blank lines and
+    /// lines starting with `#` are skipped; every other line must be for the
+    /// day after the one before it.
+    pub fn load(r: impl BufRead) -> io::Result<Self> {
+        let mut schedule = Self::default();
+        for line in r.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (date, answer) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| invalid(line))?;
+            let day = parse_date(date).ok_or_else(|| invalid(line))?;
+            if schedule.answers.is_empty() {
+                schedule.start = day;
+            } else if day != schedule.start + schedule.answers.len() as i64 {
+                return Err(invalid(line));
+            }
+            schedule.answers.push(answer.trim().to_string());
+        }
+        Ok(schedule)
+    }
+}
+
+/// Days since 1970-01-01 of a date written `YYYY-MM-DD`, or `None` if it
+/// isn't a real date.
+pub fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    let days = days_from_civil(year, month, day);
+    // Dates like February 30 come back as some other day.
+    (civil_from_days(days) == (year, month, day)).then_some(days)
+}
+
+/// `days` since 1970-01-01 as `YYYY-MM-DD`.
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad schedule line: {line}"),
+    )
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(
+            parse_date("2024-02-29").map(format_date).as_deref(),
+            Some("2024-02-29")
+        );
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2023-13-01"), None);
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let pool = ["cigar", "rebut", "sissy", "humph", "awake"];
+        let start = parse_date("2026-12-30").unwrap();
+        let schedule = Schedule::generate(start, start + 3, &pool, 7).unwrap();
+        assert_eq!(schedule.answers.len(), 4);
+        let mut unique = schedule.answers.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+        assert_eq!(
+            Schedule::generate(start, start + 3, &pool, 7),
+            Some(schedule.clone())
+        );
+        assert_eq!(Schedule::generate(start, start + 5, &pool, 7), None);
+
+        let mut saved = Vec::new();
+        schedule.save(&mut saved, str::to_uppercase).unwrap();
+        let text = String::from_utf8(saved).unwrap();
+        assert!(text.starts_with("2026-12-30 "));
+        assert!(text.contains("\n2027-01-02 "));
+        let loaded = Schedule::load(text.as_bytes()).unwrap();
+        assert_eq!(loaded.number(start + 2), Some(2));
+        assert_eq!(loaded.number(start + 4), None);
+        assert_eq!(
+            loaded.answer(0),
+            Some(&schedule.answers[0].to_uppercase()[..])
+        );
+
+        // A skipped day would shift every puzzle after it.
+        assert!(Schedule::load("2026-12-30 cigar\n2027-01-01 rebut\n".as_bytes()).is_err());
+    }
+}
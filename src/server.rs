@@ -0,0 +1,401 @@
+use std::{
+    cell::OnceCell,
+    collections::HashSet,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpListener,
+};
+
+use crate::{
+    guesser::Guesser,
+    hint::{self, Scoring, Suggestion},
+    json::{self, Value},
+    model::AnswerModel,
+    normalize::Normalizer,
+    pattern,
+};
+
+/// The page served at `/` with `--ui`, which talks to `/suggest`.
+const PAGE: &str = include_str!("ui/index.html");
+
+/// The largest request body accepted, far more than any game's history.
+const MAX_BODY: usize = 64 * 1024;
+
+/// How many suggestions are returned unless the request asks for a number.
+const SUGGESTED: usize = 5;
+
+/// How many of the candidates are returned by name.
+const SAMPLED: usize = 50;
+
+/// An HTTP request, as much of one as the API reads.
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl Request {
+    /// Read a request: its request line, its headers, and a body as long as
+    /// its `Content-Length`.
+    pub fn read(mut r: impl BufRead) -> io::Result<Self> {
+        let mut line = String::new();
+        r.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+            return Err(invalid("bad request line"));
+        };
+        let (method, path) = (method.to_string(), path.to_string());
+
+        let mut length = 0;
+        loop {
+            line.clear();
+            if r.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| invalid("bad Content-Length"))?;
+                }
+            }
+        }
+        if length > MAX_BODY {
+            return Err(invalid("request body too large"));
+        }
+
+        let mut body = vec![0; length];
+        r.read_exact(&mut body)?;
+        let body = String::from_utf8(body).map_err(|_| invalid("request body isn't UTF-8"))?;
+        Ok(Self { method, path, body })
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// An HTTP response.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub(crate) fn json(status: u16, value: &Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: value.to_string(),
+        }
+    }
+
+    pub(crate) fn error(status: u16, message: &str) -> Self {
+        Self::json(
+            status,
+            &Value::Object(vec![("error".to_string(), message.into())]),
+        )
+    }
+
+    pub fn write(&self, mut w: impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Error",
+        };
+        write!(
+            w,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        w.flush()
+    }
+}
+
+/// The suggestion API: `POST /suggest` with the guesses played so far and
+/// their feedback, as in
+///
+/// ```text
+/// {"history": [{"word": "salet", "feedback": "bbybb"}], "top": 5}
+/// ```
+///
+/// answers with how many candidates remain, the solver's next guess, the
+/// best guesses by score, and some of the candidates. Every request is
+/// solved from scratch, so the server keeps no sessions.
+pub struct Api<'a, 'm, F> {
+    /// Makes a guesser configured as the command line asked.
+    guesser: F,
+    /// The words that may be played.
+    words: HashSet<&'a str>,
+    /// The words suggestions are drawn from, or `None` for the candidates.
+    probes: Option<&'a [&'a str]>,
+    model: &'m AnswerModel<'a>,
+    scoring: Scoring,
+    normalizer: Normalizer,
+    ui: bool,
+    /// The suggestions before any guess, which are the slowest to score and
+    /// never change.
+    opening: OnceCell<Vec<Suggestion<'a>>>,
+}
+
+impl<'a: 'b, 'm, 'b, F: Fn() -> Guesser<'a, 'b>> Api<'a, 'm, F> {
+    /// Answer with guessers from `guesser`, accepting any of `words` as a
+    /// guess.
+    pub fn new(guesser: F, words: &[&'a str], model: &'m AnswerModel<'a>) -> Self {
+        Self {
+            guesser,
+            words: words.iter().copied().collect(),
+            probes: None,
+            model,
+            scoring: Scoring::default(),
+            normalizer: Normalizer::default(),
+            ui: false,
+            opening: OnceCell::new(),
+        }
+    }
+
+    /// Draw suggestions from `probes` rather than only the candidates.
+    pub fn with_probes(mut self, probes: Option<&'a [&'a str]>) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Rank suggestions by `scoring`.
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Read words with `normalizer`, as the word lists were loaded.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Also serve the bundled page at `/`.
+    pub fn with_ui(mut self, ui: bool) -> Self {
+        self.ui = ui;
+        self
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/" | "/index.html") if self.ui => Response {
+                status: 200,
+                content_type: "text/html; charset=utf-8",
+                body: PAGE.to_string(),
+            },
+            ("POST", "/suggest") => match self.suggest(&request.body) {
+                Ok(value) => Response::json(200, &value),
+                Err(message) => Response::error(400, &message),
+            },
+            (_, "/suggest") => Response::error(405, "use POST"),
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    fn suggest(&self, body: &str) -> Result<Value, String> {
+        let request = json::parse(body).map_err(|e| e.to_string())?;
+        let top = match request.get("top") {
+            Some(top) => top.as_usize().ok_or("top must be a whole number")?,
+            None => SUGGESTED,
+        };
+        let history = match request.get("history") {
+            Some(history) => history.as_array().ok_or("history must be a list")?,
+            None => &[],
+        };
+
+        let mut guesser = self.new_game();
+        for guess in history {
+            let typed = guess
+                .get("word")
+                .and_then(Value::as_str)
+                .ok_or("each guess needs a word")?;
+            let feedback = guess
+                .get("feedback")
+                .and_then(Value::as_str)
+                .ok_or("each guess needs feedback")?;
+            let word = self.word(typed)?;
+            let mask = pattern::parse(feedback).map_err(|e| format!("{typed}: {e}"))?;
+            guesser.apply_feedback(word, mask);
+        }
+
+        let suggestions = self.suggestions(&guesser, top);
+        let candidates = guesser.candidates();
+        let sample = candidates
+            .iter()
+            .take(SAMPLED)
+            .map(|w| self.decode(w))
+            .collect();
+        let guess = guesser.next_guess().map_or(Value::Null, |w| self.decode(w));
+
+        Ok(Value::Object(vec![
+            (
+                "candidates".to_string(),
+                Value::Number(guesser.candidates().len() as f64),
+            ),
+            ("guess".to_string(), guess),
+            ("suggestions".to_string(), suggestions),
+            ("sample".to_string(), Value::Array(sample)),
+        ]))
+    }
+
+    /// A guesser for a new game.
+    pub(crate) fn new_game(&self) -> Guesser<'a, 'b> {
+        (self.guesser)()
+    }
+
+    /// The playable word that `typed` means.
+    pub(crate) fn word(&self, typed: &str) -> Result<&'a str, String> {
+        self.normalizer
+            .word(typed)
+            .into_iter()
+            .find_map(|word| self.words.get(word.as_str()).copied())
+            .ok_or_else(|| format!("{typed}: not in word list"))
+    }
+
+    /// `word` as it's typed rather than as it's stored.
+    pub(crate) fn decode(&self, word: &str) -> Value {
+        Value::String(self.normalizer.tiles().decode(word).into_owned())
+    }
+
+    /// The `top` best next guesses in `guesser`'s game, with their scores.
+    /// Before any guess they come from the cached opening, which keeps only
+    /// the best [`SAMPLED`].
+    pub(crate) fn suggestions(&self, guesser: &Guesser<'a, 'b>, top: usize) -> Value {
+        let candidates = guesser.candidates();
+        let guesses = self.probes.unwrap_or(candidates);
+        let fresh;
+        let suggestions = if guesser.history().is_empty() {
+            let opening = self.opening.get_or_init(|| {
+                hint::suggest(guesses, candidates, self.model, self.scoring, SAMPLED)
+            });
+            &opening[..top.min(opening.len())]
+        } else {
+            fresh = hint::suggest(guesses, candidates, self.model, self.scoring, top);
+            &fresh[..]
+        };
+
+        let suggestions = suggestions
+            .iter()
+            .map(|s| {
+                Value::Object(vec![
+                    ("word".to_string(), self.decode(s.word)),
+                    ("information".to_string(), Value::Number(s.information)),
+                    (
+                        "expected_remaining".to_string(),
+                        Value::Number(s.expected_remaining),
+                    ),
+                    ("probability".to_string(), Value::Number(s.probability)),
+                    (
+                        "expected_guesses".to_string(),
+                        Value::Number(s.expected_guesses),
+                    ),
+                ])
+            })
+            .collect();
+        Value::Array(suggestions)
+    }
+}
+
+/// Answer connections on `listener` one at a time with `api`, until the
+/// listener fails. A connection that fails only drops that connection.
+pub fn serve<'a: 'b, 'b, F: Fn() -> Guesser<'a, 'b>>(
+    listener: TcpListener,
+    api: &Api<'a, '_, F>,
+) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let response = match Request::read(BufReader::new(&stream)) {
+            Ok(request) => api.handle(&request),
+            Err(e) => Response::error(400, &e.to_string()),
+        };
+        if let Err(e) = response.write(&stream) {
+            eprintln!("warning: couldn't answer a request: {e}");
+        }
+    }
+    Ok(())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn read_request() {
+        let raw = "POST /suggest HTTP/1.1\r\nHost: localhost\r\ncontent-length: 7\r\n\r\n{\"a\":1}";
+        assert_eq!(
+            Request::read(raw.as_bytes()).unwrap(),
+            Request {
+                method: "POST".to_string(),
+                path: "/suggest".to_string(),
+                body: "{\"a\":1}".to_string(),
+            }
+        );
+        assert!(Request::read(&b"\r\n"[..]).is_err());
+    }
+
+    #[test]
+    fn suggestions() {
+        let dictionary = ["hatch", "catch", "match", "patch"];
+        let exclusions = HashSet::new();
+        let model = AnswerModel::new([("hatch", 4), ("catch", 3), ("match", 2), ("patch", 1)]);
+        let strategy = crate::strategy::Likeliest;
+        let api = Api::new(
+            || Guesser::new(&dictionary, &exclusions, &model, &strategy).with_opener(None),
+            &dictionary,
+            &model,
+        );
+        let post = |body: &str| {
+            api.handle(&Request {
+                method: "POST".to_string(),
+                path: "/suggest".to_string(),
+                body: body.to_string(),
+            })
+        };
+
+        let response = post(r#"{"history": [{"word": "HATCH", "feedback": "bgggg"}], "top": 1}"#);
+        assert_eq!(response.status, 200);
+        let value = json::parse(&response.body).unwrap();
+        assert_eq!(value.get("candidates").unwrap().as_usize(), Some(3));
+        assert_eq!(value.get("guess").unwrap().as_str(), Some("catch"));
+        assert_eq!(
+            value.get("suggestions").unwrap().as_array().unwrap().len(),
+            1
+        );
+
+        let response = post("{}");
+        let value = json::parse(&response.body).unwrap();
+        assert_eq!(value.get("candidates").unwrap().as_usize(), Some(4));
+        assert_eq!(value.get("sample").unwrap().as_array().unwrap().len(), 4);
+
+        assert_eq!(
+            post(r#"{"history": [{"word": "zzzzz", "feedback": "bbbbb"}]}"#).status,
+            400
+        );
+        assert_eq!(
+            post(r#"{"history": [{"word": "hatch", "feedback": "bb"}]}"#).status,
+            400
+        );
+        assert_eq!(post("not json").status, 400);
+
+        let get = |path: &str| Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            body: String::new(),
+        };
+        assert_eq!(api.handle(&get("/")).status, 404);
+        assert_eq!(api.handle(&get("/suggest")).status, 405);
+        let api = api.with_ui(true);
+        assert!(api.handle(&get("/")).body.contains("<html"));
+    }
+}
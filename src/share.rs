@@ -0,0 +1,141 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::{guesser::Guess, json, pattern::Tiles, stats::GUESSES};
+
+/// Clipboard tools tried in order, with their arguments: macOS, Wayland, X11,
+/// and Windows (including WSL).
+const CLIPBOARDS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// The emoji grid the game shares when it's over, headed by `title` and the
+/// score, such as `Wordle 4/6`. Games that weren't solved within the limit
+/// score `X`.
+pub fn grid(title: &str, history: &[Guess]) -> String {
+    let solved = history.last().is_some_and(Guess::is_correct) && history.len() <= GUESSES;
+    let score = if solved {
+        history.len().to_string()
+    } else {
+        "X".to_string()
+    };
+
+    let mut grid = format!("{title} {score}/{GUESSES}\n");
+    for guess in history {
+        grid.push('\n');
+        grid.push_str(&Tiles(guess.mask()).to_string());
+    }
+    grid
+}
+
+/// `time` in seconds to a tenth, such as `12.3s`.
+pub fn seconds(time: Duration) -> String {
+    format!("{:.1}s", time.as_secs_f64())
+}
+
+/// A line for the end of a timed game's grid: the total time, then each
+/// guess's, such as `⏱️ 20.5s (4.2 + 16.3)`.
+pub fn timing(times: &[Duration]) -> String {
+    let total: Duration = times.iter().sum();
+    let each: Vec<String> = times
+        .iter()
+        .map(|time| format!("{:.1}", time.as_secs_f64()))
+        .collect();
+    format!("⏱️ {} ({})", seconds(total), each.join(" + "))
+}
+
+/// Put `text` on the system clipboard, using the first clipboard tool that's
+/// installed.
+pub fn copy(text: &str) -> io::Result<()> {
+    for (program, args) in CLIPBOARDS {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no clipboard tool found (tried pbcopy, wl-copy, xclip, xsel, clip.exe)",
+    ))
+}
+
+/// Post `text` to a Slack or Discord webhook at `url`, using `curl`.
+///
+/// The message is sent under both services' field names, since each ignores
+/// the other's.
+pub fn post(url: &str, text: &str) -> io::Result<()> {
+    let text = json::quote(text);
+    let body = format!("{{\"text\":{text},\"content\":{text}}}");
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--max-time", "30"])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(body.as_bytes())?;
+            }
+            child.wait_with_output()
+        })?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(message.trim()));
+    }
+    Ok(())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn solved() {
+        let history = [
+            Guess::check("cigar", "tares"),
+            Guess::check("cigar", "cigar"),
+        ];
+        assert_eq!(
+            grid("Wordle", &history),
+            "Wordle 2/6\n\n⬛🟨🟨⬛⬛\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn unsolved() {
+        let history = [Guess::check("cigar", "tares")];
+        assert_eq!(grid("Wordle", &history), "Wordle X/6\n\n⬛🟨🟨⬛⬛");
+
+        let history = [Guess::check("cigar", "cigar"); 7];
+        assert!(grid("Wordle", &history).starts_with("Wordle X/6\n"));
+    }
+
+    #[test]
+    fn timed() {
+        let times = [Duration::from_millis(4210), Duration::from_millis(16_340)];
+        assert_eq!(timing(&times), "⏱️ 20.6s (4.2 + 16.3)");
+    }
+}
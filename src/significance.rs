@@ -0,0 +1,211 @@
+use std::fmt;
+
+use crate::random::Rng;
+
+/// How many resamples the bootstrap interval is drawn from.
+const RESAMPLES: usize = 2_000;
+
+/// The significance level: the interval leaves out this share of the
+/// bootstrap means, and p-values below it are called significant.
+const ALPHA: f64 = 0.05;
+
+/// Whether two runs over the same answers really differ, or only by chance:
+/// a paired test on each answer's scores, with failures scored as one past
+/// the limit, as [`Outcome::score`](crate::report::Outcome::score) does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Significance {
+    /// How many answers were compared.
+    pub pairs: usize,
+    /// The second run's average score less the first's.
+    pub mean: f64,
+    /// A 95% confidence interval for `mean`, by a paired bootstrap.
+    pub interval: (f64, f64),
+    /// The chance of a difference at least this lopsided if the runs were
+    /// equally good, by the two-sided Wilcoxon signed-rank test. Answers
+    /// scored the same by both runs are left out, as the test asks.
+    pub p_value: f64,
+}
+
+/// Test the differences between paired scores, each `(a, b)` being one
+/// answer's scores in the two runs. The bootstrap is seeded, so the same
+/// scores always give the same interval.
+pub fn paired(scores: &[(usize, usize)]) -> Significance {
+    let differences: Vec<f64> = scores.iter().map(|&(a, b)| b as f64 - a as f64).collect();
+    Significance {
+        pairs: differences.len(),
+        mean: mean(&differences),
+        interval: bootstrap(&differences),
+        p_value: wilcoxon(&differences),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// The percentile interval of the means of `differences` resampled with
+/// replacement.
+fn bootstrap(differences: &[f64]) -> (f64, f64) {
+    if differences.is_empty() {
+        return (0.0, 0.0);
+    }
+    interval(differences, |sample| {
+        sample.iter().copied().sum::<f64>() / sample.len() as f64
+    })
+}
+
+/// A 95% confidence interval for `statistic` over `values`, by resampling
+/// them with replacement and taking the percentiles of the statistic. The
+/// resampling is seeded, so the same values always give the same interval.
+/// Resamples the statistic is undefined for, as NaN, are left out.
+pub fn interval<T>(values: &[T], statistic: impl Fn(&[&T]) -> f64) -> (f64, f64) {
+    let mut rng = Rng::new(0x5eed);
+    let n = values.len();
+    let mut sample = Vec::with_capacity(n);
+    let mut statistics: Vec<f64> = (0..RESAMPLES)
+        .filter_map(|_| {
+            sample.clear();
+            sample.extend((0..n).map(|_| &values[rng.below(n)]));
+            Some(statistic(&sample)).filter(|s| !s.is_nan())
+        })
+        .collect();
+    if statistics.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    statistics.sort_by(f64::total_cmp);
+    let last = statistics.len() - 1;
+    let at = |q: f64| statistics[(last as f64 * q).round() as usize];
+    (at(ALPHA / 2.0), at(1.0 - ALPHA / 2.0))
+}
+
+/// The two-sided p-value of the Wilcoxon signed-rank test, by the normal
+/// approximation with a correction for tied ranks. Scores are small whole
+/// numbers, so ties are the rule rather than the exception.
+fn wilcoxon(differences: &[f64]) -> f64 {
+    let mut nonzero: Vec<f64> = differences.iter().copied().filter(|&d| d != 0.0).collect();
+    if nonzero.is_empty() {
+        return 1.0;
+    }
+    nonzero.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
+
+    // Tied magnitudes share the average of the ranks they span.
+    let n = nonzero.len();
+    let mut positive = 0.0;
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < n {
+        let end = start
+            + nonzero[start..]
+                .iter()
+                .take_while(|d| d.abs() == nonzero[start].abs())
+                .count();
+        let rank = (start + 1 + end) as f64 / 2.0;
+        positive += rank * nonzero[start..end].iter().filter(|&&d| d > 0.0).count() as f64;
+        let tied = (end - start) as f64;
+        ties += tied * tied * tied - tied;
+        start = end;
+    }
+
+    let n = n as f64;
+    let expected = n * (n + 1.0) / 4.0;
+    let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    let z = (positive - expected).abs() / variance.sqrt();
+    erfc(z / std::f64::consts::SQRT_2).min(1.0)
+}
+
+/// The complementary error function, to within about 1.2e-7 (Numerical
+/// Recipes' `erfcc`).
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let poly = -x * x - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * poly.exp();
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+impl fmt::Display for Significance {
+    /// A line for the difference and its interval and one for the test.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low, high) = self.interval;
+        writeln!(
+            f,
+            "{:<16} {:+.4} guesses per answer, second less first (95% CI {low:+.4} to \
+             {high:+.4})",
+            "difference", self.mean
+        )?;
+        let verdict = if self.p_value < ALPHA {
+            "significant"
+        } else {
+            "not significant"
+        };
+        writeln!(
+            f,
+            "{:<16} {:.4}, {verdict} (Wilcoxon signed-rank over {} answers)",
+            "p-value", self.p_value, self.pairs
+        )
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn erfc_matches_known_values() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-6);
+        assert!((erfc(1.0) - 0.157_299_2).abs() < 1e-6);
+        assert!((erfc(-1.0) - 1.842_700_8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn identical_runs() {
+        let significance = paired(&[(3, 3), (4, 4), (5, 5)]);
+        assert_eq!(significance.mean, 0.0);
+        assert_eq!(significance.interval, (0.0, 0.0));
+        assert_eq!(significance.p_value, 1.0);
+    }
+
+    #[test]
+    fn consistent_improvement() {
+        // Forty answers each a guess better, and ten a guess worse.
+        let scores: Vec<(usize, usize)> = (0..50)
+            .map(|i| if i < 40 { (4, 3) } else { (3, 4) })
+            .collect();
+        let significance = paired(&scores);
+        assert_eq!(significance.mean, -0.6);
+        assert!(significance.interval.0 < -0.6 && -0.6 < significance.interval.1);
+        assert!(significance.interval.1 < 0.0);
+        assert!(significance.p_value < 0.001);
+
+        // One answer either way is no evidence at all.
+        assert!(paired(&[(4, 3), (3, 4), (4, 4)]).p_value > 0.5);
+    }
+
+    #[test]
+    fn intervals() {
+        let values: Vec<f64> = (0..100).map(|i| (i % 2) as f64).collect();
+        let mean = |sample: &[&f64]| sample.iter().copied().sum::<f64>() / sample.len() as f64;
+        let (low, high) = interval(&values, mean);
+        assert!(low < 0.5 && 0.5 < high);
+        assert!(0.35 < low && high < 0.65);
+
+        // A statistic that's never defined has no interval.
+        assert!(interval(&values, |_| f64::NAN).0.is_nan());
+    }
+}
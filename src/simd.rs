@@ -0,0 +1,69 @@
+//! Batched pattern computation with SSE2, enabled by the `simd` feature.
+
+use std::arch::x86_64::{
+    __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8,
+};
+
+use crate::guesser::Correctness;
+
+#[inline]
+fn load(word: &[u8]) -> __m128i {
+    let mut bytes = [0u8; 16];
+    bytes[..5].copy_from_slice(&word[..5]);
+    // SAFETY: `bytes` is 16 bytes long, and SSE2 is part of the x86_64
+    // baseline, so the intrinsic is always available.
+    unsafe { _mm_loadu_si128(bytes.as_ptr().cast()) }
+}
+
+/// A bitmask of the (first five) lanes in which `a` and `b` are equal.
+#[inline]
+fn equal_lanes(a: __m128i, b: __m128i) -> u32 {
+    // SAFETY: SSE2 is part of the x86_64 baseline.
+    unsafe { _mm_movemask_epi8(_mm_cmpeq_epi8(a, b)) as u32 & 0b11111 }
+}
+
+/// Compute the pattern of `guess` against every answer.
+///
+/// Rather than tracking which answer letters have been used, this counts: a
+/// non-green guess letter is misplaced if the answer has more non-green copies
+/// of it than there are earlier non-green copies in the guess.
+pub(crate) fn compute_batch<'w>(
+    guess: &[u8],
+    answers: impl Iterator<Item = &'w [u8]>,
+) -> Vec<[Correctness; 5]> {
+    let mut earlier = [0u32; 5];
+    for (i, bits) in earlier.iter_mut().enumerate() {
+        for j in 0..i {
+            if guess[j] == guess[i] {
+                *bits |= 1 << j;
+            }
+        }
+    }
+
+    let word = load(guess);
+    // SAFETY: SSE2 is part of the x86_64 baseline.
+    let letters: [__m128i; 5] = std::array::from_fn(|i| unsafe { _mm_set1_epi8(guess[i] as i8) });
+
+    answers
+        .map(|answer| {
+            let answer = load(answer);
+            let green = equal_lanes(answer, word);
+
+            let mut c = [Correctness::Wrong; 5];
+            for i in 0..5 {
+                if green & (1 << i) != 0 {
+                    c[i] = Correctness::Correct;
+                    continue;
+                }
+
+                let available = (equal_lanes(answer, letters[i]) & !green).count_ones();
+                let claimed = (earlier[i] & !green).count_ones();
+                if claimed < available {
+                    c[i] = Correctness::Misplaced;
+                }
+            }
+
+            c
+        })
+        .collect()
+}
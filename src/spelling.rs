@@ -0,0 +1,59 @@
+/// Whether `a` is one typo away from `b`: a single wrong, missing, or extra
+/// letter, or two neighbouring letters swapped.
+pub fn is_near_miss(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    match a.len().abs_diff(b.len()) {
+        0 => {
+            let differ: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+            match differ[..] {
+                [_] => true,
+                [i, j] => j == i + 1 && a[i] == b[j] && a[j] == b[i],
+                _ => false,
+            }
+        },
+        1 => {
+            let (short, long) = if a.len() < b.len() { (a, b) } else { (b, a) };
+            let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+            short[prefix..] == long[prefix + 1..]
+        },
+        _ => false,
+    }
+}
+
+/// Up to `n` of `words` that are one typo away from `word`, in the order
+/// they appear in `words`.
+pub fn near_misses<'a>(word: &str, words: &[&'a str], n: usize) -> Vec<&'a str> {
+    words
+        .iter()
+        .copied()
+        .filter(|candidate| is_near_miss(word, candidate))
+        .take(n)
+        .collect()
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn typos() {
+        assert!(is_near_miss("crame", "crane"));
+        assert!(is_near_miss("carne", "crane"));
+        assert!(is_near_miss("cane", "crane"));
+        assert!(is_near_miss("craane", "crane"));
+
+        assert!(!is_near_miss("crane", "crane"));
+        assert!(!is_near_miss("nacre", "crane"));
+        assert!(!is_near_miss("crmae", "crane"));
+        assert!(!is_near_miss("can", "crane"));
+    }
+
+    #[test]
+    fn suggestions() {
+        let words = ["crane", "crate", "trace", "grate", "crank"];
+        assert_eq!(near_misses("crame", &words, 5), vec!["crane", "crate"]);
+        assert_eq!(near_misses("crame", &words, 1), vec!["crane"]);
+        assert!(near_misses("zzzzz", &words, 5).is_empty());
+    }
+}
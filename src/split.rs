@@ -0,0 +1,102 @@
+use crate::random::Rng;
+
+/// One side of a seeded split of the answers, so parameters tuned on one
+/// half can be judged on answers they never saw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Split {
+    /// The half to tune parameters on.
+    Tune,
+    /// The held-out half to evaluate them on.
+    Eval,
+}
+
+impl Split {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "tune" => Some(Split::Tune),
+            "eval" => Some(Split::Eval),
+            _ => None,
+        }
+    }
+
+    /// The words in `words` on this side of the split made by `seed`, in the
+    /// same order. The same seed always splits the same words the same way,
+    /// and the two sides never overlap. With an odd count, the extra word
+    /// goes to [`Split::Tune`].
+    pub fn select<'w>(self, words: &[&'w str], seed: u64) -> Vec<&'w str> {
+        let mut order: Vec<usize> = (0..words.len()).collect();
+        Rng::new(seed).shuffle(&mut order);
+
+        let mut tune = vec![false; words.len()];
+        for &i in &order[..words.len().div_ceil(2)] {
+            tune[i] = true;
+        }
+
+        words
+            .iter()
+            .zip(tune)
+            .filter(|&(_, tune)| tune == (self == Split::Tune))
+            .map(|(&word, _)| word)
+            .collect()
+    }
+}
+
+/// `n` words picked from `words` by `seed`, in the same order, or all of
+/// them if there aren't more than `n`. The same seed always picks the same
+/// words, and a larger sample by the same seed contains every smaller one.
+pub fn sample<'w>(words: &[&'w str], n: usize, seed: u64) -> Vec<&'w str> {
+    let mut order: Vec<usize> = (0..words.len()).collect();
+    Rng::new(seed).shuffle(&mut order);
+
+    let mut picked = vec![false; words.len()];
+    for &i in order.iter().take(n) {
+        picked[i] = true;
+    }
+
+    words
+        .iter()
+        .zip(picked)
+        .filter(|&(_, picked)| picked)
+        .map(|(&word, _)| word)
+        .collect()
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn halves() {
+        let words = ["cigar", "rebut", "sissy", "humph", "awake"];
+        let tune = Split::Tune.select(&words, 7);
+        let eval = Split::Eval.select(&words, 7);
+        assert_eq!((tune.len(), eval.len()), (3, 2));
+        assert!(tune.iter().all(|word| !eval.contains(word)));
+        assert_eq!(Split::Tune.select(&words, 7), tune);
+
+        // Each side keeps the list's order.
+        let position = |word| words.iter().position(|w| *w == word);
+        assert!(tune.windows(2).all(|w| position(w[0]) < position(w[1])));
+
+        assert_ne!(
+            (1..10)
+                .map(|seed| Split::Tune.select(&words, seed))
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            1
+        );
+        assert_eq!(Split::by_name("eval"), Some(Split::Eval));
+        assert_eq!(Split::by_name("test"), None);
+    }
+
+    #[test]
+    fn samples() {
+        let words = ["cigar", "rebut", "sissy", "humph", "awake"];
+        let three = sample(&words, 3, 7);
+        assert_eq!(three.len(), 3);
+        assert_eq!(sample(&words, 3, 7), three);
+        assert!(sample(&words, 2, 7).iter().all(|word| three.contains(word)));
+        assert_eq!(sample(&words, 10, 7), words);
+        assert!(sample(&words, 0, 7).is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    time::Duration,
+};
+
+use crate::share;
+
+/// How many guesses a game may take and still count as a win.
+pub const GUESSES: usize = 6;
+
+/// The widest bar in the guess distribution, in characters.
+const BAR: usize = 20;
+
+/// A player's record across hosted games, as the game's statistics screen
+/// shows it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub played: usize,
+    pub wins: usize,
+    pub streak: usize,
+    pub max_streak: usize,
+    /// How many wins took each number of guesses, from one up.
+    pub distribution: [usize; GUESSES],
+    /// The fastest timed win, if any game has been won against the clock.
+    pub best_time: Option<Duration>,
+}
+
+impl Stats {
+    /// Record a game solved in `guesses`, or abandoned if `None`. Games that
+    /// take more than [`GUESSES`] guesses are losses.
+    pub fn record(&mut self, guesses: Option<usize>) {
+        self.played += 1;
+        match guesses {
+            Some(n @ 1..=GUESSES) => {
+                self.wins += 1;
+                self.streak += 1;
+                self.max_streak = self.max_streak.max(self.streak);
+                self.distribution[n - 1] += 1;
+            },
+            _ => self.streak = 0,
+        }
+    }
+
+    /// Record that a timed game solved in `guesses`, or abandoned if `None`,
+    /// took `time`. Only wins can set a best; returns whether this one did.
+    pub fn record_time(&mut self, guesses: Option<usize>, time: Duration) -> bool {
+        let won = matches!(guesses, Some(1..=GUESSES));
+        if !won || self.best_time.is_some_and(|best| best <= time) {
+            return false;
+        }
+        self.best_time = Some(time);
+        true
+    }
+
+    /// The share of games won, as a whole percentage.
+    pub fn win_percentage(&self) -> usize {
+        match self.played {
+            0 => 0,
+            played => (self.wins * 100 + played / 2) / played,
+        }
+    }
+
+    /// Write the record as `key value` lines.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "played {}", self.played)?;
+        writeln!(w, "wins {}", self.wins)?;
+        writeln!(w, "streak {}", self.streak)?;
+        writeln!(w, "max-streak {}", self.max_streak)?;
+        let distribution: Vec<String> = self.distribution.iter().map(usize::to_string).collect();
+        writeln!(w, "distribution {}", distribution.join(" "))?;
+        if let Some(best) = self.best_time {
+            writeln!(w, "best-time {}", best.as_millis())?;
+        }
+        Ok(())
+    }
+
+    /// Read a record written by [`save`](Self::save). Unknown keys are
+    /// ignored, and missing ones are left at zero.
+    pub fn load(r: impl BufRead) -> io::Result<Self> {
+        let mut stats = Self::default();
+        for line in r.lines() {
+            let line = line?;
+            let Some((key, value)) = line.split_once(' ') else {
+                continue;
+            };
+
+            let field = match key {
+                "played" => &mut stats.played,
+                "wins" => &mut stats.wins,
+                "streak" => &mut stats.streak,
+                "max-streak" => &mut stats.max_streak,
+                "distribution" => {
+                    let counts: Vec<&str> = value.split_whitespace().collect();
+                    if counts.len() != GUESSES {
+                        return Err(invalid(&line));
+                    }
+                    for (slot, count) in stats.distribution.iter_mut().zip(counts) {
+                        *slot = count.parse().map_err(|_| invalid(&line))?;
+                    }
+                    continue;
+                },
+                "best-time" => {
+                    let millis = value.trim().parse().map_err(|_| invalid(&line))?;
+                    stats.best_time = Some(Duration::from_millis(millis));
+                    continue;
+                },
+                _ => continue,
+            };
+            *field = value.trim().parse().map_err(|_| invalid(&line))?;
+        }
+        Ok(stats)
+    }
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad stats line: {line}"),
+    )
+}
+
+impl fmt::Display for Stats {
+    /// Renders the totals, then the guess distribution as a bar chart.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "played {}, won {}%, current streak {}, max streak {}",
+            self.played,
+            self.win_percentage(),
+            self.streak,
+            self.max_streak
+        )?;
+        if let Some(best) = self.best_time {
+            writeln!(f, "best time {}", share::seconds(best))?;
+        }
+
+        let most = self.distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in self.distribution.iter().enumerate() {
+            let bar = "#".repeat((count * BAR).div_ceil(most));
+            writeln!(f, "{} |{bar} {count}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn streaks() {
+        let mut stats = Stats::default();
+        for guesses in [Some(3), Some(4), None, Some(3), Some(7), Some(2)] {
+            stats.record(guesses);
+        }
+
+        assert_eq!(stats.played, 6);
+        assert_eq!(stats.wins, 4);
+        assert_eq!(stats.win_percentage(), 67);
+        assert_eq!(stats.streak, 1);
+        assert_eq!(stats.max_streak, 2);
+        assert_eq!(stats.distribution, [0, 1, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut stats = Stats::default();
+        stats.record(Some(4));
+        stats.record(Some(1));
+
+        let mut saved = Vec::new();
+        stats.save(&mut saved).unwrap();
+        assert_eq!(Stats::load(&saved[..]).unwrap(), stats);
+
+        assert_eq!(Stats::load(&b""[..]).unwrap(), Stats::default());
+        assert!(Stats::load(&b"played lots\n"[..]).is_err());
+        assert!(Stats::load(&b"distribution 1 2\n"[..]).is_err());
+
+        stats.record_time(Some(1), Duration::from_millis(8250));
+        let mut saved = Vec::new();
+        stats.save(&mut saved).unwrap();
+        assert_eq!(Stats::load(&saved[..]).unwrap(), stats);
+    }
+
+    #[test]
+    fn best_time() {
+        let mut stats = Stats::default();
+        assert!(!stats.record_time(None, Duration::from_secs(5)));
+        assert!(stats.record_time(Some(4), Duration::from_secs(40)));
+        assert!(!stats.record_time(Some(3), Duration::from_secs(41)));
+        assert!(!stats.record_time(Some(7), Duration::from_secs(9)));
+        assert!(stats.record_time(Some(5), Duration::from_secs(30)));
+        assert_eq!(stats.best_time, Some(Duration::from_secs(30)));
+        assert!(stats.to_string().contains("\nbest time 30.0s\n"));
+    }
+
+    #[test]
+    fn display() {
+        let mut stats = Stats::default();
+        stats.record(Some(3));
+        stats.record(Some(3));
+        stats.record(Some(5));
+
+        let shown = stats.to_string();
+        assert!(shown.starts_with("played 3, won 100%, current streak 3, max streak 3\n"));
+        assert!(shown.contains(&format!("3 |{} 2\n", "#".repeat(20))));
+        assert!(shown.contains(&format!("5 |{} 1\n", "#".repeat(10))));
+        assert!(shown.contains("1 | 0\n"));
+    }
+}
@@ -0,0 +1,625 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    hint::{self, Scoring, Suggestion},
+    model::AnswerModel,
+    packed::Packed,
+    parallel,
+    typing::Layout,
+    weights::LetterWeights,
+};
+
+mod coverage;
+mod endgame;
+mod ensemble;
+mod greens;
+mod letters;
+mod misere;
+mod rollout;
+
+pub use coverage::Coverage;
+pub use endgame::Endgame;
+pub use ensemble::{Ensemble, Voting};
+pub use greens::Greens;
+pub use letters::Letters;
+pub use misere::Misere;
+pub use rollout::Rollout;
+
+/// A policy for picking the next guess from the remaining candidates.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// Choose the next guess. `guesses` are the words that may be played,
+    /// which may include probe words that can't be the answer; `candidates`
+    /// are the possible answers and are never empty.
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str;
+
+    /// Choose as [`choose`](Self::choose), knowing that `turns` guesses are
+    /// left counting this one, if the game has a limit. Only strategies that
+    /// aim at the limit need to know; the rest choose as they always do.
+    fn choose_within<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> &'a str {
+        let _ = turns;
+        self.choose(guesses, candidates, model)
+    }
+
+    /// Up to `n` guesses, best first, for weighing this strategy's opinion
+    /// against others'. Strategies that only ever settle on one guess rank
+    /// just the one they'd choose.
+    fn rank<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+        n: usize,
+    ) -> Vec<&'a str> {
+        let _ = n;
+        vec![self.choose_within(guesses, candidates, model, turns)]
+    }
+}
+
+/// What the strategies that can aim at more than one thing try to make
+/// best. Without a limit on guesses, the chance of failing is the average's
+/// business.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Objective {
+    /// The fewest guesses on average, for casual play.
+    #[default]
+    Average,
+    /// The fewest guesses for the hardest answer, for hard mode, where
+    /// every guess must fit the clues and a bad split is hard to escape.
+    Worst,
+    /// The best chance of finding the answer within the guesses left, for
+    /// keeping a streak.
+    FailRate,
+}
+
+impl Objective {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "avg" | "average" => Some(Objective::Average),
+            "worst" => Some(Objective::Worst),
+            "fail-rate" => Some(Objective::FailRate),
+            _ => None,
+        }
+    }
+}
+
+/// Bits a guess loses under [`Objective::FailRate`] for certain failure, and
+/// in proportion for any chance of it, enough that running out of time
+/// outweighs all but the slightest differences in information.
+const FAILURE_WEIGHT: f64 = 100.0;
+
+/// Settings shared by the strategies that support them.
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+    pub scoring: Scoring,
+    /// Letter weights from `wordle train`, or counted over the words
+    /// `--freq-source` names, for strategies that rank words by their
+    /// letters.
+    pub weights: Option<LetterWeights>,
+    /// Search exactly once this few candidates remain, if set.
+    pub endgame: Option<usize>,
+    /// Bits taken off the score of a guess that can't be the answer.
+    pub probe_penalty: f64,
+    /// How strongly the answer model evens out words' corpus counts, as its
+    /// [`smoothing`](AnswerModel::with_smoothing).
+    pub smoothing: f64,
+    /// Bits taken off the score of a guess for each key width of its typing
+    /// cost on `layout`, for timed games where fast words are worth a little
+    /// information.
+    pub typing_penalty: f64,
+    pub layout: Layout,
+    /// What the entropy strategy and the endgame search aim for.
+    pub objective: Objective,
+    /// How long the strategies that support it may spend on each guess.
+    /// Past it, they play the best of the guesses scored so far, having
+    /// scored the most promising first.
+    pub budget: Option<Duration>,
+}
+
+impl Params {
+    /// The knobs [`set`](Self::set) accepts.
+    pub const KEYS: &'static [&'static str] = &[
+        "answer-weight",
+        "probe-penalty",
+        "typing-penalty",
+        "endgame",
+        "smoothing",
+    ];
+
+    /// Turn the knob `key` to `value`, as with `--param key=value`:
+    ///
+    /// - `answer-weight`: scores by [`Scoring::Combined`] with this weight,
+    ///   trading information for the chance of guessing the answer;
+    /// - `probe-penalty`: see [`probe_penalty`](Self::probe_penalty);
+    /// - `typing-penalty`: see [`typing_penalty`](Self::typing_penalty);
+    /// - `endgame`: see [`endgame`](Self::endgame);
+    /// - `smoothing`: see [`smoothing`](Self::smoothing).
+    pub fn set(&mut self, key: &str, value: f64) -> Result<(), String> {
+        let invalid = || Err(format!("invalid value for {key}: {value}"));
+        match key {
+            "answer-weight" if value >= 0.0 => self.scoring = Scoring::Combined(value),
+            "probe-penalty" if value >= 0.0 => self.probe_penalty = value,
+            "typing-penalty" if value >= 0.0 => self.typing_penalty = value,
+            "smoothing" if value >= 0.0 => self.smoothing = value,
+            "endgame" if value >= 0.0 && value.fract() == 0.0 => {
+                self.endgame = Some(value as usize)
+            },
+            "answer-weight" | "probe-penalty" | "typing-penalty" | "endgame" | "smoothing" => {
+                return invalid()
+            },
+            _ => {
+                return Err(format!(
+                    "unknown parameter: {key} (expected one of {})",
+                    Self::KEYS.join(", ")
+                ))
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Look up a strategy by its command-line name. Strategies that support a
+/// choice of scoring also accept it as a suffix, as in `entropy/remaining`.
+///
+/// An [`Ensemble`] is named by its voting and then its members, with any
+/// weights, as in `borda:entropy,likeliest` or `vote:entropy=2,greens`.
+pub fn by_name(name: &str, params: &Params) -> Option<Box<dyn Strategy>> {
+    if let Some((voting, members)) = name.split_once(':') {
+        if let Some(voting) = Voting::by_name(voting) {
+            return ensemble(voting, members, params);
+        }
+    }
+
+    let (name, scoring) = match name.split_once('/') {
+        Some((name, scoring)) => (name, Scoring::by_name(scoring)?),
+        None => (name, params.scoring),
+    };
+
+    let strategy: Box<dyn Strategy> = match name {
+        "likeliest" => Box::new(Likeliest),
+        "entropy" => Box::new(Entropy {
+            scoring,
+            probe_penalty: params.probe_penalty,
+            typing_penalty: params.typing_penalty,
+            layout: params.layout,
+            objective: params.objective,
+            budget: params.budget,
+        }),
+        "rollout" => Box::new(Rollout {
+            budget: params.budget,
+            ..Rollout::default()
+        }),
+        "misere" => return Some(Box::new(Misere)),
+        "coverage" => Box::new(Coverage::default()),
+        "greens" => Box::new(Greens::default()),
+        "letters" => Box::new(Letters {
+            weights: params.weights.clone(),
+        }),
+        _ => return None,
+    };
+
+    // Misère play wants the longest game, so it's never handed an endgame
+    // search that minimizes guesses.
+    match params.endgame {
+        Some(threshold) => Some(Box::new(Endgame {
+            inner: strategy,
+            threshold,
+            objective: params.objective,
+        })),
+        None => Some(strategy),
+    }
+}
+
+/// An ensemble of the comma-separated `members`, each a strategy's name
+/// with an optional `=weight`. An endgame search applies to the ensemble as
+/// a whole rather than to each member.
+fn ensemble(voting: Voting, members: &str, params: &Params) -> Option<Box<dyn Strategy>> {
+    let member_params = Params {
+        endgame: None,
+        ..params.clone()
+    };
+    let members = members
+        .split(',')
+        .map(|member| {
+            let (name, weight) = match member.split_once('=') {
+                Some((name, weight)) => (name, weight.parse().ok().filter(|&w: &f64| w > 0.0)?),
+                None => (member, 1.0),
+            };
+            Some((by_name(name.trim(), &member_params)?, weight))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let strategy = Box::new(Ensemble { members, voting });
+    match params.endgame {
+        Some(threshold) => Some(Box::new(Endgame {
+            inner: strategy,
+            threshold,
+            objective: params.objective,
+        })),
+        None => Some(strategy),
+    }
+}
+
+/// Guess the candidate most likely to be the answer.
+pub struct Likeliest;
+
+impl Strategy for Likeliest {
+    fn name(&self) -> &'static str {
+        "likeliest"
+    }
+
+    fn choose<'a>(
+        &self,
+        _guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        let distribution = model.distribution(candidates);
+
+        // Ties go to the earliest candidate, which keeps the dictionary's own
+        // ordering as the tie-breaker.
+        let mut best = 0;
+        for (i, &p) in distribution.iter().enumerate() {
+            if p > distribution[best] {
+                best = i;
+            }
+        }
+
+        candidates[best]
+    }
+
+    fn rank<'a>(
+        &self,
+        _guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        _turns: Option<usize>,
+        n: usize,
+    ) -> Vec<&'a str> {
+        let distribution = model.distribution(candidates);
+        let mut ranked: Vec<usize> = (0..candidates.len()).collect();
+        ranked.sort_by(|&a, &b| distribution[b].total_cmp(&distribution[a]));
+        ranked.into_iter().take(n).map(|i| candidates[i]).collect()
+    }
+}
+
+/// Guess the word that scores best for a single step, by default the one with
+/// the highest expected information, where the expectation is taken over the
+/// model's answer distribution.
+#[derive(Default)]
+pub struct Entropy {
+    pub scoring: Scoring,
+    /// Bits taken off the score of a guess that can't be the answer, to
+    /// play probe words only when they're clearly better.
+    pub probe_penalty: f64,
+    /// Bits taken off the score of a guess per key width of its typing cost
+    /// on `layout`.
+    pub typing_penalty: f64,
+    pub layout: Layout,
+    /// What to aim for. Anything but the average is weighed first, and the
+    /// scoring only breaks ties or near ties.
+    pub objective: Objective,
+    /// How long to spend on each guess, if not as long as it takes.
+    pub budget: Option<Duration>,
+}
+
+impl Strategy for Entropy {
+    fn name(&self) -> &'static str {
+        match self.scoring {
+            Scoring::Information => "entropy",
+            Scoring::Remaining => "entropy/remaining",
+            Scoring::Combined(_) => "entropy/combined",
+        }
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        self.choose_within(guesses, candidates, model, None)
+    }
+
+    fn choose_within<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> &'a str {
+        let distribution = model.distribution(candidates);
+
+        // Every guess is scored against the same candidates, so they're
+        // packed together once up front.
+        let packed = Packed::new(candidates);
+        let answers: HashSet<&str> = if self.probe_penalty > 0.0 {
+            candidates.iter().copied().collect()
+        } else {
+            HashSet::new()
+        };
+        // On a budget, the candidates are scored before any probe, so a
+        // guess that could win is always in the running, and within each the
+        // guesses testing the most uncertain letters go first, as they tend
+        // to carry the most information.
+        let deadline = self.budget.map(|budget| Instant::now() + budget);
+        let ordered;
+        let guesses = if deadline.is_some() {
+            let possible: HashSet<&str> = candidates.iter().copied().collect();
+            let mut by_weight = Coverage::by_weight(guesses, candidates, model);
+            by_weight.sort_by_key(|guess| !possible.contains(guess));
+            ordered = by_weight;
+            &ordered
+        } else {
+            guesses
+        };
+
+        let work = guesses.len() * candidates.len();
+        let best = parallel::best_until(guesses, work, deadline, |&guess| {
+            self.score(guess, &packed, &distribution, &answers, turns)
+        });
+
+        best.copied().unwrap_or(candidates[0])
+    }
+
+    /// The best `n` guesses by the same score [`choose`](Self::choose)
+    /// plays by, though every guess is scored, whatever the budget.
+    fn rank<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+        n: usize,
+    ) -> Vec<&'a str> {
+        let distribution = model.distribution(candidates);
+        let packed = Packed::new(candidates);
+        let answers: HashSet<&str> = if self.probe_penalty > 0.0 {
+            candidates.iter().copied().collect()
+        } else {
+            HashSet::new()
+        };
+
+        let work = guesses.len() * candidates.len();
+        let scores = parallel::map(guesses, work, |&guess| {
+            self.score(guess, &packed, &distribution, &answers, turns)
+        });
+        // A stable sort keeps ties in the order the guesses came in, as
+        // choosing does.
+        let mut ranked: Vec<usize> = (0..guesses.len()).collect();
+        ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+        ranked.into_iter().take(n).map(|i| guesses[i]).collect()
+    }
+}
+
+impl Entropy {
+    /// What playing `guess` is worth against `candidates`, packed, whose
+    /// probabilities are `distribution`. `answers` holds the candidates when
+    /// probes are penalized.
+    fn score(
+        &self,
+        guess: &str,
+        candidates: &Packed,
+        distribution: &[f64],
+        answers: &HashSet<&str>,
+        turns: Option<usize>,
+    ) -> f64 {
+        let score = self.scoring.score(&Suggestion::evaluate_packed(
+            guess,
+            candidates,
+            distribution,
+        ));
+        let score = if self.probe_penalty > 0.0 && !answers.contains(guess) {
+            score - self.probe_penalty
+        } else {
+            score
+        };
+        let score = if self.typing_penalty > 0.0 {
+            score - self.typing_penalty * self.layout.cost(guess)
+        } else {
+            score
+        };
+        // Buckets are whole, so squeezing the score into less than one
+        // keeps it to breaking ties between equally large ones.
+        match (self.objective, turns) {
+            (Objective::Average, _) | (Objective::FailRate, None) => score,
+            (Objective::Worst, _) => {
+                let (largest, _) = hint::risk(guess, candidates, distribution, 0);
+                score.atan() / std::f64::consts::PI - largest as f64
+            },
+            (Objective::FailRate, Some(turns)) => {
+                let after = turns.saturating_sub(1);
+                let (_, failure) = hint::risk(guess, candidates, distribution, after);
+                score - FAILURE_WEIGHT * failure
+            },
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn names() {
+        let params = Params::default();
+        assert_eq!(by_name("entropy", &params).unwrap().name(), "entropy");
+        assert_eq!(
+            by_name("entropy/remaining", &params).unwrap().name(),
+            "entropy/remaining"
+        );
+        assert_eq!(
+            by_name("entropy/combined:3", &params).unwrap().name(),
+            "entropy/combined"
+        );
+        assert!(by_name("entropy/bogus", &params).is_none());
+        assert_eq!(by_name("coverage", &params).unwrap().name(), "coverage");
+        assert_eq!(by_name("greens", &params).unwrap().name(), "greens");
+        assert!(by_name("bogus", &params).is_none());
+        assert_eq!(
+            by_name("borda:entropy/remaining,likeliest", &params)
+                .unwrap()
+                .name(),
+            "borda"
+        );
+        assert_eq!(
+            by_name("vote:entropy=2,greens", &params).unwrap().name(),
+            "vote"
+        );
+        assert!(by_name("vote:entropy=0,greens", &params).is_none());
+        assert!(by_name("vote:entropy,bogus", &params).is_none());
+    }
+
+    #[test]
+    fn params() {
+        let mut params = Params::default();
+        params.set("answer-weight", 3.0).unwrap();
+        params.set("probe-penalty", 0.5).unwrap();
+        params.set("typing-penalty", 0.1).unwrap();
+        params.set("endgame", 20.0).unwrap();
+        params.set("smoothing", 2.0).unwrap();
+        assert_eq!(params.scoring, Scoring::Combined(3.0));
+        assert_eq!(params.probe_penalty, 0.5);
+        assert_eq!(params.typing_penalty, 0.1);
+        assert_eq!(params.endgame, Some(20));
+        assert_eq!(params.smoothing, 2.0);
+        assert!(params.set("endgame", 2.5).is_err());
+        assert!(params.set("smoothing", -1.0).is_err());
+        assert!(params.set("answer-weight", -1.0).is_err());
+        let error = params.set("bogus", 1.0).unwrap_err();
+        assert!(error.contains("smoothing"), "{error}");
+    }
+
+    #[test]
+    fn likeliest_prefers_frequent() {
+        let model = AnswerModel::new([("cigar", 1), ("rebut", 5), ("sissy", 2)]);
+        let candidates = ["cigar", "rebut", "sissy"];
+        assert_eq!(Likeliest.choose(&candidates, &candidates, &model), "rebut");
+    }
+
+    #[test]
+    fn entropy_of_even_split() {
+        let model = AnswerModel::new([]);
+        let candidates = ["cigar", "rebut"];
+        let distribution = model.distribution(&candidates);
+
+        assert_eq!(
+            Suggestion::evaluate("cigar", &candidates, &distribution).information,
+            1.0
+        );
+    }
+
+    #[test]
+    fn entropy_uses_probe_words() {
+        // No candidate can tell the others apart, but "chomp" splits them all.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+
+        for scoring in [Scoring::Information, Scoring::Remaining] {
+            let entropy = Entropy {
+                scoring,
+                ..Entropy::default()
+            };
+            assert_eq!(entropy.choose(&guesses, &candidates, &model), "chomp");
+            assert_ne!(entropy.choose(&candidates, &candidates, &model), "chomp");
+        }
+
+        // Penalized heavily enough, the probe loses to a candidate.
+        let entropy = Entropy {
+            probe_penalty: 1.0,
+            ..Entropy::default()
+        };
+        assert_ne!(entropy.choose(&guesses, &candidates, &model), "chomp");
+    }
+
+    #[test]
+    fn objectives() {
+        assert_eq!(Objective::by_name("avg"), Some(Objective::Average));
+        assert_eq!(Objective::by_name("fail-rate"), Some(Objective::FailRate));
+        assert_eq!(Objective::by_name("best"), None);
+
+        // "match" is likelier, but leaves two together if it's wrong, where
+        // "chomp" splits the three apart.
+        let model = AnswerModel::new([("hatch", 1), ("catch", 1), ("match", 4)]);
+        let candidates = ["hatch", "catch", "match"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        let entropy = |objective| Entropy {
+            scoring: Scoring::Combined(4.0),
+            objective,
+            ..Entropy::default()
+        };
+        let average = entropy(Objective::Average);
+        assert_eq!(average.choose(&guesses, &candidates, &model), "match");
+        let worst = entropy(Objective::Worst);
+        assert_eq!(worst.choose(&guesses, &candidates, &model), "chomp");
+
+        // With two guesses left, "match" fails one time in six, leaving two
+        // candidates and one guess for them; with three, neither can fail.
+        let fail_rate = entropy(Objective::FailRate);
+        let within = |turns| fail_rate.choose_within(&guesses, &candidates, &model, turns);
+        assert_eq!(within(Some(2)), "chomp");
+        assert_eq!(within(Some(3)), "match");
+        assert_eq!(within(None), "match");
+    }
+
+    #[test]
+    fn entropy_on_a_budget() {
+        // With no time at all, the first batch of guesses is still scored,
+        // and here that's every one of them.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        let entropy = Entropy {
+            budget: Some(Duration::ZERO),
+            ..Entropy::default()
+        };
+        assert_eq!(entropy.choose(&guesses, &candidates, &model), "chomp");
+    }
+
+    #[test]
+    fn entropy_weighs_typing() {
+        // Either guess tells the two apart, so the easier one to type wins
+        // once typing counts for anything.
+        let model = AnswerModel::new([]);
+        let candidates = ["pizza", "dress"];
+        let entropy = Entropy::default();
+        assert_eq!(entropy.choose(&candidates, &candidates, &model), "pizza");
+        let entropy = Entropy {
+            typing_penalty: 0.01,
+            ..Entropy::default()
+        };
+        assert_eq!(entropy.choose(&candidates, &candidates, &model), "dress");
+    }
+
+    #[test]
+    fn entropy_weighs_by_probability() {
+        let candidates = ["hatch", "catch"];
+        let uniform = AnswerModel::new([]).distribution(&candidates);
+        let skewed = AnswerModel::new([("hatch", 99), ("catch", 1)]).distribution(&candidates);
+
+        assert!(
+            Suggestion::evaluate("hatch", &candidates, &skewed).information
+                < Suggestion::evaluate("hatch", &candidates, &uniform).information
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Likeliest, Strategy};
+use crate::model::AnswerModel;
+
+/// Test as many new letters as possible, then go for the answer: a cheap
+/// coverage-first policy that many people play by.
+///
+/// While more than `until` candidates remain, the guess is the word whose
+/// distinct letters carry the most weight, where a letter weighs the chance
+/// that the answer contains it. Letters every candidate contains are already
+/// settled, as are letters none contain, so they count for nothing. After
+/// that, the likeliest candidate is played.
+pub struct Coverage {
+    pub until: usize,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self { until: 3 }
+    }
+}
+
+impl Coverage {
+    /// The chance that the answer contains each letter, where it isn't
+    /// already certain, indexed by byte.
+    fn weights(candidates: &[&str], model: &AnswerModel) -> [f64; 256] {
+        let distribution = model.distribution(candidates);
+        let mut weights = [0.0; 256];
+        for (word, p) in candidates.iter().zip(distribution) {
+            let mut seen = [false; 256];
+            for b in word.bytes() {
+                if !seen[b as usize] {
+                    seen[b as usize] = true;
+                    weights[b as usize] += p;
+                }
+            }
+        }
+
+        // Rounding leaves a letter every candidate has a hair short of one.
+        for weight in &mut weights {
+            if *weight > 1.0 - 1e-9 {
+                *weight = 0.0;
+            }
+        }
+        weights
+    }
+
+    /// `guesses` ordered by the weight of their letters, heaviest first, as
+    /// a cheap guess at which carry the most information.
+    pub(super) fn by_weight<'a>(
+        guesses: &[&'a str],
+        candidates: &[&str],
+        model: &AnswerModel,
+    ) -> Vec<&'a str> {
+        let weights = Self::weights(candidates, model);
+        let mut ordered = guesses.to_vec();
+        ordered.sort_by(|a, b| Self::score(b, &weights).total_cmp(&Self::score(a, &weights)));
+        ordered
+    }
+
+    fn score(word: &str, weights: &[f64; 256]) -> f64 {
+        let mut seen = [false; 256];
+        word.bytes()
+            .filter(|&b| !std::mem::replace(&mut seen[b as usize], true))
+            .map(|b| weights[b as usize])
+            .sum()
+    }
+}
+
+impl Strategy for Coverage {
+    fn name(&self) -> &'static str {
+        "coverage"
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        if candidates.len() <= self.until {
+            return Likeliest.choose(guesses, candidates, model);
+        }
+
+        let weights = Self::weights(candidates, model);
+        // Candidates come first, so on a tie the guess that might win does.
+        let mut best = candidates[0];
+        let mut best_score = Self::score(best, &weights);
+        for &word in candidates.iter().chain(guesses) {
+            let score = Self::score(word, &weights);
+            if score > best_score {
+                (best, best_score) = (word, score);
+            }
+        }
+
+        // A guess that tests nothing new can't help; the answer might.
+        if best_score == 0.0 {
+            return Likeliest.choose(guesses, candidates, model);
+        }
+        best
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn covers_untested_letters() {
+        // Every candidate ends in "atch", so only the first letter is open,
+        // and "plumb" tests three of them to "chomp"'s two.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch", "latch"];
+        let mut guesses = candidates.to_vec();
+        guesses.extend(["chomp", "plumb"]);
+        assert_eq!(
+            Coverage::default().choose(&guesses, &candidates, &model),
+            "plumb"
+        );
+
+        // Few enough candidates, and it goes for the answer.
+        let coverage = Coverage { until: 5 };
+        assert!(candidates.contains(&coverage.choose(&guesses, &candidates, &model)));
+    }
+
+    #[test]
+    fn repeated_letters_count_once() {
+        let model = AnswerModel::new([]);
+        let candidates = ["sassy", "tares", "bunch", "fjord"];
+        assert_eq!(
+            Coverage::default().choose(&[], &candidates, &model),
+            "tares"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use super::{Objective, Strategy};
+use std::collections::HashMap;
+
+use crate::{model::AnswerModel, optimal::Search};
+
+/// Hand over to an exact search once few enough candidates remain.
+///
+/// Above `threshold` candidates, `inner` chooses as usual. At or below it,
+/// every allowed guess is searched to the end of the game, and the one with
+/// the fewest expected guesses under the model's distribution is played, so
+/// the endgame is played as well as it can be.
+///
+/// Aiming for the worst case, the search plays for the fewest guesses any
+/// answer can take, and then the fewest expected. Aiming for the fail rate,
+/// it plays for the fewest expected among the ways of finding every answer
+/// in the guesses left, and if there are none, `inner` makes the best of it.
+pub struct Endgame {
+    pub inner: Box<dyn Strategy>,
+    pub threshold: usize,
+    pub objective: Objective,
+}
+
+impl Strategy for Endgame {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        self.choose_within(guesses, candidates, model, None)
+    }
+
+    fn choose_within<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> &'a str {
+        let inner = || self.inner.choose_within(guesses, candidates, model, turns);
+        if candidates.len() > self.threshold {
+            return inner();
+        }
+
+        // A guesser that only plays candidates will keep doing so, so the
+        // search only considers what it could actually play.
+        let probes = (guesses.len() != candidates.len()).then_some(guesses);
+        let probability: HashMap<&str, f64> = candidates
+            .iter()
+            .copied()
+            .zip(model.distribution(candidates))
+            .collect();
+        let search = |depth| {
+            Search::new(probes, probability.clone())
+                .with_depth(depth)
+                .solve(candidates)
+        };
+        // Guessing the candidates in turn finds them all, so some depth up
+        // to their count always works.
+        let solved = match (self.objective, turns) {
+            (Objective::Worst, _) => (1..=candidates.len()).find_map(search),
+            (Objective::FailRate, Some(turns)) => search(turns),
+            _ => search(usize::MAX),
+        };
+        solved.map_or_else(inner, |(_, guess)| guess)
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::strategy::Likeliest;
+
+    #[allow(dead_code)]
+    fn endgame() -> Endgame {
+        Endgame {
+            inner: Box::new(Likeliest),
+            threshold: 20,
+            objective: Objective::Average,
+        }
+    }
+
+    #[test]
+    fn splits_with_a_probe() {
+        // Any candidate leaves a three-way tie, but "chomp" splits them all.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        assert_eq!(endgame().choose(&guesses, &candidates, &model), "chomp");
+
+        // Without probes, it has to play a candidate.
+        assert!(candidates.contains(&endgame().choose(&candidates, &candidates, &model)));
+    }
+
+    #[test]
+    fn defers_above_the_threshold() {
+        let model = AnswerModel::new([("hatch", 1), ("catch", 5), ("match", 2)]);
+        let candidates = ["hatch", "catch", "match"];
+        let endgame = Endgame {
+            inner: Box::new(Likeliest),
+            threshold: 2,
+            objective: Objective::Average,
+        };
+        assert_eq!(endgame.choose(&candidates, &candidates, &model), "catch");
+    }
+
+    #[test]
+    fn aims_for_the_objective() {
+        // "match" is likeliest, and playing it first is best on average, but
+        // only "chomp" finds every answer in two guesses.
+        let model = AnswerModel::new([("hatch", 1), ("catch", 1), ("match", 4)]);
+        let candidates = ["hatch", "catch", "match"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        let endgame = |objective| Endgame {
+            objective,
+            ..endgame()
+        };
+        let choose = |objective, turns| {
+            endgame(objective).choose_within(&guesses, &candidates, &model, turns)
+        };
+        assert_eq!(choose(Objective::Average, Some(2)), "match");
+        assert_eq!(choose(Objective::Worst, None), "chomp");
+        assert_eq!(choose(Objective::FailRate, Some(2)), "chomp");
+        assert_eq!(choose(Objective::FailRate, Some(3)), "match");
+        // Out of time to find them all, the inner strategy takes its chance.
+        assert_eq!(choose(Objective::FailRate, Some(1)), "match");
+    }
+}
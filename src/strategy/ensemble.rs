@@ -0,0 +1,151 @@
+use super::Strategy;
+use crate::model::AnswerModel;
+
+/// How many guesses each member ranks in a Borda count.
+const BALLOT: usize = 10;
+
+/// How an [`Ensemble`] combines its members' opinions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Voting {
+    /// Each member ranks its best guesses, and a guess scores more the
+    /// higher it's ranked, times the member's weight.
+    Borda,
+    /// Each member votes for the guess it would choose with its weight.
+    Weighted,
+}
+
+impl Voting {
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "borda" => Some(Self::Borda),
+            "vote" => Some(Self::Weighted),
+            _ => None,
+        }
+    }
+}
+
+/// Ask several strategies each turn and play the guess they agree on most.
+///
+/// Different strategies stumble on different families of words, so a
+/// committee can avoid some of the misses any one member would make. Ties go
+/// to the guess the earliest member ranked highest.
+pub struct Ensemble {
+    /// The strategies consulted, each with the weight of its vote.
+    pub members: Vec<(Box<dyn Strategy>, f64)>,
+    pub voting: Voting,
+}
+
+impl Ensemble {
+    /// Every guess any member ranked, in the order first ranked, with the
+    /// points it scored.
+    fn tally<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> Vec<(&'a str, f64)> {
+        let ballot = match self.voting {
+            Voting::Borda => BALLOT,
+            Voting::Weighted => 1,
+        };
+        let mut tally: Vec<(&str, f64)> = Vec::new();
+        for (member, weight) in &self.members {
+            let ranked = member.rank(guesses, candidates, model, turns, ballot);
+            for (rank, guess) in ranked.into_iter().take(ballot).enumerate() {
+                let points = weight * (ballot - rank) as f64;
+                match tally.iter_mut().find(|(word, _)| *word == guess) {
+                    Some((_, total)) => *total += points,
+                    None => tally.push((guess, points)),
+                }
+            }
+        }
+        tally
+    }
+}
+
+impl Strategy for Ensemble {
+    fn name(&self) -> &'static str {
+        match self.voting {
+            Voting::Borda => "borda",
+            Voting::Weighted => "vote",
+        }
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        self.choose_within(guesses, candidates, model, None)
+    }
+
+    fn choose_within<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+    ) -> &'a str {
+        let mut best: Option<(&str, f64)> = None;
+        for (guess, points) in self.tally(guesses, candidates, model, turns) {
+            if best.is_none_or(|(_, most)| points > most) {
+                best = Some((guess, points));
+            }
+        }
+        best.map_or(candidates[0], |(guess, _)| guess)
+    }
+
+    fn rank<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+        turns: Option<usize>,
+        n: usize,
+    ) -> Vec<&'a str> {
+        let mut tally = self.tally(guesses, candidates, model, turns);
+        tally.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        tally.into_iter().take(n).map(|(guess, _)| guess).collect()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::strategy::{Entropy, Likeliest};
+
+    #[test]
+    fn votes() {
+        // "chomp" splits the candidates apart, where "match" is likeliest.
+        let model = AnswerModel::new([("hatch", 1), ("catch", 1), ("match", 4), ("patch", 1)]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        assert_eq!(
+            Entropy::default().choose(&guesses, &candidates, &model),
+            "chomp"
+        );
+
+        let ensemble = |voting, likeliest| Ensemble {
+            members: vec![
+                (Box::new(Entropy::default()) as Box<dyn Strategy>, 1.0),
+                (Box::new(Likeliest), likeliest),
+            ],
+            voting,
+        };
+        // An even vote goes to the earliest member's choice, and outweighed,
+        // it goes the other way.
+        let even = ensemble(Voting::Weighted, 1.0);
+        assert_eq!(even.choose(&guesses, &candidates, &model), "chomp");
+        let outweighed = ensemble(Voting::Weighted, 2.0);
+        assert_eq!(outweighed.choose(&guesses, &candidates, &model), "match");
+
+        // Counting whole rankings, "match" is ranked high by both.
+        let borda = ensemble(Voting::Borda, 1.0);
+        assert_eq!(borda.choose(&guesses, &candidates, &model), "match");
+        assert_eq!(borda.rank(&guesses, &candidates, &model, None, 2).len(), 2);
+    }
+}
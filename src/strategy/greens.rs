@@ -0,0 +1,98 @@
+use super::Strategy;
+use crate::{guesser::Correctness, model::AnswerModel, packed::Packed, parallel};
+
+/// Guess the word expected to light up the most tiles, for players who
+/// play for a pretty board rather than a short one.
+///
+/// Each guess scores the green tiles it's expected to get over the model's
+/// answer distribution, plus `yellow` for each yellow tile. The default
+/// weighs a yellow at a sixth of a green, so that no number of yellows in
+/// one guess outweighs a single green.
+pub struct Greens {
+    pub yellow: f64,
+}
+
+impl Default for Greens {
+    fn default() -> Self {
+        Self { yellow: 1.0 / 6.0 }
+    }
+}
+
+impl Greens {
+    /// The tiles `guess` is expected to light up among `candidates`, whose
+    /// probabilities are given by `distribution`.
+    fn score(&self, guess: &str, candidates: &Packed, distribution: &[f64]) -> f64 {
+        let guess = guess.as_bytes();
+        candidates
+            .iter()
+            .zip(distribution)
+            .map(|(answer, p)| {
+                let tiles: f64 = Correctness::compute_bytes(answer, guess)
+                    .iter()
+                    .map(|c| match c {
+                        Correctness::Correct => 1.0,
+                        Correctness::Misplaced => self.yellow,
+                        Correctness::Wrong => 0.0,
+                    })
+                    .sum();
+                p * tiles
+            })
+            .sum()
+    }
+}
+
+impl Strategy for Greens {
+    fn name(&self) -> &'static str {
+        "greens"
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        let distribution = model.distribution(candidates);
+
+        let packed = Packed::new(candidates);
+        let work = guesses.len() * candidates.len();
+        let best = parallel::best_by(guesses, work, |&guess| {
+            self.score(guess, &packed, &distribution)
+        });
+
+        best.copied().unwrap_or(candidates[0])
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn most_tiles() {
+        // "hatch" shares four places with every other candidate; "chomp"
+        // would tell them apart but lights up only yellows.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+        assert_eq!(
+            Greens::default().choose(&guesses, &candidates, &model),
+            "hatch"
+        );
+    }
+
+    #[test]
+    fn greens_before_yellows() {
+        // A green against one answer beats a yellow against each.
+        let model = AnswerModel::new([]);
+        let candidates = ["axxxx", "xxxxe"];
+        let packed = Packed::new(&candidates);
+        let distribution = model.distribution(&candidates);
+        let greens = Greens::default();
+        assert!(
+            greens.score("aqqqq", &packed, &distribution)
+                > greens.score("qaqeq", &packed, &distribution)
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use super::Strategy;
+use crate::{model::AnswerModel, weights::LetterWeights};
+
+/// Guess the candidate covering the most common letters, the way many people
+/// play: each distinct letter counts by how many words contain it, and each
+/// letter again by how many words have it in that position.
+///
+/// With trained weights, letters are weighed by those; otherwise they're
+/// weighed afresh over the remaining candidates each turn.
+#[derive(Default)]
+pub struct Letters {
+    pub weights: Option<LetterWeights>,
+}
+
+impl Strategy for Letters {
+    fn name(&self) -> &'static str {
+        "letters"
+    }
+
+    fn choose<'a>(
+        &self,
+        _guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        let trained;
+        let weights = match &self.weights {
+            Some(weights) => weights,
+            None => {
+                trained = LetterWeights::train(candidates.iter().map(|&w| (w, model.count(w))));
+                &trained
+            },
+        };
+
+        // Ties go to the earliest candidate, as with the likeliest strategy.
+        let mut best = candidates[0];
+        let mut best_score = weights.score(best);
+        for &word in &candidates[1..] {
+            let score = weights.score(word);
+            if score > best_score {
+                (best, best_score) = (word, score);
+            }
+        }
+        best
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn common_letters() {
+        let model = AnswerModel::new([]);
+        let candidates = ["fuzzy", "tares", "stare", "stale"];
+        assert_eq!(Letters::default().choose(&[], &candidates, &model), "stare");
+
+        let weights = LetterWeights::train([("fuzzy", 1)]);
+        let letters = Letters {
+            weights: Some(weights),
+        };
+        assert_eq!(letters.choose(&[], &candidates, &model), "fuzzy");
+    }
+}
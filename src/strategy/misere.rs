@@ -0,0 +1,67 @@
+use super::Strategy;
+use crate::{hint::Suggestion, model::AnswerModel, packed::Packed, parallel};
+
+/// Put off finding the answer for as long as possible, as in Anti-Wordle.
+///
+/// Only hard-mode guesses are legal, so every guess is itself a candidate and
+/// might be the answer; probe words are never played. Each candidate is
+/// scored by the chance it isn't the answer times the number of candidates
+/// expected to survive it, so the guess that's least likely to end the game
+/// and leaves the most room afterwards wins.
+pub struct Misere;
+
+impl Misere {
+    /// How good `suggestion` is at dragging the game out; higher is better.
+    #[inline]
+    fn score(suggestion: &Suggestion) -> f64 {
+        let miss = 1.0 - suggestion.probability;
+        miss * (suggestion.expected_remaining - suggestion.probability)
+    }
+}
+
+impl Strategy for Misere {
+    fn name(&self) -> &'static str {
+        "misere"
+    }
+
+    fn choose<'a>(
+        &self,
+        _guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        let distribution = model.distribution(candidates);
+
+        let packed = Packed::new(candidates);
+        let work = candidates.len() * candidates.len();
+        let best = parallel::best_by(candidates, work, |&guess| {
+            Self::score(&Suggestion::evaluate_packed(guess, &packed, &distribution))
+        });
+
+        best.copied().unwrap_or(candidates[0])
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn avoids_the_likely_answer() {
+        let model = AnswerModel::new([("hatch", 90), ("catch", 5), ("match", 5)]);
+        let candidates = ["hatch", "catch", "match"];
+        assert_ne!(Misere.choose(&candidates, &candidates, &model), "hatch");
+    }
+
+    #[test]
+    fn never_probes() {
+        // "chomp" would split the candidates apart, which is the last thing a
+        // misère player wants, and isn't a legal hard-mode guess anyway.
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+
+        assert!(candidates.contains(&Misere.choose(&guesses, &candidates, &model)));
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+use super::{Likeliest, Strategy};
+use crate::{guesser::Guess, knowledge::KnowledgeState, model::AnswerModel, parallel, random::Rng};
+
+/// Give up on a simulated game after this many guesses.
+const MAX_ROLLOUT: usize = 12;
+
+/// Estimate each guess's value by simulating games to completion.
+///
+/// For every guess under consideration, answers are sampled from the model's
+/// distribution and the rest of the game is played out with [`Likeliest`];
+/// the guess with the lowest average total is chosen. This looks further
+/// ahead than a single-step score without the cost of an exhaustive search.
+pub struct Rollout {
+    /// Simulated games per guess.
+    pub samples: usize,
+    /// The most guesses to consider; the likeliest candidates are kept.
+    pub breadth: usize,
+    pub seed: u64,
+    /// How long to spend on each guess, if not as long as it takes.
+    pub budget: Option<Duration>,
+}
+
+impl Default for Rollout {
+    fn default() -> Self {
+        Self {
+            samples: 64,
+            breadth: 100,
+            seed: 0x5eed,
+            budget: None,
+        }
+    }
+}
+
+impl Rollout {
+    /// Play `guess` and then the default policy against `answer`, returning
+    /// the total number of guesses.
+    fn simulate(guess: &str, answer: &str, candidates: &[&str], model: &AnswerModel) -> usize {
+        let mut guess = guess;
+        let mut candidates = candidates.to_vec();
+        let mut knowledge = KnowledgeState::new();
+
+        for turn in 1..MAX_ROLLOUT {
+            if guess == answer {
+                return turn;
+            }
+
+            knowledge.add(&Guess::check(answer, guess));
+            candidates.retain(|word| knowledge.allows(word));
+            if candidates.is_empty() {
+                break;
+            }
+
+            guess = Likeliest.choose(&candidates, &candidates, model);
+        }
+
+        MAX_ROLLOUT
+    }
+}
+
+impl Strategy for Rollout {
+    fn name(&self) -> &'static str {
+        "rollout"
+    }
+
+    fn choose<'a>(
+        &self,
+        guesses: &[&'a str],
+        candidates: &[&'a str],
+        model: &AnswerModel,
+    ) -> &'a str {
+        if candidates.len() <= 2 {
+            return Likeliest.choose(guesses, candidates, model);
+        }
+
+        let distribution = model.distribution(candidates);
+
+        // On a budget, the likeliest guesses are simulated first, so time
+        // runs out on the ones least likely to win.
+        let deadline = self.budget.map(|budget| Instant::now() + budget);
+        let mut considered: Vec<&str> = guesses.to_vec();
+        if considered.len() > self.breadth || deadline.is_some() {
+            considered.sort_by(|a, b| model.weight(b).total_cmp(&model.weight(a)));
+            considered.truncate(self.breadth);
+        }
+
+        // Every guess is measured against the same sampled answers, so the
+        // comparison between them isn't at the mercy of sampling noise.
+        let mut rng = Rng::new(self.seed ^ candidates.len() as u64);
+        let answers: Vec<&str> = (0..self.samples)
+            .map(|_| candidates[rng.weighted(&distribution)])
+            .collect();
+
+        let work = considered.len() * self.samples * candidates.len();
+        let best = parallel::best_until(&considered, work, deadline, |&guess| {
+            let total: usize = answers
+                .iter()
+                .map(|answer| Self::simulate(guess, answer, candidates, model))
+                .sum();
+            -(total as f64)
+        });
+
+        best.copied().unwrap_or(candidates[0])
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simulate_counts_guesses() {
+        let model = AnswerModel::new([("hatch", 3), ("catch", 2), ("match", 1)]);
+        let candidates = ["hatch", "catch", "match"];
+
+        assert_eq!(Rollout::simulate("hatch", "hatch", &candidates, &model), 1);
+        assert_eq!(Rollout::simulate("hatch", "catch", &candidates, &model), 2);
+        assert_eq!(Rollout::simulate("hatch", "match", &candidates, &model), 3);
+    }
+
+    #[test]
+    fn picks_a_splitting_guess() {
+        let model = AnswerModel::new([]);
+        let candidates = ["hatch", "catch", "match", "patch"];
+        let mut guesses = candidates.to_vec();
+        guesses.push("chomp");
+
+        assert_eq!(
+            Rollout::default().choose(&guesses, &candidates, &model),
+            "chomp"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::borrow::Cow;
+
+use crate::{alphabet::Alphabet, guesser::Guess, pattern::Tiles};
+
+/// Symbols that stand in for multi-letter tiles, in the order they're used.
+const SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Multi-letter tiles, such as "ll" and "ch" in older Spanish conventions or
+/// "ng" in Filipino, that count as a single tile.
+///
+/// Words are handled as five bytes everywhere, so each tile is encoded as a
+/// single symbol the word lists don't otherwise use when they're loaded.
+/// Feedback and matching then work on tiles with no changes, and words are
+/// decoded again for display.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tileset {
+    /// Each tile and its symbol, longest tiles first, so "rr" isn't read as
+    /// two "r"s.
+    tiles: Vec<(String, u8)>,
+}
+
+impl Tileset {
+    /// A tileset for `tiles`, with symbols outside `reserved`. Tiles of one
+    /// letter are ignored. Returns `None` if there aren't enough symbols.
+    pub fn new<'t>(tiles: impl IntoIterator<Item = &'t str>, reserved: &Alphabet) -> Option<Self> {
+        let mut unique: Vec<&str> = Vec::new();
+        for tile in tiles {
+            if tile.chars().count() > 1 && !unique.contains(&tile) {
+                unique.push(tile);
+            }
+        }
+        unique.sort_by_key(|t| std::cmp::Reverse(t.len()));
+
+        let mut symbols = SYMBOLS.iter().copied().filter(|&s| !reserved.contains(s));
+        let tiles = unique
+            .into_iter()
+            .map(|tile| Some((tile.to_string(), symbols.next()?)))
+            .collect::<Option<_>>()?;
+        Some(Self { tiles })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The symbols standing in for tiles.
+    pub fn symbols(&self) -> impl Iterator<Item = u8> + '_ {
+        self.tiles.iter().map(|&(_, symbol)| symbol)
+    }
+
+    /// `text` with every tile replaced by its symbol.
+    pub fn encode<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !self
+            .tiles
+            .iter()
+            .any(|(tile, _)| text.contains(tile.as_str()))
+        {
+            return Cow::Borrowed(text);
+        }
+
+        let mut encoded = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            match self
+                .tiles
+                .iter()
+                .find(|(tile, _)| rest.starts_with(tile.as_str()))
+            {
+                Some((tile, symbol)) => {
+                    encoded.push(*symbol as char);
+                    rest = &rest[tile.len()..];
+                },
+                None => {
+                    encoded.push(c);
+                    rest = &rest[c.len_utf8()..];
+                },
+            }
+        }
+        Cow::Owned(encoded)
+    }
+
+    /// `word` with every tile symbol spelled out again.
+    pub fn decode<'t>(&self, word: &'t str) -> Cow<'t, str> {
+        if !word.bytes().any(|b| self.tile(b).is_some()) {
+            return Cow::Borrowed(word);
+        }
+
+        let mut decoded = String::with_capacity(word.len() * 2);
+        for c in word.chars() {
+            match c.is_ascii().then(|| self.tile(c as u8)).flatten() {
+                Some(tile) => decoded.push_str(tile),
+                None => decoded.push(c),
+            }
+        }
+        Cow::Owned(decoded)
+    }
+
+    /// Render `guess` as its [`Display`](std::fmt::Display) impl does, but
+    /// with tiles spelled out: as colored tiles, or if `plain`, as the word
+    /// followed by its emoji.
+    pub fn render(&self, guess: &Guess, plain: bool) -> String {
+        if plain {
+            return format!("{} {}", self.decode(guess.word()), Tiles(guess.mask()));
+        }
+
+        let mut rendered = String::new();
+        for (symbol, c) in guess.word().bytes().zip(guess.mask()) {
+            let label = match self.tile(symbol) {
+                Some(tile) => tile.to_uppercase(),
+                None => (symbol as char).to_ascii_uppercase().to_string(),
+            };
+            rendered.push_str(&c.paint(label));
+        }
+        rendered
+    }
+
+    /// The tile `symbol` stands for, if any.
+    pub fn tile(&self, symbol: u8) -> Option<&str> {
+        self.tiles
+            .iter()
+            .find(|&&(_, s)| s == symbol)
+            .map(|(tile, _)| tile.as_str())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::guesser::{Correctness::*, Guess};
+
+    #[test]
+    fn round_trip() {
+        let tiles = Tileset::new(["ll", "ch", "rr", "ll"], &Alphabet::default()).unwrap();
+        let encoded = tiles.encode("calle chico perro");
+        assert_eq!(encoded, "caAe Bico peCo");
+        assert_eq!(tiles.decode(&encoded), "calle chico perro");
+        assert_eq!(tiles.tile(b'B'), Some("ch"));
+        assert_eq!(tiles.tile(b'D'), None);
+        assert!(matches!(tiles.encode("playa"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn feedback_by_tile() {
+        let tiles = Tileset::new(["ll"], &Alphabet::default()).unwrap();
+        // "llaves" is five tiles, the first of them "ll", so the lone "l" in
+        // "lacio" isn't in it.
+        let answer = tiles.encode("llaves");
+        let guess = tiles.encode("lacio");
+        assert_eq!(answer.len(), 5);
+        assert_eq!(
+            Guess::check(&answer, &guess).mask(),
+            [Wrong, Correct, Wrong, Wrong, Wrong]
+        );
+    }
+
+    #[test]
+    fn render() {
+        let guess = Guess::check("cigar", "tares");
+        let none = Tileset::default();
+        assert_eq!(none.render(&guess, false), guess.to_string());
+        assert_eq!(none.render(&guess, true), format!("{guess:#}"));
+
+        let tiles = Tileset::new(["ch"], &Alphabet::default()).unwrap();
+        let guess = Guess::check("Aicas", "Aicas");
+        assert_eq!(tiles.render(&guess, true), "chicas 🟩🟩🟩🟩🟩");
+        assert!(tiles.render(&guess, false).contains(" CH "));
+    }
+
+    #[test]
+    fn reserved_symbols() {
+        let reserved = Alphabet::new("abcAB").unwrap();
+        let tiles = Tileset::new(["ng"], &reserved).unwrap();
+        assert_eq!(tiles.encode("ngayon"), "Cayon");
+
+        let everything = Alphabet::new(std::str::from_utf8(SYMBOLS).unwrap()).unwrap();
+        assert_eq!(Tileset::new(["ng"], &everything), None);
+        assert!(Tileset::new(["a"], &reserved).unwrap().is_empty());
+    }
+}
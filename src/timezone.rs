@@ -0,0 +1,546 @@
+use std::{error::Error, fmt, fs, path::Path};
+
+const DAY: i64 = 24 * 60 * 60;
+const HOUR: i64 = 60 * 60;
+
+/// Where the system keeps its time zone database.
+const ZONEINFO: &str = "/usr/share/zoneinfo";
+
+/// Why a time zone couldn't be found or read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TimeZoneError {
+    Unknown(String),
+    Invalid(String),
+}
+
+impl fmt::Display for TimeZoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZoneError::Unknown(name) => write!(f, "unknown time zone: {name}"),
+            TimeZoneError::Invalid(name) => write!(f, "couldn't read time zone {name}"),
+        }
+    }
+}
+
+impl Error for TimeZoneError {}
+
+/// The offsets from UTC a place has kept over time.
+///
+/// Zones come from the system's time zone database, as `TZif` files, so
+/// there's nothing to keep up to date here; fixed offsets such as `+05:30`
+/// and POSIX `TZ` rules such as `EST5EDT,M3.2.0,M11.1.0` also work.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeZone {
+    /// The offset before the first transition, in seconds east of UTC.
+    initial: i64,
+    /// When the offset changed, in Unix seconds, and what it changed to.
+    transitions: Vec<(i64, i64)>,
+    /// The rule for every time after the last transition.
+    rule: Option<Rule>,
+}
+
+impl TimeZone {
+    pub fn utc() -> Self {
+        Self::fixed(0)
+    }
+
+    /// A zone always `offset` seconds east of UTC.
+    pub fn fixed(offset: i64) -> Self {
+        Self {
+            initial: offset,
+            transitions: Vec::new(),
+            rule: None,
+        }
+    }
+
+    /// Look up a zone by name: `UTC`, `local`, an offset such as `+05:30` or
+    /// `-0800`, a name from the time zone database such as
+    /// `America/New_York`, or a POSIX `TZ` rule.
+    pub fn parse(name: &str) -> Result<Self, TimeZoneError> {
+        match name {
+            "UTC" | "utc" | "Z" => Ok(Self::utc()),
+            "local" => Ok(Self::local()),
+            _ if name.starts_with(['+', '-']) => parse_offset(name)
+                .map(Self::fixed)
+                .ok_or_else(|| TimeZoneError::Unknown(name.to_string())),
+            _ => Self::named(name),
+        }
+    }
+
+    /// The zone this machine is set to, from `TZ` or `/etc/localtime`, or
+    /// UTC if neither says.
+    pub fn local() -> Self {
+        let from_env = std::env::var("TZ").ok().filter(|tz| !tz.is_empty());
+        let zone = match from_env {
+            Some(tz) => {
+                let tz = tz.strip_prefix(':').unwrap_or(&tz);
+                if tz.starts_with('/') {
+                    Self::from_file(tz)
+                } else {
+                    Self::named(tz)
+                }
+            },
+            None => Self::from_file("/etc/localtime"),
+        };
+        zone.unwrap_or_else(|_| Self::utc())
+    }
+
+    /// A zone from the time zone database, or else a POSIX `TZ` rule.
+    fn named(name: &str) -> Result<Self, TimeZoneError> {
+        let unknown = || TimeZoneError::Unknown(name.to_string());
+        if name.starts_with('/') || name.split('/').any(|part| part == "..") {
+            return Err(unknown());
+        }
+
+        match Self::from_file(Path::new(ZONEINFO).join(name)) {
+            Err(TimeZoneError::Unknown(_)) => Rule::parse(name)
+                .map(|rule| Self {
+                    initial: rule.std,
+                    transitions: Vec::new(),
+                    rule: Some(rule),
+                })
+                .ok_or_else(unknown),
+            zone => zone,
+        }
+    }
+
+    fn from_file(path: impl AsRef<Path>) -> Result<Self, TimeZoneError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let data = fs::read(path).map_err(|_| TimeZoneError::Unknown(name.clone()))?;
+        Self::from_tzif(&data).ok_or(TimeZoneError::Invalid(name))
+    }
+
+    /// Read a compiled zone in the `TZif` format of RFC 8536. From version 2
+    /// on, the 64-bit block and the footer's rule are used.
+    fn from_tzif(data: &[u8]) -> Option<Self> {
+        let header = Header::read(data)?;
+        let (header, data, time_size) = if header.version >= b'2' {
+            let rest = data.get(header.len(4)..)?;
+            (Header::read(rest)?, rest, 8)
+        } else {
+            (header, data, 4)
+        };
+
+        let mut at = 44;
+        let mut take = |n: usize| {
+            let bytes = data.get(at..at + n);
+            at += n;
+            bytes
+        };
+
+        let times = take(header.timecnt * time_size)?;
+        let indices = take(header.timecnt)?;
+        let types: Vec<i64> = take(header.typecnt * 6)?
+            .chunks_exact(6)
+            .map(|t| i32::from_be_bytes([t[0], t[1], t[2], t[3]]) as i64)
+            .collect();
+        take(header.len(time_size) - 44 - header.timecnt * (time_size + 1) - header.typecnt * 6)?;
+
+        let transitions = times
+            .chunks_exact(time_size)
+            .zip(indices)
+            .map(|(time, &index)| {
+                let time = match *time {
+                    [a, b, c, d] => i32::from_be_bytes([a, b, c, d]) as i64,
+                    _ => i64::from_be_bytes(time.try_into().ok()?),
+                };
+                Some((time, *types.get(index as usize)?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let footer = data.get(at..).unwrap_or_default();
+        let rule = std::str::from_utf8(footer)
+            .ok()
+            .filter(|_| header.version >= b'2')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .and_then(Rule::parse);
+
+        Some(Self {
+            initial: *types.first()?,
+            transitions,
+            rule,
+        })
+    }
+
+    /// The offset from UTC at `time`, both in seconds.
+    pub fn offset_at(&self, time: i64) -> i64 {
+        let i = self.transitions.partition_point(|&(at, _)| at <= time);
+        match &self.rule {
+            Some(rule) if i == self.transitions.len() => rule.offset_at(time),
+            _ if i == 0 => self.initial,
+            _ => self.transitions[i - 1].1,
+        }
+    }
+
+    /// The local date at `time`, in days since 1970-01-01.
+    pub fn day(&self, time: i64) -> i64 {
+        (time + self.offset_at(time)).div_euclid(DAY)
+    }
+}
+
+/// The counts from a `TZif` header.
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn read(data: &[u8]) -> Option<Self> {
+        if data.get(..4)? != b"TZif" {
+            return None;
+        }
+        let count = |i: usize| {
+            let bytes = data.get(20 + i * 4..24 + i * 4)?;
+            Some(u32::from_be_bytes(bytes.try_into().ok()?) as usize)
+        };
+
+        Some(Self {
+            version: *data.get(4)?,
+            isutcnt: count(0)?,
+            isstdcnt: count(1)?,
+            leapcnt: count(2)?,
+            timecnt: count(3)?,
+            typecnt: count(4)?,
+            charcnt: count(5)?,
+        })
+    }
+
+    /// The length of the header and its data block, with times of
+    /// `time_size` bytes.
+    fn len(&self, time_size: usize) -> usize {
+        44 + self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/// A POSIX `TZ` rule: a standard offset, and maybe a yearly daylight saving
+/// period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    /// Standard time, in seconds east of UTC.
+    std: i64,
+    dst: Option<Dst>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Dst {
+    offset: i64,
+    /// When daylight saving starts, in local standard time.
+    start: (Date, i64),
+    /// When it ends, in local daylight time.
+    end: (Date, i64),
+}
+
+/// A day of the year, in the forms a `TZ` rule may give it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Date {
+    /// `Jn`: day 1 to 365, never counting February 29.
+    Julian(i64),
+    /// `n`: day 0 to 365, counting February 29 in leap years.
+    Zero(i64),
+    /// `Mm.w.d`: weekday `d` (Sunday is 0) of week `w` of month `m`, where
+    /// week 5 means the last.
+    Month(i64, i64, i64),
+}
+
+impl Rule {
+    fn parse(rule: &str) -> Option<Self> {
+        let mut p = Parser(rule.as_bytes());
+        p.name()?;
+        let std = -p.offset()?;
+        if p.0.is_empty() {
+            return Some(Self { std, dst: None });
+        }
+
+        p.name()?;
+        let offset = match p.0.first() {
+            Some(b',') | None => std + HOUR,
+            _ => -p.offset()?,
+        };
+        // Without dates, the US rules are assumed, as POSIX suggests.
+        let (start, end) = if p.0.is_empty() {
+            (
+                (Date::Month(3, 2, 0), 2 * HOUR),
+                (Date::Month(11, 1, 0), 2 * HOUR),
+            )
+        } else {
+            p.eat(b',')?;
+            let start = p.transition()?;
+            p.eat(b',')?;
+            (start, p.transition()?)
+        };
+
+        p.0.is_empty().then_some(Self {
+            std,
+            dst: Some(Dst { offset, start, end }),
+        })
+    }
+
+    fn offset_at(&self, time: i64) -> i64 {
+        let Some(dst) = &self.dst else {
+            return self.std;
+        };
+
+        let (year, _, _) = civil_from_days((time + self.std).div_euclid(DAY));
+        let start = dst.start.0.day_in(year) * DAY + dst.start.1 - self.std;
+        let end = dst.end.0.day_in(year) * DAY + dst.end.1 - dst.offset;
+        // South of the equator, daylight saving spans the new year.
+        let in_dst = if start < end {
+            start <= time && time < end
+        } else {
+            !(end <= time && time < start)
+        };
+        if in_dst {
+            dst.offset
+        } else {
+            self.std
+        }
+    }
+}
+
+impl Date {
+    /// This date in `year`, in days since 1970-01-01.
+    fn day_in(self, year: i64) -> i64 {
+        let new_year = days_from_civil(year, 1, 1);
+        match self {
+            Date::Julian(n) => {
+                let leap = days_from_civil(year, 3, 1) - days_from_civil(year, 2, 28) == 2;
+                new_year + n - 1 + (leap && n >= 60) as i64
+            },
+            Date::Zero(n) => new_year + n,
+            Date::Month(month, week, weekday) => {
+                let first = days_from_civil(year, month, 1);
+                let (next_year, next_month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                let length = days_from_civil(next_year, next_month, 1) - first;
+
+                // 1970-01-01 was a Thursday.
+                let mut day = (weekday - (first + 4)).rem_euclid(7) + (week - 1) * 7;
+                while day >= length {
+                    day -= 7;
+                }
+                first + day
+            },
+        }
+    }
+}
+
+/// A cursor over a `TZ` rule.
+struct Parser<'a>(&'a [u8]);
+
+impl Parser<'_> {
+    fn eat(&mut self, b: u8) -> Option<()> {
+        let (&first, rest) = self.0.split_first()?;
+        (first == b).then(|| self.0 = rest)
+    }
+
+    /// A zone abbreviation, either letters or anything within `<>`.
+    fn name(&mut self) -> Option<()> {
+        let len = if self.eat(b'<').is_some() {
+            self.0.iter().position(|&b| b == b'>')? + 1
+        } else {
+            self.0
+                .iter()
+                .take_while(|b| b.is_ascii_alphabetic())
+                .count()
+        };
+        (len >= 3).then(|| self.0 = &self.0[len..])
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        let len = self.0.iter().take_while(|b| b.is_ascii_digit()).count();
+        let n = std::str::from_utf8(&self.0[..len]).ok()?.parse().ok()?;
+        self.0 = &self.0[len..];
+        Some(n)
+    }
+
+    /// `[+-]hh[:mm[:ss]]`, in seconds.
+    fn offset(&mut self) -> Option<i64> {
+        let sign = match self.0.first() {
+            Some(b'-') => -1,
+            _ => 1,
+        };
+        if matches!(self.0.first(), Some(b'+' | b'-')) {
+            self.0 = &self.0[1..];
+        }
+
+        let mut seconds = self.number()? * HOUR;
+        for unit in [60, 1] {
+            if self.eat(b':').is_none() {
+                break;
+            }
+            seconds += self.number()? * unit;
+        }
+        Some(sign * seconds)
+    }
+
+    /// A date and an optional `/time`, which defaults to 02:00.
+    fn transition(&mut self) -> Option<(Date, i64)> {
+        let date = if self.eat(b'J').is_some() {
+            Date::Julian(self.number()?)
+        } else if self.eat(b'M').is_some() {
+            let month = self.number()?;
+            self.eat(b'.')?;
+            let week = self.number()?;
+            self.eat(b'.')?;
+            Date::Month(month, week, self.number()?)
+        } else {
+            Date::Zero(self.number()?)
+        };
+
+        let time = match self.eat(b'/') {
+            Some(()) => self.offset()?,
+            None => 2 * HOUR,
+        };
+        Some((date, time))
+    }
+}
+
+/// An ISO 8601 offset such as `+05:30`, `-0800`, or `+9`, in seconds east of
+/// UTC.
+fn parse_offset(offset: &str) -> Option<i64> {
+    let (sign, digits) = match offset.split_at(1) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return None,
+    };
+    let digits = digits.replace(':', "");
+    if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.as_str(), "0"),
+        n => digits.split_at(n - 2),
+    };
+    let (hours, minutes): (i64, i64) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * HOUR + minutes * 60))
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The proleptic Gregorian date `days` after 1970-01-01, as year, month, and
+/// day.
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn calendar() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2021, 6, 19), 18797);
+        assert_eq!(civil_from_days(18797), (2021, 6, 19));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(
+            TimeZone::parse("+05:30"),
+            Ok(TimeZone::fixed(5 * HOUR + 1800))
+        );
+        assert_eq!(TimeZone::parse("-0800"), Ok(TimeZone::fixed(-8 * HOUR)));
+        assert_eq!(TimeZone::parse("+9"), Ok(TimeZone::fixed(9 * HOUR)));
+        assert_eq!(TimeZone::parse("UTC"), Ok(TimeZone::utc()));
+        assert!(TimeZone::parse("+25").is_err());
+        assert!(TimeZone::parse("+05:3x").is_err());
+        assert!(TimeZone::parse("../../etc/passwd").is_err());
+
+        // 23:30 UTC is already tomorrow an hour east, still today an hour
+        // west.
+        let late = 18797 * DAY + 23 * HOUR + 1800;
+        assert_eq!(TimeZone::fixed(HOUR).day(late), 18798);
+        assert_eq!(TimeZone::fixed(-HOUR).day(late), 18797);
+    }
+
+    #[test]
+    fn posix_rules() {
+        let zone = TimeZone::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        // 2024-03-10 06:59 and 07:00 UTC, either side of 2 AM local.
+        let spring = days_from_civil(2024, 3, 10) * DAY + 7 * HOUR;
+        assert_eq!(zone.offset_at(spring - 60), -5 * HOUR);
+        assert_eq!(zone.offset_at(spring), -4 * HOUR);
+        // 2024-11-03 05:59 and 06:00 UTC, either side of 2 AM daylight time.
+        let fall = days_from_civil(2024, 11, 3) * DAY + 6 * HOUR;
+        assert_eq!(zone.offset_at(fall - 60), -4 * HOUR);
+        assert_eq!(zone.offset_at(fall), -5 * HOUR);
+
+        // Sydney's daylight saving spans the new year.
+        let zone = TimeZone::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(
+            zone.offset_at(days_from_civil(2024, 1, 15) * DAY),
+            11 * HOUR
+        );
+        assert_eq!(
+            zone.offset_at(days_from_civil(2024, 7, 15) * DAY),
+            10 * HOUR
+        );
+
+        assert_eq!(Date::Julian(60).day_in(2024), days_from_civil(2024, 3, 1));
+        assert_eq!(Date::Zero(59).day_in(2024), days_from_civil(2024, 2, 29));
+    }
+
+    #[test]
+    fn tzif() {
+        // A version 1 file with one transition, from +01:00 to +02:00 at
+        // Unix time 1000.
+        let mut data = b"TZif".to_vec();
+        data.extend([0; 16]);
+        for count in [0u32, 0, 0, 1, 2, 8] {
+            data.extend(count.to_be_bytes());
+        }
+        data.extend(1000i32.to_be_bytes());
+        data.push(1);
+        data.extend(3600i32.to_be_bytes());
+        data.extend([0, 0]);
+        data.extend(7200i32.to_be_bytes());
+        data.extend([1, 4]);
+        data.extend(b"AAA\0BBB\0");
+
+        let zone = TimeZone::from_tzif(&data).unwrap();
+        assert_eq!(zone.offset_at(999), HOUR);
+        assert_eq!(zone.offset_at(1000), 2 * HOUR);
+        assert_eq!(TimeZone::from_tzif(b"nope"), None);
+    }
+}
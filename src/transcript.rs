@@ -0,0 +1,112 @@
+use crate::{audit::Grid, guesser::Correctness, pattern};
+
+/// One game as a player posted it to a chat: their name, the header's puzzle
+/// number, and each row's feedback beside the word played, where they wrote
+/// it down.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Transcript {
+    /// Empty if the header named no one.
+    pub player: String,
+    pub number: Option<usize>,
+    pub rows: Vec<(Option<String>, [Correctness; 5])>,
+}
+
+impl Transcript {
+    /// The game as its words and their feedback, if it has rows and every
+    /// one names its word.
+    pub fn game(&self) -> Option<Vec<(&str, [Correctness; 5])>> {
+        if self.rows.is_empty() {
+            return None;
+        }
+        self.rows
+            .iter()
+            .map(|(word, mask)| Some((word.as_deref()?, *mask)))
+            .collect()
+    }
+}
+
+/// Read every game in a chat log. Each starts with a share header such as
+/// `alice: Wordle 1,234 3/6`, where a timestamp before the name, as in
+/// `[09:03] alice:` or `1/5/24, 9:03 AM - alice:`, is dropped. A line per row
+/// follows, with its tiles and the word played, in either order. Any other
+/// line ends the game.
+pub fn parse(text: &str) -> Vec<Transcript> {
+    let mut transcripts = Vec::new();
+    let mut current: Option<Transcript> = None;
+    for line in text.lines().map(str::trim) {
+        if let Some(transcript) = header(line) {
+            transcripts.extend(current.replace(transcript));
+            continue;
+        }
+        match (&mut current, row(line)) {
+            (Some(transcript), Some(row)) => transcript.rows.push(row),
+            (Some(_), None) => transcripts.extend(current.take()),
+            (None, _) => {},
+        }
+    }
+    transcripts.extend(current);
+    transcripts
+}
+
+/// The game a header line starts, with no rows yet.
+fn header(line: &str) -> Option<Transcript> {
+    let at = line.find("Wordle ")?;
+    let grid = Grid::parse(&line[at..]).ok()?;
+    grid.score?;
+    let name = line[..at].trim().strip_suffix(':').unwrap_or_default();
+    let name = ["] ", " - "].iter().fold(name, |name, stamp| {
+        name.rsplit_once(stamp).map_or(name, |(_, name)| name)
+    });
+    Some(Transcript {
+        player: name.trim().to_string(),
+        number: grid.number,
+        rows: Vec::new(),
+    })
+}
+
+/// A row's word, if given, and its feedback.
+fn row(line: &str) -> Option<(Option<String>, [Correctness; 5])> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    match tokens[..] {
+        [tiles] => Some((None, pattern::parse(tiles).ok()?)),
+        [first, second] => match (pattern::parse(first), pattern::parse(second)) {
+            (Ok(mask), Err(_)) => Some((Some(second.to_string()), mask)),
+            (_, Ok(mask)) => Some((Some(first.to_string()), mask)),
+            (Err(_), Err(_)) => None,
+        },
+        _ => None,
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn chat_log() {
+        let log = "\
+1/5/24, 9:03 AM - Alice Smith: Wordle 1,294 3/6
+🟨⬛⬛⬛🟩 crane
+moist ⬛🟩⬛⬛🟩
+🟩🟩🟩🟩🟩 joist
+1/5/24, 9:05 AM - Bob: nice one!
+[09:10] bob: Wordle 1,294 X/6
+⬛⬛⬛⬛⬛
+🟩🟩🟩⬛🟩 hoist
+";
+        let transcripts = parse(log);
+        assert_eq!(transcripts.len(), 2);
+        let alice = &transcripts[0];
+        assert_eq!(alice.player, "Alice Smith");
+        assert_eq!(alice.number, Some(1294));
+        let game = alice.game().unwrap();
+        assert_eq!(game[1], ("moist", pattern::parse("BGBBG").unwrap()));
+        assert_eq!(game[2].0, "joist");
+
+        // Bob's opener went unrecorded, so his game can't be replayed.
+        let bob = &transcripts[1];
+        assert_eq!((bob.player.as_str(), bob.rows.len()), ("bob", 2));
+        assert_eq!(bob.game(), None);
+        assert_eq!(parse("carol: Wordle 1,294 2/6\ncrane")[0].game(), None);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use crate::guesser::{Correctness, Guess};
+
+/// A strategy's complete plan: the guesses it plays to find each answer, so
+/// games can be replayed later without searching again.
+///
+/// Written out as one line per answer, listing the guesses in order with the
+/// answer last, so the file is easy to read and to diff.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecisionTree {
+    /// Each answer's path, ending with the answer itself.
+    paths: BTreeMap<String, Vec<String>>,
+}
+
+impl DecisionTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the guesses that find the answer, which is the last of them.
+    pub fn insert<S: AsRef<str>>(&mut self, path: &[S]) {
+        if let Some(answer) = path.last() {
+            let path = path.iter().map(|word| word.as_ref().to_string()).collect();
+            self.paths.insert(answer.as_ref().to_string(), path);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// The guesses that find `answer`, ending with it.
+    pub fn path(&self, answer: &str) -> Option<&[String]> {
+        self.paths.get(answer).map(Vec::as_slice)
+    }
+
+    /// The guess the tree plays after `history`, or `None` if `history`
+    /// strayed from the tree or no answer in it fits the feedback.
+    pub fn next(&self, history: &[Guess]) -> Option<&str> {
+        self.paths
+            .iter()
+            .find(|(answer, path)| {
+                path.len() > history.len()
+                    && history.iter().zip(path.iter()).all(|(guess, word)| {
+                        guess.word() == word && Correctness::compute(answer, word) == guess.mask()
+                    })
+            })
+            .map(|(_, path)| path[history.len()].as_str())
+    }
+
+    /// Write the tree as lines of space-separated guesses, ordered by answer.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        for path in self.paths.values() {
+            writeln!(w, "{}", path.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// Read a tree written by [`save`](Self::save). Blank lines and comments
+    /// starting with `#` are skipped.
+    pub fn load(r: impl BufRead) -> io::Result<Self> {
+        let mut tree = Self::new();
+        for line in r.lines() {
+            let line = line?;
+            let path: Vec<&str> = line.split_whitespace().collect();
+            match path[..] {
+                [] => continue,
+                [comment, ..] if comment.starts_with('#') => continue,
+                _ if tree.path(path[path.len() - 1]).is_some() => {
+                    return Err(invalid(&line));
+                },
+                _ => tree.insert(&path),
+            }
+        }
+        Ok(tree)
+    }
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("answer appears twice in tree: {line}"),
+    )
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn tree() -> DecisionTree {
+        let mut tree = DecisionTree::new();
+        tree.insert(&["match", "hatch", "catch"]);
+        tree.insert(&["match", "hatch"]);
+        tree.insert(&["match"]);
+        tree
+    }
+
+    #[test]
+    fn replay() {
+        let tree = tree();
+        assert_eq!(tree.next(&[]), Some("match"));
+        let opened = Guess::check("catch", "match");
+        assert_eq!(tree.next(&[opened]), Some("hatch"));
+        let second = Guess::check("catch", "hatch");
+        assert_eq!(tree.next(&[opened, second]), Some("catch"));
+
+        // Off the tree, there's nothing to replay.
+        assert_eq!(tree.next(&[Guess::check("catch", "latch")]), None);
+        assert_eq!(tree.path("hatch").unwrap(), ["match", "hatch"]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let tree = tree();
+        let mut saved = Vec::new();
+        tree.save(&mut saved).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&saved),
+            "match hatch catch\nmatch hatch\nmatch\n"
+        );
+        assert_eq!(DecisionTree::load(&saved[..]).unwrap(), tree);
+
+        let loaded = DecisionTree::load(&b"# salet\n\nmatch\n"[..]).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(DecisionTree::load(&b"match catch\nhatch catch\n"[..]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{parallel, random::Rng};
+
+/// A strategy knob the tuner turns, as named for `--param`, and the range
+/// it searches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Knob {
+    pub key: &'static str,
+    pub min: f64,
+    pub max: f64,
+    /// Whether the knob only takes whole numbers.
+    pub whole: bool,
+}
+
+impl Knob {
+    /// `value` moved into range, and rounded if the knob is whole.
+    fn clamp(&self, value: f64) -> f64 {
+        let value = value.clamp(self.min, self.max);
+        if self.whole {
+            value.round()
+        } else {
+            value
+        }
+    }
+}
+
+/// The knobs tuned unless others are given: how much the scoring weighs the
+/// chance of guessing the answer, how much it holds probe words back, and
+/// how far the answer model evens out corpus counts.
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        key: "answer-weight",
+        min: 0.0,
+        max: 6.0,
+        whole: false,
+    },
+    Knob {
+        key: "probe-penalty",
+        min: 0.0,
+        max: 2.0,
+        whole: false,
+    },
+    Knob {
+        key: "smoothing",
+        min: 0.0,
+        max: 100_000.0,
+        whole: false,
+    },
+];
+
+/// A genetic search for the knob settings a fitness function scores lowest.
+///
+/// Each generation keeps its best setting as is and breeds the rest from
+/// pairs of parents picked by tournament: every knob comes from one parent
+/// or the other, and is then nudged at random with probability `mutation`.
+/// Settings are scored in parallel, since each is typically a whole
+/// benchmark run.
+pub struct Tuner<'k> {
+    knobs: &'k [Knob],
+    pub population: usize,
+    pub generations: usize,
+    pub mutation: f64,
+    rng: Rng,
+}
+
+impl<'k> Tuner<'k> {
+    pub fn new(knobs: &'k [Knob], seed: u64) -> Self {
+        Self {
+            knobs,
+            population: 12,
+            generations: 10,
+            mutation: 0.3,
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn with_population(mut self, population: usize) -> Self {
+        self.population = population.max(2);
+        self
+    }
+
+    pub fn with_generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    /// Evolve settings for the knobs, each a value per knob in order, and
+    /// return the best found with its fitness. `progress` hears the best of
+    /// each generation as it's found.
+    pub fn evolve(
+        &mut self,
+        fitness: impl Fn(&[f64]) -> f64 + Sync,
+        mut progress: impl FnMut(usize, &[f64], f64),
+    ) -> (Vec<f64>, f64) {
+        let mut population: Vec<Vec<f64>> = (0..self.population)
+            .map(|_| self.knobs.iter().map(|k| self.random(k)).collect())
+            .collect();
+        let mut scores = parallel::map(&population, usize::MAX, |genome| fitness(genome));
+
+        for generation in 0..=self.generations {
+            let best = (0..population.len())
+                .min_by(|&a, &b| scores[a].total_cmp(&scores[b]))
+                .unwrap_or(0);
+            progress(generation, &population[best], scores[best]);
+            if generation == self.generations {
+                return (population.swap_remove(best), scores[best]);
+            }
+
+            let mut children = vec![population[best].clone()];
+            while children.len() < self.population {
+                let a = self.tournament(&scores);
+                let b = self.tournament(&scores);
+                let child = self.breed(&population[a], &population[b]);
+                children.push(child);
+            }
+            let fresh = parallel::map(&children[1..], usize::MAX, |genome| fitness(genome));
+            scores = std::iter::once(scores[best]).chain(fresh).collect();
+            population = children;
+        }
+        unreachable!("the last generation returns")
+    }
+
+    fn random(&mut self, knob: &Knob) -> f64 {
+        knob.clamp(knob.min + self.rng.next_f64() * (knob.max - knob.min))
+    }
+
+    /// The better of two members picked at random.
+    fn tournament(&mut self, scores: &[f64]) -> usize {
+        let (a, b) = (self.rng.below(scores.len()), self.rng.below(scores.len()));
+        if scores[a] <= scores[b] {
+            a
+        } else {
+            b
+        }
+    }
+
+    fn breed(&mut self, a: &[f64], b: &[f64]) -> Vec<f64> {
+        self.knobs
+            .iter()
+            .zip(a.iter().zip(b))
+            .map(|(knob, (&a, &b))| {
+                let mut value = if self.rng.next_f64() < 0.5 { a } else { b };
+                if self.rng.next_f64() < self.mutation {
+                    // A nudge of up to a tenth of the range either way.
+                    value += (self.rng.next_f64() - 0.5) * 0.2 * (knob.max - knob.min);
+                }
+                knob.clamp(value)
+            })
+            .collect()
+    }
+}
+
+/// Write `params` as a profile for `--profile`: a `key=value` line each,
+/// after a comment saying where they came from.
+pub fn save_profile(params: &[(String, f64)], comment: &str, mut w: impl Write) -> io::Result<()> {
+    for line in comment.lines() {
+        writeln!(w, "# {line}")?;
+    }
+    for (key, value) in params {
+        writeln!(w, "{key}={value}")?;
+    }
+    w.flush()
+}
+
+/// Read a profile written by [`save_profile`]. Blank lines and lines
+/// starting with `#` are skipped.
+pub fn load_profile(r: impl BufRead) -> io::Result<Vec<(String, f64)>> {
+    let mut params = Vec::new();
+    for line in r.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("bad line: {line}"));
+        let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim().parse().map_err(|_| invalid())?;
+        params.push((key.trim().to_string(), value));
+    }
+    Ok(params)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn finds_the_minimum() {
+        let knobs = [
+            Knob {
+                key: "x",
+                min: -10.0,
+                max: 10.0,
+                whole: false,
+            },
+            Knob {
+                key: "n",
+                min: 0.0,
+                max: 20.0,
+                whole: true,
+            },
+        ];
+        let fitness = |genome: &[f64]| (genome[0] - 3.0).powi(2) + (genome[1] - 7.0).abs();
+        let mut bests = Vec::new();
+        let (best, score) = Tuner::new(&knobs, 7)
+            .with_population(20)
+            .with_generations(40)
+            .evolve(fitness, |_, _, score| bests.push(score));
+
+        assert!(score < 0.5, "{best:?} scored {score}");
+        assert_eq!(best[1].fract(), 0.0);
+        assert_eq!(bests.len(), 41);
+        // The best is always kept, so it never gets worse.
+        assert!(bests.windows(2).all(|pair| pair[1] <= pair[0]));
+    }
+
+    #[test]
+    fn profiles() {
+        let params = vec![
+            ("answer-weight".to_string(), 2.5),
+            ("probe-penalty".to_string(), 0.25),
+        ];
+        let mut saved = Vec::new();
+        save_profile(&params, "tuned for entropy\nscore 3.5", &mut saved).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&saved),
+            "# tuned for entropy\n# score 3.5\nanswer-weight=2.5\nprobe-penalty=0.25\n"
+        );
+        assert_eq!(load_profile(&saved[..]).unwrap(), params);
+        assert!(load_profile(&b"answer-weight 2"[..]).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+/// A keyboard layout, for judging how quickly words are typed.
+///
+/// Keys sit on three rows, each shifted right of the one above as on a
+/// staggered keyboard, and each column is typed by the finger that covers it
+/// in touch typing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub name: &'static str,
+    rows: [&'static str; 3],
+    /// The column each row's first letter is in, past any punctuation keys.
+    indent: [usize; 3],
+}
+
+/// How far each row sits right of the top one, in key widths.
+const STAGGER: [f64; 3] = [0.0, 0.25, 0.75];
+
+/// What typing two different keys with the same finger costs on top of the
+/// travel between them, in key widths: the finger has to finish one key
+/// before it can start the next.
+const SAME_FINGER: f64 = 1.5;
+
+impl Layout {
+    pub const QWERTY: Layout = Layout {
+        name: "qwerty",
+        rows: ["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+        indent: [0; 3],
+    };
+    pub const DVORAK: Layout = Layout {
+        name: "dvorak",
+        rows: ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"],
+        indent: [3, 0, 1],
+    };
+    pub const COLEMAK: Layout = Layout {
+        name: "colemak",
+        rows: ["qwfpgjluy", "arstdhneio", "zxcvbkm"],
+        indent: [0; 3],
+    };
+
+    /// The layouts `--layout` accepts.
+    pub const ALL: [Layout; 3] = [Self::QWERTY, Self::DVORAK, Self::COLEMAK];
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|layout| layout.name == name)
+    }
+
+    /// The row and column of `letter`, if the layout has it.
+    fn key(&self, letter: u8) -> Option<(usize, usize)> {
+        let letter = letter.to_ascii_lowercase();
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            keys.bytes()
+                .position(|key| key == letter)
+                .map(|column| (row, column + self.indent[row]))
+        })
+    }
+
+    /// How hard `word` is to type: the distance the fingers travel from each
+    /// key to the next, in key widths, plus a penalty for each pair of
+    /// different keys typed by the same finger. Letters the layout lacks cost
+    /// nothing.
+    pub fn cost(&self, word: &str) -> f64 {
+        let keys: Vec<(usize, usize)> = word.bytes().filter_map(|b| self.key(b)).collect();
+        keys.windows(2)
+            .map(|pair| {
+                let [(r1, c1), (r2, c2)] = [pair[0], pair[1]];
+                if (r1, c1) == (r2, c2) {
+                    return 0.0;
+                }
+                let x = (c1 as f64 + STAGGER[r1]) - (c2 as f64 + STAGGER[r2]);
+                let y = r1 as f64 - r2 as f64;
+                let travel = (x * x + y * y).sqrt();
+                if finger(c1) == finger(c2) {
+                    travel + SAME_FINGER
+                } else {
+                    travel
+                }
+            })
+            .sum()
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::QWERTY
+    }
+}
+
+/// The finger that types keys in `column`, counting from the left pinky:
+/// each index finger covers two columns and each pinky the rest of its side.
+fn finger(column: usize) -> usize {
+    match column {
+        0..=3 => column,
+        4 => 3,
+        5 | 6 => 4,
+        7 => 5,
+        8 => 6,
+        _ => 7,
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        assert_eq!(Layout::by_name("dvorak"), Some(Layout::DVORAK));
+        assert_eq!(Layout::by_name("azerty"), None);
+        assert_eq!(Layout::QWERTY.key(b'a'), Some((1, 0)));
+        assert_eq!(Layout::DVORAK.key(b'p'), Some((0, 3)));
+    }
+
+    #[test]
+    fn costs() {
+        let qwerty = Layout::QWERTY;
+        // Repeats cost nothing, and neighbors one key width.
+        assert_eq!(qwerty.cost("aaaaa"), 0.0);
+        assert_eq!(qwerty.cost("as"), 1.0);
+        // "ed" is typed by one finger, so it costs more than "ef", which is
+        // farther but moves from one finger to the next.
+        assert!(qwerty.cost("ed") > qwerty.cost("ef"));
+        // Words kept to one corner are easier than ones that cross the board.
+        assert!(qwerty.cost("dress") < qwerty.cost("pizza"));
+        // Dvorak puts the vowels under the left hand's home row.
+        assert!(Layout::DVORAK.cost("aoeui") < qwerty.cost("aoeui"));
+    }
+}
@@ -0,0 +1,453 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::guesser::Correctness;
+
+/// How many tiles a Waffle grid has: a five by five square with four holes.
+pub const TILES: usize = 21;
+
+/// The tiles of each word, as indices into the grid in reading order: the
+/// three rows, then the three columns.
+pub const WORDS: [[usize; 5]; 6] = [
+    [0, 1, 2, 3, 4],
+    [8, 9, 10, 11, 12],
+    [16, 17, 18, 19, 20],
+    [0, 5, 8, 13, 16],
+    [2, 6, 10, 14, 18],
+    [4, 7, 12, 15, 20],
+];
+
+/// The order words are filled in while searching: the top row, then the
+/// columns hanging from it, which leave the other rows little freedom.
+const SEARCH_ORDER: [usize; 6] = [0, 3, 4, 5, 1, 2];
+
+/// Why a Waffle puzzle couldn't be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// The puzzle didn't have five lines of letters and five of colors.
+    WrongLines(usize),
+    /// A line, counted from one, had the wrong number of tiles.
+    WrongTiles {
+        line: usize,
+        want: usize,
+        got: usize,
+    },
+    InvalidLetter(char),
+    InvalidTile(char),
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::WrongLines(n) => write!(
+                f,
+                "a waffle needs 5 lines of letters and 5 of colors, got {n} lines"
+            ),
+            PuzzleError::WrongTiles { line, want, got } => {
+                write!(f, "line {line} needs {want} tiles, got {got}")
+            },
+            PuzzleError::InvalidLetter(c) => write!(f, "invalid waffle letter: {c:?}"),
+            PuzzleError::InvalidTile(c) => write!(f, "invalid feedback tile: {c:?}"),
+        }
+    }
+}
+
+impl Error for PuzzleError {}
+
+/// A Waffle puzzle: a grid of six interlocking words with its letters
+/// scrambled, and the color of each tile.
+///
+/// Green tiles are in place. A yellow tile's letter belongs elsewhere in one
+/// of the words the tile is part of, and a gray one's in neither, with
+/// repeated letters counted as Wordle counts them. The solution uses exactly
+/// the letters on the grid, so only swaps are needed to reach it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    letters: [u8; TILES],
+    marks: [Correctness; TILES],
+}
+
+impl Puzzle {
+    /// Read a puzzle as five lines of letters, then five of colors in any
+    /// form [`Correctness::from_char`] reads. Whitespace is ignored, so the
+    /// holes may be left as spaces; the second and fourth lines of each have
+    /// three tiles.
+    pub fn parse(text: &str) -> Result<Self, PuzzleError> {
+        let lines: Vec<Vec<char>> = text
+            .lines()
+            .map(|line| line.chars().filter(|c| !c.is_whitespace()).collect())
+            .filter(|line: &Vec<char>| !line.is_empty())
+            .collect();
+        if lines.len() != 10 {
+            return Err(PuzzleError::WrongLines(lines.len()));
+        }
+
+        let tiles = |first: usize| -> Result<Vec<char>, PuzzleError> {
+            let mut tiles = Vec::with_capacity(TILES);
+            for (i, line) in lines[first..first + 5].iter().enumerate() {
+                let want = if i % 2 == 0 { 5 } else { 3 };
+                if line.len() != want {
+                    return Err(PuzzleError::WrongTiles {
+                        line: first + i + 1,
+                        want,
+                        got: line.len(),
+                    });
+                }
+                tiles.extend(line);
+            }
+            Ok(tiles)
+        };
+
+        let mut puzzle = Self {
+            letters: [0; TILES],
+            marks: [Correctness::Wrong; TILES],
+        };
+        for (slot, c) in puzzle.letters.iter_mut().zip(tiles(0)?) {
+            let c = c.to_ascii_lowercase();
+            if !c.is_ascii_alphabetic() {
+                return Err(PuzzleError::InvalidLetter(c));
+            }
+            *slot = c as u8;
+        }
+        for (slot, c) in puzzle.marks.iter_mut().zip(tiles(5)?) {
+            *slot = Correctness::from_char(c).ok_or(PuzzleError::InvalidTile(c))?;
+        }
+        Ok(puzzle)
+    }
+
+    /// The letters on the grid, in reading order.
+    pub fn letters(&self) -> &[u8; TILES] {
+        &self.letters
+    }
+
+    /// The colors `letters` would be given if `solution` were the answer.
+    pub fn marks(solution: &[u8; TILES], letters: &[u8; TILES]) -> [Correctness; TILES] {
+        let mut marks = [Correctness::Wrong; TILES];
+        let mut unused = [[0u8; 256]; 6];
+        for (tiles, unused) in WORDS.iter().zip(&mut unused) {
+            for &i in tiles.iter().filter(|&&i| solution[i] != letters[i]) {
+                unused[solution[i] as usize] += 1;
+            }
+        }
+
+        for i in 0..TILES {
+            if solution[i] == letters[i] {
+                marks[i] = Correctness::Correct;
+                continue;
+            }
+            let letter = letters[i] as usize;
+            let word = (0..6).find(|&w| WORDS[w].contains(&i) && unused[w][letter] > 0);
+            if let Some(w) = word {
+                unused[w][letter] -= 1;
+                marks[i] = Correctness::Misplaced;
+            }
+        }
+        marks
+    }
+
+    /// The first arrangement, trying `words` in order for each, that would
+    /// give the grid's colors, or `None` if none of them fit.
+    pub fn solve(&self, words: &[&str]) -> Option<[u8; TILES]> {
+        let mut counts = [0u8; 256];
+        for &letter in &self.letters {
+            counts[letter as usize] += 1;
+        }
+
+        // A word must keep the green letters, and can't move any other letter
+        // to where it already is.
+        let fits = |tiles: &[usize; 5], word: &[u8]| {
+            tiles.iter().zip(word).all(|(&i, &letter)| {
+                (self.marks[i] == Correctness::Correct) == (self.letters[i] == letter)
+                    && counts[letter as usize] > 0
+            })
+        };
+        let candidates: Vec<Vec<&[u8]>> = WORDS
+            .iter()
+            .map(|tiles| {
+                words
+                    .iter()
+                    .map(|word| word.as_bytes())
+                    .filter(|word| word.len() == 5 && fits(tiles, word))
+                    .collect()
+            })
+            .collect();
+
+        let mut grid = [None; TILES];
+        self.search(0, &candidates, &mut grid, &mut counts)
+    }
+
+    fn search(
+        &self,
+        depth: usize,
+        candidates: &[Vec<&[u8]>],
+        grid: &mut [Option<u8>; TILES],
+        counts: &mut [u8; 256],
+    ) -> Option<[u8; TILES]> {
+        let Some(&slot) = SEARCH_ORDER.get(depth) else {
+            let solution = grid.map(|letter| letter.unwrap_or_default());
+            return (Self::marks(&solution, &self.letters) == self.marks).then_some(solution);
+        };
+
+        let tiles = &WORDS[slot];
+        for word in &candidates[slot] {
+            // Tiles shared with words already placed must agree, and the
+            // rest must have their letters left to give.
+            let mut placed = Vec::with_capacity(5);
+            let mut fits = true;
+            for (&i, &letter) in tiles.iter().zip(word.iter()) {
+                match grid[i] {
+                    Some(existing) if existing == letter => {},
+                    Some(_) => fits = false,
+                    None if counts[letter as usize] > 0 => {
+                        counts[letter as usize] -= 1;
+                        grid[i] = Some(letter);
+                        placed.push(i);
+                    },
+                    None => fits = false,
+                }
+                if !fits {
+                    break;
+                }
+            }
+
+            if fits {
+                if let Some(solution) = self.search(depth + 1, candidates, grid, counts) {
+                    return Some(solution);
+                }
+            }
+            for i in placed {
+                counts[grid[i].unwrap_or_default() as usize] += 1;
+                grid[i] = None;
+            }
+        }
+        None
+    }
+}
+
+/// The six words of a grid, rows first, then columns.
+pub fn words(grid: &[u8; TILES]) -> [String; 6] {
+    WORDS.map(|tiles| tiles.iter().map(|&i| grid[i] as char).collect())
+}
+
+/// Where tile `i` is on the grid, as `r<row>c<column>` counted from one.
+pub fn position(i: usize) -> String {
+    let (row, column) = match i {
+        0..=4 => (0, i),
+        5..=7 => (1, (i - 5) * 2),
+        8..=12 => (2, i - 8),
+        13..=15 => (3, (i - 13) * 2),
+        _ => (4, i - 16),
+    };
+    format!("r{}c{}", row + 1, column + 1)
+}
+
+/// The fewest swaps of two tiles that turn `from` into `to`, which must have
+/// the same letters.
+///
+/// Every tile out of place is an edge from the letter it holds to the one it
+/// needs, and a cycle of `n` such edges takes `n - 1` swaps, so the fewest
+/// swaps come from splitting the edges into as many cycles as possible.
+pub fn swaps(from: &[u8; TILES], to: &[u8; TILES]) -> Vec<(usize, usize)> {
+    let mut edges: Vec<(u8, u8)> = (0..TILES)
+        .filter(|&i| from[i] != to[i])
+        .map(|i| (from[i], to[i]))
+        .collect();
+    edges.sort_unstable();
+
+    let mut tiles = *from;
+    let mut swaps = Vec::new();
+    for cycle in most_cycles(edges, &mut HashMap::new()) {
+        let n = cycle.len();
+        let mut taken: Vec<usize> = Vec::with_capacity(n);
+        for j in 0..n {
+            let (holds, needs) = (cycle[j], cycle[(j + 1) % n]);
+            let tile = (0..TILES)
+                .find(|&i| tiles[i] == holds && to[i] == needs && !taken.contains(&i))
+                .expect("every edge of a cycle is a tile out of place");
+            taken.push(tile);
+        }
+        // Each swap puts the first of the pair in place and passes the
+        // letter it held along the cycle.
+        for pair in taken.windows(2) {
+            tiles.swap(pair[0], pair[1]);
+            swaps.push((pair[0], pair[1]));
+        }
+    }
+    swaps
+}
+
+/// The most cycles, as the letters around each, that `edges` split into.
+/// Every cycle through the first edge is tried, since one of them must be
+/// part of the best split.
+fn most_cycles(
+    edges: Vec<(u8, u8)>,
+    memo: &mut HashMap<Vec<(u8, u8)>, Vec<Vec<u8>>>,
+) -> Vec<Vec<u8>> {
+    let Some(&(start, next)) = edges.first() else {
+        return Vec::new();
+    };
+    if let Some(best) = memo.get(&edges) {
+        return best.clone();
+    }
+
+    let mut paths = Vec::new();
+    let mut used = vec![false; edges.len()];
+    used[0] = true;
+    paths_back(&edges, &mut used, &mut vec![start, next], start, &mut paths);
+
+    let mut best: Option<Vec<Vec<u8>>> = None;
+    for (path, used) in paths {
+        let rest: Vec<(u8, u8)> = edges
+            .iter()
+            .zip(&used)
+            .filter(|&(_, &used)| !used)
+            .map(|(&edge, _)| edge)
+            .collect();
+        let mut cycles = most_cycles(rest, memo);
+        if best
+            .as_ref()
+            .is_none_or(|best| cycles.len() + 1 > best.len())
+        {
+            cycles.insert(0, path);
+            best = Some(cycles);
+        }
+    }
+
+    let best = best.expect("letters out of place always form a cycle");
+    memo.insert(edges, best.clone());
+    best
+}
+
+/// Every path from the end of `path` back to `start` through unused edges,
+/// visiting no letter twice, with the edges it uses.
+fn paths_back(
+    edges: &[(u8, u8)],
+    used: &mut Vec<bool>,
+    path: &mut Vec<u8>,
+    start: u8,
+    paths: &mut Vec<(Vec<u8>, Vec<bool>)>,
+) {
+    let at = *path.last().unwrap_or(&start);
+    if at == start {
+        path.pop();
+        paths.push((path.clone(), used.clone()));
+        path.push(start);
+        return;
+    }
+
+    for i in 0..edges.len() {
+        let (from, to) = edges[i];
+        // Parallel edges lead to the same paths, so only the first unused one
+        // is followed.
+        let repeat = i > 0 && edges[i - 1] == edges[i] && !used[i - 1];
+        if used[i] || from != at || repeat || (to != start && path.contains(&to)) {
+            continue;
+        }
+        used[i] = true;
+        path.push(to);
+        paths_back(edges, used, path, start, paths);
+        path.pop();
+        used[i] = false;
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // The rows are fable, codes, and speed; the columns are focus, badge, and
+    // eased.
+    #[allow(dead_code)]
+    const SOLUTION: &str = "fable\noaa\ncodes\nuge\nspeed\n";
+
+    #[allow(dead_code)]
+    fn grid(text: &str) -> [u8; TILES] {
+        let bytes: Vec<u8> = text.bytes().filter(|b| b.is_ascii_alphabetic()).collect();
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn parses() {
+        let text = "fable\no a a\ncodes\nu g e\nspeed\nggggg\ng g g\nggggg\ng g g\nggggg\n";
+        let puzzle = Puzzle::parse(text).unwrap();
+        assert_eq!(puzzle.letters(), &grid(SOLUTION));
+        assert_eq!(words(&grid(SOLUTION))[4], "badge");
+
+        assert_eq!(Puzzle::parse("fable\n"), Err(PuzzleError::WrongLines(1)));
+        let short = text.replacen("g g g", "gg", 2);
+        assert_eq!(
+            Puzzle::parse(&short),
+            Err(PuzzleError::WrongTiles {
+                line: 7,
+                want: 3,
+                got: 2
+            })
+        );
+        assert_eq!(position(0), "r1c1");
+        assert_eq!(position(14), "r4c3");
+    }
+
+    #[test]
+    fn marks() {
+        let solution = grid(SOLUTION);
+        let mut letters = solution;
+        // Swapping the a of fable with the u of focus leaves the a out of
+        // focus and the u out of fable.
+        letters.swap(1, 13);
+        let marks = Puzzle::marks(&solution, &letters);
+        assert_eq!(marks[1], Correctness::Wrong);
+        assert_eq!(marks[13], Correctness::Wrong);
+        assert_eq!(
+            marks.iter().filter(|&&m| m == Correctness::Correct).count(),
+            19
+        );
+
+        // Swapping within a row leaves both letters yellow.
+        let mut letters = solution;
+        letters.swap(1, 3);
+        let marks = Puzzle::marks(&solution, &letters);
+        assert_eq!(marks[1], Correctness::Misplaced);
+        assert_eq!(marks[3], Correctness::Misplaced);
+    }
+
+    #[test]
+    fn solves() {
+        let solution = grid(SOLUTION);
+        let mut letters = solution;
+        for (a, b) in [(1, 13), (3, 9), (17, 6), (11, 14)] {
+            letters.swap(a, b);
+        }
+        let puzzle = Puzzle {
+            letters,
+            marks: Puzzle::marks(&solution, &letters),
+        };
+
+        let mut words = vec!["table", "fable", "codes", "speed", "focus", "eased"];
+        assert_eq!(puzzle.solve(&words), None);
+        words.push("badge");
+        assert_eq!(puzzle.solve(&words), Some(solution));
+
+        let swaps = swaps(&letters, &solution);
+        assert_eq!(swaps.len(), 4);
+        let mut tiles = letters;
+        for (a, b) in swaps {
+            tiles.swap(a, b);
+        }
+        assert_eq!(tiles, solution);
+    }
+
+    #[test]
+    fn fewest_swaps() {
+        // Three tiles in a cycle take two swaps, and a pair takes one.
+        let from = grid("abcde fgh ijklm nop qrstu");
+        let mut to = from;
+        to.swap(0, 1);
+        to.swap(1, 2);
+        to.swap(10, 20);
+        assert_eq!(swaps(&from, &to).len(), 3);
+
+        // Repeated letters pair up rather than joining one long cycle.
+        let from = grid("aabbf ccc ddddd eee fffff");
+        let to = grid("bbaaf ccc ddddd eee fffff");
+        assert_eq!(swaps(&from, &to).len(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+/// How common each letter is, overall and at each position, for strategies
+/// that rank words by the letters they cover.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LetterWeights {
+    /// The share of words containing each letter.
+    letters: BTreeMap<u8, f64>,
+    /// The share of words with each letter at each position.
+    positions: [BTreeMap<u8, f64>; 5],
+}
+
+impl LetterWeights {
+    /// Derive weights from `(word, count)` pairs. Each word counts once,
+    /// plus the logarithm of its count, so a handful of very common words
+    /// don't swamp the rest.
+    pub fn train<'a>(pairs: impl IntoIterator<Item = (&'a str, usize)>) -> Self {
+        let mut weights = Self::default();
+        let mut total = 0.0;
+        for (word, count) in pairs {
+            let weight = 1.0 + (count as f64).ln_1p();
+            total += weight;
+
+            let mut seen = Vec::with_capacity(5);
+            for (i, letter) in word.bytes().enumerate().take(5) {
+                *weights.positions[i].entry(letter).or_default() += weight;
+                if !seen.contains(&letter) {
+                    seen.push(letter);
+                    *weights.letters.entry(letter).or_default() += weight;
+                }
+            }
+        }
+
+        if total > 0.0 {
+            let maps = std::iter::once(&mut weights.letters).chain(&mut weights.positions);
+            for weight in maps.flat_map(BTreeMap::values_mut) {
+                *weight /= total;
+            }
+        }
+        weights
+    }
+
+    pub fn letter(&self, letter: u8) -> f64 {
+        self.letters.get(&letter).copied().unwrap_or(0.0)
+    }
+
+    pub fn position(&self, i: usize, letter: u8) -> f64 {
+        self.positions[i].get(&letter).copied().unwrap_or(0.0)
+    }
+
+    /// How much of what's common `word` covers: the weight of each distinct
+    /// letter, plus that of each letter at its position.
+    pub fn score(&self, word: &str) -> f64 {
+        let mut score = 0.0;
+        for (i, letter) in word.bytes().enumerate().take(5) {
+            if !word.as_bytes()[..i].contains(&letter) {
+                score += self.letter(letter);
+            }
+            score += self.position(i, letter);
+        }
+        score
+    }
+
+    /// Write the weights as lines of `letter <letter> <weight>` and
+    /// `position <n> <letter> <weight>`, counting positions from one.
+    pub fn save(&self, mut w: impl Write) -> io::Result<()> {
+        for (&letter, weight) in &self.letters {
+            writeln!(w, "letter {} {weight}", letter as char)?;
+        }
+        for (i, position) in self.positions.iter().enumerate() {
+            for (&letter, weight) in position {
+                writeln!(w, "position {} {} {weight}", i + 1, letter as char)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read weights written by [`save`](Self::save). Blank lines and
+    /// comments starting with `#` are skipped.
+    pub fn load(r: impl BufRead) -> io::Result<Self> {
+        let mut weights = Self::default();
+        for line in r.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (map, letter, weight) = match fields[..] {
+                [] => continue,
+                [comment, ..] if comment.starts_with('#') => continue,
+                ["letter", letter, weight] => (&mut weights.letters, letter, weight),
+                ["position", i, letter, weight] => match i.parse::<usize>() {
+                    Ok(i @ 1..=5) => (&mut weights.positions[i - 1], letter, weight),
+                    _ => return Err(invalid(&line)),
+                },
+                _ => return Err(invalid(&line)),
+            };
+
+            let (&[letter], Ok(weight)) = (letter.as_bytes(), weight.parse()) else {
+                return Err(invalid(&line));
+            };
+            map.insert(letter, weight);
+        }
+        Ok(weights)
+    }
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad weights line: {line}"),
+    )
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn train() {
+        let weights = LetterWeights::train([("sheep", 3), ("eerie", 3)]);
+        assert_eq!(weights.letter(b'e'), 1.0);
+        assert_eq!(weights.letter(b'r'), 0.5);
+        assert_eq!(weights.letter(b'z'), 0.0);
+        assert_eq!(weights.position(2, b'e'), 0.5);
+        assert_eq!(weights.position(2, b'p'), 0.0);
+        // Each distinct letter, then each letter in place.
+        assert_eq!(weights.score("sheep"), 2.5 + 2.5);
+        assert_eq!(weights.score("eeeee"), 1.0 + 2.5);
+
+        // Commoner words count for more, but not in proportion.
+        let weights = LetterWeights::train([("sheep", 1000), ("eerie", 0)]);
+        assert!((0.5..0.9).contains(&weights.letter(b's')));
+        assert_eq!(LetterWeights::train([]), LetterWeights::default());
+    }
+
+    #[test]
+    fn round_trip() {
+        let weights = LetterWeights::train([("cigar", 3), ("rebut", 1), ("sissy", 4)]);
+        let mut saved = Vec::new();
+        weights.save(&mut saved).unwrap();
+        assert_eq!(LetterWeights::load(&saved[..]).unwrap(), weights);
+
+        let loaded = LetterWeights::load(&b"# trained\n\nletter e 0.5\nposition 5 y 0.25\n"[..]);
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.letter(b'e'), 0.5);
+        assert_eq!(loaded.position(4, b'y'), 0.25);
+
+        for bad in [
+            "letter ee 0.5",
+            "position 6 e 0.5",
+            "letter e lots",
+            "vowel e 1",
+        ] {
+            assert!(LetterWeights::load(bad.as_bytes()).is_err(), "{bad}");
+        }
+    }
+}